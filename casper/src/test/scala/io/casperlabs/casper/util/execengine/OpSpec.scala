@@ -1,5 +1,6 @@
 package io.casperlabs.casper.util.execengine
 
+import io.casperlabs.ipc
 import org.scalacheck.{Arbitrary, Gen}
 
 import org.scalatest.{FlatSpec, Matchers}
@@ -50,6 +51,15 @@ class OpSpec extends FlatSpec with Matchers with PropertyChecks {
     }
   }
 
+  "Op.fromTransform" should "not commute a prune with an add" in {
+    val prune = ipc.Transform(ipc.Transform.TransformInstance.Prune(ipc.TransformPrune()))
+    val add   = ipc.Transform(ipc.Transform.TransformInstance.AddI32(ipc.TransformAddInt32(1)))
+
+    val pruneOp = Op.fromTransform(prune)
+    pruneOp shouldBe Some(Op.Write)
+    (pruneOp.get ~ Op.fromTransform(add).get) shouldBe false
+  }
+
   "OpMap addition" should "be commutative" in {
     forAll { (a: OpMap[Int], b: OpMap[Int]) =>
       (a + b) shouldEqual (b + a)
@@ -0,0 +1,312 @@
+//! Consts and functions used to run the `build` subcommand, which builds the Wasm contracts of a
+//! package or workspace, strips their custom sections and checks that they would be accepted by
+//! the execution engine.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    str,
+};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use parity_wasm::elements::{Internal, Module, Section};
+use serde_json::Value;
+
+use engine_wasm_prep::{
+    wasm_costs::WasmCosts, wasm_limits::WasmLimits, PreprocessingError, Preprocessor,
+};
+
+use crate::common;
+
+pub const SUBCOMMAND_NAME: &str = "build";
+const ABOUT: &str =
+    "Builds the Wasm contracts of the package or workspace at <path>, strips their custom \
+     sections and checks that they would be accepted by the CasperLabs Platform";
+
+const PATH_ARG_NAME: &str = "path";
+const PATH_ARG_VALUE_NAME: &str = "path";
+const PATH_ARG_DEFAULT: &str = ".";
+const PATH_ARG_HELP: &str = "Path to the package or workspace containing the contracts";
+
+const BUILD_ARGS: [&str; 5] = [
+    "build",
+    "--release",
+    "--target",
+    "wasm32-unknown-unknown",
+    "--message-format=json-render-diagnostics",
+];
+const COMPILER_ARTIFACT: &str = "compiler-artifact";
+const WASM_EXTENSION: &str = "wasm";
+const SESSION_ENTRY_POINT: &str = "call";
+
+/// The args of the `build` subcommand.
+#[derive(Debug)]
+pub struct Args {
+    path: PathBuf,
+}
+
+impl Args {
+    pub fn new(arg_matches: &ArgMatches) -> Self {
+        let path = arg_matches
+            .value_of(PATH_ARG_NAME)
+            .expect("expected path")
+            .into();
+        Args { path }
+    }
+}
+
+pub fn subcommand() -> App<'static, 'static> {
+    let path_arg = Arg::with_name(PATH_ARG_NAME)
+        .value_name(PATH_ARG_VALUE_NAME)
+        .default_value(PATH_ARG_DEFAULT)
+        .help(PATH_ARG_HELP);
+
+    SubCommand::with_name(SUBCOMMAND_NAME)
+        .about(ABOUT)
+        .arg(path_arg)
+}
+
+/// Runs `cargo build` for the Wasm target, returning the paths of the Wasm files built.
+fn build(path: &Path) -> Vec<PathBuf> {
+    let mut command = Command::new("cargo");
+    command
+        .args(&BUILD_ARGS)
+        .current_dir(path)
+        .stderr(Stdio::inherit());
+
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(error) => {
+            common::print_error_and_exit(&format!(": failed to run '{:?}': {}", command, error))
+        }
+    };
+
+    if !output.status.success() {
+        common::print_error_and_exit(&format!(": failed to run '{:?}'", command));
+    }
+
+    let stdout = str::from_utf8(&output.stdout).expect("should be valid UTF8");
+    let mut wasm_files = Vec::new();
+    for message in stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+    {
+        if message["reason"] != COMPILER_ARTIFACT {
+            continue;
+        }
+        let filenames = message["filenames"].as_array().into_iter().flatten();
+        for filename in filenames.filter_map(Value::as_str).map(PathBuf::from) {
+            if filename
+                .extension()
+                .and_then(|extension| extension.to_str())
+                == Some(WASM_EXTENSION)
+                && !wasm_files.contains(&filename)
+            {
+                wasm_files.push(filename);
+            }
+        }
+    }
+    wasm_files
+}
+
+/// Removes the custom sections (e.g. names and debug info) from `module`, as they're not needed
+/// for execution.
+fn strip_custom_sections(module: &mut Module) {
+    module.sections_mut().retain(|section| match section {
+        Section::Custom(_) | Section::Name(_) | Section::Reloc(_) => false,
+        _ => true,
+    });
+}
+
+/// Returns the names of the functions exported by `module`.
+fn exported_functions(module: &Module) -> Vec<String> {
+    module
+        .export_section()
+        .map(|export_section| {
+            export_section
+                .entries()
+                .iter()
+                .filter(|entry| match entry.internal() {
+                    Internal::Function(_) => true,
+                    _ => false,
+                })
+                .map(|entry| entry.field().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Checks that `module_bytes` would be accepted when deployed, by preprocessing it as the
+/// execution engine does and instantiating it against the host functions.
+fn check(module_bytes: &[u8]) -> Result<(), PreprocessingError> {
+    Preprocessor::new(WasmCosts::default(), WasmLimits::default()).check(module_bytes)
+}
+
+/// Strips the custom sections from the Wasm file at `wasm_file` and checks it would be accepted
+/// when deployed, then prints its size and exported functions.
+fn strip_and_check(wasm_file: &Path) {
+    let original_bytes = match fs::read(wasm_file) {
+        Ok(bytes) => bytes,
+        Err(error) => common::print_error_and_exit(&format!(
+            ": failed to read '{}': {}",
+            wasm_file.display(),
+            error
+        )),
+    };
+
+    let mut module = match engine_wasm_prep::deserialize(&original_bytes) {
+        Ok(module) => module,
+        Err(error) => common::print_error_and_exit(&format!(
+            ": failed to parse '{}': {}",
+            wasm_file.display(),
+            error
+        )),
+    };
+    strip_custom_sections(&mut module);
+    let exported_functions = exported_functions(&module);
+    let module_bytes = match parity_wasm::serialize(module) {
+        Ok(bytes) => bytes,
+        Err(error) => common::print_error_and_exit(&format!(
+            ": failed to serialize '{}': {}",
+            wasm_file.display(),
+            error
+        )),
+    };
+    common::write_file(wasm_file, &module_bytes);
+
+    if let Err(error) = check(&module_bytes) {
+        common::print_error_and_exit(&format!(
+            ": '{}' would be rejected when deployed: {}",
+            wasm_file.display(),
+            error
+        ));
+    }
+
+    let (session_entry_points, contract_entry_points): (Vec<_>, Vec<_>) = exported_functions
+        .into_iter()
+        .partition(|name| name == SESSION_ENTRY_POINT);
+    let list = |names: Vec<String>| {
+        if names.is_empty() {
+            "none".to_string()
+        } else {
+            names.join(", ")
+        }
+    };
+    println!("{}", wasm_file.display());
+    println!(
+        "    size: {} bytes (stripped from {} bytes)",
+        module_bytes.len(),
+        original_bytes.len()
+    );
+    println!("    session entry point: {}", list(session_entry_points));
+    println!("    contract entry points: {}", list(contract_entry_points));
+}
+
+pub fn run(args: &Args) {
+    let wasm_files = build(&args.path);
+    if wasm_files.is_empty() {
+        common::print_error_and_exit(&format!(
+            ": no Wasm files were built in '{}'",
+            args.path.display()
+        ));
+    }
+
+    for wasm_file in &wasm_files {
+        strip_and_check(wasm_file);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{
+        builder,
+        elements::{CustomSection, Instruction, Instructions},
+    };
+
+    use super::*;
+
+    fn module(instructions: Vec<Instruction>, import: Option<&str>) -> Module {
+        // Imported functions precede the module's own functions in the function index space.
+        let call_index = if import.is_some() { 1 } else { 0 };
+        let mut module_builder = builder::module()
+            .memory()
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(instructions))
+            .build()
+            .build()
+            .export()
+            .field(SESSION_ENTRY_POINT)
+            .internal()
+            .func(call_index)
+            .build();
+        if let Some(field) = import {
+            module_builder = module_builder
+                .import()
+                .module("env")
+                .field(field)
+                .external()
+                .func(0)
+                .build();
+        }
+        module_builder.build()
+    }
+
+    fn module_bytes(module: Module) -> Vec<u8> {
+        parity_wasm::serialize(module).unwrap()
+    }
+
+    #[test]
+    fn should_strip_custom_sections() {
+        let mut module = module(vec![Instruction::End], None);
+        module
+            .sections_mut()
+            .push(Section::Custom(CustomSection::new(
+                "producers".to_string(),
+                vec![1, 2, 3],
+            )));
+        assert!(module.custom_sections().next().is_some());
+
+        strip_custom_sections(&mut module);
+        assert!(module.custom_sections().next().is_none());
+        assert_eq!(
+            exported_functions(&module),
+            vec![SESSION_ENTRY_POINT.to_string()]
+        );
+        check(&module_bytes(module)).expect("should pass checks");
+    }
+
+    #[test]
+    fn should_reject_float_instructions() {
+        let module = module(
+            vec![
+                Instruction::F32Const(0),
+                Instruction::Drop,
+                Instruction::End,
+            ],
+            None,
+        );
+        match check(&module_bytes(module)) {
+            Err(PreprocessingError::FloatingPointInstruction {
+                instruction: Instruction::F32Const(0),
+                ..
+            }) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_reject_unknown_host_import() {
+        let module = module(vec![Instruction::End], Some("not_a_host_function"));
+        match check(&module_bytes(module)) {
+            Err(PreprocessingError::UnknownHostFunction(name)) => {
+                assert_eq!(name, "not_a_host_function")
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
@@ -23,10 +23,29 @@ pub fn print_error_and_exit(msg: &str) -> ! {
     process::exit(FAILURE_EXIT_CODE)
 }
 
+/// A package or workspace generated by the tool which builds Wasm contracts.
+pub struct WasmBuild {
+    /// Path of the package or workspace, relative to the root path.
+    pub path: String,
+    /// Source files of the contracts, relative to the root path.
+    pub sources: Vec<String>,
+    /// Names of the Wasm files it builds.
+    pub wasm_files: Vec<String>,
+}
+
 pub fn run_cargo_new(package_name: &str) {
+    run_cargo_new_with_args(package_name, &[]);
+}
+
+pub fn run_cargo_new_lib(package_name: &str) {
+    run_cargo_new_with_args(package_name, &["--lib"]);
+}
+
+fn run_cargo_new_with_args(package_name: &str, args: &[&str]) {
     let mut command = Command::new("cargo");
     command
         .args(&["new", "--vcs", "none"])
+        .args(args)
         .arg(package_name)
         .current_dir(ARGS.root_path());
 
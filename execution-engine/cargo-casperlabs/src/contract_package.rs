@@ -1,18 +1,16 @@
-//! Consts and functions used to generate the files comprising the "contract" package when running
-//! the tool.
+//! Consts and functions used to generate the files comprising a contract package when running the
+//! tool.
 
 use std::path::PathBuf;
 
-use lazy_static::lazy_static;
-
 use crate::{
-    common::{self, CL_CONTRACT, CL_TYPES},
+    common::{self, WasmBuild, CL_CONTRACT, CL_TYPES},
     ARGS, TOOLCHAIN,
 };
 
 const PACKAGE_NAME: &str = "contract";
 
-const MAIN_RS_CONTENTS: &str = r#"#![cfg_attr(
+pub const HELLO_WORLD_MAIN_RS: &str = r#"#![cfg_attr(
     not(target_arch = "wasm32"),
     crate_type = "target arch should be wasm32"
 )]
@@ -50,13 +48,81 @@ const CONFIG_CONTENTS: &str = r#"[build]
 target = "wasm32-unknown-unknown"
 "#;
 
-lazy_static! {
-    static ref CARGO_TOML: PathBuf = ARGS.root_path().join(PACKAGE_NAME).join("Cargo.toml");
-    static ref RUST_TOOLCHAIN: PathBuf = ARGS.root_path().join(PACKAGE_NAME).join("rust-toolchain");
-    static ref MAIN_RS: PathBuf = ARGS.root_path().join(PACKAGE_NAME).join("src/main.rs");
-    static ref CONFIG: PathBuf = ARGS.root_path().join(PACKAGE_NAME).join(".cargo/config");
-    static ref CARGO_TOML_ADDITIONAL_CONTENTS: String = format!(
-        r#"{}
+const PROFILE_CONTENTS: &str = r#"
+[profile.release]
+lto = true
+"#;
+
+/// The location of a contract package generated by the tool.
+pub struct ContractPackage {
+    /// Path of the package, relative to the root path.
+    pub path: &'static str,
+    /// Name of the package's binary, and so of its Wasm file.
+    pub bin_name: &'static str,
+}
+
+impl ContractPackage {
+    /// Returns the build of a standalone package.
+    pub fn wasm_build(&self) -> WasmBuild {
+        WasmBuild {
+            path: self.path.to_string(),
+            sources: self.sources(),
+            wasm_files: vec![self.wasm_file()],
+        }
+    }
+
+    /// Returns the package's source files, relative to the root path.
+    pub fn sources(&self) -> Vec<String> {
+        vec![
+            format!("{}/Cargo.toml", self.path),
+            format!("{}/src/main.rs", self.path),
+        ]
+    }
+
+    /// Returns the name of the package's Wasm file.
+    pub fn wasm_file(&self) -> String {
+        format!("{}.wasm", self.bin_name)
+    }
+
+    fn root(&self) -> PathBuf {
+        ARGS.root_path().join(self.path)
+    }
+}
+
+/// The contract package of the default template.
+pub const HELLO_WORLD: ContractPackage = ContractPackage {
+    path: PACKAGE_NAME,
+    bin_name: PACKAGE_NAME,
+};
+
+/// Generates a standalone contract package with the given `main.rs`.
+pub fn create(package: &ContractPackage, main_rs: &str) {
+    create_workspace_member(package, main_rs, "");
+    common::append_to_file(package.root().join("Cargo.toml"), PROFILE_CONTENTS);
+    common::write_file(
+        package.root().join("rust-toolchain"),
+        format!("{}\n", TOOLCHAIN),
+    );
+    let config = package.root().join(".cargo/config");
+    common::create_dir_all(config.parent().expect("should have parent"));
+    common::write_file(config, CONFIG_CONTENTS);
+}
+
+/// Generates a contract package with the given `main.rs` as a member of a workspace, which
+/// provides its release profile and toolchain.  `dependencies` are added to the package's
+/// dependencies.
+pub fn create_workspace_member(package: &ContractPackage, main_rs: &str, dependencies: &str) {
+    common::run_cargo_new(package.path);
+    common::append_to_file(
+        package.root().join("Cargo.toml"),
+        cargo_toml_additional_contents(package.bin_name, dependencies),
+    );
+    common::write_file(package.root().join("src/main.rs"), main_rs);
+}
+
+fn cargo_toml_additional_contents(bin_name: &str, dependencies: &str) -> String {
+    format!(
+        r#"{}{}
 {}
 
 [[bin]]
@@ -68,32 +134,7 @@ test = false
 
 [features]
 default = ["casperlabs-contract/std", "casperlabs-types/std", "casperlabs-contract/test-support"]
-
-[profile.release]
-lto = true
 "#,
-        *CL_CONTRACT, *CL_TYPES, PACKAGE_NAME
-    );
-}
-
-pub fn run_cargo_new() {
-    common::run_cargo_new(PACKAGE_NAME);
-}
-
-pub fn update_cargo_toml() {
-    common::append_to_file(&*CARGO_TOML, &*CARGO_TOML_ADDITIONAL_CONTENTS);
-}
-
-pub fn add_rust_toolchain() {
-    common::write_file(&*RUST_TOOLCHAIN, format!("{}\n", TOOLCHAIN));
-}
-
-pub fn update_main_rs() {
-    common::write_file(&*MAIN_RS, MAIN_RS_CONTENTS);
-}
-
-pub fn add_config() {
-    let folder = CONFIG.parent().expect("should have parent");
-    common::create_dir_all(folder);
-    common::write_file(&*CONFIG, CONFIG_CONTENTS);
+        dependencies, *CL_CONTRACT, *CL_TYPES, bin_name
+    )
 }
@@ -7,14 +7,22 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use clap::{crate_version, App, Arg};
+use clap::{crate_version, App, AppSettings, Arg};
 use lazy_static::lazy_static;
 
+mod build_contracts;
 pub mod common;
 mod contract_package;
 pub mod dependency;
+mod makefile;
+mod template;
 mod tests_package;
+mod token;
 mod travis_yml;
+mod versioned;
+mod workspace;
+
+use template::Template;
 
 const APP_NAME: &str = "cargo-casperlabs";
 const ABOUT: &str =
@@ -32,6 +40,16 @@ const USE_SYSTEM_CONTRACTS_ARG_SHORT: &str = "z";
 const USE_SYSTEM_CONTRACTS_ARG_HELP: &str =
     "Use system contracts instead of host-side logic for Mint, Proof of Stake and Standard Payment";
 
+const TEMPLATE_ARG_NAME: &str = "template";
+const TEMPLATE_ARG_LONG: &str = "template";
+const TEMPLATE_ARG_SHORT: &str = "t";
+const TEMPLATE_ARG_VALUE_NAME: &str = "template";
+const TEMPLATE_ARG_HELP: &str = "Template of the contracts and tests to create:\n\
+    hello-world: a contract storing a message\n\
+    versioned: a stored contract and a session which upgrades it to a new version\n\
+    workspace: a workspace of contracts sharing a library crate\n\
+    token: a token contract with minting restricted to a user group\n";
+
 const WORKSPACE_PATH_ARG_NAME: &str = "workspace-path";
 const WORKSPACE_PATH_ARG_LONG: &str = "workspace-path";
 
@@ -53,7 +71,9 @@ lazy_static! {
 struct Args {
     root_path: PathBuf,
     use_system_contracts: bool,
+    template: Template,
     workspace_path: Option<PathBuf>,
+    build_args: Option<build_contracts::Args>,
 }
 
 impl Args {
@@ -85,6 +105,14 @@ impl Args {
             .short(USE_SYSTEM_CONTRACTS_ARG_SHORT)
             .help(USE_SYSTEM_CONTRACTS_ARG_HELP);
 
+        let template_arg = Arg::with_name(TEMPLATE_ARG_NAME)
+            .long(TEMPLATE_ARG_LONG)
+            .short(TEMPLATE_ARG_SHORT)
+            .value_name(TEMPLATE_ARG_VALUE_NAME)
+            .possible_values(&Template::NAMES)
+            .default_value(Template::HelloWorld.name())
+            .help(TEMPLATE_ARG_HELP);
+
         let workspace_path_arg = Arg::with_name(WORKSPACE_PATH_ARG_NAME)
             .long(WORKSPACE_PATH_ARG_LONG)
             .takes_value(true)
@@ -94,18 +122,32 @@ impl Args {
             .version(crate_version!())
             .about(ABOUT)
             .usage(USAGE.as_str())
+            .setting(AppSettings::SubcommandsNegateReqs)
             .arg(root_path_arg)
             .arg(use_system_contracts_arg)
+            .arg(template_arg)
             .arg(workspace_path_arg)
+            .subcommand(build_contracts::subcommand())
             .get_matches_from(filtered_args_iter);
 
+        let build_args = arg_matches
+            .subcommand_matches(build_contracts::SUBCOMMAND_NAME)
+            .map(build_contracts::Args::new);
+
+        // The path is only optional if a subcommand is given.
         let root_path = arg_matches
             .value_of(ROOT_PATH_ARG_NAME)
-            .expect("expected path")
-            .into();
+            .map(PathBuf::from)
+            .unwrap_or_default();
 
         let use_system_contracts = arg_matches.is_present(USE_SYSTEM_CONTRACTS_ARG_NAME);
 
+        let template = arg_matches
+            .value_of(TEMPLATE_ARG_NAME)
+            .expect("expected template")
+            .parse()
+            .expect("expected valid template");
+
         let workspace_path = arg_matches
             .value_of(WORKSPACE_PATH_ARG_NAME)
             .map(PathBuf::from);
@@ -113,7 +155,9 @@ impl Args {
         Args {
             root_path,
             use_system_contracts,
+            template,
             workspace_path,
+            build_args,
         }
     }
 
@@ -125,12 +169,25 @@ impl Args {
         self.use_system_contracts
     }
 
+    pub fn template(&self) -> Template {
+        self.template
+    }
+
     pub fn workspace_path(&self) -> Option<&Path> {
         self.workspace_path.as_deref()
     }
+
+    pub fn build_args(&self) -> Option<&build_contracts::Args> {
+        self.build_args.as_ref()
+    }
 }
 
 fn main() {
+    if let Some(build_args) = ARGS.build_args() {
+        build_contracts::run(build_args);
+        return;
+    }
+
     if ARGS.root_path().exists() {
         common::print_error_and_exit(&format!(
             ": destination '{}' already exists",
@@ -140,18 +197,7 @@ fn main() {
 
     common::create_dir_all(ARGS.root_path());
 
-    contract_package::run_cargo_new();
-    contract_package::update_cargo_toml();
-    contract_package::add_rust_toolchain();
-    contract_package::update_main_rs();
-    contract_package::add_config();
-
-    tests_package::run_cargo_new();
-    tests_package::update_cargo_toml(ARGS.use_system_contracts());
-    tests_package::add_rust_toolchain();
-    tests_package::add_build_rs();
-    tests_package::replace_main_rs();
-    tests_package::copy_wasm_files();
+    ARGS.template().create();
 
     travis_yml::create();
 }
@@ -0,0 +1,75 @@
+//! Consts and functions used to generate the Makefile when running the tool.
+
+use crate::{common, common::WasmBuild, ARGS, TOOLCHAIN};
+
+const FILENAME: &str = "Makefile";
+const TESTS_PATH: &str = "tests";
+
+/// Returns the command to run `command` in `path`, relative to the root path.
+fn in_path(path: &str, command: &str) -> String {
+    if path == "." {
+        command.to_string()
+    } else {
+        format!("cd {} && {}", path, command)
+    }
+}
+
+/// Returns the contents of a Makefile which builds optimised Wasm for `builds`, and runs the
+/// tests.
+fn contents(builds: &[WasmBuild]) -> String {
+    let mut build_commands = String::new();
+    let mut strip_commands = String::new();
+    let mut clean_commands = String::new();
+    for build in builds {
+        build_commands.push_str(&format!(
+            "\t{}\n",
+            in_path(&build.path, "cargo build --release --target $(WASM_TARGET)")
+        ));
+        for wasm_file in &build.wasm_files {
+            strip_commands.push_str(&format!(
+                "\t$(WASM_STRIP) {}/target/$(WASM_TARGET)/release/{}\n",
+                build.path, wasm_file
+            ));
+        }
+        clean_commands.push_str(&format!("\t{}\n", in_path(&build.path, "cargo clean")));
+    }
+    clean_commands.push_str(&format!("\t{}\n", in_path(TESTS_PATH, "cargo clean")));
+
+    format!(
+        r#"RUST_TOOLCHAIN := {}
+WASM_TARGET    := wasm32-unknown-unknown
+WASM_STRIP     := $(shell command -v wasm-strip 2> /dev/null)
+
+.PHONY: all
+all: build-contracts
+
+.PHONY: prepare
+prepare:
+	rustup install $(RUST_TOOLCHAIN)
+	rustup target add --toolchain $(RUST_TOOLCHAIN) $(WASM_TARGET)
+
+# Builds the contracts with link-time optimisation, then strips their custom sections (e.g. names
+# and debug info) if `wasm-strip` from WABT is installed.
+.PHONY: build-contracts
+build-contracts:
+{}ifdef WASM_STRIP
+{}endif
+
+.PHONY: test
+test: build-contracts
+	{}
+
+.PHONY: clean
+clean:
+{}"#,
+        TOOLCHAIN,
+        build_commands,
+        strip_commands,
+        in_path(TESTS_PATH, "cargo test"),
+        clean_commands
+    )
+}
+
+pub fn create(builds: &[WasmBuild]) {
+    common::write_file(ARGS.root_path().join(FILENAME), contents(builds));
+}
@@ -0,0 +1,89 @@
+//! The templates of project which the tool can generate.
+
+use std::str::FromStr;
+
+use crate::{contract_package, makefile, tests_package, token, versioned, workspace, ARGS};
+
+/// A template of project.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Template {
+    /// A contract storing a message, and its tests.
+    HelloWorld,
+    /// A stored contract in a contract package, an upgrader session which adds a new version of
+    /// the contract, and their tests.
+    Versioned,
+    /// A workspace of contracts sharing a library crate, and their tests.
+    Workspace,
+    /// A token contract with access to minting restricted to a user group, and its tests.
+    Token,
+}
+
+impl Template {
+    /// The names of all templates, as passed to the `--template` arg.
+    pub const NAMES: [&'static str; 4] = ["hello-world", "versioned", "workspace", "token"];
+
+    /// Returns the name of the template, as passed to the `--template` arg.
+    pub fn name(self) -> &'static str {
+        match self {
+            Template::HelloWorld => Self::NAMES[0],
+            Template::Versioned => Self::NAMES[1],
+            Template::Workspace => Self::NAMES[2],
+            Template::Token => Self::NAMES[3],
+        }
+    }
+
+    /// Generates the files of the template at the root path.
+    pub fn create(self) {
+        match self {
+            Template::HelloWorld => create_hello_world(),
+            Template::Versioned => versioned::create(),
+            Template::Workspace => workspace::create(),
+            Template::Token => token::create(),
+        }
+    }
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        [
+            Template::HelloWorld,
+            Template::Versioned,
+            Template::Workspace,
+            Template::Token,
+        ]
+        .iter()
+        .find(|template| template.name() == name)
+        .copied()
+        .ok_or_else(|| format!("unknown template '{}'", name))
+    }
+}
+
+fn create_hello_world() {
+    let package = &contract_package::HELLO_WORLD;
+    contract_package::create(package, contract_package::HELLO_WORLD_MAIN_RS);
+
+    let builds = [package.wasm_build()];
+    tests_package::create(
+        ARGS.use_system_contracts(),
+        &builds,
+        tests_package::HELLO_WORLD_INTEGRATION_TESTS_RS,
+        "",
+    );
+    makefile::create(&builds);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_all_template_names() {
+        for name in Template::NAMES.iter() {
+            let template: Template = name.parse().unwrap();
+            assert_eq!(template.name(), *name);
+        }
+        assert!("unknown".parse::<Template>().is_err());
+    }
+}
@@ -6,7 +6,7 @@ use std::path::{Path, PathBuf};
 use lazy_static::lazy_static;
 
 use crate::{
-    common::{self, CL_CONTRACT, CL_TYPES},
+    common::{self, WasmBuild, CL_CONTRACT, CL_TYPES},
     dependency::Dependency,
     ARGS, TOOLCHAIN,
 };
@@ -17,7 +17,7 @@ const POS_INSTALL: &str = "pos_install.wasm";
 const STANDARD_PAYMENT: &str = "standard_payment.wasm";
 const STANDARD_PAYMENT_INSTALL: &str = "standard_payment_install.wasm";
 
-const INTEGRATION_TESTS_RS_CONTENTS: &str = r#"#[cfg(test)]
+pub const HELLO_WORLD_INTEGRATION_TESTS_RS: &str = r#"#[cfg(test)]
 mod tests {
     use casperlabs_engine_test_support::{Code, Error, SessionBuilder, TestContextBuilder, Value};
     use casperlabs_types::{account::AccountHash, U512, RuntimeArgs, runtime_args};
@@ -60,40 +60,44 @@ fn main() {
 }
 "#;
 
-const BUILD_RS_CONTENTS: &str = r#"use std::{env, fs, path::PathBuf, process::Command};
+const BUILD_RS_HEADER: &str = r#"use std::{env, fs, path::PathBuf, process::Command};
 
-const CONTRACT_ROOT: &str = "../contract";
-const CONTRACT_CARGO_TOML: &str = "../contract/Cargo.toml";
-const CONTRACT_MAIN_RS: &str = "../contract/src/main.rs";
-const BUILD_ARGS: [&str; 2] = ["build", "--release"];
-const WASM_FILENAME: &str = "contract.wasm";
-const ORIGINAL_WASM_DIR: &str = "../contract/target/wasm32-unknown-unknown/release";
+const BUILD_ARGS: [&str; 4] = ["build", "--release", "--target", "wasm32-unknown-unknown"];
+const ORIGINAL_WASM_DIR: &str = "target/wasm32-unknown-unknown/release";
 const NEW_WASM_DIR: &str = "wasm";
+"#;
 
-fn main() {
+const BUILD_RS_MAIN: &str = r#"fn main() {
     // Watch contract source files for changes.
-    println!("cargo:rerun-if-changed={}", CONTRACT_CARGO_TOML);
-    println!("cargo:rerun-if-changed={}", CONTRACT_MAIN_RS);
-
-    // Build the contract.
-    let output = Command::new("cargo")
-        .current_dir(CONTRACT_ROOT)
-        .args(&BUILD_ARGS)
-        .output()
-        .expect("Expected to build Wasm contracts");
-    assert!(
-        output.status.success(),
-        "Failed to build Wasm contracts:\n{:?}",
-        output
-    );
+    for source in SOURCES {
+        println!("cargo:rerun-if-changed={}", source);
+    }
 
-    // Move the compiled Wasm file to our own build folder ("wasm/contract.wasm").
     let new_wasm_dir = env::current_dir().unwrap().join(NEW_WASM_DIR);
     let _ = fs::create_dir(&new_wasm_dir);
 
-    let original_wasm_file = PathBuf::from(ORIGINAL_WASM_DIR).join(WASM_FILENAME);
-    let copied_wasm_file = new_wasm_dir.join(WASM_FILENAME);
-    fs::copy(original_wasm_file, copied_wasm_file).unwrap();
+    for (root, wasm_filenames) in CONTRACTS {
+        // Build the contracts.
+        let output = Command::new("cargo")
+            .current_dir(root)
+            .args(&BUILD_ARGS)
+            .output()
+            .expect("Expected to build Wasm contracts");
+        assert!(
+            output.status.success(),
+            "Failed to build Wasm contracts:\n{:?}",
+            output
+        );
+
+        // Move the compiled Wasm files to our own build folder ("wasm").
+        for wasm_filename in wasm_filenames.iter() {
+            let original_wasm_file = PathBuf::from(root)
+                .join(ORIGINAL_WASM_DIR)
+                .join(wasm_filename);
+            let copied_wasm_file = new_wasm_dir.join(wasm_filename);
+            fs::copy(original_wasm_file, copied_wasm_file).unwrap();
+        }
+    }
 }
 "#;
 
@@ -130,13 +134,26 @@ default = ["casperlabs-contract/std", "casperlabs-types/std", "casperlabs-engine
     static ref WASM_DEST_DIR: PathBuf = ARGS.root_path().join(PACKAGE_NAME).join("wasm");
 }
 
-pub fn run_cargo_new() {
+/// Generates the tests package, where `integration_tests_rs` tests the contracts built by
+/// `builds`, and `dependencies` are added to the package's (non-dev) dependencies.
+pub fn create(
+    use_system_contracts: bool,
+    builds: &[WasmBuild],
+    integration_tests_rs: &str,
+    dependencies: &str,
+) {
     common::run_cargo_new(PACKAGE_NAME);
+    update_cargo_toml(use_system_contracts, dependencies);
+    add_rust_toolchain();
+    add_build_rs(builds);
+    replace_main_rs(integration_tests_rs);
+    copy_wasm_files();
 }
 
-pub fn update_cargo_toml(use_system_contracts: bool) {
+fn update_cargo_toml(use_system_contracts: bool, dependencies: &str) {
     let cargo_toml_additional_contents = format!(
-        "{}{}\n",
+        "{}{}{}\n",
+        dependencies,
         &*CARGO_TOML_ADDITIONAL_CONTENTS,
         if use_system_contracts {
             ", \"casperlabs-engine-test-support/use-system-contracts\"]"
@@ -147,20 +164,48 @@ pub fn update_cargo_toml(use_system_contracts: bool) {
     common::append_to_file(&*CARGO_TOML, cargo_toml_additional_contents);
 }
 
-pub fn add_rust_toolchain() {
+fn add_rust_toolchain() {
     common::write_file(&*RUST_TOOLCHAIN, format!("{}\n", TOOLCHAIN));
 }
 
-pub fn add_build_rs() {
-    common::write_file(&*BUILD_RS, BUILD_RS_CONTENTS);
+fn add_build_rs(builds: &[WasmBuild]) {
+    common::write_file(&*BUILD_RS, build_rs_contents(builds));
+}
+
+/// Returns the contents of a build script which builds the contracts of `builds` and copies their
+/// Wasm files to the tests package's "wasm" folder.
+fn build_rs_contents(builds: &[WasmBuild]) -> String {
+    let mut sources = String::new();
+    let mut contracts = String::new();
+    for build in builds {
+        for source in &build.sources {
+            sources.push_str(&format!("    \"../{}\",\n", source));
+        }
+        let wasm_files = build
+            .wasm_files
+            .iter()
+            .map(|wasm_file| format!("\"{}\"", wasm_file))
+            .collect::<Vec<_>>()
+            .join(", ");
+        contracts.push_str(&format!(
+            "    (\"../{}\", &[{}]),\n",
+            build.path, wasm_files
+        ));
+    }
+    format!(
+        "{}\n// Contract source files.\nconst SOURCES: &[&str] = &[\n{}];\n\n// Paths of the packages \
+         or workspaces which build the contracts, and the Wasm files each builds.\nconst CONTRACTS: \
+         &[(&str, &[&str])] = &[\n{}];\n\n{}",
+        BUILD_RS_HEADER, sources, contracts, BUILD_RS_MAIN
+    )
 }
 
-pub fn replace_main_rs() {
+fn replace_main_rs(integration_tests_rs: &str) {
     common::remove_file(&*MAIN_RS);
-    common::write_file(&*INTEGRATION_TESTS_RS, INTEGRATION_TESTS_RS_CONTENTS);
+    common::write_file(&*INTEGRATION_TESTS_RS, integration_tests_rs);
 }
 
-pub fn copy_wasm_files() {
+fn copy_wasm_files() {
     common::create_dir_all(&*WASM_DEST_DIR);
     common::copy_file(
         WASM_SRC_DIR.join(MINT_INSTALL),
@@ -0,0 +1,242 @@
+//! Consts and functions used to generate the "token" template: a token contract whose `mint` entry
+//! point can only be called by members of an "admin" user group.
+
+use crate::{
+    contract_package::{self, ContractPackage},
+    makefile, tests_package, ARGS,
+};
+
+const CONTRACT: ContractPackage = ContractPackage {
+    path: "contract",
+    bin_name: "contract",
+};
+
+const CONTRACT_MAIN_RS: &str = r#"#![cfg_attr(
+    not(target_arch = "wasm32"),
+    crate_type = "target arch should be wasm32"
+)]
+#![no_main]
+
+use std::{collections::BTreeSet, convert::TryInto};
+
+use casperlabs_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casperlabs_types::{
+    account::AccountHash,
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys},
+    ApiError, CLType, CLTyped, Parameter, URef, U512,
+};
+
+const PACKAGE_HASH_KEY: &str = "token_package_hash";
+const ACCESS_UREF_KEY: &str = "token_access_uref";
+const CONTRACT_HASH_KEY: &str = "token_contract_hash";
+const ADMIN_UREF_KEY: &str = "token_admin_uref";
+const ADMIN_GROUP: &str = "admin";
+const ENTRY_POINT_MINT: &str = "mint";
+const ENTRY_POINT_TRANSFER: &str = "transfer";
+const ARG_RECIPIENT: &str = "recipient";
+const ARG_AMOUNT: &str = "amount";
+
+#[repr(u16)]
+enum Error {
+    InsufficientBalance = 0,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+// Balances are stored in the contract's named keys, under the account hash in hex.
+fn balance_key(account: AccountHash) -> String {
+    format!("balance_{}", account)
+}
+
+fn balance_uref(account: AccountHash) -> Option<URef> {
+    runtime::get_key(&balance_key(account)).map(|key| key.try_into().unwrap_or_revert())
+}
+
+fn credit(account: AccountHash, amount: U512) {
+    match balance_uref(account) {
+        Some(uref) => storage::add(uref, amount),
+        None => runtime::put_key(&balance_key(account), storage::new_uref(amount).into()),
+    }
+}
+
+fn debit(account: AccountHash, amount: U512) {
+    let uref = balance_uref(account).unwrap_or_revert_with(Error::InsufficientBalance);
+    let balance: U512 = storage::read(uref).unwrap_or_revert().unwrap_or_revert();
+    if balance < amount {
+        runtime::revert(Error::InsufficientBalance);
+    }
+    storage::write(uref, balance - amount);
+}
+
+// Creates `amount` new tokens owned by `recipient`.  Only callable by members of the admin group.
+#[no_mangle]
+pub extern "C" fn mint() {
+    let recipient: AccountHash = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    credit(recipient, amount);
+}
+
+// Transfers `amount` of the caller's tokens to `recipient`.
+#[no_mangle]
+pub extern "C" fn transfer() {
+    let recipient: AccountHash = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    debit(runtime::get_caller(), amount);
+    credit(recipient, amount);
+}
+
+fn entry_points() -> EntryPoints {
+    let args = vec![
+        Parameter::new(ARG_RECIPIENT, AccountHash::cl_type()),
+        Parameter::new(ARG_AMOUNT, CLType::U512),
+    ];
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_MINT,
+        args.clone(),
+        CLType::Unit,
+        EntryPointAccess::groups(&[ADMIN_GROUP]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_TRANSFER,
+        args,
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+// Installs the token contract, making the installing account its admin.
+#[no_mangle]
+pub extern "C" fn call() {
+    let (contract_package_hash, access_uref) = storage::create_contract_package_at_hash();
+    runtime::put_key(PACKAGE_HASH_KEY, contract_package_hash.into());
+    runtime::put_key(ACCESS_UREF_KEY, access_uref.into());
+
+    // Create the admin group with a single new URef.  Only callers holding a URef of the group can
+    // call `mint`, so the URef is kept in the installing account's named keys.  More admins can be
+    // added later with `storage::provision_contract_user_group_uref`.
+    let admin_urefs =
+        storage::create_contract_user_group(contract_package_hash, ADMIN_GROUP, 1, BTreeSet::new())
+            .unwrap_or_revert();
+    runtime::put_key(ADMIN_UREF_KEY, admin_urefs[0].into());
+
+    let (contract_hash, _version) =
+        storage::add_contract_version(contract_package_hash, entry_points(), NamedKeys::new());
+    runtime::put_key(CONTRACT_HASH_KEY, contract_hash.into());
+}
+"#;
+
+const INTEGRATION_TESTS_RS: &str = r#"#[cfg(test)]
+mod tests {
+    use casperlabs_engine_test_support::{
+        Code, RunResult, SessionBuilder, TestContext, TestContextBuilder, Value,
+    };
+    use casperlabs_types::{
+        account::AccountHash, runtime_args, ApiError, Key, RuntimeArgs, U512,
+    };
+
+    const ADMIN: AccountHash = AccountHash::new([7u8; 32]);
+    const USER: AccountHash = AccountHash::new([8u8; 32]);
+    // define constants to match those in the contract
+    const PACKAGE_HASH_KEY: &str = "token_package_hash";
+    const CONTRACT_HASH_KEY: &str = "token_contract_hash";
+    const ENTRY_POINT_MINT: &str = "mint";
+    const ENTRY_POINT_TRANSFER: &str = "transfer";
+    const ARG_RECIPIENT: &str = "recipient";
+    const ARG_AMOUNT: &str = "amount";
+    const ERROR_INSUFFICIENT_BALANCE: u16 = 0;
+
+    fn run(context: &mut TestContext, account: AccountHash, code: Code, args: RuntimeArgs) -> RunResult {
+        let session = SessionBuilder::new(code, args)
+            .with_address(account)
+            .with_authorization_keys(&[account])
+            .without_expect_success()
+            .build();
+        context.run_with_result(session)
+    }
+
+    fn call(
+        context: &mut TestContext,
+        account: AccountHash,
+        entry_point: &str,
+        recipient: AccountHash,
+        amount: u64,
+    ) -> RunResult {
+        // The package hash is only in the admin's named keys, so is passed to other accounts.
+        let package_hash = context
+            .query(ADMIN, &[PACKAGE_HASH_KEY])
+            .and_then(Value::into_t::<Key>)
+            .expect("should have package hash")
+            .into_seed();
+        let code = Code::VersionedHash(package_hash, None, entry_point.to_string());
+        let args = runtime_args! {
+            ARG_RECIPIENT => recipient,
+            ARG_AMOUNT => U512::from(amount),
+        };
+        run(context, account, code, args)
+    }
+
+    fn balance(context: &TestContext, account: AccountHash) -> U512 {
+        let balance_key = format!("balance_{}", account);
+        context
+            .query(ADMIN, &[CONTRACT_HASH_KEY, balance_key.as_str()])
+            .and_then(Value::into_t)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn should_only_let_admin_mint_and_anyone_transfer() {
+        let mut context = TestContextBuilder::new()
+            .with_account(ADMIN, U512::from(128_000_000))
+            .with_account(USER, U512::from(128_000_000))
+            .build();
+
+        let result = run(&mut context, ADMIN, Code::from("contract.wasm"), RuntimeArgs::new());
+        assert!(result.is_success(), "{:?}", result.error());
+
+        // Only the admin can mint.
+        assert!(call(&mut context, ADMIN, ENTRY_POINT_MINT, ADMIN, 100).is_success());
+        assert!(!call(&mut context, USER, ENTRY_POINT_MINT, USER, 100).is_success());
+        assert_eq!(balance(&context, ADMIN), U512::from(100));
+        assert_eq!(balance(&context, USER), U512::zero());
+
+        // Anyone can transfer their own tokens.
+        assert!(call(&mut context, ADMIN, ENTRY_POINT_TRANSFER, USER, 40).is_success());
+        assert!(call(&mut context, USER, ENTRY_POINT_TRANSFER, ADMIN, 10).is_success());
+        assert_eq!(balance(&context, ADMIN), U512::from(70));
+        assert_eq!(balance(&context, USER), U512::from(30));
+
+        // But not more than they own.
+        let result = call(&mut context, USER, ENTRY_POINT_TRANSFER, ADMIN, 31);
+        assert_eq!(result.api_error(), Some(ApiError::User(ERROR_INSUFFICIENT_BALANCE)));
+        assert_eq!(balance(&context, USER), U512::from(30));
+    }
+}
+
+fn main() {
+    panic!("Execute \"cargo test\" to test the contract, not \"cargo run\".");
+}
+"#;
+
+pub fn create() {
+    contract_package::create(&CONTRACT, CONTRACT_MAIN_RS);
+
+    let builds = [CONTRACT.wasm_build()];
+    tests_package::create(
+        ARGS.use_system_contracts(),
+        &builds,
+        INTEGRATION_TESTS_RS,
+        "",
+    );
+    makefile::create(&builds);
+}
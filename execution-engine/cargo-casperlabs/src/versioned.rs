@@ -0,0 +1,249 @@
+//! Consts and functions used to generate the "versioned" template: a stored counter contract in a
+//! contract package, and an upgrader session which adds a new version of the contract to the
+//! package.
+
+use crate::{
+    contract_package::{self, ContractPackage},
+    makefile, tests_package, ARGS,
+};
+
+const CONTRACT: ContractPackage = ContractPackage {
+    path: "contract",
+    bin_name: "contract",
+};
+
+const UPGRADER: ContractPackage = ContractPackage {
+    path: "upgrader",
+    bin_name: "upgrader",
+};
+
+const CONTRACT_MAIN_RS: &str = r#"#![cfg_attr(
+    not(target_arch = "wasm32"),
+    crate_type = "target arch should be wasm32"
+)]
+#![no_main]
+
+use std::convert::TryInto;
+
+use casperlabs_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casperlabs_types::{
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys},
+    CLType, URef,
+};
+
+const PACKAGE_HASH_KEY: &str = "counter_package_hash";
+const ACCESS_UREF_KEY: &str = "counter_access_uref";
+const COUNT_KEY: &str = "count";
+const ENTRY_POINT_INCREMENT: &str = "increment";
+
+fn count_uref() -> URef {
+    runtime::get_key(COUNT_KEY)
+        .unwrap_or_revert()
+        .try_into()
+        .unwrap_or_revert()
+}
+
+// Increments the count held by the contract.
+#[no_mangle]
+pub extern "C" fn increment() {
+    storage::add(count_uref(), 1);
+}
+
+fn entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_INCREMENT,
+        Vec::new(),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+// Installs version 1 of the counter contract.
+#[no_mangle]
+pub extern "C" fn call() {
+    // Create an empty contract package.  Only holders of its access URef can add new versions of the
+    // contract to it, so the access URef is kept in the account's named keys for the upgrader.
+    let (contract_package_hash, access_uref) = storage::create_contract_package_at_hash();
+    runtime::put_key(PACKAGE_HASH_KEY, contract_package_hash.into());
+    runtime::put_key(ACCESS_UREF_KEY, access_uref.into());
+
+    // The count is kept in the account's named keys too, so that later versions can share it.
+    let count_uref = storage::new_uref(0);
+    runtime::put_key(COUNT_KEY, count_uref.into());
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(COUNT_KEY.to_string(), count_uref.into());
+
+    storage::add_contract_version(contract_package_hash, entry_points(), named_keys);
+}
+"#;
+
+const UPGRADER_MAIN_RS: &str = r#"#![cfg_attr(
+    not(target_arch = "wasm32"),
+    crate_type = "target arch should be wasm32"
+)]
+#![no_main]
+
+use std::convert::TryInto;
+
+use casperlabs_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casperlabs_types::{
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys},
+    CLType, URef,
+};
+
+const PACKAGE_HASH_KEY: &str = "counter_package_hash";
+const COUNT_KEY: &str = "count";
+const ENTRY_POINT_INCREMENT: &str = "increment";
+const ENTRY_POINT_DECREMENT: &str = "decrement";
+
+fn count_uref() -> URef {
+    runtime::get_key(COUNT_KEY)
+        .unwrap_or_revert()
+        .try_into()
+        .unwrap_or_revert()
+}
+
+// Increments the count held by the contract.
+#[no_mangle]
+pub extern "C" fn increment() {
+    storage::add(count_uref(), 1);
+}
+
+// Decrements the count held by the contract.  This entry point is new in version 2.
+#[no_mangle]
+pub extern "C" fn decrement() {
+    storage::add(count_uref(), -1);
+}
+
+fn entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    for name in &[ENTRY_POINT_INCREMENT, ENTRY_POINT_DECREMENT] {
+        entry_points.add_entry_point(EntryPoint::new(
+            *name,
+            Vec::new(),
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+    }
+    entry_points
+}
+
+// Adds version 2 of the counter contract to the package installed by the "contract" session.
+//
+// This must be run by the account which installed the package, as it holds the package's access
+// URef.
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_package_hash = runtime::get_key(PACKAGE_HASH_KEY)
+        .unwrap_or_revert()
+        .into_seed();
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(COUNT_KEY.to_string(), runtime::get_key(COUNT_KEY).unwrap_or_revert());
+
+    storage::add_contract_version(contract_package_hash, entry_points(), named_keys);
+}
+"#;
+
+const INTEGRATION_TESTS_RS: &str = r#"#[cfg(test)]
+mod tests {
+    use casperlabs_engine_test_support::{
+        Code, SessionBuilder, TestContext, TestContextBuilder, Value,
+    };
+    use casperlabs_types::{
+        account::AccountHash, contracts::CONTRACT_INITIAL_VERSION, RuntimeArgs, U512,
+    };
+
+    const MY_ACCOUNT: AccountHash = AccountHash::new([7u8; 32]);
+    // define constants to match those in the contracts
+    const PACKAGE_HASH_KEY: &str = "counter_package_hash";
+    const COUNT_KEY: &str = "count";
+    const ENTRY_POINT_INCREMENT: &str = "increment";
+    const ENTRY_POINT_DECREMENT: &str = "decrement";
+
+    fn run(context: &mut TestContext, code: Code) -> bool {
+        let session = SessionBuilder::new(code, RuntimeArgs::new())
+            .with_address(MY_ACCOUNT)
+            .with_authorization_keys(&[MY_ACCOUNT])
+            .without_expect_success()
+            .build();
+        context.run_with_result(session).is_success()
+    }
+
+    fn call(context: &mut TestContext, version: Option<u32>, entry_point: &str) -> bool {
+        let code = Code::VersionedNamedKey(
+            PACKAGE_HASH_KEY.to_string(),
+            version,
+            entry_point.to_string(),
+        );
+        run(context, code)
+    }
+
+    fn count(context: &TestContext) -> i32 {
+        context
+            .query(MY_ACCOUNT, &[COUNT_KEY])
+            .and_then(Value::into_t)
+            .expect("should have count")
+    }
+
+    #[test]
+    fn should_install_and_upgrade_counter() {
+        let mut context = TestContextBuilder::new()
+            .with_account(MY_ACCOUNT, U512::from(128_000_000))
+            .build();
+
+        // Install version 1, which can only increment the count.
+        assert!(run(&mut context, Code::from("contract.wasm")));
+        assert!(call(&mut context, None, ENTRY_POINT_INCREMENT));
+        assert_eq!(count(&context), 1);
+        assert!(!call(&mut context, None, ENTRY_POINT_DECREMENT));
+
+        // Upgrade to version 2, which becomes the latest version.
+        assert!(run(&mut context, Code::from("upgrader.wasm")));
+        assert!(call(&mut context, None, ENTRY_POINT_DECREMENT));
+        assert_eq!(count(&context), 0);
+
+        // Version 1 can still be called explicitly, and shares the count with version 2.
+        assert!(call(
+            &mut context,
+            Some(CONTRACT_INITIAL_VERSION),
+            ENTRY_POINT_INCREMENT
+        ));
+        assert!(!call(
+            &mut context,
+            Some(CONTRACT_INITIAL_VERSION),
+            ENTRY_POINT_DECREMENT
+        ));
+        assert_eq!(count(&context), 1);
+    }
+}
+
+fn main() {
+    panic!("Execute \"cargo test\" to test the contract, not \"cargo run\".");
+}
+"#;
+
+pub fn create() {
+    contract_package::create(&CONTRACT, CONTRACT_MAIN_RS);
+    contract_package::create(&UPGRADER, UPGRADER_MAIN_RS);
+
+    let builds = [CONTRACT.wasm_build(), UPGRADER.wasm_build()];
+    tests_package::create(
+        ARGS.use_system_contracts(),
+        &builds,
+        INTEGRATION_TESTS_RS,
+        "",
+    );
+    makefile::create(&builds);
+}
@@ -0,0 +1,208 @@
+//! Consts and functions used to generate the "workspace" template: a Cargo workspace of a library
+//! crate shared by two contracts, a stored counter contract and a session which calls it.
+
+use crate::{
+    common::{self, WasmBuild},
+    contract_package::{self, ContractPackage},
+    makefile, tests_package, ARGS, TOOLCHAIN,
+};
+
+const COMMON_PATH: &str = "common";
+const CONTRACTS_PATH: &str = "contracts";
+
+const COUNTER: ContractPackage = ContractPackage {
+    path: "contracts/counter",
+    bin_name: "counter",
+};
+
+const COUNTER_CALL: ContractPackage = ContractPackage {
+    path: "contracts/counter-call",
+    bin_name: "counter_call",
+};
+
+const CARGO_TOML_CONTENTS: &str = r#"[workspace]
+members = [
+    "common",
+    "contracts/counter",
+    "contracts/counter-call",
+]
+# The tests are built for the host rather than for Wasm, so are kept out of the workspace.
+exclude = ["tests"]
+
+[profile.release]
+lto = true
+"#;
+
+const MEMBER_DEPENDENCIES: &str = "common = { path = \"../../common\" }\n";
+const TESTS_DEPENDENCIES: &str = "common = { path = \"../common\" }\n";
+
+const COMMON_LIB_RS: &str = r#"//! Names shared by the contracts in the workspace and their tests.
+
+#![no_std]
+
+/// The name under which the counter contract's hash is stored in the installing account's named
+/// keys.
+pub const COUNTER_HASH_KEY: &str = "counter_hash";
+/// The name under which the count is stored in the counter contract's named keys.
+pub const COUNT_KEY: &str = "count";
+/// The name of the counter contract's entry point which increments the count.
+pub const ENTRY_POINT_INCREMENT: &str = "increment";
+"#;
+
+const COUNTER_MAIN_RS: &str = r#"#![cfg_attr(
+    not(target_arch = "wasm32"),
+    crate_type = "target arch should be wasm32"
+)]
+#![no_main]
+
+use std::convert::TryInto;
+
+use casperlabs_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casperlabs_types::{
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys},
+    CLType, URef,
+};
+use common::{COUNTER_HASH_KEY, COUNT_KEY, ENTRY_POINT_INCREMENT};
+
+// Increments the count held by the contract.
+#[no_mangle]
+pub extern "C" fn increment() {
+    let count_uref: URef = runtime::get_key(COUNT_KEY)
+        .unwrap_or_revert()
+        .try_into()
+        .unwrap_or_revert();
+    storage::add(count_uref, 1);
+}
+
+// Installs the counter contract.
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_INCREMENT,
+        Vec::new(),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(COUNT_KEY.to_string(), storage::new_uref(0).into());
+
+    let (contract_hash, _version) = storage::new_contract(entry_points, Some(named_keys), None, None);
+    runtime::put_key(COUNTER_HASH_KEY, contract_hash.into());
+}
+"#;
+
+const COUNTER_CALL_MAIN_RS: &str = r#"#![cfg_attr(
+    not(target_arch = "wasm32"),
+    crate_type = "target arch should be wasm32"
+)]
+#![no_main]
+
+use casperlabs_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casperlabs_types::RuntimeArgs;
+use common::{COUNTER_HASH_KEY, ENTRY_POINT_INCREMENT};
+
+// Calls the counter contract installed by the "counter" session.
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_hash = runtime::get_key(COUNTER_HASH_KEY)
+        .unwrap_or_revert()
+        .into_seed();
+    runtime::call_contract::<()>(contract_hash, ENTRY_POINT_INCREMENT, RuntimeArgs::new());
+}
+"#;
+
+const INTEGRATION_TESTS_RS: &str = r#"#[cfg(test)]
+mod tests {
+    use casperlabs_engine_test_support::{
+        Code, SessionBuilder, TestContext, TestContextBuilder, Value,
+    };
+    use casperlabs_types::{account::AccountHash, RuntimeArgs, U512};
+    use common::{COUNTER_HASH_KEY, COUNT_KEY};
+
+    const MY_ACCOUNT: AccountHash = AccountHash::new([7u8; 32]);
+
+    fn run(context: &mut TestContext, wasm_file: &str) {
+        let session = SessionBuilder::new(Code::from(wasm_file), RuntimeArgs::new())
+            .with_address(MY_ACCOUNT)
+            .with_authorization_keys(&[MY_ACCOUNT])
+            .build();
+        context.run(session);
+    }
+
+    #[test]
+    fn should_call_counter_from_session() {
+        let mut context = TestContextBuilder::new()
+            .with_account(MY_ACCOUNT, U512::from(128_000_000))
+            .build();
+
+        run(&mut context, "counter.wasm");
+
+        for expected_count in 1..=2 {
+            run(&mut context, "counter_call.wasm");
+            let count: i32 = context
+                .query(MY_ACCOUNT, &[COUNTER_HASH_KEY, COUNT_KEY])
+                .and_then(Value::into_t)
+                .expect("should have count");
+            assert_eq!(count, expected_count);
+        }
+    }
+}
+
+fn main() {
+    panic!("Execute \"cargo test\" to test the contracts, not \"cargo run\".");
+}
+"#;
+
+fn wasm_build() -> WasmBuild {
+    let mut sources = vec![
+        "Cargo.toml".to_string(),
+        format!("{}/Cargo.toml", COMMON_PATH),
+        format!("{}/src/lib.rs", COMMON_PATH),
+    ];
+    sources.extend(COUNTER.sources());
+    sources.extend(COUNTER_CALL.sources());
+    WasmBuild {
+        path: ".".to_string(),
+        sources,
+        wasm_files: vec![COUNTER.wasm_file(), COUNTER_CALL.wasm_file()],
+    }
+}
+
+pub fn create() {
+    // The packages are created before the workspace's Cargo.toml, as `cargo new` would otherwise
+    // try to add them to it.
+    common::run_cargo_new_lib(COMMON_PATH);
+    common::write_file(
+        ARGS.root_path().join(COMMON_PATH).join("src/lib.rs"),
+        COMMON_LIB_RS,
+    );
+
+    common::create_dir_all(ARGS.root_path().join(CONTRACTS_PATH));
+    contract_package::create_workspace_member(&COUNTER, COUNTER_MAIN_RS, MEMBER_DEPENDENCIES);
+    contract_package::create_workspace_member(
+        &COUNTER_CALL,
+        COUNTER_CALL_MAIN_RS,
+        MEMBER_DEPENDENCIES,
+    );
+
+    let builds = [wasm_build()];
+    tests_package::create(
+        ARGS.use_system_contracts(),
+        &builds,
+        INTEGRATION_TESTS_RS,
+        TESTS_DEPENDENCIES,
+    );
+
+    common::write_file(ARGS.root_path().join("Cargo.toml"), CARGO_TOML_CONTENTS);
+    common::write_file(
+        ARGS.root_path().join("rust-toolchain"),
+        format!("{}\n", TOOLCHAIN),
+    );
+    makefile::create(&builds);
+}
@@ -8,6 +8,8 @@ const FAILURE_EXIT_CODE: i32 = 101;
 const SUCCESS_EXIT_CODE: i32 = 0;
 const USE_SYSTEM_CONTRACTS: &str = "--use-system-contracts";
 const TURBO: &str = "turbo";
+const TEMPLATE: &str = "--template";
+const BUILD: &str = "build";
 
 lazy_static! {
     static ref WORKSPACE_PATH_ARG: String =
@@ -48,9 +50,13 @@ fn output_from_command(mut command: Command) -> Output {
     }
 }
 
-fn run_tool_and_resulting_tests(turbo: bool) {
+fn run_tool_and_resulting_tests(turbo: bool, template: Option<&str>) {
     // Run 'cargo-casperlabs <test dir>/<subdir> --workspace-path=<path to EE root>'
-    let subdir = if turbo { TURBO } else { USE_SYSTEM_CONTRACTS };
+    let subdir = match template {
+        Some(template) => template,
+        None if turbo => TURBO,
+        None => USE_SYSTEM_CONTRACTS,
+    };
     let test_dir = TEST_DIR.path().join(subdir);
     let mut tool_cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
     tool_cmd.arg(&test_dir);
@@ -59,6 +65,10 @@ fn run_tool_and_resulting_tests(turbo: bool) {
     if !turbo {
         tool_cmd.arg(&*USE_SYSTEM_CONTRACTS);
     }
+    // And '--template=<template>' if a template other than the default is to be used
+    if let Some(template) = template {
+        tool_cmd.arg(format!("{}={}", TEMPLATE, template));
+    }
     // The CI environment doesn't have a Git user configured, so we can set the env var `USER` for
     // use by 'cargo new' which is called as a subprocess of 'cargo-casperlabs'.
     tool_cmd.env("USER", "tester");
@@ -75,10 +85,46 @@ fn run_tool_and_resulting_tests(turbo: bool) {
 
 #[test]
 fn should_succeed_without_using_system_contracts() {
-    run_tool_and_resulting_tests(true);
+    run_tool_and_resulting_tests(true, None);
 }
 
 #[test]
 fn should_succeed_using_system_contracts() {
-    run_tool_and_resulting_tests(false);
+    run_tool_and_resulting_tests(false, None);
+}
+
+#[test]
+fn should_succeed_with_versioned_template() {
+    run_tool_and_resulting_tests(true, Some("versioned"));
+}
+
+#[test]
+fn should_succeed_with_workspace_template() {
+    run_tool_and_resulting_tests(true, Some("workspace"));
+}
+
+#[test]
+fn should_succeed_with_token_template() {
+    run_tool_and_resulting_tests(true, Some("token"));
+}
+
+#[test]
+fn should_build_and_check_contract() {
+    // Run 'cargo-casperlabs <test dir>/build --workspace-path=<path to EE root>'
+    let test_dir = TEST_DIR.path().join(BUILD);
+    let mut tool_cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    tool_cmd.arg(&test_dir);
+    tool_cmd.arg(&*WORKSPACE_PATH_ARG);
+    tool_cmd.env("USER", "tester");
+    output_from_command(tool_cmd);
+
+    // Run 'cargo-casperlabs build <test dir>/build/contract'
+    let mut build_cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    build_cmd.arg(BUILD).arg(test_dir.join("contract"));
+    let build_output = output_from_command(build_cmd);
+    assert_eq!(SUCCESS_EXIT_CODE, build_output.status.code().unwrap());
+
+    let stdout = String::from_utf8_lossy(&build_output.stdout);
+    assert!(stdout.contains("contract.wasm"));
+    assert!(stdout.contains("session entry point: call"));
 }
@@ -1,12 +1,12 @@
 //! Functions for managing accounts.
 
-use alloc::vec::Vec;
+use alloc::{collections::BTreeMap, vec::Vec};
 use core::convert::TryFrom;
 
 use casperlabs_types::{
     account::{
-        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        AccountHash, ActionType, AddKeyFailure, ManageAccountFailure, RemoveKeyFailure,
+        SetThresholdFailure, UpdateKeyFailure, Weight,
     },
     bytesrepr, URef, UREF_SERIALIZED_LENGTH,
 };
@@ -84,3 +84,38 @@ pub fn update_associated_key(
         Err(UpdateKeyFailure::try_from(result).unwrap_or_revert())
     }
 }
+
+/// Replaces the account's whole set of associated keys with `associated_keys` and, if `main_purse`
+/// is `Some`, sets it as the account's main purse, all as a single operation.
+///
+/// The authorization keys of the deploy must meet the account's [`ActionType::KeyManagement`]
+/// threshold, and the total [`Weight`] of the new keys must still meet every action threshold.
+pub fn manage_account(
+    associated_keys: BTreeMap<AccountHash, Weight>,
+    main_purse: Option<URef>,
+) -> Result<(), ManageAccountFailure> {
+    let (associated_keys_ptr, associated_keys_size, _bytes1) = to_ptr(associated_keys);
+    let (main_purse_ptr, main_purse_size, _bytes2) = to_ptr(main_purse);
+    let result = unsafe {
+        ext_ffi::manage_account(
+            associated_keys_ptr,
+            associated_keys_size,
+            main_purse_ptr,
+            main_purse_size,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(ManageAccountFailure::try_from(result).unwrap_or_revert())
+    }
+}
+
+/// Replaces the account's whole set of associated keys with `associated_keys`.
+///
+/// This can be used to rotate an account to a new primary key in a single deploy.
+pub fn replace_associated_keys(
+    associated_keys: BTreeMap<AccountHash, Weight>,
+) -> Result<(), ManageAccountFailure> {
+    manage_account(associated_keys, None)
+}
@@ -133,7 +133,7 @@ fn deserialize_contract_result<T: CLTyped + FromBytes>(bytes_written: usize) ->
     bytesrepr::deserialize(serialized_result).unwrap_or_revert()
 }
 
-fn get_named_arg_size(name: &str) -> Option<usize> {
+pub(crate) fn get_named_arg_size(name: &str) -> Option<usize> {
     let mut arg_size: usize = 0;
     let ret = unsafe {
         ext_ffi::get_named_arg_size(
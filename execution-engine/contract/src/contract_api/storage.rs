@@ -90,6 +90,24 @@ pub fn write_local<K: ToBytes, V: CLTyped + ToBytes>(key: K, value: V) {
     }
 }
 
+/// Removes the value under `uref` from the global state.
+///
+/// Subsequent reads of `uref` will find no value, until a new one is written under it.
+pub fn remove(uref: URef) {
+    let (uref_ptr, uref_size, _bytes) = contract_api::to_ptr(uref);
+    unsafe {
+        ext_ffi::remove_uref(uref_ptr, uref_size);
+    }
+}
+
+/// Removes the value under `key` from the context-local partition of global state.
+pub fn remove_local<K: ToBytes>(key: K) {
+    let (key_ptr, key_size, _bytes) = contract_api::to_ptr(key);
+    unsafe {
+        ext_ffi::remove_local(key_ptr, key_size);
+    }
+}
+
 /// Adds `value` to the one currently under `uref` in the global state.
 pub fn add<T: CLTyped + ToBytes>(uref: URef, value: T) {
     let key = Key::from(uref);
@@ -347,3 +365,87 @@ pub fn disable_contract_version(
 
     api_error::result_from(result)
 }
+
+/// Enable a previously disabled version of a contract from the contract stored at the given hash.
+/// That version of the contract will be callable again by `call_versioned_contract`.
+pub fn enable_contract_version(
+    contract_package_hash: ContractPackageHash,
+    contract_hash: ContractHash,
+) -> Result<(), ApiError> {
+    let (contract_package_hash_ptr, contract_package_hash_size, _bytes1) =
+        contract_api::to_ptr(contract_package_hash);
+    let (contract_hash_ptr, contract_hash_size, _bytes2) = contract_api::to_ptr(contract_hash);
+
+    let result = unsafe {
+        ext_ffi::enable_contract_version(
+            contract_package_hash_ptr,
+            contract_package_hash_size,
+            contract_hash_ptr,
+            contract_hash_size,
+        )
+    };
+
+    api_error::result_from(result)
+}
+
+/// Attach metadata, such as a semver label or a changelog hash, to a version of a contract from
+/// the contract stored at the given hash. Any metadata previously attached to that version is
+/// replaced.
+pub fn set_contract_version_metadata(
+    contract_package_hash: ContractPackageHash,
+    contract_hash: ContractHash,
+    metadata: &str,
+) -> Result<(), ApiError> {
+    let (contract_package_hash_ptr, contract_package_hash_size, _bytes1) =
+        contract_api::to_ptr(contract_package_hash);
+    let (contract_hash_ptr, contract_hash_size, _bytes2) = contract_api::to_ptr(contract_hash);
+    let (metadata_ptr, metadata_size, _bytes3) = contract_api::to_ptr(metadata);
+
+    let result = unsafe {
+        ext_ffi::set_contract_version_metadata(
+            contract_package_hash_ptr,
+            contract_package_hash_size,
+            contract_hash_ptr,
+            contract_hash_size,
+            metadata_ptr,
+            metadata_size,
+        )
+    };
+
+    api_error::result_from(result)
+}
+
+fn rotate_access_key_impl(
+    contract_package_hash: ContractPackageHash,
+    lock: bool,
+) -> Result<URef, ApiError> {
+    let (contract_package_hash_ptr, contract_package_hash_size, _bytes) =
+        contract_api::to_ptr(contract_package_hash);
+    let mut access_addr = [0u8; 32];
+
+    let result = unsafe {
+        ext_ffi::rotate_access_key(
+            contract_package_hash_ptr,
+            contract_package_hash_size,
+            lock as u32,
+            access_addr.as_mut_ptr(),
+        )
+    };
+    api_error::result_from(result)?;
+
+    Ok(URef::new(access_addr, AccessRights::READ_ADD_WRITE))
+}
+
+/// Issues a new access key for a (versioned) contract package and returns it. The previous access
+/// key no longer grants control over the package, so the returned key can be handed over to a
+/// different account (e.g. a multisig account) to transfer maintenance of the package.
+pub fn rotate_access_key(contract_package_hash: ContractPackageHash) -> Result<URef, ApiError> {
+    rotate_access_key_impl(contract_package_hash, false)
+}
+
+/// Issues a new access key for a (versioned) contract package like [`rotate_access_key`], and
+/// additionally locks the package permanently. Versions can no longer be added to or disabled in
+/// a locked package, while the returned key can still be used to manage its user groups.
+pub fn lock_contract_package(contract_package_hash: ContractPackageHash) -> Result<URef, ApiError> {
+    rotate_access_key_impl(contract_package_hash, true)
+}
@@ -0,0 +1,177 @@
+//! Support for declaring a contract's entry points alongside their implementations.
+
+/// Defines contract entry points and generates the matching [`EntryPoints`] declaration.
+///
+/// Each function in the macro body is prefixed with its entry point type, either `contract` or
+/// `session`, optionally followed by a list of the groups allowed to call it, e.g.
+/// `contract["admin"]`.  Entry points without a group list are public.
+///
+/// For every function the macro exports a `#[no_mangle]` Wasm entry point which reads each
+/// parameter from the named runtime argument of the same name, runs the function body, and returns
+/// the result to the caller via [`runtime::ret`].  A missing argument for a parameter of type
+/// `Option<T>` is read as `None`.
+///
+/// The macro also generates `pub fn entry_points() -> EntryPoints` which declares every function
+/// with its parameter names and types, return type, access and entry point type as written in
+/// the signature, so the declared ABI can't drift from the code.
+///
+/// [`EntryPoints`]: casperlabs_types::EntryPoints
+/// [`runtime::ret`]: crate::contract_api::runtime::ret
+///
+/// # Example
+///
+/// ```rust,no_run
+/// #![no_std]
+///
+/// use casperlabs_contract::{
+///     contract_api::{runtime, storage},
+///     entry_points,
+///     unwrap_or_revert::UnwrapOrRevert,
+/// };
+/// use casperlabs_types::{Key, URef};
+///
+/// const COUNT_KEY: &str = "count";
+///
+/// fn count_uref() -> URef {
+///     runtime::get_key(COUNT_KEY)
+///         .and_then(Key::into_uref)
+///         .unwrap_or_revert()
+/// }
+///
+/// entry_points! {
+///     /// Increments the counter by `step`, or by one if no step is given.
+///     contract fn counter_inc(step: Option<i32>) {
+///         storage::add(count_uref(), step.unwrap_or(1));
+///     }
+///
+///     /// Returns the current value of the counter.
+///     contract fn counter_get() -> i32 {
+///         storage::read(count_uref()).unwrap_or_revert().unwrap_or_revert()
+///     }
+///
+///     /// Resets the counter; only callable by members of the "admin" group.
+///     contract["admin"] fn counter_reset() {
+///         storage::write(count_uref(), 0i32);
+///     }
+/// }
+///
+/// #[no_mangle]
+/// pub extern "C" fn call() {
+///     let (contract_hash, _version) = storage::new_contract(entry_points(), None, None, None);
+///     runtime::put_key("counter", contract_hash.into());
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! entry_points {
+    (@export
+        $(#[$meta:meta])*
+        $name:ident($($arg:ident: $arg_ty:ty),*) -> $ret:ty $body:block
+    ) => {
+        $(#[$meta])*
+        #[no_mangle]
+        pub extern "C" fn $name() {
+            fn $name($($arg: $arg_ty),*) -> $ret $body
+            let ret: $ret = $name($($crate::__private::get_named_arg(stringify!($arg))),*);
+            let ret = $crate::unwrap_or_revert::UnwrapOrRevert::unwrap_or_revert(
+                $crate::__private::types::CLValue::from_t(ret),
+            );
+            $crate::contract_api::runtime::ret(ret)
+        }
+    };
+    (@export
+        $(#[$meta:meta])*
+        $name:ident($($arg:ident: $arg_ty:ty),*) $body:block
+    ) => {
+        $(#[$meta])*
+        #[no_mangle]
+        pub extern "C" fn $name() {
+            fn $name($($arg: $arg_ty),*) $body
+            $name($($crate::__private::get_named_arg(stringify!($arg))),*)
+        }
+    };
+    (@ret_type) => {
+        $crate::__private::types::CLType::Unit
+    };
+    (@ret_type $ret:ty) => {
+        <$ret as $crate::__private::types::CLTyped>::cl_type()
+    };
+    (@access) => {
+        $crate::__private::types::EntryPointAccess::Public
+    };
+    (@access [$($group:literal),*]) => {
+        $crate::__private::types::EntryPointAccess::groups(&[$($group),*])
+    };
+    (@entry_point_type contract) => {
+        $crate::__private::types::EntryPointType::Contract
+    };
+    (@entry_point_type session) => {
+        $crate::__private::types::EntryPointType::Session
+    };
+    (@entry_point_type $other:ident) => {
+        compile_error!(concat!(
+            "unknown entry point type `",
+            stringify!($other),
+            "`, expected `contract` or `session`"
+        ))
+    };
+    (
+        $(
+            $(#[$meta:meta])*
+            $entry_point_type:ident $([$($group:literal),* $(,)?])?
+            fn $name:ident($($arg:ident: $arg_ty:ty),* $(,)?) $(-> $ret:ty)? $body:block
+        )*
+    ) => {
+        $(
+            $crate::entry_points!(@export
+                $(#[$meta])*
+                $name($($arg: $arg_ty),*) $(-> $ret)? $body
+            );
+        )*
+
+        /// Returns the entry points defined by `entry_points!`.
+        pub fn entry_points() -> $crate::__private::types::EntryPoints {
+            let mut entry_points = $crate::__private::types::EntryPoints::new();
+            $(
+                entry_points.add_entry_point($crate::__private::types::EntryPoint::new(
+                    stringify!($name),
+                    $crate::__private::vec![$(
+                        $crate::__private::types::Parameter::new(
+                            stringify!($arg),
+                            <$arg_ty as $crate::__private::types::CLTyped>::cl_type(),
+                        )
+                    ),*],
+                    $crate::entry_points!(@ret_type $($ret)?),
+                    $crate::entry_points!(@access $([$($group),*])?),
+                    $crate::entry_points!(@entry_point_type $entry_point_type),
+                ));
+            )*
+            entry_points
+        }
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec;
+
+    pub use casperlabs_types as types;
+
+    use casperlabs_types::{
+        bytesrepr::{self, FromBytes},
+        CLType, CLTyped,
+    };
+
+    use crate::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+
+    /// Reads the named argument, treating a missing argument for an `Option` parameter as `None`.
+    pub fn get_named_arg<T: FromBytes + CLTyped>(name: &str) -> T {
+        if let CLType::Option(_) = T::cl_type() {
+            if runtime::get_named_arg_size(name).is_none() {
+                // `None` is serialized as a single zero tag byte
+                return bytesrepr::deserialize(vec![0]).unwrap_or_revert();
+            }
+        }
+        runtime::get_named_arg(name)
+    }
+}
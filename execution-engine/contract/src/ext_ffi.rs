@@ -2,6 +2,13 @@
 //!
 //! Generally should not be used directly.  See the [`contract_api`](crate::contract_api) for
 //! high-level bindings suitable for writing smart contracts.
+//!
+//! If the `native` feature is enabled, these are replaced by bindings to a host-native backend;
+//! see [`native`](crate::native).
+#[cfg(feature = "native")]
+pub use crate::native::ffi::*;
+
+#[cfg(not(feature = "native"))]
 extern "C" {
     /// The bytes in the span of wasm memory from `key_ptr` to `key_ptr + key_size` must correspond
     /// to a valid global state key, otherwise the function will fail. If the key is de-serialized
@@ -60,6 +67,26 @@ extern "C" {
         value_ptr: *const u8,
         value_size: usize,
     );
+    /// The bytes in wasm memory from offset `key_ptr` to `key_ptr + key_size`
+    /// will be used together with the current context’s seed to form a local key.
+    /// This function removes the value under that local key from the global state.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_ptr` - pointer to bytes representing the user-defined key to remove
+    /// * `key_size` - size of the key (in bytes)
+    pub fn remove_local(key_ptr: *const u8, key_size: usize);
+    /// This function removes the value under the provided
+    /// [`casperlabs_types::uref::URef`] (read via de-serializing the bytes in wasm memory from
+    /// offset `uref_ptr` to `uref_ptr + uref_size`) from the global state. This function will
+    /// cause a `Trap` if the [`casperlabs_types::uref::URef`] fails to de-serialize or if
+    /// writing to it is not permitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `uref_ptr` - pointer to bytes representing the [`casperlabs_types::uref::URef`] to remove
+    /// * `uref_size` - size of the [`casperlabs_types::uref::URef`] (in bytes)
+    pub fn remove_uref(uref_ptr: *const u8, uref_size: usize);
     /// This function adds the provided value (read via de-serializing the bytes
     /// in wasm memory from offset `value_ptr` to `value_ptr + value_size`) to the
     /// current value under the provided key (read via de-serializing the bytes in
@@ -233,6 +260,30 @@ extern "C" {
     /// * `action` - index representing the action threshold to set
     /// * `threshold` - new value of the threshold for performing this action
     pub fn set_action_threshold(permission_level: u32, threshold: u32) -> i32;
+    /// This function replaces the whole set of associated keys of the current account and
+    /// optionally changes its main purse, as a single atomic operation. The associated keys are
+    /// a serialized `BTreeMap<AccountHash, Weight>`, and the main purse is a serialized
+    /// `Option<URef>` where `None` leaves the main purse unchanged. The new main purse has to be
+    /// an existing purse which the caller holds with full access rights. The return value is a
+    /// status code where 0 means success, 1 means the new set of keys is empty, 2 means the new
+    /// set of keys has too many entries, 3 means permission denied (this could be because the
+    /// function was called outside of session code or because the key management threshold was
+    /// not met by the keys authorizing the deploy), 4 means the total weight of the new keys
+    /// would not meet either the deploy or key management thresholds, and 5 means the new main
+    /// purse is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `associated_keys_ptr` - pointer to serialized map of account hashes to weights
+    /// * `associated_keys_size` - size of serialized map of account hashes to weights
+    /// * `main_purse_ptr` - pointer to serialized optional new main purse
+    /// * `main_purse_size` - size of serialized optional new main purse
+    pub fn manage_account(
+        associated_keys_ptr: *const u8,
+        associated_keys_size: usize,
+        main_purse_ptr: *const u8,
+        main_purse_size: usize,
+    ) -> i32;
     /// This function returns the public key of the account for this deploy. The
     /// result is always 36-bytes in length (4 bytes prefix on a 32-byte public
     /// key); it is up to the caller to ensure the right amount of memory is
@@ -486,6 +537,41 @@ extern "C" {
         contract_hash_ptr: *const u8,
         contract_hash_size: usize,
     ) -> i32;
+    /// Enables a previously disabled contract in a contract package. Returns non-zero standard
+    /// error for a failure, otherwise a zero indicates success.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_package_hash_ptr` - pointer to serialized contract package hash.
+    /// * `contract_package_hash_size` - size of contract package hash in serialized form.
+    /// * `contract_hash_ptr` - pointer to serialized contract hash.
+    /// * `contract_hash_size` - size of contract hash in serialized form.
+    pub fn enable_contract_version(
+        contract_package_hash_ptr: *const u8,
+        contract_package_hash_size: usize,
+        contract_hash_ptr: *const u8,
+        contract_hash_size: usize,
+    ) -> i32;
+    /// Attaches metadata (e.g. a semver label or a changelog hash) to a contract version in a
+    /// contract package, replacing any previously attached metadata. Returns non-zero standard
+    /// error for a failure, otherwise a zero indicates success.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_package_hash_ptr` - pointer to serialized contract package hash.
+    /// * `contract_package_hash_size` - size of contract package hash in serialized form.
+    /// * `contract_hash_ptr` - pointer to serialized contract hash.
+    /// * `contract_hash_size` - size of contract hash in serialized form.
+    /// * `metadata_ptr` - pointer to serialized metadata string.
+    /// * `metadata_size` - size of serialized metadata string.
+    pub fn set_contract_version_metadata(
+        contract_package_hash_ptr: *const u8,
+        contract_package_hash_size: usize,
+        contract_hash_ptr: *const u8,
+        contract_hash_size: usize,
+        metadata_ptr: *const u8,
+        metadata_size: usize,
+    ) -> i32;
     /// Calls a contract by its hash. Requires entry point name that has to be present on a
     /// specified contract, and serialized named arguments. Returns a standard error code in
     /// case of failure, otherwise a successful execution returns zero. Bytes returned from contract
@@ -626,6 +712,23 @@ extern "C" {
         urefs_ptr: *const u8,
         urefs_size: usize,
     ) -> i32;
+    /// Issues a new access key for a contract package, invalidating the previous one. Optionally
+    /// locks the package permanently, so that no versions can be added to or disabled in it.
+    /// Returns non-zero standard error for a failure, otherwise a zero indicates success.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_package_hash_ptr` - pointer to serialized contract package hash.
+    /// * `contract_package_hash_size` - size of contract package hash in serialized form.
+    /// * `lock` - non-zero if the contract package should be permanently locked.
+    /// * `access_addr_ptr` - pointer to a 32-byte buffer where the address of the new access key
+    ///   will be written.
+    pub fn rotate_access_key(
+        contract_package_hash_ptr: *const u8,
+        contract_package_hash_size: usize,
+        lock: u32,
+        access_addr_ptr: *mut u8,
+    ) -> i32;
 
     /// Prints data directly to stanadard output on the host.
     ///
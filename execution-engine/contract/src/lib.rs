@@ -6,6 +6,12 @@
 //! By default, the library is `no_std`, however you can enable full `std` functionality by enabling
 //! the crate's `std` feature.
 //!
+//! # Native execution
+//!
+//! Enabling the crate's `native` feature (which implies `std`) replaces the Wasm host imports in
+//! [`ext_ffi`] with calls to a [`native::Host`], allowing contract code to be compiled natively
+//! and run in-process by the execution engine, e.g. to step through it in a debugger.
+//!
 //! # Example
 //!
 //! The following example contains session code which persists an integer value under an unforgeable
@@ -72,7 +78,13 @@ extern crate std;
 pub static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 pub mod contract_api;
+mod entry_points;
 pub mod ext_ffi;
 #[cfg(not(any(feature = "std", test)))]
 pub mod handlers;
+#[cfg(feature = "native")]
+pub mod native;
 pub mod unwrap_or_revert;
+
+#[doc(hidden)]
+pub use entry_points::__private;
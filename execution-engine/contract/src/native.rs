@@ -0,0 +1,314 @@
+//! A host-native backend for the functions in [`ext_ffi`](crate::ext_ffi).
+//!
+//! With the `native` feature enabled, the functions in `ext_ffi` are no longer imported from the
+//! Wasm host.  Instead they forward their arguments to the [`Host`] set for the current thread by
+//! [`with_host`].  This allows contract code to be compiled natively and run in-process by the
+//! execution engine, e.g. under `cargo test` or a source-level debugger.
+//!
+//! Pointer arguments are passed to the host as native addresses, and sizes as native `usize`s.
+
+use std::cell::Cell;
+
+/// A host able to execute the functions in [`ext_ffi`](crate::ext_ffi) on behalf of contract code
+/// running natively.
+pub trait Host {
+    /// Calls the host function `name`, e.g. `"read_value"`, with `args` as passed to the function
+    /// of that name in [`ext_ffi`](crate::ext_ffi), and returns its result, or 0 if it has none.
+    ///
+    /// If the host function stops execution of the contract, e.g. `revert`, this should not return
+    /// but unwind the stack back to the caller of [`with_host`].
+    fn call(&mut self, name: &'static str, args: &[usize]) -> i32;
+}
+
+/// The host set for the current thread by [`with_host`], as a type-erased pointer to it along with
+/// the function calling it.
+#[derive(Clone, Copy)]
+struct HostRef {
+    host: *mut (),
+    call: unsafe fn(*mut (), &'static str, &[usize]) -> i32,
+}
+
+thread_local! {
+    static HOST: Cell<Option<HostRef>> = Cell::new(None);
+}
+
+/// Restores the host of the current thread to the given one when dropped, including while
+/// unwinding.
+struct RestoreHost(Option<HostRef>);
+
+impl Drop for RestoreHost {
+    fn drop(&mut self) {
+        HOST.with(|host| host.set(self.0));
+    }
+}
+
+/// Calls the host `H` behind the type-erased pointer `host`.
+///
+/// Safety: `host` must point to an `H` which is valid and not otherwise borrowed for the duration
+/// of the call.
+unsafe fn call_host<H: Host>(host: *mut (), name: &'static str, args: &[usize]) -> i32 {
+    (*(host as *mut H)).call(name, args)
+}
+
+/// Runs `f` with `host` executing any calls it makes to the functions in
+/// [`ext_ffi`](crate::ext_ffi) on the current thread.
+pub fn with_host<H: Host, T, F: FnOnce() -> T>(host: &mut H, f: F) -> T {
+    let host_ref = HostRef {
+        host: host as *mut H as *mut (),
+        call: call_host::<H>,
+    };
+    // `host` stays mutably borrowed until this returns, and the previous host is restored before
+    // returning or unwinding, so the pointer never outlives the borrow.
+    let _restore = RestoreHost(HOST.with(|current| current.replace(Some(host_ref))));
+    f()
+}
+
+fn call(name: &'static str, args: &[usize]) -> i32 {
+    // The host is taken out for the duration of the call, so that a call made by the host itself,
+    // without setting a new one, panics rather than borrowing it a second time.
+    let host_ref = HOST
+        .with(Cell::take)
+        .unwrap_or_else(|| panic!("no native host set to call '{}'", name));
+    let _restore = RestoreHost(Some(host_ref));
+    unsafe { (host_ref.call)(host_ref.host, name, args) }
+}
+
+/// Converts the result of a host function into the return type of its native binding.
+trait FromHostResult {
+    fn from_host_result(result: i32) -> Self;
+}
+
+impl FromHostResult for i32 {
+    fn from_host_result(result: i32) -> Self {
+        result
+    }
+}
+
+impl FromHostResult for () {
+    fn from_host_result(_result: i32) -> Self {}
+}
+
+macro_rules! native_bindings {
+    ($($(#[$attr:meta])* fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        $(
+            $(#[$attr])*
+            pub unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                FromHostResult::from_host_result(call(stringify!($name), &[$($arg as usize),*]))
+            }
+        )*
+
+        /// Returns the names of the host functions called by the bindings above.
+        pub(super) fn binding_names() -> Vec<&'static str> {
+            let mut names = Vec::new();
+            $(
+                $(#[$attr])*
+                names.push(stringify!($name));
+            )*
+            names
+        }
+    };
+}
+
+/// Returns the names of all the host functions which the native bindings in
+/// [`ext_ffi`](crate::ext_ffi) pass to [`Host::call`].
+pub fn host_function_names() -> Vec<&'static str> {
+    let mut names = ffi::binding_names();
+    names.extend_from_slice(&["ret", "revert"]);
+    names
+}
+
+/// The native bindings re-exported by [`ext_ffi`](crate::ext_ffi), documented there.
+#[allow(missing_docs, clippy::missing_safety_doc, clippy::too_many_arguments)]
+pub(crate) mod ffi {
+    use super::{call, FromHostResult};
+
+    native_bindings! {
+        fn read_value(key_ptr: *const u8, key_size: usize, output_size: *mut usize) -> i32;
+        fn read_value_local(key_ptr: *const u8, key_size: usize, output_size: *mut usize) -> i32;
+        fn write(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize);
+        fn write_local(
+            key_ptr: *const u8,
+            key_size: usize,
+            value_ptr: *const u8,
+            value_size: usize,
+        );
+        fn remove_local(key_ptr: *const u8, key_size: usize);
+        fn remove_uref(uref_ptr: *const u8, uref_size: usize);
+        fn add(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize);
+        fn add_local(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize);
+        fn new_uref(uref_ptr: *mut u8, value_ptr: *const u8, value_size: usize);
+        fn load_named_keys(total_keys: *mut usize, result_size: *mut usize) -> i32;
+        fn get_key(
+            name_ptr: *const u8,
+            name_size: usize,
+            output_ptr: *mut u8,
+            output_size: usize,
+            bytes_written_ptr: *mut usize,
+        ) -> i32;
+        fn has_key(name_ptr: *const u8, name_size: usize) -> i32;
+        fn put_key(name_ptr: *const u8, name_size: usize, key_ptr: *const u8, key_size: usize);
+        fn remove_key(name_ptr: *const u8, name_size: usize);
+        fn is_valid_uref(uref_ptr: *const u8, uref_size: usize) -> i32;
+        fn add_associated_key(
+            account_hash_ptr: *const u8,
+            account_hash_size: usize,
+            weight: i32,
+        ) -> i32;
+        fn remove_associated_key(account_hash_ptr: *const u8, account_hash_size: usize) -> i32;
+        fn update_associated_key(
+            account_hash_ptr: *const u8,
+            account_hash_size: usize,
+            weight: i32,
+        ) -> i32;
+        fn set_action_threshold(permission_level: u32, threshold: u32) -> i32;
+        fn manage_account(
+            associated_keys_ptr: *const u8,
+            associated_keys_size: usize,
+            main_purse_ptr: *const u8,
+            main_purse_size: usize,
+        ) -> i32;
+        fn get_caller(output_size: *mut usize) -> i32;
+        fn get_blocktime(dest_ptr: *const u8);
+        fn create_purse(purse_ptr: *const u8, purse_size: usize) -> i32;
+        fn transfer_to_account(
+            target_ptr: *const u8,
+            target_size: usize,
+            amount_ptr: *const u8,
+            amount_size: usize,
+        ) -> i32;
+        fn transfer_from_purse_to_account(
+            source_ptr: *const u8,
+            source_size: usize,
+            target_ptr: *const u8,
+            target_size: usize,
+            amount_ptr: *const u8,
+            amount_size: usize,
+        ) -> i32;
+        fn transfer_from_purse_to_purse(
+            source_ptr: *const u8,
+            source_size: usize,
+            target_ptr: *const u8,
+            target_size: usize,
+            amount_ptr: *const u8,
+            amount_size: usize,
+        ) -> i32;
+        fn get_balance(purse_ptr: *const u8, purse_size: usize, result_size: *mut usize) -> i32;
+        fn get_phase(dest_ptr: *mut u8);
+        fn get_system_contract(
+            system_contract_index: u32,
+            dest_ptr: *mut u8,
+            dest_size: usize,
+        ) -> i32;
+        fn get_main_purse(dest_ptr: *mut u8);
+        fn read_host_buffer(dest_ptr: *mut u8, dest_size: usize, bytes_written: *mut usize) -> i32;
+        fn create_contract_package_at_hash(hash_addr_ptr: *mut u8, access_addr_ptr: *mut u8);
+        fn create_contract_user_group(
+            contract_package_hash_ptr: *const u8,
+            contract_package_hash_size: usize,
+            label_ptr: *const u8,
+            label_size: usize,
+            num_new_urefs: u8,
+            existing_urefs_ptr: *const u8,
+            existing_urefs_size: usize,
+            output_size_ptr: *mut usize,
+        ) -> i32;
+        fn add_contract_version(
+            contract_package_hash_ptr: *const u8,
+            contract_package_hash_size: usize,
+            version_ptr: *const u32,
+            entry_points_ptr: *const u8,
+            entry_points_size: usize,
+            named_keys_ptr: *const u8,
+            named_keys_size: usize,
+            output_ptr: *mut u8,
+            output_size: usize,
+            bytes_written_ptr: *mut usize,
+        ) -> i32;
+        fn disable_contract_version(
+            contract_package_hash_ptr: *const u8,
+            contract_package_hash_size: usize,
+            contract_hash_ptr: *const u8,
+            contract_hash_size: usize,
+        ) -> i32;
+        fn enable_contract_version(
+            contract_package_hash_ptr: *const u8,
+            contract_package_hash_size: usize,
+            contract_hash_ptr: *const u8,
+            contract_hash_size: usize,
+        ) -> i32;
+        fn set_contract_version_metadata(
+            contract_package_hash_ptr: *const u8,
+            contract_package_hash_size: usize,
+            contract_hash_ptr: *const u8,
+            contract_hash_size: usize,
+            metadata_ptr: *const u8,
+            metadata_size: usize,
+        ) -> i32;
+        fn call_contract(
+            contract_hash_ptr: *const u8,
+            contract_hash_size: usize,
+            entry_point_name_ptr: *const u8,
+            entry_point_name_size: usize,
+            runtime_args_ptr: *const u8,
+            runtime_args_size: usize,
+            result_size: *mut usize,
+        ) -> i32;
+        fn call_versioned_contract(
+            contract_package_hash_ptr: *const u8,
+            contract_package_hash_size: usize,
+            contract_version_ptr: *const u8,
+            contract_version_size: usize,
+            entry_point_name_ptr: *const u8,
+            entry_point_name_size: usize,
+            runtime_args_ptr: *const u8,
+            runtime_args_size: usize,
+            result_size: *mut usize,
+        ) -> i32;
+        fn get_named_arg_size(name_ptr: *const u8, name_size: usize, dest_size: *mut usize) -> i32;
+        fn get_named_arg(
+            name_ptr: *const u8,
+            name_size: usize,
+            dest_ptr: *mut u8,
+            dest_size: usize,
+        ) -> i32;
+        fn remove_contract_user_group(
+            contract_package_hash_ptr: *const u8,
+            contract_package_hash_size: usize,
+            label_ptr: *const u8,
+            label_size: usize,
+        ) -> i32;
+        fn provision_contract_user_group_uref(
+            contract_package_hash_ptr: *const u8,
+            contract_package_hash_size: usize,
+            label_ptr: *const u8,
+            label_size: usize,
+            value_size_ptr: *const usize,
+        ) -> i32;
+        fn remove_contract_user_group_urefs(
+            contract_package_hash_ptr: *const u8,
+            contract_package_hash_size: usize,
+            label_ptr: *const u8,
+            label_size: usize,
+            urefs_ptr: *const u8,
+            urefs_size: usize,
+        ) -> i32;
+        fn rotate_access_key(
+            contract_package_hash_ptr: *const u8,
+            contract_package_hash_size: usize,
+            lock: u32,
+            access_addr_ptr: *mut u8,
+        ) -> i32;
+        #[cfg(feature = "test-support")]
+        fn print(text_ptr: *const u8, text_size: usize);
+    }
+
+    pub unsafe fn ret(value_ptr: *const u8, value_size: usize) -> ! {
+        call("ret", &[value_ptr as usize, value_size]);
+        unreachable!("host should not return from 'ret'")
+    }
+
+    pub unsafe fn revert(status: u32) -> ! {
+        call("revert", &[status as usize]);
+        unreachable!("host should not return from 'revert'")
+    }
+}
@@ -14,8 +14,9 @@ use contract::{
 use types::{
     account::{AccountHash, ActionType, Weight},
     contracts::NamedKeys,
-    runtime_args, ApiError, BlockTime, CLType, CLValue, ContractHash, ContractVersion, EntryPoint,
-    EntryPointAccess, EntryPointType, EntryPoints, Key, Parameter, Phase, RuntimeArgs, U512,
+    runtime_args, ApiError, BlockTime, CLType, CLTyped, CLValue, ContractHash, ContractVersion,
+    EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key, Parameter, Phase, RuntimeArgs,
+    U512,
 };
 
 const MIN_FUNCTION_NAME_LENGTH: usize = 1;
@@ -151,12 +152,16 @@ pub extern "C" fn call() {
         ARG_BYTES => random_bytes.clone()
     };
 
-    let (contract_hash, _contract_version) = store_function(entry_point_name, None);
+    let (contract_hash, _contract_version) =
+        store_function(entry_point_name, None, NamedKeys::cl_type());
     let named_keys: NamedKeys =
         runtime::call_contract(contract_hash, entry_point_name, runtime_args.clone());
 
-    let (contract_hash, _contract_version) =
-        store_function(entry_point_name, Some(named_keys.clone()));
+    let (contract_hash, _contract_version) = store_function(
+        entry_point_name,
+        Some(named_keys.clone()),
+        NamedKeys::cl_type(),
+    );
     // Store large function with 10 named keys, then execute it.
     runtime::call_contract::<NamedKeys>(contract_hash, entry_point_name, runtime_args);
 
@@ -171,10 +176,11 @@ pub extern "C" fn call() {
 
     // Store small function with no named keys, then execute it.
     let (contract_hash, _contract_version) =
-        store_function(entry_point_name, Some(NamedKeys::new()));
+        store_function(entry_point_name, Some(NamedKeys::new()), CLType::Unit);
     runtime::call_contract::<()>(contract_hash, entry_point_name, runtime_args.clone());
 
-    let (contract_hash, _contract_version) = store_function(entry_point_name, Some(named_keys));
+    let (contract_hash, _contract_version) =
+        store_function(entry_point_name, Some(named_keys), CLType::Unit);
     // Store small function with 10 named keys, then execute it.
     runtime::call_contract::<()>(contract_hash, entry_point_name, runtime_args);
 
@@ -227,6 +233,7 @@ pub extern "C" fn call() {
 fn store_function(
     entry_point_name: &str,
     named_keys: Option<NamedKeys>,
+    ret: CLType,
 ) -> (ContractHash, ContractVersion) {
     let entry_points = {
         let mut entry_points = EntryPoints::new();
@@ -237,7 +244,7 @@ fn store_function(
                 Parameter::new(ARG_SEED, CLType::U64),
                 Parameter::new(ARG_BYTES, CLType::List(Box::new(CLType::U8))),
             ],
-            CLType::Unit,
+            ret,
             EntryPointAccess::Public,
             EntryPointType::Contract,
         );
@@ -114,7 +114,10 @@ pub extern "C" fn install() {
 
         let unbond = EntryPoint::new(
             METHOD_UNBOND.to_string(),
-            vec![Parameter::new(ARG_AMOUNT, CLType::U512)],
+            vec![Parameter::new(
+                ARG_AMOUNT,
+                CLType::Option(Box::new(CLType::U512)),
+            )],
             CLType::Unit,
             EntryPointAccess::Public,
             EntryPointType::Contract,
@@ -0,0 +1,55 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::ToString;
+
+use contract::{
+    contract_api::{runtime, storage},
+    entry_points,
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{contracts::NamedKeys, Key, URef};
+
+const COUNT_KEY: &str = "count";
+const CONTRACT_HASH_KEY: &str = "contract_abi_hash";
+const PACKAGE_HASH_KEY: &str = "contract_abi_package_hash";
+const PACKAGE_ACCESS_KEY: &str = "contract_abi_package_access";
+
+fn count_uref() -> URef {
+    runtime::get_key(COUNT_KEY)
+        .and_then(Key::into_uref)
+        .unwrap_or_revert()
+}
+
+entry_points! {
+    /// Increments the counter by `step`, or by one if no step is given.
+    contract fn counter_inc(step: Option<i32>) {
+        storage::add(count_uref(), step.unwrap_or(1));
+    }
+
+    /// Returns the current value of the counter.
+    contract fn counter_get() -> i32 {
+        storage::read(count_uref()).unwrap_or_revert().unwrap_or_revert()
+    }
+
+    /// Resets the counter; only callable by members of the "admin" group.
+    contract["admin"] fn counter_reset() {
+        storage::write(count_uref(), 0i32);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(COUNT_KEY.to_string(), storage::new_uref(0i32).into());
+
+    let (contract_hash, _contract_version) = storage::new_contract(
+        entry_points(),
+        Some(named_keys),
+        Some(PACKAGE_HASH_KEY.to_string()),
+        Some(PACKAGE_ACCESS_KEY.to_string()),
+    );
+    runtime::put_key(CONTRACT_HASH_KEY, contract_hash.into());
+}
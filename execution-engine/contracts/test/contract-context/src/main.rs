@@ -95,7 +95,7 @@ fn create_entrypoints_1() -> EntryPoints {
     let session_code_test = EntryPoint::new(
         SESSION_CODE.to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Session,
     );
@@ -104,7 +104,7 @@ fn create_entrypoints_1() -> EntryPoints {
     let contract_code_test = EntryPoint::new(
         CONTRACT_CODE.to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     );
@@ -113,7 +113,7 @@ fn create_entrypoints_1() -> EntryPoints {
     let session_code_caller_as_session = EntryPoint::new(
         "session_code_caller_as_session".to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Session,
     );
@@ -122,7 +122,7 @@ fn create_entrypoints_1() -> EntryPoints {
     let session_code_caller_as_contract = EntryPoint::new(
         "session_code_caller_as_contract".to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     );
@@ -131,7 +131,7 @@ fn create_entrypoints_1() -> EntryPoints {
     let add_new_key = EntryPoint::new(
         "add_new_key".to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Session,
     );
@@ -139,7 +139,7 @@ fn create_entrypoints_1() -> EntryPoints {
     let add_new_key_as_session = EntryPoint::new(
         "add_new_key_as_session".to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Session,
     );
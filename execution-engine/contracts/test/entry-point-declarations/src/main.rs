@@ -0,0 +1,74 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    contracts::{EntryPoint, EntryPoints, Parameter},
+    runtime_args, CLType, ContractHash, EntryPointAccess, EntryPointType, RuntimeArgs, U512,
+};
+
+const HASH_KEY_NAME: &str = "entry_point_declarations_hash";
+const DECLARES_U512_ARG: &str = "declares_u512_arg";
+const DECLARES_I32_RET: &str = "declares_i32_ret";
+const ARG_ENTRY_POINT: &str = "entry_point";
+const ARG_AMOUNT: &str = "amount";
+
+// Declared to take a `U512` amount, but called with an `Option<U512>`.
+#[no_mangle]
+pub extern "C" fn declares_u512_arg() {}
+
+// Declared to return an `I32`, but never calls `runtime::ret`.
+#[no_mangle]
+pub extern "C" fn declares_i32_ret() {}
+
+fn store() -> ContractHash {
+    let entry_points = {
+        let mut entry_points = EntryPoints::new();
+        let declares_u512_arg = EntryPoint::new(
+            DECLARES_U512_ARG.to_string(),
+            vec![Parameter::new(ARG_AMOUNT, CLType::U512)],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(declares_u512_arg);
+        let declares_i32_ret = EntryPoint::new(
+            DECLARES_I32_RET.to_string(),
+            Vec::new(),
+            CLType::I32,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(declares_i32_ret);
+        entry_points
+    };
+
+    let (contract_hash, _contract_version) = storage::new_contract(entry_points, None, None, None);
+    runtime::put_key(HASH_KEY_NAME, contract_hash.into());
+    contract_hash
+}
+
+/// Calls the named entry point of the contract stored by the first call, storing it if needed.
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_hash = match runtime::get_key(HASH_KEY_NAME) {
+        Some(key) => key.into_hash().unwrap_or_revert(),
+        None => store(),
+    };
+    let entry_point: String = runtime::get_named_arg(ARG_ENTRY_POINT);
+    let args = runtime_args! {
+        ARG_AMOUNT => Some(U512::one()),
+    };
+    runtime::call_contract::<()>(contract_hash, &entry_point, args);
+}
@@ -104,7 +104,7 @@ fn create_entry_points_1() -> EntryPoints {
     let restricted_session = EntryPoint::new(
         RESTRICTED_SESSION.to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         EntryPointAccess::groups(&["Group 1"]),
         EntryPointType::Session,
     );
@@ -113,7 +113,7 @@ fn create_entry_points_1() -> EntryPoints {
     let restricted_contract = EntryPoint::new(
         RESTRICTED_CONTRACT.to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         EntryPointAccess::groups(&["Group 1"]),
         EntryPointType::Contract,
     );
@@ -122,7 +122,7 @@ fn create_entry_points_1() -> EntryPoints {
     let restricted_session_caller = EntryPoint::new(
         RESTRICTED_SESSION_CALLER.to_string(),
         vec![Parameter::new(ARG_PACKAGE_HASH, CLType::Key)],
-        CLType::I32,
+        CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Session,
     );
@@ -131,7 +131,7 @@ fn create_entry_points_1() -> EntryPoints {
     let restricted_contract = EntryPoint::new(
         RESTRICTED_CONTRACT.to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         EntryPointAccess::groups(&["Group 1"]),
         EntryPointType::Contract,
     );
@@ -140,7 +140,7 @@ fn create_entry_points_1() -> EntryPoints {
     let unrestricted_contract_caller = EntryPoint::new(
         UNRESTRICTED_CONTRACT_CALLER.to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         // Made public because we've tested deploy level auth into a contract in
         // RESTRICTED_CONTRACT entrypoint
         EntryPointAccess::Public,
@@ -153,7 +153,7 @@ fn create_entry_points_1() -> EntryPoints {
     let unrestricted_contract_caller_as_session = EntryPoint::new(
         RESTRICTED_CONTRACT_CALLER_AS_SESSION.to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         // Made public because we've tested deploy level auth into a contract in
         // RESTRICTED_CONTRACT entrypoint
         EntryPointAccess::Public,
@@ -166,7 +166,7 @@ fn create_entry_points_1() -> EntryPoints {
     let uncallable_session = EntryPoint::new(
         UNCALLABLE_SESSION.to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         // Made public because we've tested deploy level auth into a contract in
         // RESTRICTED_CONTRACT entrypoint
         EntryPointAccess::groups(&[]),
@@ -179,7 +179,7 @@ fn create_entry_points_1() -> EntryPoints {
     let uncallable_contract = EntryPoint::new(
         UNCALLABLE_CONTRACT.to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         // Made public because we've tested deploy level auth into a contract in
         // RESTRICTED_CONTRACT entrypoint
         EntryPointAccess::groups(&[]),
@@ -194,7 +194,7 @@ fn create_entry_points_1() -> EntryPoints {
     let call_restricted_entry_points = EntryPoint::new(
         CALL_RESTRICTED_ENTRY_POINTS.to_string(),
         Vec::new(),
-        CLType::I32,
+        CLType::Unit,
         // Made public because we've tested deploy level auth into a contract in
         // RESTRICTED_CONTRACT entrypoint
         EntryPointAccess::Public,
@@ -0,0 +1,35 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::{AccountHash, Weight},
+    URef,
+};
+
+const ARG_ACCOUNT: &str = "account";
+const NEW_MAIN_PURSE: &str = "new_main_purse";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+
+    // Move all funds of the current main purse into a new purse
+    let source: URef = account::get_main_purse();
+    let destination = system::create_purse();
+    let balance = system::get_balance(source).unwrap_or_revert();
+    system::transfer_from_purse_to_purse(source, destination, balance).unwrap_or_revert();
+    runtime::put_key(NEW_MAIN_PURSE, destination.into());
+
+    // Rotate the account to the new key and migrate its main purse in one call
+    let mut associated_keys = BTreeMap::new();
+    associated_keys.insert(account, Weight::new(1));
+    account::manage_account(associated_keys, Some(destination)).unwrap_or_revert();
+}
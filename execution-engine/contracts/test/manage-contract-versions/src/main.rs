@@ -0,0 +1,102 @@
+#![no_std]
+#![no_main]
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys},
+    CLType, CLTyped, ContractHash, ContractPackageHash, Key, Parameter,
+};
+
+const PACKAGE_HASH_KEY: &str = "package_hash_key";
+const PACKAGE_ACCESS_KEY: &str = "package_access_key";
+const CONTRACT_HASH_V1_KEY: &str = "contract_hash_v1";
+const DISABLE_VERSION: &str = "disable_version";
+const ENABLE_VERSION: &str = "enable_version";
+const SET_VERSION_METADATA: &str = "set_version_metadata";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+const ARG_METADATA: &str = "metadata";
+
+fn package_hash() -> ContractPackageHash {
+    runtime::get_key(PACKAGE_HASH_KEY)
+        .and_then(Key::into_hash)
+        .unwrap_or_revert()
+}
+
+#[no_mangle]
+pub extern "C" fn disable_version() {
+    let contract_hash: ContractHash = runtime::get_named_arg(ARG_CONTRACT_HASH);
+    storage::disable_contract_version(package_hash(), contract_hash).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn enable_version() {
+    let contract_hash: ContractHash = runtime::get_named_arg(ARG_CONTRACT_HASH);
+    storage::enable_contract_version(package_hash(), contract_hash).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_version_metadata() {
+    let contract_hash: ContractHash = runtime::get_named_arg(ARG_CONTRACT_HASH);
+    let metadata: String = runtime::get_named_arg(ARG_METADATA);
+    storage::set_contract_version_metadata(package_hash(), contract_hash, &metadata)
+        .unwrap_or_revert();
+}
+
+fn create_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+
+    let disable_version = EntryPoint::new(
+        DISABLE_VERSION.to_string(),
+        vec![Parameter::new(ARG_CONTRACT_HASH, ContractHash::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Session,
+    );
+    entry_points.add_entry_point(disable_version);
+
+    let enable_version = EntryPoint::new(
+        ENABLE_VERSION.to_string(),
+        vec![Parameter::new(ARG_CONTRACT_HASH, ContractHash::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Session,
+    );
+    entry_points.add_entry_point(enable_version);
+
+    let set_version_metadata = EntryPoint::new(
+        SET_VERSION_METADATA.to_string(),
+        vec![
+            Parameter::new(ARG_CONTRACT_HASH, ContractHash::cl_type()),
+            Parameter::new(ARG_METADATA, CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Session,
+    );
+    entry_points.add_entry_point(set_version_metadata);
+
+    entry_points
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let (package_hash, access_uref) = storage::create_contract_package_at_hash();
+
+    runtime::put_key(PACKAGE_HASH_KEY, package_hash.into());
+    runtime::put_key(PACKAGE_ACCESS_KEY, access_uref.into());
+
+    let (contract_hash_v1, _version) =
+        storage::add_contract_version(package_hash, create_entry_points(), NamedKeys::new());
+    runtime::put_key(CONTRACT_HASH_V1_KEY, contract_hash_v1.into());
+
+    // A second version is installed so the first one can be managed while it is disabled
+    storage::add_contract_version(package_hash, create_entry_points(), NamedKeys::new());
+}
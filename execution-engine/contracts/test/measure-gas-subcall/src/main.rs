@@ -10,8 +10,8 @@ use contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use types::{
-    contracts::Parameters, ApiError, CLType, CLValue, ContractHash, ContractVersion, EntryPoint,
-    EntryPointAccess, EntryPointType, EntryPoints, Phase, RuntimeArgs,
+    contracts::Parameters, ApiError, CLType, CLTyped, CLValue, ContractHash, ContractVersion,
+    EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Phase, RuntimeArgs,
 };
 
 const ARG_TARGET: &str = "target_contract";
@@ -52,7 +52,7 @@ fn store() -> (ContractHash, ContractVersion) {
         let entry_point_2 = EntryPoint::new(
             GET_PHASE_EXT,
             Parameters::default(),
-            CLType::Unit,
+            Phase::cl_type(),
             EntryPointAccess::Public,
             EntryPointType::Contract,
         );
@@ -115,7 +115,10 @@ fn upgrade_proof_of_stake() -> (ContractHash, ContractVersion) {
 
         let unbond = EntryPoint::new(
             METHOD_UNBOND,
-            vec![Parameter::new(ARG_AMOUNT, CLType::U512)],
+            vec![Parameter::new(
+                ARG_AMOUNT,
+                CLType::Option(Box::new(CLType::U512)),
+            )],
             CLType::Unit,
             EntryPointAccess::Public,
             EntryPointType::Contract,
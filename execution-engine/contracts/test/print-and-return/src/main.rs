@@ -0,0 +1,30 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{ApiError, CLValue};
+
+const ARG_LINES: &str = "lines";
+const ARG_ERROR: &str = "error";
+
+/// Prints each of the given lines, then either reverts with the given user error or returns the
+/// number of lines printed.
+#[no_mangle]
+pub extern "C" fn call() {
+    let lines: Vec<String> = runtime::get_named_arg(ARG_LINES);
+    let error: Option<u32> = runtime::get_named_arg(ARG_ERROR);
+
+    for line in &lines {
+        runtime::print(line);
+    }
+
+    if let Some(error) = error {
+        runtime::revert(ApiError::User(error as u16));
+    }
+    let line_count = CLValue::from_t(lines.len() as u32).unwrap_or_revert();
+    runtime::ret(line_count)
+}
@@ -0,0 +1,68 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, Key, URef};
+
+const LOCAL_KEY: [u8; 32] = [66u8; 32];
+const VALUE_KEY: &str = "value";
+
+const CMD_WRITE: &str = "write";
+const CMD_REMOVE: &str = "remove";
+
+const INITIAL_VALUE: u64 = 10;
+
+const ARG_COMMAND: &str = "command";
+
+#[repr(u16)]
+enum Error {
+    ValueStillPresent = 0,
+    LocalValueStillPresent = 1,
+    UnknownCommand = 2,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+fn value_uref() -> URef {
+    runtime::get_key(VALUE_KEY)
+        .and_then(Key::into_uref)
+        .unwrap_or_revert()
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = runtime::get_named_arg(ARG_COMMAND);
+
+    if command == CMD_WRITE {
+        let uref = storage::new_uref(INITIAL_VALUE);
+        runtime::put_key(VALUE_KEY, uref.into());
+        storage::write_local(LOCAL_KEY, INITIAL_VALUE);
+    } else if command == CMD_REMOVE {
+        let uref = value_uref();
+        storage::remove(uref);
+        storage::remove_local(LOCAL_KEY);
+
+        // Removed values are gone for the rest of the deploy as well
+        let value: Option<u64> = storage::read(uref).unwrap_or_revert();
+        if value.is_some() {
+            runtime::revert(Error::ValueStillPresent);
+        }
+        let local_value: Option<u64> = storage::read_local(&LOCAL_KEY).unwrap_or_revert();
+        if local_value.is_some() {
+            runtime::revert(Error::LocalValueStillPresent);
+        }
+    } else {
+        runtime::revert(Error::UnknownCommand);
+    }
+}
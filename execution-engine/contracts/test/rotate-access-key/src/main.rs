@@ -0,0 +1,69 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::ToString, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys},
+    CLType, ContractPackageHash, Key,
+};
+
+const PACKAGE_HASH_KEY: &str = "package_hash_key";
+const PACKAGE_ACCESS_KEY: &str = "package_access_key";
+const ROTATE_ACCESS_KEY: &str = "rotate_access_key";
+const LOCK_PACKAGE: &str = "lock_package";
+const ADD_VERSION: &str = "add_version";
+
+fn package_hash() -> ContractPackageHash {
+    runtime::get_key(PACKAGE_HASH_KEY)
+        .and_then(Key::into_hash)
+        .unwrap_or_revert()
+}
+
+#[no_mangle]
+pub extern "C" fn rotate_access_key() {
+    let access_uref = storage::rotate_access_key(package_hash()).unwrap_or_revert();
+    runtime::put_key(PACKAGE_ACCESS_KEY, access_uref.into());
+}
+
+#[no_mangle]
+pub extern "C" fn lock_package() {
+    let access_uref = storage::lock_contract_package(package_hash()).unwrap_or_revert();
+    runtime::put_key(PACKAGE_ACCESS_KEY, access_uref.into());
+}
+
+#[no_mangle]
+pub extern "C" fn add_version() {
+    storage::add_contract_version(package_hash(), create_entry_points(), NamedKeys::new());
+}
+
+fn create_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    for name in &[ROTATE_ACCESS_KEY, LOCK_PACKAGE, ADD_VERSION] {
+        let entry_point = EntryPoint::new(
+            name.to_string(),
+            Vec::new(),
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Session,
+        );
+        entry_points.add_entry_point(entry_point);
+    }
+    entry_points
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let (package_hash, access_uref) = storage::create_contract_package_at_hash();
+
+    runtime::put_key(PACKAGE_HASH_KEY, package_hash.into());
+    runtime::put_key(PACKAGE_ACCESS_KEY, access_uref.into());
+
+    storage::add_contract_version(package_hash, create_entry_points(), NamedKeys::new());
+}
@@ -0,0 +1,51 @@
+//! Throws arbitrary bytes at the Wasm `Preprocessor` and, if they are accepted, runs them as the
+//! session code of a deploy so that they reach the `Executor`.
+//!
+//! Neither is expected to panic, whatever the input.
+
+#![no_main]
+
+use lazy_static::lazy_static;
+use libfuzzer_sys::fuzz_target;
+
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_WASM_COSTS, DEFAULT_WASM_LIMITS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::Preprocessor;
+use types::{runtime_args, RuntimeArgs};
+
+lazy_static! {
+    static ref PREPROCESSOR: Preprocessor =
+        Preprocessor::new(*DEFAULT_WASM_COSTS, *DEFAULT_WASM_LIMITS);
+}
+
+thread_local! {
+    static BUILDER: InMemoryWasmTestBuilder = {
+        let mut builder = InMemoryWasmTestBuilder::default();
+        builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+        builder
+    };
+}
+
+fuzz_target!(|module_bytes: &[u8]| {
+    if PREPROCESSOR.preprocess(module_bytes).is_err() {
+        return;
+    }
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_bytes(module_bytes.to_vec(), RuntimeArgs::new())
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    BUILDER.with(|builder| {
+        builder.clone().exec(exec_request);
+    });
+});
@@ -0,0 +1,266 @@
+use serde_json::{json, Map, Value};
+
+use engine_shared::newtypes::Blake2bHash;
+use types::{
+    contracts::NamedKeys, CLType, Contract, ContractHash, ContractPackageHash, ContractWasmHash,
+    EntryPoint, EntryPointAccess, EntryPointType, Key, ProtocolVersion,
+};
+
+pub enum ContractAbiResult {
+    RootNotFound,
+    ValueNotFound(String),
+    Success(ContractAbi),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractAbiRequest {
+    state_hash: Blake2bHash,
+    key: Key,
+    protocol_version: ProtocolVersion,
+}
+
+impl ContractAbiRequest {
+    /// Creates a request for the ABI of the contract stored under `key`.
+    ///
+    /// `key` may point either at a contract or at a contract package, in which case the ABI of the
+    /// package's current version is returned.  The contract must be compatible with
+    /// `protocol_version`.
+    pub fn new(state_hash: Blake2bHash, key: Key, protocol_version: ProtocolVersion) -> Self {
+        ContractAbiRequest {
+            state_hash,
+            key,
+            protocol_version,
+        }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+}
+
+/// The public interface of a stored contract: its entry points and the named keys it exposes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractAbi {
+    contract_hash: ContractHash,
+    contract_package_hash: ContractPackageHash,
+    contract_wasm_hash: ContractWasmHash,
+    protocol_version: ProtocolVersion,
+    entry_points: Vec<EntryPoint>,
+    named_keys: NamedKeys,
+}
+
+impl ContractAbi {
+    pub fn new(contract_hash: ContractHash, contract: Contract) -> Self {
+        let (contract_package_hash, contract_wasm_hash, named_keys, entry_points, protocol_version) =
+            contract.into();
+        ContractAbi {
+            contract_hash,
+            contract_package_hash,
+            contract_wasm_hash,
+            protocol_version,
+            entry_points: entry_points.take_entry_points(),
+            named_keys,
+        }
+    }
+
+    pub fn contract_hash(&self) -> ContractHash {
+        self.contract_hash
+    }
+
+    pub fn contract_package_hash(&self) -> ContractPackageHash {
+        self.contract_package_hash
+    }
+
+    pub fn contract_wasm_hash(&self) -> ContractWasmHash {
+        self.contract_wasm_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    pub fn entry_points(&self) -> &[EntryPoint] {
+        &self.entry_points
+    }
+
+    pub fn named_keys(&self) -> &NamedKeys {
+        &self.named_keys
+    }
+
+    /// Returns the ABI as a JSON value.
+    ///
+    /// Hashes are base16 encoded, keys use their `Display` representation and `CLType`s are
+    /// rendered as either a type name (e.g. `"U512"`) or an object keyed by the type name (e.g.
+    /// `{"Option": "U512"}`).
+    pub fn to_json(&self) -> Value {
+        let entry_points: Vec<Value> = self.entry_points.iter().map(entry_point_to_json).collect();
+        let named_keys: Map<String, Value> = self
+            .named_keys
+            .iter()
+            .map(|(name, key)| (name.clone(), Value::String(key.to_string())))
+            .collect();
+        json!({
+            "contract_hash": base16::encode_lower(&self.contract_hash),
+            "contract_package_hash": base16::encode_lower(&self.contract_package_hash),
+            "contract_wasm_hash": base16::encode_lower(&self.contract_wasm_hash),
+            "protocol_version": self.protocol_version.to_string(),
+            "entry_points": entry_points,
+            "named_keys": named_keys,
+        })
+    }
+}
+
+fn entry_point_to_json(entry_point: &EntryPoint) -> Value {
+    let args: Vec<Value> = entry_point
+        .args()
+        .iter()
+        .map(|parameter| {
+            json!({
+                "name": parameter.name(),
+                "cl_type": cl_type_to_json(parameter.cl_type()),
+            })
+        })
+        .collect();
+    let access = match entry_point.access() {
+        EntryPointAccess::Public => json!("Public"),
+        EntryPointAccess::Groups(groups) => {
+            let groups: Vec<&str> = groups.iter().map(|group| group.value()).collect();
+            json!({ "Groups": groups })
+        }
+    };
+    let entry_point_type = match entry_point.entry_point_type() {
+        EntryPointType::Session => "Session",
+        EntryPointType::Contract => "Contract",
+    };
+    json!({
+        "name": entry_point.name(),
+        "args": args,
+        "ret": cl_type_to_json(entry_point.ret()),
+        "access": access,
+        "entry_point_type": entry_point_type,
+    })
+}
+
+fn cl_type_to_json(cl_type: &CLType) -> Value {
+    match cl_type {
+        CLType::Option(inner) => json!({ "Option": cl_type_to_json(inner) }),
+        CLType::List(inner) => json!({ "List": cl_type_to_json(inner) }),
+        CLType::FixedList(inner, length) => {
+            json!({ "FixedList": [cl_type_to_json(inner), length] })
+        }
+        CLType::Result { ok, err } => json!({
+            "Result": { "ok": cl_type_to_json(ok), "err": cl_type_to_json(err) }
+        }),
+        CLType::Map { key, value } => json!({
+            "Map": { "key": cl_type_to_json(key), "value": cl_type_to_json(value) }
+        }),
+        CLType::Tuple1(types) => json!({ "Tuple1": tuple_to_json(types) }),
+        CLType::Tuple2(types) => json!({ "Tuple2": tuple_to_json(types) }),
+        CLType::Tuple3(types) => json!({ "Tuple3": tuple_to_json(types) }),
+        simple => json!(format!("{:?}", simple)),
+    }
+}
+
+fn tuple_to_json(types: &[Box<CLType>]) -> Vec<Value> {
+    types
+        .iter()
+        .map(|cl_type| cl_type_to_json(cl_type))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use types::{
+        contracts::{NamedKeys, Parameter},
+        AccessRights, CLType, Contract, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
+        Key, ProtocolVersion, URef,
+    };
+
+    use super::{cl_type_to_json, ContractAbi};
+
+    #[test]
+    fn should_render_cl_types_as_json() {
+        assert_eq!(cl_type_to_json(&CLType::U512), json!("U512"));
+        assert_eq!(
+            cl_type_to_json(&CLType::Option(Box::new(CLType::URef))),
+            json!({ "Option": "URef" })
+        );
+        assert_eq!(
+            cl_type_to_json(&CLType::Map {
+                key: Box::new(CLType::String),
+                value: Box::new(CLType::List(Box::new(CLType::U8))),
+            }),
+            json!({ "Map": { "key": "String", "value": { "List": "U8" } } })
+        );
+        assert_eq!(
+            cl_type_to_json(&CLType::FixedList(Box::new(CLType::U8), 32)),
+            json!({ "FixedList": ["U8", 32] })
+        );
+        assert_eq!(
+            cl_type_to_json(&CLType::Tuple2([
+                Box::new(CLType::Bool),
+                Box::new(CLType::Unit)
+            ])),
+            json!({ "Tuple2": ["Bool", "Unit"] })
+        );
+    }
+
+    #[test]
+    fn should_render_contract_abi_as_json() {
+        let mut entry_points = EntryPoints::new();
+        entry_points.add_entry_point(EntryPoint::new(
+            "transfer",
+            vec![
+                Parameter::new("target", CLType::FixedList(Box::new(CLType::U8), 32)),
+                Parameter::new("amount", CLType::U512),
+            ],
+            CLType::Unit,
+            EntryPointAccess::groups(&["admin"]),
+            EntryPointType::Contract,
+        ));
+        let uref = URef::new([2; 32], AccessRights::READ);
+        let mut named_keys = NamedKeys::new();
+        named_keys.insert("balance".to_string(), Key::from(uref));
+
+        let contract = Contract::new(
+            [3; 32],
+            [4; 32],
+            named_keys,
+            entry_points,
+            ProtocolVersion::V1_0_0,
+        );
+        let abi = ContractAbi::new([1; 32], contract);
+        let json = abi.to_json();
+
+        assert_eq!(json["contract_hash"], json!("01".repeat(32)));
+        assert_eq!(json["contract_package_hash"], json!("03".repeat(32)));
+        assert_eq!(json["protocol_version"], json!("1.0.0"));
+        assert_eq!(
+            json["entry_points"],
+            json!([{
+                "name": "transfer",
+                "args": [
+                    { "name": "target", "cl_type": { "FixedList": ["U8", 32] } },
+                    { "name": "amount", "cl_type": "U512" },
+                ],
+                "ret": "Unit",
+                "access": { "Groups": ["admin"] },
+                "entry_point_type": "Contract",
+            }])
+        );
+        assert_eq!(
+            json["named_keys"],
+            json!({ "balance": Key::from(uref).to_string() })
+        );
+    }
+}
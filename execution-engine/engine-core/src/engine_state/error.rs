@@ -47,6 +47,8 @@ pub enum Error {
     InvalidUpgradeResult,
     #[fail(display = "Unsupported deploy item variant: {}", _0)]
     InvalidDeployItemVariant(String),
+    #[fail(display = "Root not found: {}", _0)]
+    RootNotFound(Blake2bHash),
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
@@ -89,16 +91,3 @@ impl From<mint::Error> for Error {
         Error::Mint(error)
     }
 }
-
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct RootNotFound(Blake2bHash);
-
-impl RootNotFound {
-    pub fn new(hash: Blake2bHash) -> Self {
-        RootNotFound(hash)
-    }
-
-    pub fn to_vec(&self) -> Vec<u8> {
-        self.0.to_vec()
-    }
-}
@@ -8,7 +8,7 @@ use rand::{
 
 use engine_shared::{motes::Motes, newtypes::Blake2bHash, TypeMismatch};
 use engine_storage::global_state::CommitResult;
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{wasm_costs::WasmCosts, wasm_limits::WasmLimits};
 use types::{account::AccountHash, bytesrepr, Key, ProtocolVersion, U512};
 
 use crate::engine_state::execution_effect::ExecutionEffect;
@@ -188,6 +188,7 @@ pub struct ExecConfig {
     standard_payment_installer_bytes: Vec<u8>,
     accounts: Vec<GenesisAccount>,
     wasm_costs: WasmCosts,
+    wasm_limits: WasmLimits,
 }
 
 impl ExecConfig {
@@ -197,6 +198,7 @@ impl ExecConfig {
         standard_payment_installer_bytes: Vec<u8>,
         accounts: Vec<GenesisAccount>,
         wasm_costs: WasmCosts,
+        wasm_limits: WasmLimits,
     ) -> ExecConfig {
         ExecConfig {
             mint_installer_bytes,
@@ -204,6 +206,7 @@ impl ExecConfig {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            wasm_limits,
         }
     }
     pub fn mint_installer_bytes(&self) -> &[u8] {
@@ -222,6 +225,10 @@ impl ExecConfig {
         self.wasm_costs
     }
 
+    pub fn wasm_limits(&self) -> WasmLimits {
+        self.wasm_limits
+    }
+
     pub fn get_bonded_validators(&self) -> impl Iterator<Item = (AccountHash, Motes)> + '_ {
         let zero = Motes::zero();
         self.accounts.iter().filter_map(move |genesis_account| {
@@ -272,6 +279,16 @@ impl Distribution<ExecConfig> for Standard {
             max_stack_height: rng.gen(),
             opcodes_mul: rng.gen(),
             opcodes_div: rng.gen(),
+            memfill: rng.gen_range(1, u32::MAX),
+        };
+
+        let wasm_limits = WasmLimits {
+            max_memory_pages: rng.gen(),
+            max_table_size: rng.gen(),
+            max_locals: rng.gen(),
+            max_globals: rng.gen(),
+            max_functions: rng.gen(),
+            max_module_size: rng.gen(),
         };
 
         ExecConfig {
@@ -280,6 +297,7 @@ impl Distribution<ExecConfig> for Standard {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            wasm_limits,
         }
     }
 }
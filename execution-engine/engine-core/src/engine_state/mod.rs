@@ -1,3 +1,4 @@
+pub mod contract_abi;
 pub mod deploy_item;
 pub mod engine_config;
 mod error;
@@ -6,10 +7,10 @@ pub mod execute_request;
 pub mod execution_effect;
 pub mod execution_result;
 pub mod genesis;
+pub mod module_cache;
 pub mod op;
 pub mod query;
 pub mod run_genesis_request;
-pub mod system_contract_cache;
 mod transfer;
 pub mod upgrade;
 pub mod utils;
@@ -22,7 +23,6 @@ use std::{
 
 use log::{debug, warn};
 use num_traits::Zero;
-use parity_wasm::elements::Module;
 
 use engine_shared::{
     account::Account,
@@ -37,7 +37,7 @@ use engine_storage::{
     global_state::{CommitResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
 };
-use engine_wasm_prep::{wasm_costs::WasmCosts, Preprocessor};
+use engine_wasm_prep::{wasm_costs::WasmCosts, InstrumentedModule, Preprocessor};
 use types::{
     account::AccountHash,
     bytesrepr::{self, ToBytes},
@@ -50,13 +50,12 @@ use types::{
     U512,
 };
 
-pub use self::{
-    engine_config::EngineConfig,
-    error::{Error, RootNotFound},
-    transfer::TransferRuntimeArgsBuilder,
-};
+pub use self::{engine_config::EngineConfig, error::Error, transfer::TransferRuntimeArgsBuilder};
+#[cfg(feature = "native")]
+use crate::runtime::NativeEntryPoint;
 use crate::{
     engine_state::{
+        contract_abi::{ContractAbi, ContractAbiRequest, ContractAbiResult},
         deploy_item::DeployItem,
         error::Error::MissingSystemContract,
         executable_deploy_item::ExecutableDeployItem,
@@ -65,13 +64,14 @@ use crate::{
         genesis::{
             ExecConfig, GenesisAccount, GenesisResult, POS_PAYMENT_PURSE, POS_REWARDS_PURSE,
         },
+        module_cache::ModuleCache,
         query::{QueryRequest, QueryResult},
-        system_contract_cache::SystemContractCache,
         transfer::TransferTargetMode,
         upgrade::{UpgradeConfig, UpgradeResult},
     },
     execution::{
         self, AddressGenerator, AddressGeneratorBuilder, DirectSystemContractCall, Executor,
+        Interrupt, OutputRecorder,
     },
     tracking_copy::{TrackingCopy, TrackingCopyExt},
 };
@@ -89,21 +89,31 @@ const ARG_AMOUNT: &str = "amount";
 #[derive(Debug)]
 pub struct EngineState<S> {
     config: EngineConfig,
-    system_contract_cache: SystemContractCache,
+    module_cache: ModuleCache,
     state: S,
 }
 
+/// The code run as the session of a deploy.
+#[derive(Clone, Copy)]
+enum SessionCode {
+    /// The deploy's own session code.
+    Deploy,
+    /// A natively compiled contract, run in place of the deploy's session code.
+    #[cfg(feature = "native")]
+    Native(NativeEntryPoint),
+}
+
 #[derive(Clone, Debug)]
 pub enum GetModuleResult {
     Session {
-        module: Module,
+        module: InstrumentedModule,
         contract_package: ContractPackage,
         entry_point: EntryPoint,
     },
     Contract {
         // Contract hash
         base_key: Key,
-        module: Module,
+        module: InstrumentedModule,
         contract: Contract,
         contract_package: ContractPackage,
         entry_point: EntryPoint,
@@ -111,7 +121,7 @@ pub enum GetModuleResult {
 }
 
 impl GetModuleResult {
-    pub fn take_module(self) -> Module {
+    pub fn take_module(self) -> InstrumentedModule {
         match self {
             GetModuleResult::Session { module, .. } => module,
             GetModuleResult::Contract { module, .. } => module,
@@ -125,10 +135,10 @@ where
     S::Error: Into<execution::Error>,
 {
     pub fn new(state: S, config: EngineConfig) -> EngineState<S> {
-        let system_contract_cache = Default::default();
+        let module_cache = Default::default();
         EngineState {
             config,
-            system_contract_cache,
+            module_cache,
             state,
         }
     }
@@ -137,6 +147,10 @@ where
         &self.config
     }
 
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
     pub fn wasm_costs(
         &self,
         protocol_version: ProtocolVersion,
@@ -173,7 +187,8 @@ where
 
         let initial_root_hash = self.state.empty_root();
         let wasm_costs = ee_config.wasm_costs();
-        let preprocessor = Preprocessor::new(wasm_costs);
+        let wasm_limits = ee_config.wasm_limits();
+        let preprocessor = Preprocessor::new(wasm_costs, wasm_limits);
 
         // Spec #3: Create "virtual system account" object.
         let mut virtual_system_account = {
@@ -222,7 +237,7 @@ where
             let hash_address_generator = Rc::clone(&hash_address_generator);
             let uref_address_generator = Rc::clone(&uref_address_generator);
             let tracking_copy = Rc::clone(&tracking_copy);
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let module_cache = ModuleCache::clone(&self.module_cache);
             let protocol_data = ProtocolData::default();
 
             executor.exec_wasm_direct(
@@ -241,7 +256,7 @@ where
                 tracking_copy,
                 phase,
                 protocol_data,
-                system_contract_cache,
+                module_cache,
             )?
         };
 
@@ -262,7 +277,7 @@ where
             let hash_address_generator = Rc::clone(&hash_address_generator);
             let uref_address_generator = Rc::clone(&uref_address_generator);
             let install_deploy_hash = genesis_config_hash.into();
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let module_cache = ModuleCache::clone(&self.module_cache);
 
             // Constructs a partial protocol data with already known uref to pass the validation
             // step
@@ -293,7 +308,7 @@ where
                 tracking_copy,
                 phase,
                 partial_protocol_data,
-                system_contract_cache,
+                module_cache,
             )?
         };
 
@@ -303,6 +318,7 @@ where
         // specification.
         let protocol_data = ProtocolData::partial_without_standard_payment(
             wasm_costs,
+            wasm_limits,
             mint_hash,
             proof_of_stake_hash,
         );
@@ -327,7 +343,7 @@ where
             let hash_address_generator = Rc::clone(&hash_address_generator);
             let uref_address_generator = Rc::clone(&uref_address_generator);
             let tracking_copy = Rc::clone(&tracking_copy);
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let module_cache = ModuleCache::clone(&self.module_cache);
 
             executor.exec_wasm_direct(
                 standard_payment_installer_module,
@@ -345,13 +361,14 @@ where
                 tracking_copy,
                 phase,
                 protocol_data,
-                system_contract_cache,
+                module_cache,
             )?
         };
 
         // Spec #2: Associate given CostTable with given ProtocolVersion.
         let protocol_data = ProtocolData::new(
             wasm_costs,
+            wasm_limits,
             mint_hash,
             proof_of_stake_hash,
             standard_payment_hash,
@@ -401,7 +418,7 @@ where
                     .borrow_mut()
                     .get_contract_wasm(correlation_id, contract.contract_wasm_hash())?;
                 let bytes = contract_wasm.bytes();
-                engine_wasm_prep::deserialize(&bytes)?
+                preprocessor.preprocess(&bytes)?
             };
             // For each account...
             for (account, named_keys) in accounts.into_iter() {
@@ -425,7 +442,7 @@ where
                         .build();
                     Rc::new(RefCell::new(generator))
                 };
-                let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+                let module_cache = ModuleCache::clone(&self.module_cache);
 
                 let mint_result: Result<URef, mint::Error> = {
                     // ...call the Mint's "mint" endpoint to create purse with tokens...
@@ -448,7 +465,7 @@ where
                         tracking_copy_exec,
                         phase,
                         protocol_data,
-                        system_contract_cache,
+                        module_cache,
                     )?;
 
                     runtime
@@ -535,9 +552,16 @@ where
             None => *current_protocol_data.wasm_costs(),
         };
 
+        // resolve Wasm limits for new protocol version
+        let new_wasm_limits = match upgrade_config.wasm_limits() {
+            Some(new_wasm_limits) => new_wasm_limits,
+            None => *current_protocol_data.wasm_limits(),
+        };
+
         // 3.1.2.2 persist wasm CostTable
         let mut new_protocol_data = ProtocolData::new(
             new_wasm_costs,
+            new_wasm_limits,
             current_protocol_data.mint(),
             current_protocol_data.proof_of_stake(),
             current_protocol_data.standard_payment(),
@@ -561,7 +585,7 @@ where
 
                 // preprocess installer module
                 let upgrade_installer_module = {
-                    let preprocessor = Preprocessor::new(new_wasm_costs);
+                    let preprocessor = Preprocessor::new(new_wasm_costs, new_wasm_limits);
                     preprocessor.preprocess(bytes)?
                 };
 
@@ -614,7 +638,7 @@ where
                     Rc::new(RefCell::new(generator))
                 };
                 let tracking_copy = Rc::clone(&tracking_copy);
-                let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+                let module_cache = ModuleCache::clone(&self.module_cache);
 
                 let executor = Executor::new(self.config);
 
@@ -634,7 +658,7 @@ where
                     Rc::clone(&tracking_copy),
                     phase,
                     new_protocol_data,
-                    system_contract_cache,
+                    module_cache,
                 )?;
 
                 if !new_protocol_data.update_from(result) {
@@ -691,22 +715,212 @@ where
             .into())
     }
 
+    pub fn get_contract_abi(
+        &self,
+        correlation_id: CorrelationId,
+        contract_abi_request: ContractAbiRequest,
+    ) -> Result<ContractAbiResult, Error> {
+        let mut tracking_copy = match self.tracking_copy(contract_abi_request.state_hash())? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(ContractAbiResult::RootNotFound),
+        };
+
+        let key = contract_abi_request.key();
+        let (contract_hash, contract) = match tracking_copy
+            .get(correlation_id, &key)
+            .map_err(|err| Error::Exec(err.into()))?
+        {
+            Some(StoredValue::Contract(contract)) => (key.into_seed(), contract),
+            Some(StoredValue::ContractPackage(contract_package)) => {
+                let contract_hash = match contract_package
+                    .current_contract_version()
+                    .and_then(|version| contract_package.lookup_contract_hash(version))
+                {
+                    Some(contract_hash) => *contract_hash,
+                    None => {
+                        return Ok(ContractAbiResult::ValueNotFound(format!(
+                            "No active contract versions for contract package at {}",
+                            key
+                        )))
+                    }
+                };
+                let contract = tracking_copy.get_contract(correlation_id, contract_hash)?;
+                (contract_hash, contract)
+            }
+            Some(other) => {
+                return Ok(ContractAbiResult::ValueNotFound(format!(
+                    "Value at {} is not a contract: {}",
+                    key,
+                    other.type_name()
+                )))
+            }
+            None => {
+                return Ok(ContractAbiResult::ValueNotFound(format!(
+                    "Value not found at {}",
+                    key
+                )))
+            }
+        };
+
+        let protocol_version = contract_abi_request.protocol_version();
+        if !contract.is_compatible_protocol_version(protocol_version) {
+            let exec_error = execution::Error::IncompatibleProtocolMajorVersion {
+                expected: protocol_version.value().major,
+                actual: contract.protocol_version().value().major,
+            };
+            return Err(Error::Exec(exec_error));
+        }
+
+        Ok(ContractAbiResult::Success(ContractAbi::new(
+            contract_hash,
+            contract,
+        )))
+    }
+
     pub fn run_execute(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+    ) -> Result<Vec<ExecutionResult>, Error> {
+        self.run_execute_with_interrupt(correlation_id, exec_request, Interrupt::new())
+    }
+
+    /// Like [`EngineState::run_execute`], but stops and returns an error once `interrupt` is set.
+    pub fn run_execute_with_interrupt(
         &self,
         correlation_id: CorrelationId,
         mut exec_request: ExecuteRequest,
-    ) -> Result<Vec<ExecutionResult>, RootNotFound> {
-        // TODO: do not unwrap
-        let wasm_costs = self
-            .wasm_costs(exec_request.protocol_version)
-            .unwrap()
-            .unwrap();
-        let executor = Executor::new(self.config);
-        let preprocessor = Preprocessor::new(wasm_costs);
+        interrupt: Interrupt,
+    ) -> Result<Vec<ExecutionResult>, Error> {
+        let mut results = Vec::new();
+        self.run_execute_streaming(
+            correlation_id,
+            exec_request.parent_state_hash,
+            exec_request.block_time,
+            exec_request.protocol_version,
+            exec_request.take_deploys(),
+            interrupt,
+            |result| results.push(result),
+        )?;
+        Ok(results)
+    }
 
+    /// Like [`EngineState::run_execute`], but records the deploys' [`Output`](execution::Output) to
+    /// `output`.
+    pub fn run_execute_with_output(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+        output: OutputRecorder,
+    ) -> Result<Vec<ExecutionResult>, Error> {
         let mut results = Vec::new();
+        self.execute_deploys(
+            correlation_id,
+            exec_request.parent_state_hash,
+            exec_request.block_time,
+            exec_request.protocol_version,
+            exec_request.take_deploys(),
+            Executor::new(self.config).with_output(output),
+            |result| results.push(result),
+        )?;
+        Ok(results)
+    }
+
+    /// Like [`EngineState::run_execute_with_output`], but runs `native_entry_point`, a natively
+    /// compiled contract, as the session code of each deploy in place of its own.
+    ///
+    /// Payment code is run and the fee is charged just as for Wasm session code, and the session
+    /// has the same gas limit.  Native code is not instrumented though, so only the gas charged by
+    /// the host functions it calls counts towards that limit and the fee.
+    #[cfg(feature = "native")]
+    pub fn run_native_execute(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+        native_entry_point: NativeEntryPoint,
+        output: OutputRecorder,
+    ) -> Result<Vec<ExecutionResult>, Error> {
+        let protocol_version = exec_request.protocol_version;
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+        let preprocessor =
+            Preprocessor::new(*protocol_data.wasm_costs(), *protocol_data.wasm_limits());
+        let executor = Executor::new(self.config).with_output(output);
+
+        exec_request
+            .take_deploys()
+            .into_iter()
+            .map(|deploy_item| match deploy_item {
+                Err(exec_result) => Ok(exec_result),
+                Ok(deploy_item) => self.execute_deploy(
+                    correlation_id,
+                    &executor,
+                    &preprocessor,
+                    protocol_version,
+                    exec_request.parent_state_hash,
+                    BlockTime::new(exec_request.block_time),
+                    deploy_item,
+                    SessionCode::Native(native_entry_point),
+                ),
+            })
+            .collect()
+    }
+
+    /// Like [`EngineState::run_execute_with_interrupt`], but takes the deploys one at a time from
+    /// `deploys`, passing the result of each to `on_result` as soon as it is ready.
+    ///
+    /// If an error is returned, any results already passed to `on_result` must be discarded.
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_execute_streaming<I, F>(
+        &self,
+        correlation_id: CorrelationId,
+        parent_state_hash: Blake2bHash,
+        block_time: u64,
+        protocol_version: ProtocolVersion,
+        deploys: I,
+        interrupt: Interrupt,
+        on_result: F,
+    ) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = Result<DeployItem, ExecutionResult>>,
+        F: FnMut(ExecutionResult),
+    {
+        self.execute_deploys(
+            correlation_id,
+            parent_state_hash,
+            block_time,
+            protocol_version,
+            deploys,
+            Executor::new(self.config).with_interrupt(interrupt),
+            on_result,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn execute_deploys<I, F>(
+        &self,
+        correlation_id: CorrelationId,
+        parent_state_hash: Blake2bHash,
+        block_time: u64,
+        protocol_version: ProtocolVersion,
+        deploys: I,
+        executor: Executor,
+        mut on_result: F,
+    ) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = Result<DeployItem, ExecutionResult>>,
+        F: FnMut(ExecutionResult),
+    {
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+        let preprocessor =
+            Preprocessor::new(*protocol_data.wasm_costs(), *protocol_data.wasm_limits());
 
-        for deploy_item in exec_request.take_deploys() {
+        for deploy_item in deploys {
             let result = match deploy_item {
                 Err(exec_result) => Ok(exec_result),
                 Ok(deploy_item) => match deploy_item.session {
@@ -714,31 +928,35 @@ where
                         correlation_id,
                         &executor,
                         &preprocessor,
-                        exec_request.protocol_version,
-                        exec_request.parent_state_hash,
-                        BlockTime::new(exec_request.block_time),
+                        protocol_version,
+                        parent_state_hash,
+                        BlockTime::new(block_time),
                         deploy_item,
                     ),
                     _ => self.deploy(
                         correlation_id,
                         &executor,
                         &preprocessor,
-                        exec_request.protocol_version,
-                        exec_request.parent_state_hash,
-                        BlockTime::new(exec_request.block_time),
+                        protocol_version,
+                        parent_state_hash,
+                        BlockTime::new(block_time),
                         deploy_item,
                     ),
                 },
             };
+            // A deploy cut short by the interrupt has no meaningful result, so none are returned.
+            if executor.interrupt().is_interrupted() {
+                return Err(Error::Exec(execution::Error::Interrupted));
+            }
             match result {
-                Ok(result) => results.push(result),
+                Ok(result) => on_result(result),
                 Err(error) => {
                     return Err(error);
                 }
             };
         }
 
-        Ok(results)
+        Ok(())
     }
 
     pub fn get_module(
@@ -841,7 +1059,12 @@ where
             .borrow_mut()
             .get_contract_wasm(correlation_id, contract.contract_wasm_hash())?;
 
-        let module = engine_wasm_prep::deserialize(contract_wasm.bytes())?;
+        let module = self.module_cache.get_or_instrument(
+            contract.contract_wasm_hash(),
+            *protocol_version,
+            &contract_wasm,
+            preprocessor,
+        )?;
 
         match entry_point.entry_point_type() {
             EntryPointType::Session => Ok(GetModuleResult::Session {
@@ -864,8 +1087,9 @@ where
         tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
         contract_hash: ContractHash,
         correlation_id: CorrelationId,
+        preprocessor: &Preprocessor,
         protocol_version: &ProtocolVersion,
-    ) -> Result<Module, error::Error> {
+    ) -> Result<InstrumentedModule, error::Error> {
         let contract = tracking_copy
             .borrow_mut()
             .get_contract(correlation_id, contract_hash)?;
@@ -884,7 +1108,12 @@ where
             .borrow_mut()
             .get_contract_wasm(correlation_id, contract.contract_wasm_hash())?;
 
-        let module = engine_wasm_prep::deserialize(contract_wasm.bytes())?;
+        let module = self.module_cache.get_or_instrument(
+            contract.contract_wasm_hash(),
+            *protocol_version,
+            &contract_wasm,
+            preprocessor,
+        )?;
 
         Ok(module)
     }
@@ -929,7 +1158,7 @@ where
         prestate_hash: Blake2bHash,
         blocktime: BlockTime,
         deploy_item: DeployItem,
-    ) -> Result<ExecutionResult, RootNotFound> {
+    ) -> Result<ExecutionResult, Error> {
         let protocol_data = match self.state.get_protocol_data(protocol_version) {
             Ok(Some(protocol_data)) => protocol_data,
             Ok(None) => {
@@ -945,7 +1174,7 @@ where
 
         let tracking_copy = match self.tracking_copy(prestate_hash) {
             Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
-            Ok(None) => return Err(RootNotFound::new(prestate_hash)),
+            Ok(None) => return Err(Error::RootNotFound(prestate_hash)),
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
         };
 
@@ -990,6 +1219,8 @@ where
                 contract_wasm_hash,
                 use_system_contracts,
                 preprocessor,
+                &self.module_cache,
+                protocol_version,
             ) {
                 Ok(module) => module,
                 Err(error) => {
@@ -1031,7 +1262,7 @@ where
                             Rc::clone(&tracking_copy),
                             Phase::Session,
                             protocol_data,
-                            SystemContractCache::clone(&self.system_contract_cache),
+                            ModuleCache::clone(&self.module_cache),
                         );
                     match maybe_uref {
                         Some(main_purse) => {
@@ -1088,7 +1319,7 @@ where
                 tracking_copy,
                 Phase::Session,
                 protocol_data,
-                SystemContractCache::clone(&self.system_contract_cache),
+                ModuleCache::clone(&self.module_cache),
             );
 
         Ok(execution_result)
@@ -1104,7 +1335,31 @@ where
         prestate_hash: Blake2bHash,
         blocktime: BlockTime,
         deploy_item: DeployItem,
-    ) -> Result<ExecutionResult, RootNotFound> {
+    ) -> Result<ExecutionResult, Error> {
+        self.execute_deploy(
+            correlation_id,
+            executor,
+            preprocessor,
+            protocol_version,
+            prestate_hash,
+            blocktime,
+            deploy_item,
+            SessionCode::Deploy,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn execute_deploy(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        prestate_hash: Blake2bHash,
+        blocktime: BlockTime,
+        deploy_item: DeployItem,
+        session_code: SessionCode,
+    ) -> Result<ExecutionResult, Error> {
         // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
 
         // Obtain current protocol data for given version
@@ -1127,7 +1382,7 @@ where
         // do this second; as there is no reason to proceed if the prestate hash is invalid
         let tracking_copy = match self.tracking_copy(prestate_hash) {
             Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
-            Ok(None) => return Err(RootNotFound::new(prestate_hash)),
+            Ok(None) => return Err(Error::RootNotFound(prestate_hash)),
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
         };
 
@@ -1162,21 +1417,25 @@ where
         let payment = deploy_item.payment;
         let deploy_hash = deploy_item.deploy_hash;
 
-        // Create session code `A` from provided session bytes
+        // Create session code `A` from provided session bytes, unless native code is run instead
         // validation_spec_1: valid wasm bytes
         // we do this upfront as there is no reason to continue if session logic is invalid
-        let session_module = match self.get_module(
-            Rc::clone(&tracking_copy),
-            &session,
-            &account,
-            correlation_id,
-            preprocessor,
-            &protocol_version,
-        ) {
-            Ok(module) => module,
-            Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error));
-            }
+        let session_module = match session_code {
+            SessionCode::Deploy => match self.get_module(
+                Rc::clone(&tracking_copy),
+                &session,
+                &account,
+                correlation_id,
+                preprocessor,
+                &protocol_version,
+            ) {
+                Ok(module) => Some(module),
+                Err(error) => {
+                    return Ok(ExecutionResult::precondition_failure(error));
+                }
+            },
+            #[cfg(feature = "native")]
+            SessionCode::Native(_) => None,
         };
 
         // Get mint system contract details
@@ -1194,20 +1453,15 @@ where
         };
 
         // cache mint module
-        if !self.system_contract_cache.has(mint_hash) {
-            let mint_module = match tracking_copy.borrow_mut().get_system_module(
-                correlation_id,
-                mint_contract.contract_wasm_hash(),
-                self.config.use_system_contracts(),
-                preprocessor,
-            ) {
-                Ok(contract) => contract,
-                Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
-                }
-            };
-
-            self.system_contract_cache.insert(mint_hash, mint_module);
+        if let Err(error) = tracking_copy.borrow_mut().get_system_module(
+            correlation_id,
+            mint_contract.contract_wasm_hash(),
+            self.config.use_system_contracts(),
+            preprocessor,
+            &self.module_cache,
+            protocol_version,
+        ) {
+            return Ok(ExecutionResult::precondition_failure(error.into()));
         }
 
         // Get proof of stake system contract URef from account (an account on a
@@ -1232,6 +1486,8 @@ where
             proof_of_stake_contract.contract_wasm_hash(),
             self.config.use_system_contracts(),
             preprocessor,
+            &self.module_cache,
+            protocol_version,
         ) {
             Ok(module) => module,
             Err(error) => {
@@ -1239,12 +1495,6 @@ where
             }
         };
 
-        // cache proof_of_stake module
-        if !self.system_contract_cache.has(proof_of_stake_hash) {
-            self.system_contract_cache
-                .insert(proof_of_stake_hash, proof_of_stake_module.clone());
-        }
-
         // Get account main purse balance key
         // validation_spec_5: account main purse minimum balance
         let account_main_purse_balance_key: Key = {
@@ -1325,6 +1575,7 @@ where
                     Rc::clone(&tracking_copy),
                     standard_payment_hash,
                     correlation_id,
+                    preprocessor,
                     &protocol_version,
                 )
                 .map(|module| GetModuleResult::Session {
@@ -1394,7 +1645,7 @@ where
                 }
             };
 
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let module_cache = ModuleCache::clone(&self.module_cache);
 
             if self.config.use_system_contracts() || !module_bytes_is_empty {
                 executor.exec(
@@ -1413,7 +1664,7 @@ where
                     Rc::clone(&tracking_copy),
                     phase,
                     protocol_data,
-                    system_contract_cache,
+                    module_cache,
                     &payment_package,
                 )
             } else {
@@ -1446,7 +1697,7 @@ where
                     Rc::clone(&tracking_copy),
                     phase,
                     protocol_data,
-                    system_contract_cache,
+                    module_cache,
                 ) {
                     Ok((_instance, runtime)) => runtime,
                     Err(error) => {
@@ -1548,40 +1799,6 @@ where
         let post_payment_tracking_copy = tracking_copy.borrow();
         let session_tracking_copy = Rc::new(RefCell::new(post_payment_tracking_copy.fork()));
 
-        // session_code_spec_2: execute session code
-        let (
-            session_module,
-            session_base_key,
-            mut session_named_keys,
-            session_package,
-            session_entry_point,
-        ) = match session_module {
-            GetModuleResult::Session {
-                module,
-                contract_package,
-                entry_point,
-            } => (
-                module,
-                base_key,
-                account.named_keys().clone(),
-                contract_package,
-                entry_point,
-            ),
-            GetModuleResult::Contract {
-                module,
-                base_key,
-                contract,
-                contract_package,
-                entry_point,
-            } => (
-                module,
-                base_key,
-                contract.named_keys().clone(),
-                contract_package,
-                entry_point,
-            ),
-        };
-
         let session_args = match session.into_runtime_args() {
             Ok(args) => args,
             Err(e) => {
@@ -1598,27 +1815,85 @@ where
             let session_gas_limit: Gas = Gas::from_motes(payment_purse_balance, CONV_RATE)
                 .unwrap_or_default()
                 - payment_result_cost;
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let module_cache = ModuleCache::clone(&self.module_cache);
+
+            // session_code_spec_2: execute session code
+            match (session_code, session_module) {
+                #[cfg(feature = "native")]
+                (SessionCode::Native(native_entry_point), _) => executor.exec_native(
+                    native_entry_point,
+                    EntryPoint::default(),
+                    session_args,
+                    base_key,
+                    &account,
+                    &mut account.named_keys().clone(),
+                    authorization_keys.clone(),
+                    blocktime,
+                    deploy_hash,
+                    session_gas_limit,
+                    protocol_version,
+                    correlation_id,
+                    Rc::clone(&session_tracking_copy),
+                    Phase::Session,
+                    protocol_data,
+                    module_cache,
+                    &ContractPackage::default(),
+                ),
+                (SessionCode::Deploy, session_module) => {
+                    let (
+                        session_module,
+                        session_base_key,
+                        mut session_named_keys,
+                        session_package,
+                        session_entry_point,
+                    ) = match session_module.expect("session module should have been created") {
+                        GetModuleResult::Session {
+                            module,
+                            contract_package,
+                            entry_point,
+                        } => (
+                            module,
+                            base_key,
+                            account.named_keys().clone(),
+                            contract_package,
+                            entry_point,
+                        ),
+                        GetModuleResult::Contract {
+                            module,
+                            base_key,
+                            contract,
+                            contract_package,
+                            entry_point,
+                        } => (
+                            module,
+                            base_key,
+                            contract.named_keys().clone(),
+                            contract_package,
+                            entry_point,
+                        ),
+                    };
 
-            executor.exec(
-                session_module,
-                session_entry_point,
-                session_args,
-                session_base_key,
-                &account,
-                &mut session_named_keys,
-                authorization_keys.clone(),
-                blocktime,
-                deploy_hash,
-                session_gas_limit,
-                protocol_version,
-                correlation_id,
-                Rc::clone(&session_tracking_copy),
-                Phase::Session,
-                protocol_data,
-                system_contract_cache,
-                &session_package,
-            )
+                    executor.exec(
+                        session_module,
+                        session_entry_point,
+                        session_args,
+                        session_base_key,
+                        &account,
+                        &mut session_named_keys,
+                        authorization_keys.clone(),
+                        blocktime,
+                        deploy_hash,
+                        session_gas_limit,
+                        protocol_version,
+                        correlation_id,
+                        Rc::clone(&session_tracking_copy),
+                        Phase::Session,
+                        protocol_data,
+                        module_cache,
+                        &session_package,
+                    )
+                }
+            }
         };
         debug!("Session result: {:?}", session_result);
 
@@ -1663,7 +1938,7 @@ where
             let mut proof_of_stake_keys = proof_of_stake_contract.named_keys().to_owned();
 
             let gas_limit = Gas::new(U512::from(std::u64::MAX));
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let module_cache = ModuleCache::clone(&self.module_cache);
 
             executor.exec_system_contract(
                 DirectSystemContractCall::FinalizePayment,
@@ -1682,7 +1957,7 @@ where
                 finalization_tc,
                 Phase::FinalizePayment,
                 protocol_data,
-                system_contract_cache,
+                module_cache,
             )
         };
 
@@ -1725,7 +2000,8 @@ where
     /// Calculates bonded validators at `root_hash` state.
     ///
     /// Should only be called with a valid root hash after a successful call to
-    /// [`StateProvider::commit`]. Will panic if called with an invalid root hash.
+    /// [`StateProvider::commit`]. Returns [`Error::RootNotFound`] if called with an invalid root
+    /// hash.
     fn get_bonded_validators(
         &self,
         correlation_id: CorrelationId,
@@ -1744,7 +2020,7 @@ where
 
         let reader = match self.state.checkout(root_hash)? {
             Some(reader) => reader,
-            None => panic!("get_bonded_validators called with an invalid root hash"),
+            None => return Err(Error::RootNotFound(root_hash)),
         };
 
         let contract = match reader.read(correlation_id, &proof_of_stake_key)? {
@@ -0,0 +1,247 @@
+use std::sync::{Arc, Mutex};
+
+use linked_hash_map::LinkedHashMap;
+
+use engine_wasm_prep::{InstrumentedModule, PreprocessingError, Preprocessor};
+use types::{ContractWasm, ContractWasmHash, ProtocolVersion};
+
+/// The number of modules held by a default [`ModuleCache`].
+pub const DEFAULT_CAPACITY: usize = 256;
+
+type CacheKey = (ContractWasmHash, ProtocolVersion);
+
+#[derive(Debug)]
+struct Modules {
+    capacity: usize,
+    // Ordered from least to most recently used.
+    modules: LinkedHashMap<CacheKey, InstrumentedModule>,
+}
+
+/// A bounded cache of stored contract Wasm, instrumented under the costs of a protocol version.
+///
+/// Once full, the least recently used module is evicted to make room for a new one.
+#[derive(Clone, Debug)]
+pub struct ModuleCache(Arc<Mutex<Modules>>);
+
+impl Default for ModuleCache {
+    fn default() -> Self {
+        ModuleCache::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl ModuleCache {
+    /// Creates a cache holding at most `capacity` modules.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let modules = Modules {
+            capacity,
+            modules: LinkedHashMap::new(),
+        };
+        ModuleCache(Arc::new(Mutex::new(modules)))
+    }
+
+    /// Returns the number of modules in the cache.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().modules.len()
+    }
+
+    /// Returns `true` if the cache holds no modules.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a clone of the module stored under `contract_wasm_hash`, as instrumented for
+    /// `protocol_version`.
+    pub fn get(
+        &self,
+        contract_wasm_hash: ContractWasmHash,
+        protocol_version: ProtocolVersion,
+    ) -> Option<InstrumentedModule> {
+        let mut guarded_modules = self.0.lock().unwrap();
+        guarded_modules
+            .modules
+            .get_refresh(&(contract_wasm_hash, protocol_version))
+            .cloned()
+    }
+
+    /// Inserts `module`, the Wasm stored under `contract_wasm_hash` as instrumented for
+    /// `protocol_version`, evicting the least recently used modules if the cache is full.
+    pub fn insert(
+        &self,
+        contract_wasm_hash: ContractWasmHash,
+        protocol_version: ProtocolVersion,
+        module: InstrumentedModule,
+    ) {
+        let mut guarded_modules = self.0.lock().unwrap();
+        let Modules { capacity, modules } = &mut *guarded_modules;
+        modules.insert((contract_wasm_hash, protocol_version), module);
+        while modules.len() > *capacity {
+            modules.pop_front();
+        }
+    }
+
+    /// Returns the module stored under `contract_wasm_hash` as instrumented for
+    /// `protocol_version`, instrumenting `contract_wasm` with `preprocessor` and caching the result
+    /// if it isn't already cached.
+    pub fn get_or_instrument(
+        &self,
+        contract_wasm_hash: ContractWasmHash,
+        protocol_version: ProtocolVersion,
+        contract_wasm: &ContractWasm,
+        preprocessor: &Preprocessor,
+    ) -> Result<InstrumentedModule, PreprocessingError> {
+        if let Some(module) = self.get(contract_wasm_hash, protocol_version) {
+            return Ok(module);
+        }
+        let module = preprocessor.preprocess_stored(contract_wasm.bytes())?;
+        self.insert(contract_wasm_hash, protocol_version, module.clone());
+        Ok(module)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{
+        builder,
+        elements::{Instruction, Instructions},
+    };
+
+    use engine_shared::wasm;
+    use engine_wasm_prep::{wasm_costs::WasmCosts, wasm_limits::WasmLimits};
+    use types::SemVer;
+
+    use super::*;
+
+    const HASH_1: ContractWasmHash = [1; 32];
+    const HASH_2: ContractWasmHash = [2; 32];
+    const HASH_3: ContractWasmHash = [3; 32];
+
+    // A module whose `call` function has instructions to be metered.
+    fn contract_wasm() -> ContractWasm {
+        let module = builder::module()
+            .memory()
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::I32Const(1),
+                Instruction::Drop,
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .export()
+            .field("call")
+            .internal()
+            .func(0)
+            .build()
+            .build();
+        ContractWasm::new(parity_wasm::serialize(module).unwrap())
+    }
+
+    fn module() -> InstrumentedModule {
+        let preprocessor = Preprocessor::new(WasmCosts::default(), WasmLimits::default());
+        wasm::do_nothing_module(&preprocessor).unwrap()
+    }
+
+    #[test]
+    fn should_key_by_hash_and_protocol_version() {
+        let cache = ModuleCache::default();
+        let next_version = ProtocolVersion::new(SemVer::new(1, 1, 0));
+        cache.insert(HASH_1, ProtocolVersion::V1_0_0, module());
+
+        assert!(cache.get(HASH_1, ProtocolVersion::V1_0_0).is_some());
+        assert!(cache.get(HASH_1, next_version).is_none());
+        assert!(cache.get(HASH_2, ProtocolVersion::V1_0_0).is_none());
+    }
+
+    #[test]
+    fn should_evict_least_recently_used() {
+        let cache = ModuleCache::with_capacity(2);
+        cache.insert(HASH_1, ProtocolVersion::V1_0_0, module());
+        cache.insert(HASH_2, ProtocolVersion::V1_0_0, module());
+        assert!(cache.get(HASH_1, ProtocolVersion::V1_0_0).is_some());
+
+        cache.insert(HASH_3, ProtocolVersion::V1_0_0, module());
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(HASH_1, ProtocolVersion::V1_0_0).is_some());
+        assert!(cache.get(HASH_2, ProtocolVersion::V1_0_0).is_none());
+        assert!(cache.get(HASH_3, ProtocolVersion::V1_0_0).is_some());
+    }
+
+    #[test]
+    fn should_hold_nothing_with_zero_capacity() {
+        let cache = ModuleCache::with_capacity(0);
+        cache.insert(HASH_1, ProtocolVersion::V1_0_0, module());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn should_instrument_under_given_costs() {
+        let cache = ModuleCache::default();
+        let cheap = Preprocessor::new(WasmCosts::default(), WasmLimits::default());
+        let expensive = Preprocessor::new(
+            WasmCosts {
+                regular: WasmCosts::default().regular + 1,
+                ..WasmCosts::default()
+            },
+            WasmLimits::default(),
+        );
+        let next_version = ProtocolVersion::new(SemVer::new(1, 1, 0));
+
+        let first = cache
+            .get_or_instrument(HASH_1, ProtocolVersion::V1_0_0, &contract_wasm(), &cheap)
+            .unwrap();
+        let second = cache
+            .get_or_instrument(HASH_1, next_version, &contract_wasm(), &expensive)
+            .unwrap();
+
+        assert_eq!(first.original(), second.original());
+        assert_ne!(first.instrumented(), second.instrumented());
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn should_keep_original_bytes() {
+        let cache = ModuleCache::default();
+        let preprocessor = Preprocessor::new(WasmCosts::default(), WasmLimits::default());
+        let contract_wasm = contract_wasm();
+
+        let module = cache
+            .get_or_instrument(
+                HASH_1,
+                ProtocolVersion::V1_0_0,
+                &contract_wasm,
+                &preprocessor,
+            )
+            .unwrap();
+
+        assert_eq!(module.original(), contract_wasm.bytes());
+    }
+
+    #[test]
+    fn should_run_legacy_instrumented_wasm_as_it_is() {
+        let cache = ModuleCache::default();
+        let preprocessor = Preprocessor::new(WasmCosts::default(), WasmLimits::default());
+        // Contract Wasm used to be stored already instrumented.
+        let (_original, instrumented) = preprocessor
+            .preprocess(contract_wasm().bytes())
+            .unwrap()
+            .into_parts();
+        let legacy_contract_wasm =
+            ContractWasm::new(parity_wasm::serialize(instrumented.clone()).unwrap());
+
+        let module = cache
+            .get_or_instrument(
+                HASH_1,
+                ProtocolVersion::V1_0_0,
+                &legacy_contract_wasm,
+                &preprocessor,
+            )
+            .unwrap();
+
+        assert_eq!(module.original(), legacy_contract_wasm.bytes());
+        assert_eq!(*module.instrumented(), instrumented);
+    }
+}
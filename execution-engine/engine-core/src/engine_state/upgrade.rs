@@ -2,7 +2,7 @@ use std::fmt;
 
 use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
 use engine_storage::global_state::CommitResult;
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{wasm_costs::WasmCosts, wasm_limits::WasmLimits};
 use types::{bytesrepr, Key, ProtocolVersion};
 
 use crate::engine_state::execution_effect::ExecutionEffect;
@@ -60,6 +60,7 @@ pub struct UpgradeConfig {
     upgrade_installer_args: Option<Vec<u8>>,
     upgrade_installer_bytes: Option<Vec<u8>>,
     wasm_costs: Option<WasmCosts>,
+    wasm_limits: Option<WasmLimits>,
     activation_point: Option<ActivationPoint>,
 }
 
@@ -71,6 +72,7 @@ impl UpgradeConfig {
         upgrade_installer_args: Option<Vec<u8>>,
         upgrade_installer_bytes: Option<Vec<u8>>,
         wasm_costs: Option<WasmCosts>,
+        wasm_limits: Option<WasmLimits>,
         activation_point: Option<ActivationPoint>,
     ) -> Self {
         UpgradeConfig {
@@ -80,6 +82,7 @@ impl UpgradeConfig {
             upgrade_installer_args,
             upgrade_installer_bytes,
             wasm_costs,
+            wasm_limits,
             activation_point,
         }
     }
@@ -110,6 +113,10 @@ impl UpgradeConfig {
         self.wasm_costs
     }
 
+    pub fn wasm_limits(&self) -> Option<WasmLimits> {
+        self.wasm_limits
+    }
+
     pub fn activation_point(&self) -> Option<u64> {
         self.activation_point
     }
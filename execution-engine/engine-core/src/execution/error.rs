@@ -3,7 +3,10 @@ use parity_wasm::elements;
 
 use engine_shared::TypeMismatch;
 use types::{
-    account::{AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure},
+    account::{
+        AddKeyFailure, ManageAccountFailure, RemoveKeyFailure, SetThresholdFailure,
+        UpdateKeyFailure,
+    },
     bytesrepr, system_contract_errors, AccessRights, ApiError, CLType, CLValueError,
     ContractPackageHash, ContractVersionKey, Key, URef,
 };
@@ -38,6 +41,8 @@ pub enum Error {
     ParityWasm(elements::Error),
     #[fail(display = "Out of gas error")]
     GasLimit,
+    #[fail(display = "Execution interrupted")]
+    Interrupted,
     #[fail(display = "Return")]
     Ret(Vec<URef>),
     #[fail(display = "{}", _0)]
@@ -56,6 +61,8 @@ pub enum Error {
     #[fail(display = "{}", _0)]
     SetThresholdFailure(SetThresholdFailure),
     #[fail(display = "{}", _0)]
+    ManageAccountFailure(ManageAccountFailure),
+    #[fail(display = "{}", _0)]
     SystemContract(system_contract_errors::Error),
     #[fail(display = "Deployment authorization failure")]
     DeploymentAuthorizationFailure,
@@ -89,6 +96,25 @@ pub enum Error {
         expected, actual
     )]
     InvalidKeyLength { expected: usize, actual: usize },
+    #[fail(display = "Missing argument {} for entry point {}", name, entry_point)]
+    MissingArgument { entry_point: String, name: String },
+    #[fail(
+        display = "Invalid argument {} for entry point {}: {}",
+        name, entry_point, mismatch
+    )]
+    InvalidArgument {
+        entry_point: String,
+        name: String,
+        mismatch: TypeMismatch,
+    },
+    #[fail(
+        display = "Invalid return value for entry point {}: {}",
+        entry_point, mismatch
+    )]
+    InvalidReturnValue {
+        entry_point: String,
+        mismatch: TypeMismatch,
+    },
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
@@ -168,6 +194,12 @@ impl From<SetThresholdFailure> for Error {
     }
 }
 
+impl From<ManageAccountFailure> for Error {
+    fn from(err: ManageAccountFailure) -> Self {
+        Error::ManageAccountFailure(err)
+    }
+}
+
 impl From<system_contract_errors::Error> for Error {
     fn from(error: system_contract_errors::Error) -> Self {
         Error::SystemContract(error)
@@ -1,25 +1,27 @@
 use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
 
 use log::warn;
-use parity_wasm::elements::Module;
-use wasmi::ModuleRef;
+use wasmi::{MemoryRef, ModuleRef, RuntimeValue};
 
 use engine_shared::{
     account::Account, gas::Gas, newtypes::CorrelationId, stored_value::StoredValue,
 };
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
+use engine_wasm_prep::InstrumentedModule;
 use types::{
     account::AccountHash, bytesrepr::FromBytes, contracts::NamedKeys, AccessRights, BlockTime,
     CLTyped, CLValue, ContractPackage, EntryPoint, EntryPointType, Key, Phase, ProtocolVersion,
     RuntimeArgs,
 };
 
+#[cfg(feature = "native")]
+use crate::runtime::{invoke_native, native_memory, NativeEntryPoint};
 use crate::{
     engine_state::{
         execution_effect::ExecutionEffect, execution_result::ExecutionResult,
-        system_contract_cache::SystemContractCache, EngineConfig,
+        module_cache::ModuleCache, EngineConfig,
     },
-    execution::{address_generator::AddressGenerator, Error},
+    execution::{address_generator::AddressGenerator, Error, Interrupt, OutputRecorder},
     runtime::{
         extract_access_rights_from_keys, extract_access_rights_from_urefs, instance_and_memory,
         Runtime,
@@ -73,21 +75,105 @@ macro_rules! on_fail_charge {
 
 pub struct Executor {
     config: EngineConfig,
+    interrupt: Interrupt,
+    output: OutputRecorder,
 }
 
 #[allow(clippy::too_many_arguments)]
 impl Executor {
     pub fn new(config: EngineConfig) -> Self {
-        Executor { config }
+        Executor {
+            config,
+            interrupt: Interrupt::new(),
+            output: OutputRecorder::new(),
+        }
+    }
+
+    /// Makes executions stop once `interrupt` is set.
+    pub fn with_interrupt(mut self, interrupt: Interrupt) -> Self {
+        self.interrupt = interrupt;
+        self
+    }
+
+    /// Makes executions record their output to `output`.
+    pub fn with_output(mut self, output: OutputRecorder) -> Self {
+        self.output = output;
+        self
     }
 
     pub fn config(&self) -> EngineConfig {
         self.config
     }
 
+    pub fn interrupt(&self) -> &Interrupt {
+        &self.interrupt
+    }
+
+    pub fn output(&self) -> &OutputRecorder {
+        &self.output
+    }
+
     pub fn exec<R>(
         &self,
-        module: Module,
+        module: InstrumentedModule,
+        entry_point: EntryPoint,
+        args: RuntimeArgs,
+        base_key: Key,
+        account: &Account,
+        named_keys: &mut NamedKeys,
+        authorization_keys: BTreeSet<AccountHash>,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+        gas_limit: Gas,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+        phase: Phase,
+        protocol_data: ProtocolData,
+        module_cache: ModuleCache,
+        contract_package: &ContractPackage,
+    ) -> ExecutionResult
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+    {
+        let (module_bytes, instrumented_module) = module.into_parts();
+        let (instance, memory) = on_fail_charge!(instance_and_memory(
+            instrumented_module,
+            protocol_version,
+            protocol_data.wasm_limits()
+        ));
+        let entry_point_name = entry_point.name().to_string();
+
+        self.exec_in_runtime(
+            memory,
+            module_bytes,
+            |runtime| instance.invoke_export(&entry_point_name, &[], runtime),
+            entry_point,
+            args,
+            base_key,
+            account,
+            named_keys,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            phase,
+            protocol_data,
+            module_cache,
+            contract_package,
+        )
+    }
+
+    /// Executes the natively compiled `native_entry_point` as [`exec`](Executor::exec) executes the
+    /// given entry point of a Wasm module.
+    #[cfg(feature = "native")]
+    pub fn exec_native<R>(
+        &self,
+        native_entry_point: NativeEntryPoint,
         entry_point: EntryPoint,
         args: RuntimeArgs,
         base_key: Key,
@@ -102,20 +188,70 @@ impl Executor {
         tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         phase: Phase,
         protocol_data: ProtocolData,
-        system_contract_cache: SystemContractCache,
+        module_cache: ModuleCache,
         contract_package: &ContractPackage,
     ) -> ExecutionResult
     where
         R: StateReader<Key, StoredValue>,
         R::Error: Into<Error>,
     {
-        let entry_point_name = entry_point.name();
+        let memory = on_fail_charge!(native_memory());
+
+        self.exec_in_runtime(
+            memory,
+            Vec::new(),
+            |runtime| invoke_native(runtime, native_entry_point),
+            entry_point,
+            args,
+            base_key,
+            account,
+            named_keys,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            phase,
+            protocol_data,
+            module_cache,
+            contract_package,
+        )
+    }
+
+    /// Executes an entry point in a new [`Runtime`] with the given `memory` and the bytes of the
+    /// uninstrumented module, where `invoke` runs the entry point's code.
+    fn exec_in_runtime<R, F>(
+        &self,
+        memory: MemoryRef,
+        module_bytes: Vec<u8>,
+        invoke: F,
+        entry_point: EntryPoint,
+        args: RuntimeArgs,
+        base_key: Key,
+        account: &Account,
+        named_keys: &mut NamedKeys,
+        authorization_keys: BTreeSet<AccountHash>,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+        gas_limit: Gas,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+        phase: Phase,
+        protocol_data: ProtocolData,
+        module_cache: ModuleCache,
+        contract_package: &ContractPackage,
+    ) -> ExecutionResult
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+        F: FnOnce(&mut Runtime<R>) -> Result<Option<RuntimeValue>, wasmi::Error>,
+    {
         let entry_point_type = entry_point.entry_point_type();
         let entry_point_access = entry_point.access();
 
-        let (instance, memory) =
-            on_fail_charge!(instance_and_memory(module.clone(), protocol_version));
-
         let access_rights = {
             let keys: Vec<Key> = named_keys.values().cloned().collect();
             extract_access_rights_from_keys(keys)
@@ -156,7 +292,15 @@ impl Executor {
             protocol_data,
         );
 
-        let mut runtime = Runtime::new(self.config, system_contract_cache, memory, module, context);
+        let mut runtime = Runtime::new(
+            self.config,
+            module_cache,
+            memory,
+            module_bytes,
+            context,
+            self.interrupt.clone(),
+            self.output.clone(),
+        );
 
         let accounts_access_rights = {
             let keys: Vec<Key> = account.named_keys().values().cloned().collect();
@@ -217,11 +361,11 @@ impl Executor {
             }
         }
 
-        on_fail_charge!(
-            instance.invoke_export(entry_point_name, &[], &mut runtime),
-            runtime.context().gas_counter(),
-            effects_snapshot
-        );
+        let result = invoke(&mut runtime);
+        if phase == Phase::Session {
+            self.record_returned_value(&result, &mut runtime);
+        }
+        on_fail_charge!(result, runtime.context().gas_counter(), effects_snapshot);
 
         ExecutionResult::Success {
             effect: runtime.context().effect(),
@@ -229,10 +373,36 @@ impl Executor {
         }
     }
 
+    /// Records the value passed to `ret` if `result` is the trap it raised.
+    fn record_returned_value<R>(
+        &self,
+        result: &Result<Option<RuntimeValue>, wasmi::Error>,
+        runtime: &mut Runtime<R>,
+    ) where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+    {
+        let is_ret = match result {
+            Err(error) => match error.as_host_error() {
+                Some(host_error) => match host_error.downcast_ref::<Error>() {
+                    Some(Error::Ret(_)) => true,
+                    _ => false,
+                },
+                None => false,
+            },
+            Ok(_) => false,
+        };
+        if is_ret {
+            if let Some(value) = runtime.take_host_buffer() {
+                self.output.set_returned(value);
+            }
+        }
+    }
+
     pub fn exec_system_contract<R, T>(
         &self,
         direct_system_contract_call: DirectSystemContractCall,
-        module: Module,
+        module: InstrumentedModule,
         runtime_args: RuntimeArgs,
         named_keys: &mut NamedKeys,
         extra_keys: &[Key],
@@ -247,7 +417,7 @@ impl Executor {
         tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         phase: Phase,
         protocol_data: ProtocolData,
-        system_contract_cache: SystemContractCache,
+        module_cache: ModuleCache,
     ) -> (Option<T>, ExecutionResult)
     where
         R: StateReader<Key, StoredValue>,
@@ -307,7 +477,7 @@ impl Executor {
                 tracking_copy,
                 phase,
                 protocol_data,
-                system_contract_cache,
+                module_cache,
             )
             .map_err(|e| {
                 ExecutionResult::Failure {
@@ -400,7 +570,7 @@ impl Executor {
     /// This is not meant to be used for executing system contracts.
     pub fn exec_wasm_direct<R, T>(
         &self,
-        module: Module,
+        module: InstrumentedModule,
         entry_point_name: &str,
         args: RuntimeArgs,
         account: &mut Account,
@@ -415,7 +585,7 @@ impl Executor {
         tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         phase: Phase,
         protocol_data: ProtocolData,
-        system_contract_cache: SystemContractCache,
+        module_cache: ModuleCache,
     ) -> Result<T, Error>
     where
         R: StateReader<Key, StoredValue>,
@@ -444,7 +614,7 @@ impl Executor {
             tracking_copy,
             phase,
             protocol_data,
-            system_contract_cache,
+            module_cache,
         )?;
 
         let error: wasmi::Error = match instance.invoke_export(entry_point_name, &[], &mut runtime)
@@ -482,7 +652,7 @@ impl Executor {
 
     pub fn create_runtime<'a, R>(
         &self,
-        module: Module,
+        module: InstrumentedModule,
         entry_point_type: EntryPointType,
         runtime_args: RuntimeArgs,
         named_keys: &'a mut NamedKeys,
@@ -500,7 +670,7 @@ impl Executor {
         tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         phase: Phase,
         protocol_data: ProtocolData,
-        system_contract_cache: SystemContractCache,
+        module_cache: ModuleCache,
     ) -> Result<(ModuleRef, Runtime<'a, R>), Error>
     where
         R: StateReader<Key, StoredValue>,
@@ -535,14 +705,21 @@ impl Executor {
             protocol_data,
         );
 
-        let (instance, memory) = instance_and_memory(module.clone(), protocol_version)?;
+        let (module_bytes, instrumented_module) = module.into_parts();
+        let (instance, memory) = instance_and_memory(
+            instrumented_module,
+            protocol_version,
+            protocol_data.wasm_limits(),
+        )?;
 
         let runtime = Runtime::new(
             self.config,
-            system_contract_cache,
+            module_cache,
             memory,
-            module,
+            module_bytes,
             runtime_context,
+            self.interrupt.clone(),
+            self.output.clone(),
         );
 
         Ok((instance, runtime))
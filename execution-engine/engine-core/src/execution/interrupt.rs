@@ -0,0 +1,28 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// A flag used to stop an execution from another thread.
+///
+/// All clones share the same flag.  An execution holding a clone stops with
+/// [`Error::Interrupted`](super::Error::Interrupted) the next time it is charged gas after
+/// [`Interrupt::interrupt`] has been called on any of them.
+#[derive(Clone, Debug, Default)]
+pub struct Interrupt(Arc<AtomicBool>);
+
+impl Interrupt {
+    pub fn new() -> Self {
+        Interrupt::default()
+    }
+
+    /// Requests that executions holding this flag stop.
+    pub fn interrupt(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if [`Interrupt::interrupt`] has been called.
+    pub fn is_interrupted(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
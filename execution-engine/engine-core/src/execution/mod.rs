@@ -2,6 +2,8 @@ mod address_generator;
 mod error;
 #[macro_use]
 mod executor;
+mod interrupt;
+mod output;
 #[cfg(test)]
 mod tests;
 
@@ -9,4 +11,6 @@ pub use self::{
     address_generator::{AddressGenerator, AddressGeneratorBuilder},
     error::Error,
     executor::{DirectSystemContractCall, Executor},
+    interrupt::Interrupt,
+    output::{Output, OutputRecorder},
 };
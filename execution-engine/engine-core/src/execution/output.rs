@@ -0,0 +1,44 @@
+use std::{cell::RefCell, mem, rc::Rc};
+
+use types::CLValue;
+
+/// What a request's deploys output other than their effects.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Output {
+    /// The lines printed with `print`, in order.
+    ///
+    /// This is only recorded when the `test-support` feature is enabled, as `print` is otherwise
+    /// unavailable.
+    pub printed: Vec<String>,
+    /// The value last passed to `ret` by session code, if any.
+    ///
+    /// Note that `ret` ends session code with [`Error::Ret`](super::Error::Ret), so the deploy
+    /// fails.
+    pub returned: Option<CLValue>,
+}
+
+/// Records the [`Output`] of executions, e.g. for inspection by tests.
+///
+/// All clones share the same record.
+#[derive(Clone, Debug, Default)]
+pub struct OutputRecorder(Rc<RefCell<Output>>);
+
+impl OutputRecorder {
+    pub fn new() -> Self {
+        OutputRecorder::default()
+    }
+
+    #[cfg(feature = "test-support")]
+    pub(crate) fn print(&self, text: String) {
+        self.0.borrow_mut().printed.push(text);
+    }
+
+    pub(crate) fn set_returned(&self, value: CLValue) {
+        self.0.borrow_mut().returned = Some(value);
+    }
+
+    /// Returns the output recorded so far, leaving the record empty.
+    pub fn take(&self) -> Output {
+        mem::take(&mut *self.0.borrow_mut())
+    }
+}
@@ -5,6 +5,7 @@ mod v1_resolver;
 
 use wasmi::ModuleImportResolver;
 
+use engine_wasm_prep::wasm_limits::WasmLimits;
 use types::ProtocolVersion;
 
 use self::error::ResolverError;
@@ -13,22 +14,26 @@ use crate::resolvers::memory_resolver::MemoryResolver;
 /// Creates a module resolver for given protocol version.
 ///
 /// * `protocol_version` Version of the protocol. Can't be lower than 1.
+/// * `wasm_limits` Limits of the protocol version, bounding the memory a module can import.
 pub fn create_module_resolver(
     protocol_version: ProtocolVersion,
+    wasm_limits: &WasmLimits,
 ) -> Result<impl ModuleImportResolver + MemoryResolver, ResolverError> {
     // TODO: revisit how protocol_version check here is meant to combine with upgrade
     if protocol_version >= ProtocolVersion::V1_0_0 {
-        return Ok(v1_resolver::RuntimeModuleImportResolver::default());
+        return Ok(v1_resolver::RuntimeModuleImportResolver::new(
+            wasm_limits.max_memory_pages,
+        ));
     }
     Err(ResolverError::UnknownProtocolVersion(protocol_version))
 }
 
 #[test]
 fn resolve_invalid_module() {
-    assert!(create_module_resolver(ProtocolVersion::default()).is_err());
+    assert!(create_module_resolver(ProtocolVersion::default(), &WasmLimits::default()).is_err());
 }
 
 #[test]
 fn protocol_version_1_always_resolves() {
-    assert!(create_module_resolver(ProtocolVersion::V1_0_0).is_ok());
+    assert!(create_module_resolver(ProtocolVersion::V1_0_0, &WasmLimits::default()).is_ok());
 }
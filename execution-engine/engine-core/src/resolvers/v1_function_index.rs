@@ -49,6 +49,14 @@ pub enum FunctionIndex {
     RemoveContractUserGroupIndex,
     ExtendContractUserGroupURefsIndex,
     RemoveContractUserGroupURefsIndex,
+    ManageAccountFuncIndex,
+    RotateAccessKeyIndex,
+    EnableContractVersion,
+    SetContractVersionMetadataIndex,
+    RemoveLocalFuncIndex,
+    RemoveURefFuncIndex,
+    MemoryCopyIndex,
+    MemoryFillIndex,
 }
 
 impl Into<usize> for FunctionIndex {
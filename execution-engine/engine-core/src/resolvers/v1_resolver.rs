@@ -1,10 +1,13 @@
 use std::cell::RefCell;
 
+use parity_wasm::elements::{self, FunctionType};
 use wasmi::{
     memory_units::Pages, Error as InterpreterError, FuncInstance, FuncRef, MemoryDescriptor,
     MemoryInstance, MemoryRef, ModuleImportResolver, Signature, ValueType,
 };
 
+use engine_wasm_prep::host_functions;
+
 use super::{
     error::ResolverError, memory_resolver::MemoryResolver, v1_function_index::FunctionIndex,
 };
@@ -14,11 +17,12 @@ pub struct RuntimeModuleImportResolver {
     max_memory: u32,
 }
 
-impl Default for RuntimeModuleImportResolver {
-    fn default() -> Self {
+impl RuntimeModuleImportResolver {
+    /// Creates a resolver which rejects memories of more than `max_memory` pages.
+    pub fn new(max_memory: u32) -> Self {
         RuntimeModuleImportResolver {
             memory: RefCell::new(None),
-            max_memory: 64,
+            max_memory,
         }
     }
 }
@@ -33,190 +37,101 @@ impl MemoryResolver for RuntimeModuleImportResolver {
     }
 }
 
+fn missing_host_function(field_name: &str) -> InterpreterError {
+    InterpreterError::Function(format!(
+        "host module doesn't export function with name {}",
+        field_name
+    ))
+}
+
+fn value_type(value_type: elements::ValueType) -> ValueType {
+    match value_type {
+        elements::ValueType::I32 => ValueType::I32,
+        elements::ValueType::I64 => ValueType::I64,
+        elements::ValueType::F32 => ValueType::F32,
+        elements::ValueType::F64 => ValueType::F64,
+    }
+}
+
+/// Converts the signature of a host function as given in the table of host functions to the one
+/// used by the interpreter.
+fn signature(function_type: &FunctionType) -> Signature {
+    let params: Vec<ValueType> = function_type
+        .params()
+        .iter()
+        .map(|param| value_type(*param))
+        .collect();
+    Signature::new(params, function_type.return_type().map(value_type))
+}
+
 impl ModuleImportResolver for RuntimeModuleImportResolver {
     fn resolve_func(
         &self,
         field_name: &str,
         _signature: &Signature,
     ) -> Result<FuncRef, InterpreterError> {
-        let func_ref = match field_name {
-            "read_value" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::ReadFuncIndex.into(),
-            ),
-            "read_value_local" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::ReadLocalFuncIndex.into(),
-            ),
-            "load_named_keys" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::LoadNamedKeysFuncIndex.into(),
-            ),
-            "write" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::WriteFuncIndex.into(),
-            ),
-            "write_local" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::WriteLocalFuncIndex.into(),
-            ),
-            "add" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::AddFuncIndex.into(),
-            ),
-            "new_uref" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], None),
-                FunctionIndex::NewFuncIndex.into(),
-            ),
-            "ret" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], None),
-                FunctionIndex::RetFuncIndex.into(),
-            ),
-            "get_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
-                FunctionIndex::GetKeyFuncIndex.into(),
-            ),
-            "has_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::HasKeyFuncIndex.into(),
-            ),
-            "put_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::PutKeyFuncIndex.into(),
-            ),
-            "gas" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GasFuncIndex.into(),
-            ),
-            "is_valid_uref" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::IsValidURefFnIndex.into(),
-            ),
-            "revert" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::RevertFuncIndex.into(),
-            ),
-            "add_associated_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::AddAssociatedKeyFuncIndex.into(),
-            ),
-            "remove_associated_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::RemoveAssociatedKeyFuncIndex.into(),
-            ),
-            "update_associated_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::UpdateAssociatedKeyFuncIndex.into(),
-            ),
-            "set_action_threshold" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::SetActionThresholdFuncIndex.into(),
-            ),
-            "remove_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], None),
-                FunctionIndex::RemoveKeyFuncIndex.into(),
-            ),
-            "get_caller" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 1][..], Some(ValueType::I32)),
-                FunctionIndex::GetCallerIndex.into(),
-            ),
-            "get_blocktime" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GetBlocktimeIndex.into(),
-            ),
-            "create_purse" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::CreatePurseIndex.into(),
-            ),
-            "transfer_to_account" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::TransferToAccountIndex.into(),
-            ),
-            "transfer_from_purse_to_account" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
-                FunctionIndex::TransferFromPurseToAccountIndex.into(),
-            ),
-            "transfer_from_purse_to_purse" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
-                FunctionIndex::TransferFromPurseToPurseIndex.into(),
-            ),
-            "get_balance" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::GetBalanceIndex.into(),
-            ),
-            "get_phase" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GetPhaseIndex.into(),
-            ),
-            "get_system_contract" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::GetSystemContractIndex.into(),
-            ),
-            "get_main_purse" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GetMainPurseIndex.into(),
-            ),
-            "read_host_buffer" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::ReadHostBufferIndex.into(),
-            ),
-            "create_contract_package_at_hash" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], None),
-                FunctionIndex::CreateContractPackageAtHash.into(),
-            ),
-            "create_contract_user_group" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 8][..], Some(ValueType::I32)),
-                FunctionIndex::CreateContractUserGroup.into(),
-            ),
-            "add_contract_version" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 10][..], Some(ValueType::I32)),
-                FunctionIndex::AddContractVersion.into(),
-            ),
-            "disable_contract_version" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::DisableContractVersion.into(),
-            ),
-            "call_contract" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 7][..], Some(ValueType::I32)),
-                FunctionIndex::CallContractFuncIndex.into(),
-            ),
-            "call_versioned_contract" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 9][..], Some(ValueType::I32)),
-                FunctionIndex::CallVersionedContract.into(),
-            ),
-            "get_named_arg_size" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::GetRuntimeArgsizeIndex.into(),
-            ),
-            "get_named_arg" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::GetRuntimeArgIndex.into(),
-            ),
-            "remove_contract_user_group" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::RemoveContractUserGroupIndex.into(),
-            ),
-            "provision_contract_user_group_uref" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
-                FunctionIndex::ExtendContractUserGroupURefsIndex.into(),
-            ),
-            "remove_contract_user_group_urefs" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
-                FunctionIndex::RemoveContractUserGroupURefsIndex.into(),
-            ),
-            #[cfg(feature = "test-support")]
-            "print" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], None),
-                FunctionIndex::PrintIndex.into(),
-            ),
-            _ => {
-                return Err(InterpreterError::Function(format!(
-                    "host module doesn't export function with name {}",
-                    field_name
-                )));
+        let function_index = match field_name {
+            "read_value" => FunctionIndex::ReadFuncIndex,
+            "read_value_local" => FunctionIndex::ReadLocalFuncIndex,
+            "load_named_keys" => FunctionIndex::LoadNamedKeysFuncIndex,
+            "write" => FunctionIndex::WriteFuncIndex,
+            "write_local" => FunctionIndex::WriteLocalFuncIndex,
+            "remove_local" => FunctionIndex::RemoveLocalFuncIndex,
+            "remove_uref" => FunctionIndex::RemoveURefFuncIndex,
+            "add" => FunctionIndex::AddFuncIndex,
+            "new_uref" => FunctionIndex::NewFuncIndex,
+            "ret" => FunctionIndex::RetFuncIndex,
+            "get_key" => FunctionIndex::GetKeyFuncIndex,
+            "has_key" => FunctionIndex::HasKeyFuncIndex,
+            "put_key" => FunctionIndex::PutKeyFuncIndex,
+            "gas" => FunctionIndex::GasFuncIndex,
+            "is_valid_uref" => FunctionIndex::IsValidURefFnIndex,
+            "revert" => FunctionIndex::RevertFuncIndex,
+            "add_associated_key" => FunctionIndex::AddAssociatedKeyFuncIndex,
+            "remove_associated_key" => FunctionIndex::RemoveAssociatedKeyFuncIndex,
+            "update_associated_key" => FunctionIndex::UpdateAssociatedKeyFuncIndex,
+            "set_action_threshold" => FunctionIndex::SetActionThresholdFuncIndex,
+            "manage_account" => FunctionIndex::ManageAccountFuncIndex,
+            "remove_key" => FunctionIndex::RemoveKeyFuncIndex,
+            "get_caller" => FunctionIndex::GetCallerIndex,
+            "get_blocktime" => FunctionIndex::GetBlocktimeIndex,
+            "create_purse" => FunctionIndex::CreatePurseIndex,
+            "transfer_to_account" => FunctionIndex::TransferToAccountIndex,
+            "transfer_from_purse_to_account" => FunctionIndex::TransferFromPurseToAccountIndex,
+            "transfer_from_purse_to_purse" => FunctionIndex::TransferFromPurseToPurseIndex,
+            "get_balance" => FunctionIndex::GetBalanceIndex,
+            "get_phase" => FunctionIndex::GetPhaseIndex,
+            "get_system_contract" => FunctionIndex::GetSystemContractIndex,
+            "get_main_purse" => FunctionIndex::GetMainPurseIndex,
+            "read_host_buffer" => FunctionIndex::ReadHostBufferIndex,
+            "create_contract_package_at_hash" => FunctionIndex::CreateContractPackageAtHash,
+            "create_contract_user_group" => FunctionIndex::CreateContractUserGroup,
+            "add_contract_version" => FunctionIndex::AddContractVersion,
+            "disable_contract_version" => FunctionIndex::DisableContractVersion,
+            "rotate_access_key" => FunctionIndex::RotateAccessKeyIndex,
+            "enable_contract_version" => FunctionIndex::EnableContractVersion,
+            "set_contract_version_metadata" => FunctionIndex::SetContractVersionMetadataIndex,
+            "call_contract" => FunctionIndex::CallContractFuncIndex,
+            "call_versioned_contract" => FunctionIndex::CallVersionedContract,
+            "get_named_arg_size" => FunctionIndex::GetRuntimeArgsizeIndex,
+            "get_named_arg" => FunctionIndex::GetRuntimeArgIndex,
+            "remove_contract_user_group" => FunctionIndex::RemoveContractUserGroupIndex,
+            "provision_contract_user_group_uref" => {
+                FunctionIndex::ExtendContractUserGroupURefsIndex
             }
+            "remove_contract_user_group_urefs" => FunctionIndex::RemoveContractUserGroupURefsIndex,
+            "memory_copy" => FunctionIndex::MemoryCopyIndex,
+            "memory_fill" => FunctionIndex::MemoryFillIndex,
+            #[cfg(feature = "test-support")]
+            "print" => FunctionIndex::PrintIndex,
+            _ => return Err(missing_host_function(field_name)),
         };
-        Ok(func_ref)
+        let function_type = host_functions::host_function_type(field_name)
+            .ok_or_else(|| missing_host_function(field_name))?;
+        Ok(FuncInstance::alloc_host(
+            signature(&function_type),
+            function_index.into(),
+        ))
     }
 
     fn resolve_memory(
@@ -225,8 +140,10 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
         descriptor: &MemoryDescriptor,
     ) -> Result<MemoryRef, InterpreterError> {
         if field_name == "memory" {
-            let effective_max = descriptor.maximum().unwrap_or(self.max_memory + 1);
-            if descriptor.initial() > self.max_memory || effective_max > self.max_memory {
+            let exceeds_max = descriptor
+                .maximum()
+                .map_or(true, |maximum| maximum > self.max_memory);
+            if descriptor.initial() > self.max_memory || exceeds_max {
                 Err(InterpreterError::Instantiation(
                     "Module requested too much memory".to_owned(),
                 ))
@@ -246,3 +163,31 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use engine_wasm_prep::wasm_limits::DEFAULT_MAX_MEMORY_PAGES;
+
+    use super::*;
+
+    #[test]
+    fn should_resolve_every_host_function() {
+        let resolver = RuntimeModuleImportResolver::new(DEFAULT_MAX_MEMORY_PAGES);
+        for (name, function_type) in host_functions::host_functions() {
+            let expected_signature = signature(&function_type);
+            let func_ref = resolver
+                .resolve_func(name, &expected_signature)
+                .unwrap_or_else(|error| panic!("should resolve {}: {:?}", name, error));
+            assert_eq!(*func_ref.signature(), expected_signature);
+        }
+    }
+
+    #[test]
+    fn should_not_resolve_unknown_host_function() {
+        let resolver = RuntimeModuleImportResolver::new(DEFAULT_MAX_MEMORY_PAGES);
+        let signature = Signature::new(&[][..], None);
+        assert!(resolver
+            .resolve_func("not_a_host_function", &signature)
+            .is_err());
+    }
+}
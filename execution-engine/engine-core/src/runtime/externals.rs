@@ -84,6 +84,23 @@ where
                 Ok(None)
             }
 
+            FunctionIndex::RemoveLocalFuncIndex => {
+                // args(0) = pointer to key in Wasm memory
+                // args(1) = size of key
+                let (key_bytes_ptr, key_bytes_size): (_, u32) = Args::parse(args)?;
+                scoped_instrumenter.add_property("key_bytes_size", key_bytes_size);
+                self.remove_local(key_bytes_ptr, key_bytes_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::RemoveURefFuncIndex => {
+                // args(0) = pointer to uref in Wasm memory
+                // args(1) = size of uref
+                let (uref_ptr, uref_size) = Args::parse(args)?;
+                self.remove_uref(uref_ptr, uref_size)?;
+                Ok(None)
+            }
+
             FunctionIndex::AddFuncIndex => {
                 // args(0) = pointer to key in Wasm memory
                 // args(1) = size of key
@@ -247,6 +264,27 @@ where
                 Ok(Some(RuntimeValue::I32(value)))
             }
 
+            FunctionIndex::ManageAccountFuncIndex => {
+                // args(0) = pointer to serialized map of account hashes to weights
+                // args(1) = size of serialized map of account hashes to weights
+                // args(2) = pointer to serialized optional new main purse
+                // args(3) = size of serialized optional new main purse
+                let (associated_keys_ptr, associated_keys_size, main_purse_ptr, main_purse_size): (
+                    _,
+                    u32,
+                    _,
+                    _,
+                ) = Args::parse(args)?;
+                scoped_instrumenter.add_property("associated_keys_size", associated_keys_size);
+                let value = self.manage_account(
+                    associated_keys_ptr,
+                    associated_keys_size,
+                    main_purse_ptr,
+                    main_purse_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
             FunctionIndex::CreatePurseIndex => {
                 // args(0) = pointer to array for return value
                 // args(1) = length of array for return value
@@ -481,6 +519,74 @@ where
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(result))))
             }
 
+            FunctionIndex::EnableContractVersion => {
+                // args(0) = pointer to package hash in wasm memory
+                // args(1) = size of package hash in wasm memory
+                // args(2) = pointer to contract hash in wasm memory
+                // args(3) = size of contract hash in wasm memory
+                let (package_key_ptr, package_key_size, contract_hash_ptr, contract_hash_size) =
+                    Args::parse(args)?;
+
+                let contract_package_hash = self.t_from_mem(package_key_ptr, package_key_size)?;
+                let contract_hash = self.t_from_mem(contract_hash_ptr, contract_hash_size)?;
+
+                let result = self.enable_contract_version(contract_package_hash, contract_hash)?;
+
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(result))))
+            }
+
+            FunctionIndex::SetContractVersionMetadataIndex => {
+                // args(0) = pointer to package hash in wasm memory
+                // args(1) = size of package hash in wasm memory
+                // args(2) = pointer to contract hash in wasm memory
+                // args(3) = size of contract hash in wasm memory
+                // args(4) = pointer to serialized metadata in wasm memory
+                // args(5) = size of serialized metadata in wasm memory
+                let (
+                    package_key_ptr,
+                    package_key_size,
+                    contract_hash_ptr,
+                    contract_hash_size,
+                    metadata_ptr,
+                    metadata_size,
+                ): (_, _, _, _, _, u32) = Args::parse(args)?;
+                scoped_instrumenter.add_property("metadata_size", metadata_size);
+
+                let contract_package_hash = self.t_from_mem(package_key_ptr, package_key_size)?;
+                let contract_hash = self.t_from_mem(contract_hash_ptr, contract_hash_size)?;
+                let metadata: String = self.t_from_mem(metadata_ptr, metadata_size)?;
+
+                let result = self.set_contract_version_metadata(
+                    contract_package_hash,
+                    contract_hash,
+                    metadata,
+                )?;
+
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(result))))
+            }
+
+            FunctionIndex::RotateAccessKeyIndex => {
+                // args(0) = pointer to package hash in wasm memory
+                // args(1) = size of package hash in wasm memory
+                // args(2) = non-zero if the package should be permanently locked
+                // args(3) = pointer to wasm memory where to write 32-byte access key address
+                let (package_key_ptr, package_key_size, lock, access_dest_ptr): (_, _, u32, _) =
+                    Args::parse(args)?;
+                scoped_instrumenter.add_property("lock", lock != 0);
+
+                let contract_package_hash = self.t_from_mem(package_key_ptr, package_key_size)?;
+
+                let result = match self.rotate_access_key(contract_package_hash, lock != 0)? {
+                    Ok(access_addr) => {
+                        self.function_address(access_addr, access_dest_ptr)?;
+                        Ok(())
+                    }
+                    Err(error) => Err(error),
+                };
+
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(result))))
+            }
+
             FunctionIndex::CallContractFuncIndex => {
                 // args(0) = pointer to contract hash where contract is at in global state
                 // args(1) = size of contract hash
@@ -667,6 +773,26 @@ where
                 )?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
+
+            FunctionIndex::MemoryCopyIndex => {
+                // args(0) = destination offset in wasm memory
+                // args(1) = source offset in wasm memory
+                // args(2) = number of bytes to copy
+                let (dst, src, len): (u32, u32, u32) = Args::parse(args)?;
+                scoped_instrumenter.add_property("len", len.to_string());
+                self.memory_copy(dst, src, len)?;
+                Ok(None)
+            }
+
+            FunctionIndex::MemoryFillIndex => {
+                // args(0) = destination offset in wasm memory
+                // args(1) = byte value to fill with
+                // args(2) = number of bytes to fill
+                let (dst, value, len): (u32, u32, u32) = Args::parse(args)?;
+                scoped_instrumenter.add_property("len", len.to_string());
+                self.memory_fill(dst, value, len)?;
+                Ok(None)
+            }
         }
     }
 }
@@ -1,6 +1,8 @@
 mod args;
 mod externals;
 mod mint_internal;
+#[cfg(feature = "native")]
+mod native;
 mod proof_of_stake_internal;
 mod scoped_instrumenter;
 mod standard_payment_internal;
@@ -17,12 +19,13 @@ use parity_wasm::elements::Module;
 use wasmi::{ImportsBuilder, MemoryRef, ModuleInstance, ModuleRef, Trap, TrapKind};
 
 use ::mint::Mint;
-use engine_shared::{account::Account, gas::Gas, stored_value::StoredValue};
+use engine_shared::{account::Account, gas::Gas, stored_value::StoredValue, TypeMismatch};
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
+use engine_wasm_prep::{wasm_limits::WasmLimits, Preprocessor};
 use proof_of_stake::ProofOfStake;
 use standard_payment::StandardPayment;
 use types::{
-    account::{AccountHash, ActionType, Weight},
+    account::{AccountHash, ActionType, ManageAccountFailure, Weight},
     bytesrepr::{self, FromBytes, ToBytes},
     contracts::{
         self, Contract, ContractPackage, EntryPoint, EntryPointAccess, EntryPoints, Group,
@@ -35,22 +38,28 @@ use types::{
 };
 
 use crate::{
-    engine_state::{system_contract_cache::SystemContractCache, EngineConfig},
-    execution::Error,
+    engine_state::{module_cache::ModuleCache, EngineConfig},
+    execution::{Error, Interrupt, OutputRecorder},
     resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
     runtime_context::{self, RuntimeContext},
     Address,
 };
 use contracts::{ContractVersion, ContractVersions, DisabledVersions, Groups, NamedKeys};
+#[cfg(feature = "native")]
+pub use native::{invoke_native, native_memory, NativeEntryPoint};
 use scoped_instrumenter::ScopedInstrumenter;
 
 pub struct Runtime<'a, R> {
-    system_contract_cache: SystemContractCache,
+    module_cache: ModuleCache,
     config: EngineConfig,
     memory: MemoryRef,
-    module: Module,
+    // The bytes of the module being executed, as supplied rather than as instrumented, since
+    // they're what `add_contract_version` stores.
+    module_bytes: Vec<u8>,
     host_buffer: Option<CLValue>,
     context: RuntimeContext<'a, R>,
+    interrupt: Interrupt,
+    output: OutputRecorder,
 }
 
 /// Rename function called `name` in the `module` to `call`.
@@ -72,9 +81,10 @@ pub fn rename_export_to_call(module: &mut Module, name: String) {
 pub fn instance_and_memory(
     parity_module: Module,
     protocol_version: ProtocolVersion,
+    wasm_limits: &WasmLimits,
 ) -> Result<(ModuleRef, MemoryRef), Error> {
     let module = wasmi::Module::from_parity_wasm_module(parity_module)?;
-    let resolver = create_module_resolver(protocol_version)?;
+    let resolver = create_module_resolver(protocol_version, wasm_limits)?;
     let mut imports = ImportsBuilder::new();
     imports.push_resolver("env", &resolver);
     let not_started_module = ModuleInstance::new(&module, &imports)?;
@@ -1344,6 +1354,63 @@ fn extract_urefs(cl_value: &CLValue) -> Result<Vec<URef>, Error> {
     }
 }
 
+/// Returns whether calls to `contract` check their arguments and return value against the declared
+/// entry point.
+///
+/// Contracts stored before protocol version 1.1.0 may declare types which their callers don't
+/// follow, so they're still called as they were when they were stored.
+fn checks_entry_point_declarations(contract: &Contract) -> bool {
+    contract.protocol_version() >= ProtocolVersion::from_parts(1, 1, 0)
+}
+
+/// Checks the supplied `args` against the parameters declared by `entry_point`.
+///
+/// Every declared parameter must be present with a matching type, except that a parameter declared
+/// as `CLType::Option(_)` may be omitted and one declared as `CLType::Any` accepts any value.
+/// Arguments which aren't declared are ignored.
+fn validate_entry_point_args(entry_point: &EntryPoint, args: &RuntimeArgs) -> Result<(), Error> {
+    for parameter in entry_point.args() {
+        let expected = parameter.cl_type();
+        match args.get(parameter.name()) {
+            Some(cl_value) => {
+                let found = cl_value.cl_type();
+                if *expected != CLType::Any && expected != found {
+                    return Err(Error::InvalidArgument {
+                        entry_point: entry_point.name().to_string(),
+                        name: parameter.name().to_string(),
+                        mismatch: TypeMismatch::new(
+                            format!("{:?}", expected),
+                            format!("{:?}", found),
+                        ),
+                    });
+                }
+            }
+            None => {
+                if let CLType::Option(_) = expected {
+                    continue;
+                }
+                return Err(Error::MissingArgument {
+                    entry_point: entry_point.name().to_string(),
+                    name: parameter.name().to_string(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks the value returned by a call to `entry_point` against its declared return type.
+fn validate_entry_point_ret(entry_point: &EntryPoint, ret: CLValue) -> Result<CLValue, Error> {
+    let expected = entry_point.ret();
+    if *expected != CLType::Any && expected != ret.cl_type() {
+        return Err(Error::InvalidReturnValue {
+            entry_point: entry_point.name().to_string(),
+            mismatch: TypeMismatch::new(format!("{:?}", expected), format!("{:?}", ret.cl_type())),
+        });
+    }
+    Ok(ret)
+}
+
 impl<'a, R> Runtime<'a, R>
 where
     R: StateReader<Key, StoredValue>,
@@ -1351,18 +1418,22 @@ where
 {
     pub fn new(
         config: EngineConfig,
-        system_contract_cache: SystemContractCache,
+        module_cache: ModuleCache,
         memory: MemoryRef,
-        module: Module,
+        module_bytes: Vec<u8>,
         context: RuntimeContext<'a, R>,
+        interrupt: Interrupt,
+        output: OutputRecorder,
     ) -> Self {
         Runtime {
             config,
-            system_contract_cache,
+            module_cache,
             memory,
-            module,
+            module_bytes,
             host_buffer: None,
             context,
+            interrupt,
+            output,
         }
     }
 
@@ -1370,8 +1441,8 @@ where
         &self.memory
     }
 
-    pub fn module(&self) -> &Module {
-        &self.module
+    pub fn module_bytes(&self) -> &[u8] {
+        &self.module_bytes
     }
 
     pub fn context(&self) -> &RuntimeContext<'a, R> {
@@ -1401,6 +1472,9 @@ where
     }
 
     fn gas(&mut self, amount: Gas) -> Result<(), Trap> {
+        if self.interrupt.is_interrupted() {
+            return Err(Error::Interrupted.into());
+        }
         if self.charge_gas(amount) {
             Ok(())
         } else {
@@ -1442,30 +1516,28 @@ where
         &mut self,
         entry_points: &EntryPoints,
     ) -> Result<Vec<u8>, Error> {
-        let export_section = self
-            .module
+        let module = engine_wasm_prep::deserialize(&self.module_bytes)?;
+        let export_section = module
             .export_section()
             .ok_or_else(|| Error::FunctionNotFound(String::from("Missing Export Section")))?;
 
-        let entry_point_names: Vec<&str> = entry_points.keys().map(|s| s.as_str()).collect();
-
-        let maybe_missing_name: Option<String> = entry_point_names
-            .iter()
+        let maybe_missing_name: Option<String> = entry_points
+            .keys()
             .find(|name| {
                 export_section
                     .entries()
                     .iter()
-                    .find(|export_entry| export_entry.field() == **name)
+                    .find(|export_entry| export_entry.field() == name.as_str())
                     .is_none()
             })
-            .map(|s| String::from(*s));
+            .cloned();
 
         if let Some(missing_name) = maybe_missing_name {
             Err(Error::FunctionNotFound(missing_name))
         } else {
-            let mut module = self.module.clone();
-            pwasm_utils::optimize(&mut module, entry_point_names).unwrap();
-            parity_wasm::serialize(module).map_err(Error::ParityWasm)
+            // The module is stored exactly as supplied, so that it can be instrumented again under
+            // the costs of later protocol versions.
+            Ok(self.module_bytes.clone())
         }
     }
 
@@ -1815,10 +1887,12 @@ where
 
         let mut runtime = Runtime::new(
             self.config,
-            SystemContractCache::clone(&self.system_contract_cache),
+            ModuleCache::clone(&self.module_cache),
             self.memory.clone(),
-            self.module.clone(),
+            self.module_bytes.clone(),
             runtime_context,
+            self.interrupt.clone(),
+            self.output.clone(),
         );
 
         let ret: CLValue = match entry_point_name {
@@ -1921,8 +1995,7 @@ where
     }
 
     /// Calls `version` of the contract living at `key`, invoking `method` with
-    /// supplied `args`. This function also checks the args conform with the
-    /// types given in the contract header.
+    /// supplied `args`.
     pub fn call_versioned_contract(
         &mut self,
         contract_package_hash: ContractPackageHash,
@@ -1978,15 +2051,17 @@ where
 
         self.validate_entry_point_access(&contract_package, entry_point.access())?;
 
-        for (expected, found) in entry_point
-            .args()
-            .iter()
-            .map(|a| a.cl_type())
-            .cloned()
-            .zip(args.to_values().into_iter().map(|v| v.cl_type()).cloned())
-        {
-            if expected != found {
-                return Err(Error::type_mismatch(expected, found));
+        if !checks_entry_point_declarations(&contract) {
+            for (expected, found) in entry_point
+                .args()
+                .iter()
+                .map(|a| a.cl_type())
+                .cloned()
+                .zip(args.to_values().into_iter().map(|v| v.cl_type()).cloned())
+            {
+                if expected != found {
+                    return Err(Error::type_mismatch(expected, found));
+                }
             }
         }
 
@@ -2047,6 +2122,20 @@ where
             });
         }
 
+        // Check the args conform with the types given in the contract header before the callee
+        // gets to run
+        let checks_declarations = checks_entry_point_declarations(&contract);
+        if checks_declarations {
+            validate_entry_point_args(&entry_point, &args)?;
+        }
+        let check_ret = |ret: CLValue| {
+            if checks_declarations {
+                validate_entry_point_ret(&entry_point, ret)
+            } else {
+                Ok(ret)
+            }
+        };
+
         // TODO: should we be using named_keys_mut() instead?
         let mut named_keys = match entry_point.entry_point_type() {
             EntryPointType::Session => self.context.account().named_keys().clone(),
@@ -2069,21 +2158,23 @@ where
 
             if !self.config.use_system_contracts() {
                 if self.is_mint(key) {
-                    return self.call_host_mint(
+                    let ret = self.call_host_mint(
                         self.context.protocol_version(),
                         entry_point.name(),
                         &mut named_keys,
                         &args,
                         &extra_keys,
-                    );
+                    )?;
+                    return check_ret(ret);
                 } else if self.is_proof_of_stake(key) {
-                    return self.call_host_proof_of_stake(
+                    let ret = self.call_host_proof_of_stake(
                         self.context.protocol_version(),
                         entry_point.name(),
                         &mut named_keys,
                         &args,
                         &extra_keys,
-                    );
+                    )?;
+                    return check_ret(ret);
                 }
             }
 
@@ -2091,7 +2182,6 @@ where
         };
 
         let module = {
-            let maybe_module = self.system_contract_cache.get(key.into_seed());
             let wasm_key = contract.contract_wasm_key();
 
             let contract_wasm: ContractWasm = match self.context.read_gs(&wasm_key)? {
@@ -2104,15 +2194,25 @@ where
                 }
                 None => return Err(Error::KeyNotFound(key)),
             };
-            match maybe_module {
-                Some(module) => module,
-                None => parity_wasm::deserialize_buffer(contract_wasm.bytes())?,
-            }
+            let protocol_data = self.context.protocol_data();
+            let preprocessor =
+                Preprocessor::new(*protocol_data.wasm_costs(), *protocol_data.wasm_limits());
+            self.module_cache.get_or_instrument(
+                contract.contract_wasm_hash(),
+                protocol_version,
+                &contract_wasm,
+                &preprocessor,
+            )?
         };
 
         let entry_point_name = entry_point.name();
 
-        let (instance, memory) = instance_and_memory(module.clone(), protocol_version)?;
+        let (module_bytes, instrumented_module) = module.into_parts();
+        let (instance, memory) = instance_and_memory(
+            instrumented_module,
+            protocol_version,
+            self.context.protocol_data().wasm_limits(),
+        )?;
 
         let access_rights = {
             let mut keys: Vec<Key> = named_keys.values().cloned().collect();
@@ -2122,7 +2222,7 @@ where
             extract_access_rights_from_keys(keys)
         };
 
-        let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+        let module_cache = ModuleCache::clone(&self.module_cache);
 
         let config = self.config;
 
@@ -2150,12 +2250,14 @@ where
         );
 
         let mut runtime = Runtime {
-            system_contract_cache,
+            module_cache,
             config,
             memory,
-            module,
+            module_bytes,
             host_buffer,
             context,
+            interrupt: self.interrupt.clone(),
+            output: self.output.clone(),
         };
 
         let result = instance.invoke_export(entry_point_name, &[], &mut runtime);
@@ -2179,7 +2281,8 @@ where
                     // running session code
                     *self.context.named_keys_mut() = runtime.context.named_keys().clone();
                 }
-                return Ok(runtime.take_host_buffer().unwrap_or(CLValue::from_t(())?));
+                let ret = runtime.take_host_buffer().unwrap_or(CLValue::from_t(())?);
+                return check_ret(ret);
             }
         };
 
@@ -2202,7 +2305,10 @@ where
                         // running session code
                         *self.context.named_keys_mut() = runtime.context.named_keys().clone();
                     }
-                    return runtime.take_host_buffer().ok_or(Error::ExpectedReturnValue);
+                    let ret = runtime
+                        .take_host_buffer()
+                        .ok_or(Error::ExpectedReturnValue)?;
+                    return check_ret(ret);
                 }
                 error => return Err(error.clone()),
             }
@@ -2447,6 +2553,10 @@ where
             .context
             .get_validated_contract_package(contract_package_hash)?;
 
+        if contract_package.is_locked() {
+            return Ok(Err(contracts::Error::LockedContractPackage.into()));
+        }
+
         let contract_wasm_hash = self.context.new_hash_address()?;
         let contract_wasm_key = Key::Hash(contract_wasm_hash);
         let contract_wasm = {
@@ -2552,6 +2662,85 @@ where
         Ok(Ok(()))
     }
 
+    fn enable_contract_version(
+        &mut self,
+        contract_package_hash: ContractPackageHash,
+        contract_hash: ContractHash,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let contract_package_key = contract_package_hash.into();
+        self.context.validate_key(&contract_package_key)?;
+
+        let mut contract_package: ContractPackage = self
+            .context
+            .get_validated_contract_package(contract_package_hash)?;
+
+        if let Err(err) = contract_package.enable_contract_version(contract_hash) {
+            return Ok(Err(err.into()));
+        }
+
+        self.context.state().borrow_mut().write(
+            contract_package_key,
+            StoredValue::ContractPackage(contract_package),
+        );
+
+        Ok(Ok(()))
+    }
+
+    fn set_contract_version_metadata(
+        &mut self,
+        contract_package_hash: ContractPackageHash,
+        contract_hash: ContractHash,
+        metadata: String,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let contract_package_key = contract_package_hash.into();
+        self.context.validate_key(&contract_package_key)?;
+
+        let mut contract_package: ContractPackage = self
+            .context
+            .get_validated_contract_package(contract_package_hash)?;
+
+        if let Err(err) = contract_package.set_version_metadata(contract_hash, metadata) {
+            return Ok(Err(err.into()));
+        }
+
+        self.context.state().borrow_mut().write(
+            contract_package_key,
+            StoredValue::ContractPackage(contract_package),
+        );
+
+        Ok(Ok(()))
+    }
+
+    /// Replaces the access key of a contract package with a newly issued one, and optionally
+    /// locks the package permanently. A locked package stays locked, but its access key can still
+    /// be rotated to hand over management of its user groups. Returns the address of the new
+    /// access key.
+    fn rotate_access_key(
+        &mut self,
+        contract_package_hash: ContractPackageHash,
+        lock: bool,
+    ) -> Result<Result<[u8; 32], ApiError>, Error> {
+        let contract_package_key = contract_package_hash.into();
+        self.context.validate_key(&contract_package_key)?;
+
+        let mut contract_package: ContractPackage = self
+            .context
+            .get_validated_contract_package(contract_package_hash)?;
+
+        let access_key = self.context.new_unit_uref()?;
+        contract_package.set_access_key(access_key);
+        if lock {
+            contract_package.lock();
+        }
+
+        self.context.state().borrow_mut().write(
+            contract_package_key,
+            StoredValue::ContractPackage(contract_package),
+        );
+
+        Ok(Ok(access_key.addr()))
+    }
+
     /// Writes function address (`hash_bytes`) into the Wasm memory (at
     /// `dest_ptr` pointer).
     fn function_address(&mut self, hash_bytes: [u8; 32], dest_ptr: u32) -> Result<(), Trap> {
@@ -2601,6 +2790,18 @@ where
             .map_err(Into::into)
     }
 
+    /// Removes the value under a key derived from `key` in the "local cluster" of GlobalState
+    fn remove_local(&mut self, key_ptr: u32, key_size: u32) -> Result<(), Trap> {
+        let key_bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
+        self.context.prune_ls(&key_bytes).map_err(Into::into)
+    }
+
+    /// Removes the value under `uref` from GlobalState.
+    fn remove_uref(&mut self, uref_ptr: u32, uref_size: u32) -> Result<(), Trap> {
+        let uref: URef = self.t_from_mem(uref_ptr, uref_size)?;
+        self.context.prune_gs(Key::from(uref)).map_err(Into::into)
+    }
+
     /// Adds `value` to the cell that `key` points at.
     fn add(
         &mut self,
@@ -2783,6 +2984,43 @@ where
         }
     }
 
+    fn manage_account(
+        &mut self,
+        associated_keys_ptr: u32,
+        associated_keys_size: u32,
+        main_purse_ptr: u32,
+        main_purse_size: u32,
+    ) -> Result<i32, Trap> {
+        let associated_keys: BTreeMap<AccountHash, Weight> =
+            self.t_from_mem(associated_keys_ptr, associated_keys_size)?;
+        let main_purse: Option<URef> = self.t_from_mem(main_purse_ptr, main_purse_size)?;
+
+        // Permissions and the purse reference are checked before the purse is touched
+        match self.context.validate_manage_account(main_purse) {
+            Ok(_) => {}
+            Err(Error::ManageAccountFailure(e)) => return Ok(e as i32),
+            Err(e) => return Err(e.into()),
+        }
+
+        if let Some(main_purse) = main_purse {
+            // Only an existing purse can become the main purse of an account
+            if self.get_balance(main_purse)?.is_none() {
+                return Ok(ManageAccountFailure::InvalidPurse as i32);
+            }
+        }
+
+        match self.context.manage_account(associated_keys, main_purse) {
+            Ok(_) => Ok(0),
+            // This relies on the fact that `ManageAccountFailure` is represented as
+            // i32 and first variant start with number `1`, so all other variants
+            // are greater than the first one, so it's safe to assume `0` is success,
+            // and any error is greater than 0.
+            Err(Error::ManageAccountFailure(e)) => Ok(e as i32),
+            // Any other variant just pass as `Trap`
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Looks up the public mint contract key in the context's protocol data.
     ///
     /// Returned URef is already attenuated depending on the calling account.
@@ -3123,10 +3361,31 @@ where
         Ok(Ok(()))
     }
 
+    /// Copies `len` bytes of Wasm memory from `src` to `dst`, which may overlap, charging
+    /// `memcpy` gas per byte.  Called in place of the `memory.copy` instruction.
+    fn memory_copy(&mut self, dst: u32, src: u32, len: u32) -> Result<(), Trap> {
+        let cost = U512::from(self.protocol_data().wasm_costs().memcpy) * len;
+        self.gas(Gas::new(cost))?;
+        self.memory
+            .copy(src as usize, dst as usize, len as usize)
+            .map_err(|error| Error::Interpreter(error.into()).into())
+    }
+
+    /// Sets `len` bytes of Wasm memory from `dst` to `value`, charging `memfill` gas per byte.
+    /// Called in place of the `memory.fill` instruction.
+    fn memory_fill(&mut self, dst: u32, value: u32, len: u32) -> Result<(), Trap> {
+        let cost = U512::from(self.protocol_data().wasm_costs().memfill) * len;
+        self.gas(Gas::new(cost))?;
+        self.memory
+            .clear(dst as usize, value as u8, len as usize)
+            .map_err(|error| Error::Interpreter(error.into()).into())
+    }
+
     #[cfg(feature = "test-support")]
     fn print(&mut self, text_ptr: u32, text_size: u32) -> Result<(), Trap> {
         let text = self.string_from_mem(text_ptr, text_size)?;
         println!("{}", text);
+        self.output.print(text);
         Ok(())
     }
 
@@ -3368,9 +3627,13 @@ mod tests {
         result,
     };
 
-    use types::{gens::*, CLType, CLValue, Key, URef};
+    use types::{
+        contracts::Parameter, gens::*, runtime_args, CLType, CLValue, EntryPoint, EntryPointAccess,
+        EntryPointType, Key, RuntimeArgs, URef, U512,
+    };
 
-    use super::extract_urefs;
+    use super::{extract_urefs, validate_entry_point_args, validate_entry_point_ret};
+    use crate::execution::Error;
 
     fn cl_value_with_urefs_arb() -> impl Strategy<Value = (CLValue, Vec<URef>)> {
         // If compiler brings you here it most probably means you've added a variant to `CLType`
@@ -3512,4 +3775,100 @@ mod tests {
             assert_eq!(extracted_urefs, urefs);
         }
     }
+
+    fn new_entry_point(args: Vec<Parameter>, ret: CLType) -> EntryPoint {
+        EntryPoint::new(
+            "entry_point",
+            args,
+            ret,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        )
+    }
+
+    #[test]
+    fn should_accept_args_matching_parameters() {
+        let entry_point = new_entry_point(
+            vec![
+                Parameter::new("amount", CLType::U512),
+                Parameter::new("maybe_amount", CLType::Option(Box::new(CLType::U512))),
+                Parameter::new("anything", CLType::Any),
+            ],
+            CLType::Unit,
+        );
+        let args = runtime_args! {
+            "amount" => U512::one(),
+            "maybe_amount" => Some(U512::one()),
+            "anything" => String::from("anything"),
+            "undeclared" => 1u8,
+        };
+        assert!(validate_entry_point_args(&entry_point, &args).is_ok());
+    }
+
+    #[test]
+    fn should_accept_missing_optional_arg() {
+        let entry_point = new_entry_point(
+            vec![Parameter::new(
+                "maybe_amount",
+                CLType::Option(Box::new(CLType::U512)),
+            )],
+            CLType::Unit,
+        );
+        assert!(validate_entry_point_args(&entry_point, &RuntimeArgs::new()).is_ok());
+    }
+
+    #[test]
+    fn should_reject_missing_arg() {
+        let entry_point =
+            new_entry_point(vec![Parameter::new("amount", CLType::U512)], CLType::Unit);
+        let error = validate_entry_point_args(&entry_point, &RuntimeArgs::new())
+            .expect_err("should reject missing arg");
+        match error {
+            Error::MissingArgument { entry_point, name } => {
+                assert_eq!(entry_point, "entry_point");
+                assert_eq!(name, "amount");
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_arg_of_wrong_type() {
+        let entry_point =
+            new_entry_point(vec![Parameter::new("amount", CLType::U512)], CLType::Unit);
+        let args = runtime_args! { "amount" => 1u64 };
+        let error = validate_entry_point_args(&entry_point, &args)
+            .expect_err("should reject arg of wrong type");
+        match error {
+            Error::InvalidArgument { name, mismatch, .. } => {
+                assert_eq!(name, "amount");
+                assert_eq!(mismatch.expected, format!("{:?}", CLType::U512));
+                assert_eq!(mismatch.found, format!("{:?}", CLType::U64));
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_validate_return_value() {
+        let entry_point = new_entry_point(Vec::new(), CLType::URef);
+        let uref = URef::new([1; 32], Default::default());
+        let ret = CLValue::from_t(uref).unwrap();
+        assert_eq!(
+            validate_entry_point_ret(&entry_point, ret.clone()).unwrap(),
+            ret
+        );
+
+        let unit = CLValue::from_t(()).unwrap();
+        match validate_entry_point_ret(&entry_point, unit) {
+            Err(Error::InvalidReturnValue { mismatch, .. }) => {
+                assert_eq!(mismatch.expected, format!("{:?}", CLType::URef));
+                assert_eq!(mismatch.found, format!("{:?}", CLType::Unit));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let any = new_entry_point(Vec::new(), CLType::Any);
+        assert!(validate_entry_point_ret(&any, CLValue::from_t(1u8).unwrap()).is_ok());
+    }
 }
@@ -0,0 +1,532 @@
+//! Runs contract code compiled natively against a [`Runtime`], using the host-native backend of
+//! `casperlabs-contract`.
+//!
+//! Each host function called by the contract has its arguments copied into a scratch Wasm memory
+//! owned by the runtime, is executed exactly as if called from Wasm, and then has its outputs
+//! copied back to the contract's native memory.  Native code is not instrumented, so only the gas
+//! charged by the host functions themselves is counted.
+
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    slice,
+};
+
+use wasmi::{
+    memory_units::{Bytes, Pages, RoundUpTo},
+    Externals, MemoryInstance, MemoryRef, RuntimeValue, Trap, TrapKind,
+};
+
+use contract::native::{self, Host};
+use engine_shared::stored_value::StoredValue;
+use engine_storage::global_state::StateReader;
+use types::{Key, BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH, UREF_SERIALIZED_LENGTH};
+
+use super::{Error, Runtime};
+use crate::resolvers::v1_function_index::FunctionIndex;
+
+/// The natively compiled entry point of a contract.
+pub type NativeEntryPoint = fn();
+
+const HASH_SERIALIZED_LENGTH: usize = 32;
+const VERSION_SERIALIZED_LENGTH: usize = 4;
+const SIZE_SERIALIZED_LENGTH: usize = 4;
+
+/// How an argument of a host function is passed.
+#[derive(Clone, Copy)]
+enum Arg {
+    /// An integer passed by value.
+    Value,
+    /// A pointer to input bytes, followed by their length.
+    Bytes,
+    /// A pointer to an output buffer of the given length.
+    Out(usize),
+    /// A pointer to an output buffer, followed by its length.
+    OutBytes,
+    /// A pointer to an output `usize`.
+    OutSize,
+}
+
+/// Returns the index and arguments of the host function `name`, as resolved for Wasm imports.
+fn host_function(name: &str) -> Option<(FunctionIndex, &'static [Arg])> {
+    use Arg::*;
+    let host_function: (FunctionIndex, &'static [Arg]) = match name {
+        "read_value" => (FunctionIndex::ReadFuncIndex, &[Bytes, OutSize]),
+        "read_value_local" => (FunctionIndex::ReadLocalFuncIndex, &[Bytes, OutSize]),
+        "load_named_keys" => (FunctionIndex::LoadNamedKeysFuncIndex, &[OutSize, OutSize]),
+        "write" => (FunctionIndex::WriteFuncIndex, &[Bytes, Bytes]),
+        "write_local" => (FunctionIndex::WriteLocalFuncIndex, &[Bytes, Bytes]),
+        "remove_local" => (FunctionIndex::RemoveLocalFuncIndex, &[Bytes]),
+        "remove_uref" => (FunctionIndex::RemoveURefFuncIndex, &[Bytes]),
+        "add" => (FunctionIndex::AddFuncIndex, &[Bytes, Bytes]),
+        "new_uref" => (
+            FunctionIndex::NewFuncIndex,
+            &[Out(UREF_SERIALIZED_LENGTH), Bytes],
+        ),
+        "ret" => (FunctionIndex::RetFuncIndex, &[Bytes]),
+        "get_key" => (FunctionIndex::GetKeyFuncIndex, &[Bytes, OutBytes, OutSize]),
+        "has_key" => (FunctionIndex::HasKeyFuncIndex, &[Bytes]),
+        "put_key" => (FunctionIndex::PutKeyFuncIndex, &[Bytes, Bytes]),
+        "remove_key" => (FunctionIndex::RemoveKeyFuncIndex, &[Bytes]),
+        "is_valid_uref" => (FunctionIndex::IsValidURefFnIndex, &[Bytes]),
+        "revert" => (FunctionIndex::RevertFuncIndex, &[Value]),
+        "add_associated_key" => (FunctionIndex::AddAssociatedKeyFuncIndex, &[Bytes, Value]),
+        "remove_associated_key" => (FunctionIndex::RemoveAssociatedKeyFuncIndex, &[Bytes]),
+        "update_associated_key" => (FunctionIndex::UpdateAssociatedKeyFuncIndex, &[Bytes, Value]),
+        "set_action_threshold" => (FunctionIndex::SetActionThresholdFuncIndex, &[Value, Value]),
+        "manage_account" => (FunctionIndex::ManageAccountFuncIndex, &[Bytes, Bytes]),
+        "get_caller" => (FunctionIndex::GetCallerIndex, &[OutSize]),
+        "get_blocktime" => (
+            FunctionIndex::GetBlocktimeIndex,
+            &[Out(BLOCKTIME_SERIALIZED_LENGTH)],
+        ),
+        "create_purse" => (FunctionIndex::CreatePurseIndex, &[OutBytes]),
+        "transfer_to_account" => (FunctionIndex::TransferToAccountIndex, &[Bytes, Bytes]),
+        "transfer_from_purse_to_account" => (
+            FunctionIndex::TransferFromPurseToAccountIndex,
+            &[Bytes, Bytes, Bytes],
+        ),
+        "transfer_from_purse_to_purse" => (
+            FunctionIndex::TransferFromPurseToPurseIndex,
+            &[Bytes, Bytes, Bytes],
+        ),
+        "get_balance" => (FunctionIndex::GetBalanceIndex, &[Bytes, OutSize]),
+        "get_phase" => (
+            FunctionIndex::GetPhaseIndex,
+            &[Out(PHASE_SERIALIZED_LENGTH)],
+        ),
+        "get_system_contract" => (FunctionIndex::GetSystemContractIndex, &[Value, OutBytes]),
+        "get_main_purse" => (
+            FunctionIndex::GetMainPurseIndex,
+            &[Out(UREF_SERIALIZED_LENGTH)],
+        ),
+        "read_host_buffer" => (FunctionIndex::ReadHostBufferIndex, &[OutBytes, OutSize]),
+        "create_contract_package_at_hash" => (
+            FunctionIndex::CreateContractPackageAtHash,
+            &[Out(HASH_SERIALIZED_LENGTH), Out(HASH_SERIALIZED_LENGTH)],
+        ),
+        "create_contract_user_group" => (
+            FunctionIndex::CreateContractUserGroup,
+            &[Bytes, Bytes, Value, Bytes, OutSize],
+        ),
+        "add_contract_version" => (
+            FunctionIndex::AddContractVersion,
+            &[
+                Bytes,
+                Out(VERSION_SERIALIZED_LENGTH),
+                Bytes,
+                Bytes,
+                OutBytes,
+                OutSize,
+            ],
+        ),
+        "disable_contract_version" => (FunctionIndex::DisableContractVersion, &[Bytes, Bytes]),
+        "enable_contract_version" => (FunctionIndex::EnableContractVersion, &[Bytes, Bytes]),
+        "set_contract_version_metadata" => (
+            FunctionIndex::SetContractVersionMetadataIndex,
+            &[Bytes, Bytes, Bytes],
+        ),
+        "call_contract" => (
+            FunctionIndex::CallContractFuncIndex,
+            &[Bytes, Bytes, Bytes, OutSize],
+        ),
+        "call_versioned_contract" => (
+            FunctionIndex::CallVersionedContract,
+            &[Bytes, Bytes, Bytes, Bytes, OutSize],
+        ),
+        "get_named_arg_size" => (FunctionIndex::GetRuntimeArgsizeIndex, &[Bytes, OutSize]),
+        "get_named_arg" => (FunctionIndex::GetRuntimeArgIndex, &[Bytes, OutBytes]),
+        "remove_contract_user_group" => {
+            (FunctionIndex::RemoveContractUserGroupIndex, &[Bytes, Bytes])
+        }
+        "provision_contract_user_group_uref" => (
+            FunctionIndex::ExtendContractUserGroupURefsIndex,
+            &[Bytes, Bytes, OutSize],
+        ),
+        "remove_contract_user_group_urefs" => (
+            FunctionIndex::RemoveContractUserGroupURefsIndex,
+            &[Bytes, Bytes, Bytes],
+        ),
+        "rotate_access_key" => (
+            FunctionIndex::RotateAccessKeyIndex,
+            &[Bytes, Value, Out(HASH_SERIALIZED_LENGTH)],
+        ),
+        #[cfg(feature = "test-support")]
+        "print" => (FunctionIndex::PrintIndex, &[Bytes]),
+        _ => return None,
+    };
+    Some(host_function)
+}
+
+/// The trap raised by a host function, carried by the unwinding stack of the native contract.
+struct NativeTrap(Trap);
+
+/// An output of a host function, to be copied back to native memory once it has run.
+enum Output {
+    Bytes {
+        native_ptr: *mut u8,
+        offset: u32,
+        len: usize,
+    },
+    Size {
+        native_ptr: *mut usize,
+        offset: u32,
+    },
+}
+
+/// Returns a scratch memory for the arguments of host functions called by native contract code.
+pub fn native_memory() -> Result<MemoryRef, Error> {
+    MemoryInstance::alloc(Pages(1), None).map_err(|error| Error::Interpreter(error.into()))
+}
+
+/// Runs the native `entry_point` with `runtime` executing its calls to host functions.
+///
+/// The result is the same as that of invoking the entry point of the equivalent Wasm module; in
+/// particular a panic in the contract is treated as a Wasm trap.
+pub fn invoke_native<R>(
+    runtime: &mut Runtime<R>,
+    entry_point: NativeEntryPoint,
+) -> Result<Option<RuntimeValue>, wasmi::Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    let result = panic::catch_unwind(AssertUnwindSafe(|| native::with_host(runtime, entry_point)));
+    match result {
+        Ok(()) => Ok(None),
+        Err(payload) => Err(wasmi::Error::Trap(trap_from_panic(payload))),
+    }
+}
+
+fn trap_from_panic(payload: Box<dyn Any + Send>) -> Trap {
+    match payload.downcast::<NativeTrap>() {
+        Ok(native_trap) => native_trap.0,
+        Err(_) => Trap::new(TrapKind::Unreachable),
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    /// Copies `native_args` into the scratch memory as the arguments of a host function with the
+    /// given `arg_types`, returning the Wasm arguments and the outputs to copy back.
+    ///
+    /// Safety: every pointer in `native_args` must be valid for the length implied by its type.
+    unsafe fn copy_args_in(
+        &mut self,
+        arg_types: &[Arg],
+        native_args: &[usize],
+    ) -> Result<(Vec<RuntimeValue>, Vec<Output>), Error> {
+        let mut wasm_args = Vec::with_capacity(native_args.len());
+        let mut outputs = Vec::new();
+        let mut offset = 0u32;
+        let mut native_args = native_args.iter().copied();
+        let mut next_arg = || {
+            native_args
+                .next()
+                .ok_or_else(|| Error::Interpreter("too few arguments to host function".into()))
+        };
+
+        for arg_type in arg_types {
+            let native_arg = next_arg()?;
+            match *arg_type {
+                Arg::Value => wasm_args.push(RuntimeValue::I32(native_arg as i32)),
+                Arg::Bytes => {
+                    let len = next_arg()?;
+                    self.copy_in(offset, native_arg, len)?;
+                    wasm_args.push(RuntimeValue::I32(offset as i32));
+                    wasm_args.push(RuntimeValue::I32(len as i32));
+                    offset += len as u32;
+                }
+                Arg::Out(len) => {
+                    self.copy_in(offset, native_arg, len)?;
+                    wasm_args.push(RuntimeValue::I32(offset as i32));
+                    outputs.push(Output::Bytes {
+                        native_ptr: native_arg as *mut u8,
+                        offset,
+                        len,
+                    });
+                    offset += len as u32;
+                }
+                Arg::OutBytes => {
+                    let len = next_arg()?;
+                    self.copy_in(offset, native_arg, len)?;
+                    wasm_args.push(RuntimeValue::I32(offset as i32));
+                    wasm_args.push(RuntimeValue::I32(len as i32));
+                    outputs.push(Output::Bytes {
+                        native_ptr: native_arg as *mut u8,
+                        offset,
+                        len,
+                    });
+                    offset += len as u32;
+                }
+                Arg::OutSize => {
+                    self.set_scratch(offset, &[0; SIZE_SERIALIZED_LENGTH])?;
+                    wasm_args.push(RuntimeValue::I32(offset as i32));
+                    outputs.push(Output::Size {
+                        native_ptr: native_arg as *mut usize,
+                        offset,
+                    });
+                    offset += SIZE_SERIALIZED_LENGTH as u32;
+                }
+            }
+        }
+        Ok((wasm_args, outputs))
+    }
+
+    /// Copies `len` bytes from `native_ptr` into the scratch memory at `offset`.
+    ///
+    /// Safety: `native_ptr` must be valid for reads of `len` bytes.
+    unsafe fn copy_in(&mut self, offset: u32, native_ptr: usize, len: usize) -> Result<(), Error> {
+        if len == 0 {
+            return Ok(());
+        }
+        let bytes = slice::from_raw_parts(native_ptr as *const u8, len);
+        self.set_scratch(offset, bytes)
+    }
+
+    /// Copies the outputs of a host function from the scratch memory back to native memory.
+    ///
+    /// Safety: every output pointer must be valid for writes of its length.
+    unsafe fn copy_outputs_out(&self, outputs: Vec<Output>) -> Result<(), Error> {
+        for output in outputs {
+            match output {
+                Output::Bytes {
+                    native_ptr,
+                    offset,
+                    len,
+                } => {
+                    if len > 0 {
+                        let bytes = self.scratch(offset, len)?;
+                        slice::from_raw_parts_mut(native_ptr, len).copy_from_slice(&bytes);
+                    }
+                }
+                Output::Size { native_ptr, offset } => {
+                    let mut bytes = [0; SIZE_SERIALIZED_LENGTH];
+                    bytes.copy_from_slice(&self.scratch(offset, SIZE_SERIALIZED_LENGTH)?);
+                    *native_ptr = u32::from_le_bytes(bytes) as usize;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn set_scratch(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Error> {
+        let required: Pages = Bytes(offset as usize + bytes.len()).round_up_to();
+        let current = self.memory.current_size();
+        if required > current {
+            self.memory
+                .grow(required - current)
+                .map_err(|error| Error::Interpreter(error.into()))?;
+        }
+        self.memory
+            .set(offset, bytes)
+            .map_err(|error| Error::Interpreter(error.into()))
+    }
+
+    fn scratch(&self, offset: u32, len: usize) -> Result<Vec<u8>, Error> {
+        self.memory
+            .get(offset, len)
+            .map_err(|error| Error::Interpreter(error.into()))
+    }
+
+    fn call_host_function(&mut self, name: &str, native_args: &[usize]) -> Result<i32, Trap> {
+        let (function_index, arg_types) = host_function(name).ok_or_else(|| {
+            Error::Interpreter(format!(
+                "host module doesn't export function with name {}",
+                name
+            ))
+        })?;
+        let (wasm_args, outputs) = unsafe { self.copy_args_in(arg_types, native_args)? };
+        let result = self.invoke_index(function_index.into(), wasm_args[..].into())?;
+        unsafe { self.copy_outputs_out(outputs)? };
+        match result {
+            Some(RuntimeValue::I32(value)) => Ok(value),
+            _ => Ok(0),
+        }
+    }
+}
+
+impl<'a, R> Host for Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    fn call(&mut self, name: &'static str, args: &[usize]) -> i32 {
+        match self.call_host_function(name, args) {
+            Ok(value) => value,
+            Err(trap) => panic::resume_unwind(Box::new(NativeTrap(trap))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::BTreeSet, iter::FromIterator, panic, rc::Rc};
+
+    use contract::{
+        contract_api::{runtime, storage},
+        native,
+    };
+    use engine_shared::{
+        account::{Account, AssociatedKeys},
+        additive_map::AdditiveMap,
+        gas::Gas,
+        newtypes::CorrelationId,
+        stored_value::StoredValue,
+        transform::Transform,
+    };
+    use engine_storage::{
+        global_state::{in_memory::InMemoryGlobalState, CommitResult, StateProvider},
+        protocol_data::ProtocolData,
+    };
+    use types::{
+        account::{AccountHash, Weight},
+        contracts::{ContractPackage, EntryPoint, NamedKeys},
+        runtime_args, AccessRights, ApiError, BlockTime, CLValue, Key, Phase, ProtocolVersion,
+        RuntimeArgs, URef, U512,
+    };
+
+    use super::NativeEntryPoint;
+    use crate::{
+        engine_state::{
+            execution_result::ExecutionResult, module_cache::ModuleCache, EngineConfig,
+            Error as EngineError,
+        },
+        execution::{Error as ExecError, Executor},
+        tracking_copy::TrackingCopy,
+    };
+
+    const ACCOUNT_ADDR: AccountHash = AccountHash::new([7; 32]);
+    const VALUE_KEY: &str = "value";
+
+    fn store_value() {
+        let value: u64 = runtime::get_named_arg(VALUE_KEY);
+        let uref = storage::new_uref(value);
+        runtime::put_key(VALUE_KEY, uref.into());
+    }
+
+    fn revert_with_user_error() {
+        runtime::revert(ApiError::User(7))
+    }
+
+    fn exec(entry_point: NativeEntryPoint, args: RuntimeArgs) -> (ExecutionResult, NamedKeys) {
+        let correlation_id = CorrelationId::new();
+        let account = Account::new(
+            ACCOUNT_ADDR,
+            NamedKeys::new(),
+            URef::new([0; 32], AccessRights::READ_ADD_WRITE),
+            AssociatedKeys::new(ACCOUNT_ADDR, Weight::new(1)),
+            Default::default(),
+        );
+        let base_key = Key::Account(ACCOUNT_ADDR);
+
+        let global_state = InMemoryGlobalState::empty().unwrap();
+        let mut transforms = AdditiveMap::new();
+        transforms.insert(
+            base_key,
+            Transform::Write(StoredValue::Account(account.clone())),
+        );
+        let state_root = match global_state
+            .commit(correlation_id, global_state.empty_root_hash, transforms)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            other => panic!("failed to commit mocked account: {:?}", other),
+        };
+        let reader = global_state.checkout(state_root).unwrap().unwrap();
+        let tracking_copy = Rc::new(RefCell::new(TrackingCopy::new(reader)));
+
+        let mut named_keys = NamedKeys::new();
+        let result = Executor::new(EngineConfig::new()).exec_native(
+            entry_point,
+            EntryPoint::default(),
+            args,
+            base_key,
+            &account,
+            &mut named_keys,
+            BTreeSet::from_iter(vec![ACCOUNT_ADDR]),
+            BlockTime::new(0),
+            [1; 32],
+            Gas::new(U512::from(std::u64::MAX)),
+            ProtocolVersion::V1_0_0,
+            correlation_id,
+            tracking_copy,
+            Phase::Session,
+            ProtocolData::default(),
+            ModuleCache::default(),
+            &ContractPackage::default(),
+        );
+        (result, named_keys)
+    }
+
+    #[test]
+    fn should_run_native_entry_point() {
+        let (result, named_keys) = exec(store_value, runtime_args! { VALUE_KEY => 42u64 });
+        let effect = match result {
+            ExecutionResult::Success { effect, .. } => effect,
+            ExecutionResult::Failure { error, .. } => panic!("unexpected failure: {:?}", error),
+        };
+
+        let uref_key = *named_keys.get(VALUE_KEY).expect("should have put key");
+        let expected_value = StoredValue::CLValue(CLValue::from_t(42u64).unwrap());
+        assert_eq!(
+            effect.transforms.get(&uref_key.normalize()),
+            Some(&Transform::Write(expected_value))
+        );
+    }
+
+    #[test]
+    fn should_map_every_native_binding_to_a_host_function() {
+        for name in native::host_function_names() {
+            // `print` is only mapped when this crate's `test-support` feature is enabled too
+            if name == "print" && !cfg!(feature = "test-support") {
+                continue;
+            }
+            // `add_local` has no host function for Wasm either
+            if name == "add_local" {
+                continue;
+            }
+            assert!(
+                super::host_function(name).is_some(),
+                "no host function for native binding '{}'",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn should_revert_native_entry_point() {
+        let (result, named_keys) = exec(revert_with_user_error, RuntimeArgs::new());
+        match result {
+            ExecutionResult::Failure {
+                error: EngineError::Exec(ExecError::Revert(api_error)),
+                ..
+            } => assert_eq!(api_error, ApiError::User(7)),
+            other => panic!("expected revert, got {:?}", other),
+        }
+        assert!(named_keys.is_empty());
+    }
+
+    #[test]
+    fn should_unset_host_after_unwinding() {
+        let _ = exec(revert_with_user_error, RuntimeArgs::new());
+        // No host is left set once the reverted native entry point has unwound
+        let result = panic::catch_unwind(runtime::get_blocktime);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_fail_on_missing_named_arg() {
+        let (result, _) = exec(store_value, RuntimeArgs::new());
+        match result {
+            ExecutionResult::Failure {
+                error: EngineError::Exec(ExecError::Revert(api_error)),
+                ..
+            } => assert_eq!(api_error, ApiError::MissingArgument),
+            other => panic!("expected revert, got {:?}", other),
+        }
+    }
+}
@@ -89,6 +89,8 @@ impl Drop for ScopedInstrumenter {
             FunctionIndex::GasFuncIndex => return,
             FunctionIndex::WriteFuncIndex => "host_function_write",
             FunctionIndex::WriteLocalFuncIndex => "host_function_write_local",
+            FunctionIndex::RemoveLocalFuncIndex => "host_function_remove_local",
+            FunctionIndex::RemoveURefFuncIndex => "host_function_remove_uref",
             FunctionIndex::ReadFuncIndex => "host_function_read_value",
             FunctionIndex::ReadLocalFuncIndex => "host_function_read_value_local",
             FunctionIndex::AddFuncIndex => "host_function_add",
@@ -139,6 +141,12 @@ impl Drop for ScopedInstrumenter {
             FunctionIndex::RemoveContractUserGroupURefsIndex => {
                 "host_remove_contract_user_group_urefs"
             }
+            FunctionIndex::ManageAccountFuncIndex => "host_function_manage_account",
+            FunctionIndex::RotateAccessKeyIndex => "host_rotate_access_key",
+            FunctionIndex::EnableContractVersion => "host_enable_contract_version",
+            FunctionIndex::SetContractVersionMetadataIndex => "host_set_contract_version_metadata",
+            FunctionIndex::MemoryCopyIndex => "host_memory_copy",
+            FunctionIndex::MemoryFillIndex => "host_memory_fill",
         };
 
         let mut properties = mem::take(&mut self.properties);
@@ -1,6 +1,6 @@
 use std::{
     cell::RefCell,
-    collections::{BTreeSet, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     convert::{TryFrom, TryInto},
     fmt::Debug,
     rc::Rc,
@@ -12,13 +12,16 @@ use blake2::{
 };
 
 use engine_shared::{
-    account::Account, gas::Gas, newtypes::CorrelationId, stored_value::StoredValue,
+    account::{Account, AssociatedKeys},
+    gas::Gas,
+    newtypes::CorrelationId,
+    stored_value::StoredValue,
 };
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
 use types::{
     account::{
-        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        AccountHash, ActionType, AddKeyFailure, ManageAccountFailure, RemoveKeyFailure,
+        SetThresholdFailure, UpdateKeyFailure, Weight,
     },
     bytesrepr,
     contracts::NamedKeys,
@@ -374,6 +377,19 @@ where
         Ok(())
     }
 
+    pub fn prune_ls(&mut self, key_bytes: &[u8]) -> Result<(), Error> {
+        let actual_length = key_bytes.len();
+        if actual_length != KEY_HASH_LENGTH {
+            return Err(Error::InvalidKeyLength {
+                actual: actual_length,
+                expected: KEY_HASH_LENGTH,
+            });
+        }
+        let hash: [u8; KEY_HASH_LENGTH] = key_bytes.try_into().unwrap();
+        self.tracking_copy.borrow_mut().prune(hash.into());
+        Ok(())
+    }
+
     pub fn read_gs(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
         self.validate_readable(key)?;
         self.validate_key(key)?;
@@ -422,6 +438,14 @@ where
         Ok(())
     }
 
+    /// Removes the value under `key` from global state.
+    pub fn prune_gs(&mut self, key: Key) -> Result<(), Error> {
+        self.validate_writeable(&key)?;
+        self.validate_key(&key)?;
+        self.tracking_copy.borrow_mut().prune(key);
+        Ok(())
+    }
+
     pub fn read_account(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
         if let Key::Account(_) = key {
             self.validate_key(key)?;
@@ -814,6 +838,77 @@ where
         Ok(())
     }
 
+    /// Replaces the whole set of associated keys of the current account and optionally sets a new
+    /// main purse, as a single operation gated by the key management threshold.
+    pub fn manage_account(
+        &mut self,
+        associated_keys: BTreeMap<AccountHash, Weight>,
+        main_purse: Option<URef>,
+    ) -> Result<(), Error> {
+        self.validate_manage_account(main_purse)?;
+
+        let associated_keys = {
+            let mut res = AssociatedKeys::default();
+            for (account_hash, weight) in associated_keys {
+                // Keys of a map are unique, so the only possible failure is exceeding the limit
+                res.add_key(account_hash, weight)
+                    .map_err(|_| ManageAccountFailure::MaxKeysLimit)?;
+            }
+            res
+        };
+
+        // Converts an account's public key into a URef
+        let key = Key::Account(self.account().account_hash());
+
+        // Take an account out of the global state
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        // Exit early in case of error without updating global state
+        account
+            .set_associated_keys(associated_keys)
+            .map_err(Error::from)?;
+
+        if let Some(main_purse) = main_purse {
+            account.set_main_purse(main_purse);
+        }
+
+        let account_value = self.account_to_validated_value(account)?;
+
+        self.tracking_copy.borrow_mut().write(key, account_value);
+
+        Ok(())
+    }
+
+    /// Checks that the current context is allowed to manage the account and that `main_purse`, if
+    /// given, is an unforged purse reference with full access rights.
+    pub fn validate_manage_account(&self, main_purse: Option<URef>) -> Result<(), Error> {
+        // Check permission to modify associated keys
+        if !self.is_valid_context() {
+            // Exit early with error to avoid mutations
+            return Err(ManageAccountFailure::PermissionDenied.into());
+        }
+
+        if !self
+            .account()
+            .can_manage_keys_with(&self.authorization_keys)
+        {
+            // Exit early if authorization keys weight doesn't exceed required
+            // key management threshold
+            return Err(ManageAccountFailure::PermissionDenied.into());
+        }
+
+        if let Some(main_purse) = main_purse {
+            // The new main purse has to be owned by the caller with full access rights
+            if main_purse.access_rights() != AccessRights::READ_ADD_WRITE {
+                return Err(ManageAccountFailure::InvalidPurse.into());
+            }
+            self.validate_uref(&main_purse)
+                .map_err(|_| ManageAccountFailure::InvalidPurse)?;
+        }
+
+        Ok(())
+    }
+
     pub fn protocol_data(&self) -> ProtocolData {
         self.protocol_data
     }
@@ -1,6 +1,6 @@
 use std::{
     cell::RefCell,
-    collections::{BTreeSet, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     iter::{self, FromIterator},
     rc::Rc,
 };
@@ -21,7 +21,8 @@ use engine_storage::global_state::{
 };
 use types::{
     account::{
-        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, Weight,
+        AccountHash, ActionType, AddKeyFailure, ManageAccountFailure, RemoveKeyFailure,
+        SetThresholdFailure, Weight,
     },
     contracts::NamedKeys,
     AccessRights, BlockTime, CLValue, Contract, EntryPointType, EntryPoints, Key, Phase,
@@ -737,6 +738,83 @@ fn should_verify_ownership_before_setting_action_threshold() {
     let _ = test(access_rights, query);
 }
 
+#[test]
+fn manage_account_replaces_keys_and_main_purse() {
+    let new_purse = URef::new([42; 32], AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![Key::URef(new_purse)]);
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        let account_hash = AccountHash::new([84; 32]);
+        let mut associated_keys = BTreeMap::new();
+        associated_keys.insert(account_hash, Weight::new(1));
+
+        runtime_context
+            .manage_account(associated_keys, Some(new_purse))
+            .expect("Unable to manage account");
+
+        let effect = runtime_context.effect();
+        let transform = effect.transforms.get(&runtime_context.base_key()).unwrap();
+        let account = match transform {
+            Transform::Write(StoredValue::Account(account)) => account,
+            _ => panic!("Invalid transform operation found"),
+        };
+
+        assert_eq!(
+            account.get_associated_keys().collect::<Vec<_>>(),
+            vec![(&account_hash, &Weight::new(1))]
+        );
+        assert_eq!(account.main_purse(), new_purse);
+
+        // A purse without full access rights can't become the main purse
+        let err = runtime_context
+            .manage_account(BTreeMap::new(), Some(new_purse.into_read()))
+            .expect_err("This operation should return error");
+
+        match err {
+            Error::ManageAccountFailure(ManageAccountFailure::InvalidPurse) => {}
+            ref e => panic!("Invalid error variant: {:?}", e),
+        }
+
+        // A forged purse reference can't become the main purse either
+        let forged_purse = URef::new([43; 32], AccessRights::READ_ADD_WRITE);
+        let err = runtime_context
+            .manage_account(BTreeMap::new(), Some(forged_purse))
+            .expect_err("This operation should return error");
+
+        match err {
+            Error::ManageAccountFailure(ManageAccountFailure::InvalidPurse) => {}
+            ref e => panic!("Invalid error variant: {:?}", e),
+        }
+
+        Ok(())
+    };
+    let _ = test(access_rights, query);
+}
+
+#[test]
+fn should_verify_ownership_before_managing_account() {
+    let access_rights = HashMap::new();
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        // Overwrites a `base_key` to a different one before doing any operation as
+        // account `[0; 32]`
+        runtime_context.base_key = Key::Hash([1; 32]);
+
+        let mut associated_keys = BTreeMap::new();
+        associated_keys.insert(AccountHash::new([84; 32]), Weight::new(1));
+
+        let err = runtime_context
+            .manage_account(associated_keys, None)
+            .expect_err("This operation should return error");
+
+        match err {
+            Error::ManageAccountFailure(ManageAccountFailure::PermissionDenied) => {}
+            ref e => panic!("Invalid error variant: {:?}", e),
+        }
+
+        Ok(())
+    };
+    let _ = test(access_rights, query);
+}
+
 #[test]
 fn can_roundtrip_key_value_pairs() {
     let access_rights = HashMap::new();
@@ -5,14 +5,13 @@ use engine_shared::{
     TypeMismatch,
 };
 use engine_storage::global_state::StateReader;
-use engine_wasm_prep::Preprocessor;
+use engine_wasm_prep::{InstrumentedModule, Preprocessor};
 use types::{
     account::AccountHash, CLValue, Contract, ContractHash, ContractPackage, ContractPackageHash,
-    ContractWasm, ContractWasmHash, Key, U512,
+    ContractWasm, ContractWasmHash, Key, ProtocolVersion, U512,
 };
 
-use crate::{execution, tracking_copy::TrackingCopy};
-use parity_wasm::elements::Module;
+use crate::{engine_state::module_cache::ModuleCache, execution, tracking_copy::TrackingCopy};
 
 pub trait TrackingCopyExt<R> {
     type Error;
@@ -72,7 +71,9 @@ pub trait TrackingCopyExt<R> {
         contract_wasm_hash: ContractWasmHash,
         use_system_contracts: bool,
         preprocessor: &Preprocessor,
-    ) -> Result<Module, Self::Error>;
+        module_cache: &ModuleCache,
+        protocol_version: ProtocolVersion,
+    ) -> Result<InstrumentedModule, Self::Error>;
 }
 
 impl<R> TrackingCopyExt<R> for TrackingCopy<R>
@@ -220,7 +221,9 @@ where
         contract_wasm_hash: ContractWasmHash,
         use_system_contracts: bool,
         preprocessor: &Preprocessor,
-    ) -> Result<Module, Self::Error> {
+        module_cache: &ModuleCache,
+        protocol_version: ProtocolVersion,
+    ) -> Result<InstrumentedModule, Self::Error> {
         match {
             if use_system_contracts {
                 let contract_wasm = match self.get_contract_wasm(correlation_id, contract_wasm_hash)
@@ -231,7 +234,12 @@ where
                     }
                 };
 
-                engine_wasm_prep::deserialize(contract_wasm.bytes())
+                module_cache.get_or_instrument(
+                    contract_wasm_hash,
+                    protocol_version,
+                    &contract_wasm,
+                    preprocessor,
+                )
             } else {
                 wasm::do_nothing_module(preprocessor)
             }
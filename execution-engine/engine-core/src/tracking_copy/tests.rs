@@ -143,6 +143,40 @@ fn tracking_copy_write() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Write));
 }
 
+#[test]
+fn tracking_copy_prune() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(Rc::clone(&counter));
+    let mut tc = TrackingCopy::new(db);
+    let k = Key::Hash([0u8; 32]);
+
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+
+    // read value into the cache, then prune it
+    assert!(tc.read(correlation_id, &k).unwrap().is_some());
+    tc.prune(k);
+    // prune creates a Transform and an Op
+    assert_eq!(tc.fns.len(), 1);
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Prune));
+    assert_eq!(tc.ops.len(), 1);
+    assert_eq!(tc.ops.get(&k), Some(&Op::Write));
+
+    // pruned value can be read neither from the cache nor from the DB
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), None);
+    assert_eq!(counter.get(), 1);
+    assert_eq!((&tc).read(correlation_id, &k).unwrap(), None);
+    assert_matches!(
+        tc.add(correlation_id, k, one.clone()),
+        Ok(AddResult::KeyNotFound(_))
+    );
+
+    // writing after pruning makes the value visible again
+    tc.write(k, one.clone());
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), Some(one.clone()));
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Write(one)));
+}
+
 #[test]
 fn tracking_copy_add_i32() {
     let correlation_id = CorrelationId::new();
@@ -1,10 +1,29 @@
 use std::{
+    collections::HashMap,
     env, fs,
     path::{Path, PathBuf},
 };
 
+use proc_macro2::TokenStream;
+use protobuf::descriptor::{FileDescriptorSet, MethodDescriptorProto, ServiceDescriptorProto};
+use quote::{format_ident, quote};
+
 const PROTOBUF_DIR: &str = "generated_protobuf";
 const WORKAROUND_COMMENT: &str = "// workaround for https://github.com/rust-lang/rfcs/issues/752";
+const DESCRIPTOR_SET_FILE: &str = "descriptor_set.bin";
+const CODEC_PATH: &str = "crate::engine_server::codec::ProtobufCodec";
+
+const PROTO_FILES: &[&str] = &[
+    "protobuf/io/casperlabs/casper/consensus/state.proto",
+    "protobuf/io/casperlabs/ipc/ipc.proto",
+    "protobuf/io/casperlabs/ipc/transforms.proto",
+    "protobuf/grpc/reflection/v1alpha/reflection.proto",
+];
+const PROTO_INCLUDES: &[&str] = &[
+    "protobuf/",
+    "protobuf/io/casperlabs/casper/consensus",
+    "protobuf/io/casperlabs/ipc",
+];
 
 // The generated file needs to be sourced via `include!` which doesn't work where the file has top-
 // level inner attributes (see https://github.com/rust-lang/rfcs/issues/752).
@@ -27,10 +46,185 @@ fn wrap_file_contents(target_dir: &Path, file_name_without_suffix: &str) {
     .unwrap_or_else(|_| panic!("should write {}", generated_file.display()));
 }
 
+fn file_stem(proto_file: &str) -> String {
+    Path::new(proto_file)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_else(|| panic!("should have file stem {}", proto_file))
+        .to_string()
+}
+
+fn snake_case(name: &str) -> String {
+    let mut ret = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() && index != 0 {
+            ret.push('_');
+        }
+        ret.push(c.to_ascii_lowercase());
+    }
+    ret
+}
+
+/// A gRPC method, described to `tonic-build` so that it generates server code for the
+/// rust-protobuf messages rather than for prost ones.
+struct Method {
+    name: String,
+    identifier: String,
+    client_streaming: bool,
+    server_streaming: bool,
+    request: TokenStream,
+    response: TokenStream,
+}
+
+impl Method {
+    fn new(
+        descriptor: &MethodDescriptorProto,
+        message_paths: &HashMap<String, TokenStream>,
+    ) -> Self {
+        let message_path = |type_name: &str| {
+            message_paths
+                .get(type_name)
+                .cloned()
+                .unwrap_or_else(|| panic!("should have message type {}", type_name))
+        };
+        Method {
+            name: snake_case(descriptor.get_name()),
+            identifier: descriptor.get_name().to_string(),
+            client_streaming: descriptor.get_client_streaming(),
+            server_streaming: descriptor.get_server_streaming(),
+            request: message_path(descriptor.get_input_type()),
+            response: message_path(descriptor.get_output_type()),
+        }
+    }
+}
+
+impl tonic_build::Method for Method {
+    const CODEC_PATH: &'static str = CODEC_PATH;
+    type Comment = String;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    fn client_streaming(&self) -> bool {
+        self.client_streaming
+    }
+
+    fn server_streaming(&self) -> bool {
+        self.server_streaming
+    }
+
+    fn comment(&self) -> &[Self::Comment] {
+        &[]
+    }
+
+    fn request_response_name(&self, _proto_path: &str) -> (TokenStream, TokenStream) {
+        (self.request.clone(), self.response.clone())
+    }
+}
+
+/// A gRPC service, described to `tonic-build`.
+struct Service {
+    name: String,
+    package: String,
+    methods: Vec<Method>,
+}
+
+impl Service {
+    fn new(
+        descriptor: &ServiceDescriptorProto,
+        package: &str,
+        message_paths: &HashMap<String, TokenStream>,
+    ) -> Self {
+        Service {
+            name: descriptor.get_name().to_string(),
+            package: package.to_string(),
+            methods: descriptor
+                .get_method()
+                .iter()
+                .map(|method| Method::new(method, message_paths))
+                .collect(),
+        }
+    }
+}
+
+impl tonic_build::Service for Service {
+    const CODEC_PATH: &'static str = CODEC_PATH;
+    type Comment = String;
+    type Method = Method;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn package(&self) -> &str {
+        &self.package
+    }
+
+    fn identifier(&self) -> &str {
+        &self.name
+    }
+
+    fn methods(&self) -> &[Self::Method] {
+        &self.methods
+    }
+
+    fn comment(&self) -> &[Self::Comment] {
+        &[]
+    }
+}
+
+// Generates a tonic client and server for each service, writing those from e.g. `ipc.proto` to
+// `ipc_tonic.rs`.
+fn generate_services(target_dir: &Path) {
+    let descriptor_set_file = target_dir.join(DESCRIPTOR_SET_FILE);
+    protoc::Protoc::from_env_path()
+        .write_descriptor_set(protoc::DescriptorSetOutArgs {
+            out: descriptor_set_file.to_str().unwrap(),
+            includes: PROTO_INCLUDES,
+            input: PROTO_FILES,
+            include_imports: true,
+        })
+        .expect("protoc descriptor set");
+    let bytes = fs::read(&descriptor_set_file)
+        .unwrap_or_else(|_| panic!("should read {}", descriptor_set_file.display()));
+    let descriptor_set: FileDescriptorSet =
+        protobuf::parse_from_bytes(&bytes).expect("should parse descriptor set");
+
+    let mut message_paths = HashMap::new();
+    for file in descriptor_set.get_file() {
+        let module = format_ident!("{}", file_stem(file.get_name()));
+        for message in file.get_message_type() {
+            let name = format!(".{}.{}", file.get_package(), message.get_name());
+            let ident = format_ident!("{}", message.get_name());
+            message_paths.insert(name, quote! { crate::engine_server::#module::#ident });
+        }
+    }
+
+    for file in descriptor_set.get_file() {
+        if file.get_service().is_empty() {
+            continue;
+        }
+        let mut tokens = TokenStream::new();
+        for service in file.get_service() {
+            let service = Service::new(service, file.get_package(), &message_paths);
+            tokens.extend(tonic_build::client::generate(&service, ""));
+            tokens.extend(tonic_build::server::generate(&service, ""));
+        }
+        let generated_file = target_dir.join(format!("{}_tonic.rs", file_stem(file.get_name())));
+        fs::write(&generated_file, tokens.to_string())
+            .unwrap_or_else(|_| panic!("should write {}", generated_file.display()));
+    }
+}
+
 fn main() {
-    println!("cargo:rerun-if-changed=protobuf/io/casperlabs/casper/consensus/state.proto");
-    println!("cargo:rerun-if-changed=protobuf/io/casperlabs/ipc/ipc.proto");
-    println!("cargo:rerun-if-changed=protobuf/io/casperlabs/ipc/transforms.proto");
+    for proto_file in PROTO_FILES {
+        println!("cargo:rerun-if-changed={}", proto_file);
+    }
 
     let target_dir = PathBuf::from(format!(
         "{}/../../../../{}",
@@ -40,24 +234,17 @@ fn main() {
     fs::create_dir_all(&target_dir)
         .unwrap_or_else(|_| panic!("should create dir {}", target_dir.display()));
 
-    protoc_rust_grpc::run(protoc_rust_grpc::Args {
+    protoc_rust::run(protoc_rust::Args {
         out_dir: target_dir.to_str().unwrap(),
-        input: &[
-            "protobuf/io/casperlabs/casper/consensus/state.proto",
-            "protobuf/io/casperlabs/ipc/ipc.proto",
-            "protobuf/io/casperlabs/ipc/transforms.proto",
-        ],
-        includes: &[
-            "protobuf/",
-            "protobuf/io/casperlabs/casper/consensus",
-            "protobuf/io/casperlabs/ipc",
-        ],
-        rust_protobuf: true,
+        input: PROTO_FILES,
+        includes: PROTO_INCLUDES,
+        customize: Default::default(),
     })
-    .expect("protoc-rust-grpc");
+    .expect("protoc-rust");
+
+    for proto_file in PROTO_FILES {
+        wrap_file_contents(&target_dir, &file_stem(proto_file));
+    }
 
-    wrap_file_contents(&target_dir, "state");
-    wrap_file_contents(&target_dir, "ipc");
-    wrap_file_contents(&target_dir, "transforms");
-    wrap_file_contents(&target_dir, "ipc_grpc");
+    generate_services(&target_dir);
 }
@@ -0,0 +1,74 @@
+//! A tonic [`Codec`] for the messages generated by rust-protobuf.
+
+use std::marker::PhantomData;
+
+use bytes::{Buf, BufMut};
+use protobuf::Message;
+use tonic::{
+    codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    Status,
+};
+
+/// A [`Codec`] which encodes `T` and decodes `U` using rust-protobuf.
+#[derive(Debug, Clone)]
+pub struct ProtobufCodec<T, U>(PhantomData<(T, U)>);
+
+impl<T, U> Default for ProtobufCodec<T, U> {
+    fn default() -> Self {
+        ProtobufCodec(PhantomData)
+    }
+}
+
+impl<T, U> Codec for ProtobufCodec<T, U>
+where
+    T: Message + Send + 'static,
+    U: Message + Send + 'static,
+{
+    type Encode = T;
+    type Decode = U;
+    type Encoder = ProtobufEncoder<T>;
+    type Decoder = ProtobufDecoder<U>;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        ProtobufEncoder(PhantomData)
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        ProtobufDecoder(PhantomData)
+    }
+}
+
+/// An [`Encoder`] which serializes `T`.
+#[derive(Debug, Clone, Default)]
+pub struct ProtobufEncoder<T>(PhantomData<T>);
+
+impl<T: Message> Encoder for ProtobufEncoder<T> {
+    type Item = T;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        let bytes = item
+            .write_to_bytes()
+            .map_err(|error| Status::internal(error.to_string()))?;
+        dst.put_slice(&bytes);
+        Ok(())
+    }
+}
+
+/// A [`Decoder`] which parses `U`.
+#[derive(Debug, Clone, Default)]
+pub struct ProtobufDecoder<U>(PhantomData<U>);
+
+impl<U: Message> Decoder for ProtobufDecoder<U> {
+    type Item = U;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        let bytes = src.to_bytes();
+        // Parse errors map to an INTERNAL status, as per
+        // https://github.com/grpc/grpc/blob/master/doc/statuscodes.md
+        protobuf::parse_from_bytes(&bytes)
+            .map(Some)
+            .map_err(|error| Status::internal(error.to_string()))
+    }
+}
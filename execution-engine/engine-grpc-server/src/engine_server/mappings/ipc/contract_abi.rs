@@ -0,0 +1,109 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::contract_abi::{ContractAbi, ContractAbiRequest};
+use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+
+use crate::engine_server::{
+    ipc,
+    mappings::{state::NamedKeyMap, MappingError},
+    state,
+};
+
+impl TryFrom<ipc::ContractAbiRequest> for ContractAbiRequest {
+    type Error = MappingError;
+
+    fn try_from(mut contract_abi_request: ipc::ContractAbiRequest) -> Result<Self, Self::Error> {
+        let state_hash = {
+            let state_hash = contract_abi_request.get_state_hash();
+            let length = state_hash.len();
+            if length != BLAKE2B_DIGEST_LENGTH {
+                return Err(MappingError::InvalidStateHashLength {
+                    expected: BLAKE2B_DIGEST_LENGTH,
+                    actual: length,
+                });
+            }
+            state_hash
+                .try_into()
+                .map_err(|_| MappingError::TryFromSlice)?
+        };
+
+        let key = contract_abi_request
+            .take_key()
+            .try_into()
+            .map_err(MappingError::Parsing)?;
+
+        let protocol_version = contract_abi_request.take_protocol_version().into();
+
+        Ok(ContractAbiRequest::new(state_hash, key, protocol_version))
+    }
+}
+
+impl From<ContractAbi> for ipc::ContractAbi {
+    fn from(contract_abi: ContractAbi) -> Self {
+        let mut pb_contract_abi = ipc::ContractAbi::new();
+        pb_contract_abi.set_json(contract_abi.to_json().to_string());
+        pb_contract_abi.set_contract_hash(contract_abi.contract_hash().to_vec());
+        pb_contract_abi.set_contract_package_hash(contract_abi.contract_package_hash().to_vec());
+        pb_contract_abi.set_contract_wasm_hash(contract_abi.contract_wasm_hash().to_vec());
+        pb_contract_abi.set_protocol_version(contract_abi.protocol_version().into());
+        let entry_points: Vec<state::Contract_EntryPoint> = contract_abi
+            .entry_points()
+            .iter()
+            .cloned()
+            .map(Into::into)
+            .collect();
+        pb_contract_abi.set_entry_points(entry_points.into());
+        let named_keys: Vec<state::NamedKey> =
+            NamedKeyMap::new(contract_abi.named_keys().clone()).into();
+        pb_contract_abi.set_named_keys(named_keys.into());
+        pb_contract_abi
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{
+        contracts::{NamedKeys, Parameter},
+        CLType, Contract, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key,
+        ProtocolVersion,
+    };
+
+    use super::*;
+
+    #[test]
+    fn should_map_contract_abi() {
+        let mut entry_points = EntryPoints::new();
+        entry_points.add_entry_point(EntryPoint::new(
+            "counter_inc",
+            vec![Parameter::new("step", CLType::I32)],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+        let mut named_keys = NamedKeys::new();
+        named_keys.insert("count".to_string(), Key::Hash([5; 32]));
+        let contract = Contract::new(
+            [2; 32],
+            [3; 32],
+            named_keys,
+            entry_points.clone(),
+            ProtocolVersion::V1_0_0,
+        );
+        let contract_abi = ContractAbi::new([1; 32], contract);
+
+        let pb_contract_abi: ipc::ContractAbi = contract_abi.clone().into();
+
+        assert_eq!(pb_contract_abi.get_contract_hash(), &[1; 32]);
+        assert_eq!(pb_contract_abi.get_contract_package_hash(), &[2; 32]);
+        assert_eq!(pb_contract_abi.get_contract_wasm_hash(), &[3; 32]);
+        assert_eq!(pb_contract_abi.get_named_keys().len(), 1);
+        let pb_entry_points = pb_contract_abi.get_entry_points();
+        assert_eq!(pb_entry_points.len(), 1);
+        let entry_point: EntryPoint = pb_entry_points[0].clone().try_into().unwrap();
+        assert_eq!(Some(&entry_point), entry_points.get("counter_inc"));
+        assert_eq!(
+            pb_contract_abi.get_json(),
+            contract_abi.to_json().to_string()
+        );
+    }
+}
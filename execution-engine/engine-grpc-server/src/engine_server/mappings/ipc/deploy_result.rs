@@ -38,7 +38,8 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
             | error @ EngineStateError::InvalidKeyVariant(_)
             | error @ EngineStateError::Authorization
             | error @ EngineStateError::InvalidDeployItemVariant(_)
-            | error @ EngineStateError::InvalidUpgradeResult => {
+            | error @ EngineStateError::InvalidUpgradeResult
+            | error @ EngineStateError::RootNotFound(_) => {
                 detail::precondition_error(error.to_string())
             }
             EngineStateError::Storage(storage_error) => {
@@ -1,6 +1,7 @@
 use std::convert::{TryFrom, TryInto};
 
 use engine_core::engine_state::genesis::{ExecConfig, GenesisAccount};
+use engine_wasm_prep::wasm_limits::WasmLimits;
 
 use crate::engine_server::{ipc, mappings::MappingError};
 
@@ -15,7 +16,12 @@ impl TryFrom<ipc::ChainSpec_GenesisConfig_ExecConfig> for ExecConfig {
             .into_iter()
             .map(TryInto::try_into)
             .collect::<Result<Vec<GenesisAccount>, Self::Error>>()?;
-        let wasm_costs = pb_exec_config.take_costs().take_wasm().into();
+        let wasm_costs = pb_exec_config.take_costs().take_wasm().try_into()?;
+        let wasm_limits = if !pb_exec_config.has_limits() {
+            WasmLimits::default()
+        } else {
+            pb_exec_config.take_limits().into()
+        };
         let mint_initializer_bytes = pb_exec_config.take_mint_installer();
         let proof_of_stake_initializer_bytes = pb_exec_config.take_pos_installer();
         let standard_payment_installer_bytes = pb_exec_config.take_standard_payment_installer();
@@ -25,6 +31,7 @@ impl TryFrom<ipc::ChainSpec_GenesisConfig_ExecConfig> for ExecConfig {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            wasm_limits,
         ))
     }
 }
@@ -49,6 +56,7 @@ impl From<ExecConfig> for ipc::ChainSpec_GenesisConfig_ExecConfig {
         pb_exec_config
             .mut_costs()
             .set_wasm(exec_config.wasm_costs().into());
+        pb_exec_config.set_limits(exec_config.wasm_limits().into());
         pb_exec_config
     }
 }
@@ -65,4 +73,13 @@ mod tests {
             exec_config,
         );
     }
+
+    #[test]
+    fn should_default_missing_limits() {
+        let exec_config: ExecConfig = rand::random();
+        let mut pb_exec_config: ipc::ChainSpec_GenesisConfig_ExecConfig = exec_config.into();
+        pb_exec_config.clear_limits();
+        let exec_config = ExecConfig::try_from(pb_exec_config).unwrap();
+        assert_eq!(exec_config.wasm_limits(), WasmLimits::default());
+    }
 }
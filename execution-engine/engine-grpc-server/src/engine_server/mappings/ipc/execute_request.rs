@@ -3,30 +3,31 @@ use std::convert::{TryFrom, TryInto};
 use engine_core::engine_state::{
     execute_request::ExecuteRequest, execution_result::ExecutionResult,
 };
-use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+use engine_shared::newtypes::{Blake2bHash, BLAKE2B_DIGEST_LENGTH};
 
 use crate::engine_server::{ipc, mappings::MappingError};
 
+/// Parses a parent state hash, returning it unchanged as the error if it is invalid.
+fn parse_parent_state_hash(parent_state_hash: Vec<u8>) -> Result<Blake2bHash, Vec<u8>> {
+    if parent_state_hash.len() != BLAKE2B_DIGEST_LENGTH {
+        return Err(parent_state_hash);
+    }
+    match parent_state_hash.as_slice().try_into() {
+        Ok(hash) => Ok(hash),
+        Err(_) => Err(parent_state_hash),
+    }
+}
+
 impl TryFrom<ipc::ExecuteRequest> for ExecuteRequest {
     type Error = ipc::ExecuteResponse;
 
     fn try_from(mut request: ipc::ExecuteRequest) -> Result<Self, Self::Error> {
-        let parent_state_hash = {
-            let parent_state_hash = request.take_parent_state_hash();
-            let length = parent_state_hash.len();
-            if length != BLAKE2B_DIGEST_LENGTH {
+        let parent_state_hash =
+            parse_parent_state_hash(request.take_parent_state_hash()).map_err(|hash| {
                 let mut result = ipc::ExecuteResponse::new();
-                result.mut_missing_parent().set_hash(parent_state_hash);
-                return Err(result);
-            }
-            parent_state_hash.as_slice().try_into().map_err(|_| {
-                let mut result = ipc::ExecuteResponse::new();
-                result
-                    .mut_missing_parent()
-                    .set_hash(parent_state_hash.clone());
+                result.mut_missing_parent().set_hash(hash);
                 result
-            })?
-        };
+            })?;
 
         let block_time = request.get_block_time();
 
@@ -50,6 +51,27 @@ impl TryFrom<ipc::ExecuteRequest> for ExecuteRequest {
     }
 }
 
+/// Maps the header of a streamed execute request to an `ExecuteRequest` without any deploys.
+impl TryFrom<ipc::ExecuteStreamRequest_Header> for ExecuteRequest {
+    type Error = ipc::ExecuteStreamResponse;
+
+    fn try_from(mut header: ipc::ExecuteStreamRequest_Header) -> Result<Self, Self::Error> {
+        let parent_state_hash =
+            parse_parent_state_hash(header.take_parent_state_hash()).map_err(|hash| {
+                let mut result = ipc::ExecuteStreamResponse::new();
+                result.mut_missing_parent().set_hash(hash);
+                result
+            })?;
+
+        Ok(ExecuteRequest::new(
+            parent_state_hash,
+            header.get_block_time(),
+            vec![],
+            header.take_protocol_version().into(),
+        ))
+    }
+}
+
 impl From<ExecuteRequest> for ipc::ExecuteRequest {
     fn from(req: ExecuteRequest) -> Self {
         let mut result = ipc::ExecuteRequest::new();
@@ -2,6 +2,7 @@
 //! defined in protobuf/io/casperlabs/ipc/ipc.proto
 
 mod bond;
+mod contract_abi;
 mod deploy_item;
 mod deploy_result;
 mod exec_config;
@@ -14,3 +15,4 @@ mod query_request;
 mod run_genesis_request;
 mod upgrade_request;
 mod wasm_costs;
+mod wasm_limits;
@@ -33,7 +33,12 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
         let wasm_costs = if !upgrade_point.has_new_costs() {
             None
         } else {
-            Some(upgrade_point.mut_new_costs().take_wasm().into())
+            Some(upgrade_point.mut_new_costs().take_wasm().try_into()?)
+        };
+        let wasm_limits = if !upgrade_point.has_new_limits() {
+            None
+        } else {
+            Some(upgrade_point.take_new_limits().into())
         };
         let activation_point = if !upgrade_point.has_activation_point() {
             None
@@ -48,6 +53,7 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
             upgrade_installer_args,
             upgrade_installer_bytes,
             wasm_costs,
+            wasm_limits,
             activation_point,
         ))
     }
@@ -1,6 +1,8 @@
+use std::convert::TryFrom;
+
 use engine_wasm_prep::wasm_costs::WasmCosts;
 
-use crate::engine_server::ipc::ChainSpec_CostTable_WasmCosts;
+use crate::engine_server::{ipc::ChainSpec_CostTable_WasmCosts, mappings::ParsingError};
 
 impl From<WasmCosts> for ChainSpec_CostTable_WasmCosts {
     fn from(wasm_costs: WasmCosts) -> Self {
@@ -15,14 +17,23 @@ impl From<WasmCosts> for ChainSpec_CostTable_WasmCosts {
             max_stack_height: wasm_costs.max_stack_height,
             opcodes_mul: wasm_costs.opcodes_mul,
             opcodes_div: wasm_costs.opcodes_div,
+            memfill: wasm_costs.memfill,
             ..Default::default()
         }
     }
 }
 
-impl From<ChainSpec_CostTable_WasmCosts> for WasmCosts {
-    fn from(pb_wasm_costs: ChainSpec_CostTable_WasmCosts) -> Self {
-        WasmCosts {
+impl TryFrom<ChainSpec_CostTable_WasmCosts> for WasmCosts {
+    type Error = ParsingError;
+
+    fn try_from(pb_wasm_costs: ChainSpec_CostTable_WasmCosts) -> Result<Self, Self::Error> {
+        // proto3 decodes a missing field as zero, and filling memory is never free.
+        if pb_wasm_costs.memfill == 0 {
+            return Err(ParsingError::from(
+                "Protobuf WasmCosts has zero or missing memfill field",
+            ));
+        }
+        Ok(WasmCosts {
             regular: pb_wasm_costs.regular,
             div: pb_wasm_costs.div,
             mul: pb_wasm_costs.mul,
@@ -33,7 +44,8 @@ impl From<ChainSpec_CostTable_WasmCosts> for WasmCosts {
             max_stack_height: pb_wasm_costs.max_stack_height,
             opcodes_mul: pb_wasm_costs.opcodes_mul,
             opcodes_div: pb_wasm_costs.opcodes_div,
-        }
+            memfill: pb_wasm_costs.memfill,
+        })
     }
 }
 
@@ -52,4 +64,14 @@ mod tests {
             test_utils::protobuf_round_trip::<WasmCosts, ChainSpec_CostTable_WasmCosts>(wasm_costs);
         }
     }
+
+    #[test]
+    fn should_reject_missing_memfill() {
+        let mut pb_wasm_costs = ChainSpec_CostTable_WasmCosts::from(WasmCosts {
+            memfill: 1,
+            ..WasmCosts::default()
+        });
+        pb_wasm_costs.clear_memfill();
+        assert!(WasmCosts::try_from(pb_wasm_costs).is_err());
+    }
 }
@@ -0,0 +1,47 @@
+use engine_wasm_prep::wasm_limits::WasmLimits;
+
+use crate::engine_server::ipc::ChainSpec_WasmLimits;
+
+impl From<WasmLimits> for ChainSpec_WasmLimits {
+    fn from(wasm_limits: WasmLimits) -> Self {
+        ChainSpec_WasmLimits {
+            max_memory_pages: wasm_limits.max_memory_pages,
+            max_table_size: wasm_limits.max_table_size,
+            max_locals: wasm_limits.max_locals,
+            max_globals: wasm_limits.max_globals,
+            max_functions: wasm_limits.max_functions,
+            max_module_size: wasm_limits.max_module_size,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ChainSpec_WasmLimits> for WasmLimits {
+    fn from(pb_wasm_limits: ChainSpec_WasmLimits) -> Self {
+        WasmLimits {
+            max_memory_pages: pb_wasm_limits.max_memory_pages,
+            max_table_size: pb_wasm_limits.max_table_size,
+            max_locals: pb_wasm_limits.max_locals,
+            max_globals: pb_wasm_limits.max_globals,
+            max_functions: pb_wasm_limits.max_functions,
+            max_module_size: pb_wasm_limits.max_module_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_wasm_prep::wasm_limits::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(wasm_limits in gens::wasm_limits_arb()) {
+            test_utils::protobuf_round_trip::<WasmLimits, ChainSpec_WasmLimits>(wasm_limits);
+        }
+    }
+}
@@ -14,6 +14,7 @@ impl From<ContractPackage> for state::ContractPackage {
     fn from(value: ContractPackage) -> state::ContractPackage {
         let mut contract_package = state::ContractPackage::new();
         contract_package.set_access_key(value.access_key().into());
+        contract_package.set_locked(value.is_locked());
 
         for &disabled_version in value.disabled_versions().iter() {
             contract_package
@@ -21,6 +22,15 @@ impl From<ContractPackage> for state::ContractPackage {
                 .push(disabled_version.into())
         }
 
+        for (&version, metadata) in value.all_version_metadata().iter() {
+            let mut version_metadata = state::ContractPackage_VersionMetadata::new();
+            version_metadata.set_version(version.into());
+            version_metadata.set_metadata(metadata.clone());
+            contract_package
+                .mut_version_metadata()
+                .push(version_metadata);
+        }
+
         for (existing_group, urefs) in value.groups().iter() {
             let mut entrypoint_group = state::Contract_EntryPoint_Group::new();
             entrypoint_group.set_name(existing_group.value().to_string());
@@ -66,6 +76,12 @@ impl TryFrom<state::ContractPackage> for ContractPackage {
                 .disabled_versions_mut()
                 .insert(disabled_version.try_into()?);
         }
+        for mut version_metadata in value.take_version_metadata().into_iter() {
+            let version = version_metadata.take_version().try_into()?;
+            contract_package
+                .all_version_metadata_mut()
+                .insert(version, version_metadata.take_metadata());
+        }
 
         let groups = contract_package.groups_mut();
         for mut group in value.take_groups().into_iter() {
@@ -76,6 +92,9 @@ impl TryFrom<state::ContractPackage> for ContractPackage {
             }
             groups.insert(Group::new(group_name), urefs);
         }
+        if value.get_locked() {
+            contract_package.lock();
+        }
         Ok(contract_package)
     }
 }
@@ -42,6 +42,9 @@ impl From<Transform> for transforms::Transform {
             Transform::AddUInt512(uint512) => {
                 pb_transform.mut_add_big_int().set_value(uint512.into());
             }
+            Transform::Prune => {
+                pb_transform.set_prune(Default::default());
+            }
         };
         pb_transform
     }
@@ -99,6 +102,7 @@ impl TryFrom<transforms::Transform> for Transform {
                 let error = TransformError::try_from(pb_failure)?;
                 Transform::Failure(error)
             }
+            Transform_oneof_transform_instance::prune(_) => Transform::Prune,
         };
         Ok(transform)
     }
@@ -4,7 +4,15 @@ include!(concat!(
 ));
 include!(concat!(
     env!("OUT_DIR"),
-    "/../../../../generated_protobuf/ipc_grpc.rs"
+    "/../../../../generated_protobuf/ipc_tonic.rs"
+));
+include!(concat!(
+    env!("OUT_DIR"),
+    "/../../../../generated_protobuf/reflection.rs"
+));
+include!(concat!(
+    env!("OUT_DIR"),
+    "/../../../../generated_protobuf/reflection_tonic.rs"
 ));
 include!(concat!(
     env!("OUT_DIR"),
@@ -14,28 +22,43 @@ include!(concat!(
     env!("OUT_DIR"),
     "/../../../../generated_protobuf/transforms.rs"
 ));
+pub mod codec;
 pub mod mappings;
+mod server;
+mod server_reflection;
 
 use std::{
     collections::BTreeMap,
     convert::{TryFrom, TryInto},
     fmt::Debug,
+    future::Future,
     io::ErrorKind,
     iter::FromIterator,
     marker::{Send, Sync},
+    path::Path,
     time::Instant,
 };
 
-use grpc::{Error as GrpcError, RequestOptions, ServerBuilder, SingleResponse};
 use log::{info, warn, Level};
-
-use engine_core::engine_state::{
-    execute_request::ExecuteRequest,
-    genesis::GenesisResult,
-    query::{QueryRequest, QueryResult},
-    run_genesis_request::RunGenesisRequest,
-    upgrade::{UpgradeConfig, UpgradeResult},
-    EngineState, Error as EngineError,
+use tokio::{
+    net::{self, UnixListener},
+    stream::StreamExt,
+};
+use tonic::transport::{self, Channel, Endpoint, Server, Uri};
+use tower::service_fn;
+
+use engine_core::{
+    engine_state::{
+        contract_abi::{ContractAbiRequest, ContractAbiResult},
+        execute_request::ExecuteRequest,
+        execution_result::ExecutionResult,
+        genesis::GenesisResult,
+        query::{QueryRequest, QueryResult},
+        run_genesis_request::RunGenesisRequest,
+        upgrade::{UpgradeConfig, UpgradeResult},
+        EngineState, Error as EngineError,
+    },
+    execution::Interrupt,
 };
 use engine_shared::{
     logging::{self, log_duration},
@@ -45,31 +68,73 @@ use engine_storage::global_state::{CommitResult, StateProvider};
 use types::{bytesrepr::ToBytes, ProtocolVersion};
 
 use self::{
+    execution_engine_service_client::ExecutionEngineServiceClient,
+    execution_engine_service_server::ExecutionEngineServiceServer,
     ipc::{
-        BidStateRequest, BidStateResponse, CommitRequest, CommitResponse, DistributeRewardsRequest,
-        DistributeRewardsResponse, ExecuteResponse, GenesisResponse, QueryResponse, SlashRequest,
-        SlashResponse, UnbondPayoutRequest, UnbondPayoutResponse, UpgradeRequest, UpgradeResponse,
+        CommitRequest, CommitResponse, ContractAbiResponse, ExecuteResponse, ExecuteStreamResponse,
+        GenesisResponse, QueryResponse, RootNotFound, UpgradeRequest, UpgradeResponse,
     },
-    ipc_grpc::{ExecutionEngineService, ExecutionEngineServiceServer},
-    mappings::{ParsingError, TransformMap},
+    mappings::{MappingError, ParsingError, TransformMap},
+    server::{EngineServer, UnixStream},
+    server_reflection::{ServerReflectionServer, ServerReflectionService},
 };
 
 const METRIC_DURATION_COMMIT: &str = "commit_duration";
 const METRIC_DURATION_EXEC: &str = "exec_duration";
+const METRIC_DURATION_EXEC_STREAM: &str = "exec_stream_duration";
 const METRIC_DURATION_QUERY: &str = "query_duration";
+const METRIC_DURATION_CONTRACT_ABI: &str = "contract_abi_duration";
 const METRIC_DURATION_GENESIS: &str = "genesis_duration";
 const METRIC_DURATION_UPGRADE: &str = "upgrade_duration";
 
 const TAG_RESPONSE_COMMIT: &str = "commit_response";
 const TAG_RESPONSE_EXEC: &str = "exec_response";
+const TAG_RESPONSE_EXEC_STREAM: &str = "exec_stream_response";
 const TAG_RESPONSE_QUERY: &str = "query_response";
+const TAG_RESPONSE_CONTRACT_ABI: &str = "contract_abi_response";
 const TAG_RESPONSE_GENESIS: &str = "genesis_response";
 const TAG_RESPONSE_UPGRADE: &str = "upgrade_response";
 
-const UNIMPLEMENTED: &str = "unimplemented";
-
 const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 
+// Connections are made over a Unix socket, so the endpoint's address is unused.
+const UNIX_SOCKET_ENDPOINT: &str = "http://[::]:50051";
+
+/// The methods of the gRPC `ExecutionEngineService`, each of which runs synchronously to
+/// completion.
+///
+/// [`EngineServer`] runs these on a blocking thread pool to serve them asynchronously.
+pub trait ExecutionEngineService {
+    fn query(&self, query_request: ipc::QueryRequest) -> QueryResponse;
+
+    fn contract_abi(&self, contract_abi_request: ipc::ContractAbiRequest) -> ContractAbiResponse;
+
+    /// Runs the deploys in `exec_request`, returning early with a failure if `interrupt` is set
+    /// while they are executing.
+    fn execute(&self, exec_request: ipc::ExecuteRequest, interrupt: Interrupt) -> ExecuteResponse;
+
+    /// Like [`ExecutionEngineService::execute`], but takes the deploys one at a time from
+    /// `deploys`, passing a response to `on_response` for each as soon as it has been executed.
+    ///
+    /// If execution fails, a final `missing_parent` or `failure` response is passed, and any
+    /// responses already passed must be discarded.
+    fn execute_stream<I, F>(
+        &self,
+        header: ipc::ExecuteStreamRequest_Header,
+        deploys: I,
+        interrupt: Interrupt,
+        on_response: F,
+    ) where
+        I: IntoIterator<Item = ipc::DeployItem>,
+        F: FnMut(ExecuteStreamResponse);
+
+    fn commit(&self, commit_request: CommitRequest) -> CommitResponse;
+
+    fn run_genesis(&self, run_genesis_request: ipc::RunGenesisRequest) -> GenesisResponse;
+
+    fn upgrade(&self, upgrade_request: UpgradeRequest) -> UpgradeResponse;
+}
+
 // Idea is that Engine will represent the core of the execution engine project.
 // It will act as an entry point for execution of Wasm binaries.
 // Proto definitions should be translated into domain objects when Engine's API
@@ -81,11 +146,7 @@ where
     EngineError: From<S::Error>,
     S::Error: Into<engine_core::execution::Error> + Debug,
 {
-    fn query(
-        &self,
-        _request_options: RequestOptions,
-        query_request: ipc::QueryRequest,
-    ) -> SingleResponse<QueryResponse> {
+    fn query(&self, query_request: ipc::QueryRequest) -> QueryResponse {
         let start = Instant::now();
         let correlation_id = CorrelationId::new();
 
@@ -102,7 +163,7 @@ where
                     TAG_RESPONSE_QUERY,
                     start.elapsed(),
                 );
-                return SingleResponse::completed(result);
+                return result;
             }
         };
 
@@ -159,38 +220,107 @@ where
             start.elapsed(),
         );
 
-        SingleResponse::completed(response)
+        response
     }
 
-    fn execute(
-        &self,
-        _request_options: RequestOptions,
-        exec_request: ipc::ExecuteRequest,
-    ) -> SingleResponse<ExecuteResponse> {
+    fn contract_abi(&self, contract_abi_request: ipc::ContractAbiRequest) -> ContractAbiResponse {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let state_hash = contract_abi_request.get_state_hash().to_vec();
+
+        let request: ContractAbiRequest = match contract_abi_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ContractAbiResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_CONTRACT_ABI,
+                    TAG_RESPONSE_CONTRACT_ABI,
+                    start.elapsed(),
+                );
+                return result;
+            }
+        };
+
+        let mut response = ContractAbiResponse::new();
+        match self.get_contract_abi(correlation_id, request) {
+            Ok(ContractAbiResult::Success(contract_abi)) => {
+                info!(
+                    "contract abi successful; correlation_id: {}",
+                    correlation_id
+                );
+                response.set_success(contract_abi.into());
+            }
+            Ok(ContractAbiResult::ValueNotFound(msg)) => {
+                info!("{}", msg);
+                response.set_failure(msg);
+            }
+            Ok(ContractAbiResult::RootNotFound) => {
+                info!("Root not found");
+                let mut root_not_found = RootNotFound::new();
+                root_not_found.set_hash(state_hash);
+                response.set_missing_prestate(root_not_found);
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_CONTRACT_ABI,
+            TAG_RESPONSE_CONTRACT_ABI,
+            start.elapsed(),
+        );
+
+        response
+    }
+
+    fn execute(&self, exec_request: ipc::ExecuteRequest, interrupt: Interrupt) -> ExecuteResponse {
         let start = Instant::now();
         let correlation_id = CorrelationId::new();
 
         let exec_request: ExecuteRequest = match exec_request.try_into() {
             Ok(ret) => ret,
             Err(err) => {
-                return SingleResponse::completed(err);
+                return err;
             }
         };
 
         let mut exec_response = ExecuteResponse::new();
 
-        let results = match self.run_execute(correlation_id, exec_request) {
+        let results = match self.run_execute_with_interrupt(correlation_id, exec_request, interrupt)
+        {
             Ok(results) => results,
-            Err(error) => {
+            Err(EngineError::RootNotFound(missing_parent)) => {
                 info!("deploy results error: RootNotFound");
-                exec_response.mut_missing_parent().set_hash(error.to_vec());
+                exec_response
+                    .mut_missing_parent()
+                    .set_hash(missing_parent.to_vec());
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_EXEC,
+                    TAG_RESPONSE_EXEC,
+                    start.elapsed(),
+                );
+                return exec_response;
+            }
+            Err(error) => {
+                warn!("deploy results error: {}", error);
+                exec_response.set_failure(error.to_string());
                 log_duration(
                     correlation_id,
                     METRIC_DURATION_EXEC,
                     TAG_RESPONSE_EXEC,
                     start.elapsed(),
                 );
-                return SingleResponse::completed(exec_response);
+                return exec_response;
             }
         };
 
@@ -204,14 +334,76 @@ where
             TAG_RESPONSE_EXEC,
             start.elapsed(),
         );
-        SingleResponse::completed(exec_response)
+        exec_response
     }
 
-    fn commit(
+    fn execute_stream<I, F>(
         &self,
-        _request_options: RequestOptions,
-        mut commit_request: CommitRequest,
-    ) -> SingleResponse<CommitResponse> {
+        header: ipc::ExecuteStreamRequest_Header,
+        deploys: I,
+        interrupt: Interrupt,
+        mut on_response: F,
+    ) where
+        I: IntoIterator<Item = ipc::DeployItem>,
+        F: FnMut(ExecuteStreamResponse),
+    {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let exec_request: ExecuteRequest = match header.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                on_response(err);
+                return;
+            }
+        };
+
+        let deploys = deploys.into_iter().map(|deploy_item| {
+            deploy_item
+                .try_into()
+                .map_err(|err: MappingError| ExecutionResult::precondition_failure(err.into()))
+        });
+
+        let result = self.run_execute_streaming(
+            correlation_id,
+            exec_request.parent_state_hash,
+            exec_request.block_time,
+            exec_request.protocol_version,
+            deploys,
+            interrupt,
+            |result| {
+                let mut exec_response = ExecuteStreamResponse::new();
+                exec_response.set_deploy_result(result.into());
+                on_response(exec_response);
+            },
+        );
+
+        match result {
+            Ok(()) => {}
+            Err(EngineError::RootNotFound(missing_parent)) => {
+                info!("deploy results error: RootNotFound");
+                let mut exec_response = ExecuteStreamResponse::new();
+                exec_response
+                    .mut_missing_parent()
+                    .set_hash(missing_parent.to_vec());
+                on_response(exec_response);
+            }
+            Err(error) => {
+                warn!("deploy results error: {}", error);
+                let mut exec_response = ExecuteStreamResponse::new();
+                exec_response.set_failure(error.to_string());
+                on_response(exec_response);
+            }
+        }
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_EXEC_STREAM,
+            TAG_RESPONSE_EXEC_STREAM,
+            start.elapsed(),
+        );
+    }
+
+    fn commit(&self, mut commit_request: CommitRequest) -> CommitResponse {
         let start = Instant::now();
         let correlation_id = CorrelationId::new();
 
@@ -234,7 +426,7 @@ where
                 commit_response
                     .mut_failed_transform()
                     .set_message(error_message);
-                return SingleResponse::completed(commit_response);
+                return commit_response;
             }
             Ok(hash) => hash,
         };
@@ -247,7 +439,7 @@ where
                 commit_response
                     .mut_failed_transform()
                     .set_message(error_message);
-                return SingleResponse::completed(commit_response);
+                return commit_response;
             }
             Ok(transforms) => transforms.into_inner(),
         };
@@ -312,14 +504,10 @@ where
             start.elapsed(),
         );
 
-        SingleResponse::completed(commit_response)
+        commit_response
     }
 
-    fn run_genesis(
-        &self,
-        _request_options: RequestOptions,
-        run_genesis_request: ipc::RunGenesisRequest,
-    ) -> SingleResponse<GenesisResponse> {
+    fn run_genesis(&self, run_genesis_request: ipc::RunGenesisRequest) -> GenesisResponse {
         let start = Instant::now();
         let correlation_id = CorrelationId::new();
 
@@ -331,7 +519,7 @@ where
 
                 let mut genesis_response = GenesisResponse::new();
                 genesis_response.mut_failed_deploy().set_message(err_msg);
-                return SingleResponse::completed(genesis_response);
+                return genesis_response;
             }
         };
         let genesis_config_hash = run_genesis_request.genesis_config_hash();
@@ -382,14 +570,10 @@ where
             start.elapsed(),
         );
 
-        SingleResponse::completed(genesis_response)
+        genesis_response
     }
 
-    fn upgrade(
-        &self,
-        _request_options: RequestOptions,
-        upgrade_request: UpgradeRequest,
-    ) -> SingleResponse<UpgradeResponse> {
+    fn upgrade(&self, upgrade_request: UpgradeRequest) -> UpgradeResponse {
         let start = Instant::now();
         let correlation_id = CorrelationId::new();
 
@@ -409,7 +593,7 @@ where
                     start.elapsed(),
                 );
 
-                return SingleResponse::completed(upgrade_response);
+                return upgrade_response;
             }
         };
 
@@ -450,50 +634,18 @@ where
             start.elapsed(),
         );
 
-        SingleResponse::completed(upgrade_response)
-    }
-
-    fn bid_state(
-        &self,
-        _request_options: RequestOptions,
-        _bid_state_request: BidStateRequest,
-    ) -> SingleResponse<BidStateResponse> {
-        SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()))
-    }
-
-    fn distribute_rewards(
-        &self,
-        _request_options: RequestOptions,
-        _distribute_rewards_request: DistributeRewardsRequest,
-    ) -> SingleResponse<DistributeRewardsResponse> {
-        SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()))
-    }
-
-    fn slash(
-        &self,
-        _request_options: RequestOptions,
-        _slash_request: SlashRequest,
-    ) -> SingleResponse<SlashResponse> {
-        SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()))
-    }
-
-    fn unbond_payout(
-        &self,
-        _request_options: RequestOptions,
-        _unbond_payout_request: UnbondPayoutRequest,
-    ) -> SingleResponse<UnbondPayoutResponse> {
-        SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()))
+        upgrade_response
     }
 }
 
-// Helper method which returns single DeployResult that is set to be a
-// WasmError.
-pub fn new<E: ExecutionEngineService + Sync + Send + 'static>(
-    socket: &str,
-    thread_count: usize,
-    e: E,
-) -> ServerBuilder {
-    let socket_path = std::path::Path::new(socket);
+/// Serves `engine` along with the gRPC server reflection service on the Unix socket at `socket`
+/// until `shutdown` completes.
+pub async fn serve<E, F>(socket: &str, engine: E, shutdown: F) -> Result<(), transport::Error>
+where
+    E: ExecutionEngineService + Send + Sync + 'static,
+    F: Future<Output = ()>,
+{
+    let socket_path = Path::new(socket);
 
     if let Err(e) = std::fs::remove_file(socket_path) {
         if e.kind() != ErrorKind::NotFound {
@@ -501,9 +653,28 @@ pub fn new<E: ExecutionEngineService + Sync + Send + 'static>(
         }
     }
 
-    let mut server = ServerBuilder::new_plain();
-    server.http.set_unix_addr(socket.to_owned()).unwrap();
-    server.http.set_cpu_pool_threads(thread_count);
-    server.add_service(ExecutionEngineServiceServer::new_service_def(e));
-    server
+    let mut listener = UnixListener::bind(socket_path)
+        .unwrap_or_else(|e| panic!("failed to bind to socket {}: {:?}", socket, e));
+    let incoming = listener
+        .incoming()
+        .map(|stream| stream.map(UnixStream::from));
+
+    Server::builder()
+        .add_service(ExecutionEngineServiceServer::new(EngineServer::new(engine)))
+        .add_service(ServerReflectionServer::new(ServerReflectionService::new()))
+        .serve_with_incoming_shutdown(incoming, shutdown)
+        .await
+}
+
+/// Connects a client to the server listening on the Unix socket at `socket`.
+pub async fn connect(
+    socket: &str,
+) -> Result<ExecutionEngineServiceClient<Channel>, transport::Error> {
+    let socket = socket.to_string();
+    let channel = Endpoint::from_static(UNIX_SOCKET_ENDPOINT)
+        .connect_with_connector(service_fn(move |_: Uri| {
+            net::UnixStream::connect(socket.clone())
+        }))
+        .await?;
+    Ok(ExecutionEngineServiceClient::new(channel))
 }
@@ -0,0 +1,387 @@
+//! Serves an [`ExecutionEngineService`] asynchronously over tonic.
+
+use std::{
+    io,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use log::warn;
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net,
+    runtime::Handle,
+    sync::mpsc,
+    task, time,
+};
+use tonic::{
+    metadata::MetadataMap, transport::server::Connected, Request, Response, Status, Streaming,
+};
+
+use engine_core::execution::Interrupt;
+
+use super::{
+    execution_engine_service_server,
+    ipc::{
+        BidStateRequest, BidStateResponse, CommitRequest, CommitResponse, ContractAbiRequest,
+        ContractAbiResponse, DeployItem, DistributeRewardsRequest, DistributeRewardsResponse,
+        ExecuteRequest, ExecuteResponse, ExecuteStreamRequest, ExecuteStreamResponse,
+        GenesisResponse, QueryRequest, QueryResponse, RunGenesisRequest, SlashRequest,
+        SlashResponse, UnbondPayoutRequest, UnbondPayoutResponse, UpgradeRequest, UpgradeResponse,
+    },
+    ExecutionEngineService,
+};
+
+const GRPC_TIMEOUT_HEADER: &str = "grpc-timeout";
+// The gRPC spec limits the timeout value to at most 8 digits.
+const GRPC_TIMEOUT_MAX_DIGITS: usize = 8;
+const DEADLINE_EXCEEDED: &str = "deadline exceeded";
+const EXPECTED_HEADER: &str = "expected the first message to be a header";
+const EXPECTED_DEPLOY: &str = "expected every message after the header to be a deploy";
+const RESPONSE_CHANNEL_CAPACITY: usize = 16;
+const UNIMPLEMENTED: &str = "unimplemented";
+
+/// Parses the value of a `grpc-timeout` header, e.g. "100m" for 100 milliseconds.
+fn parse_grpc_timeout(value: &str) -> Option<Duration> {
+    if value.len() < 2 || value.len() > GRPC_TIMEOUT_MAX_DIGITS + 1 {
+        return None;
+    }
+    let (digits, unit) = value.split_at(value.len() - 1);
+    if !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let amount: u64 = digits.parse().ok()?;
+    let timeout = match unit {
+        "H" => Duration::from_secs(amount * 60 * 60),
+        "M" => Duration::from_secs(amount * 60),
+        "S" => Duration::from_secs(amount),
+        "m" => Duration::from_millis(amount),
+        "u" => Duration::from_micros(amount),
+        "n" => Duration::from_nanos(amount),
+        _ => return None,
+    };
+    Some(timeout)
+}
+
+/// Returns the deadline the client set for a request, if any.
+fn get_timeout(metadata: &MetadataMap) -> Option<Duration> {
+    let value = metadata.get(GRPC_TIMEOUT_HEADER)?.to_str().ok()?;
+    let timeout = parse_grpc_timeout(value);
+    if timeout.is_none() {
+        warn!("ignoring invalid {} header: {}", GRPC_TIMEOUT_HEADER, value);
+    }
+    timeout
+}
+
+/// Sets an [`Interrupt`] when dropped.
+///
+/// A request's handler future is dropped without completing when the client cancels it, so this
+/// stops any Wasm still executing on its behalf.
+struct InterruptOnDrop(Interrupt);
+
+impl Drop for InterruptOnDrop {
+    fn drop(&mut self) {
+        self.0.interrupt();
+    }
+}
+
+/// Serves the methods of an [`ExecutionEngineService`] by running each on tokio's blocking thread
+/// pool.
+///
+/// Requests which outlive the client's deadline fail with `DEADLINE_EXCEEDED`, and any deploys
+/// they are still executing are interrupted.
+pub struct EngineServer<E> {
+    engine: Arc<E>,
+}
+
+impl<E> EngineServer<E>
+where
+    E: ExecutionEngineService + Send + Sync + 'static,
+{
+    pub fn new(engine: E) -> Self {
+        EngineServer {
+            engine: Arc::new(engine),
+        }
+    }
+
+    /// Runs `handler` on the blocking thread pool, honouring the request's deadline.
+    async fn run<T, R, F>(&self, request: Request<T>, handler: F) -> Result<Response<R>, Status>
+    where
+        T: Send + 'static,
+        R: Send + 'static,
+        F: FnOnce(&E, T, Interrupt) -> R + Send + 'static,
+    {
+        let timeout = get_timeout(request.metadata());
+        let interrupt = Interrupt::new();
+        let _interrupt_on_drop = InterruptOnDrop(interrupt.clone());
+
+        let engine = Arc::clone(&self.engine);
+        let message = request.into_inner();
+        let handle = task::spawn_blocking(move || handler(&engine, message, interrupt));
+
+        let result = match timeout {
+            Some(timeout) => time::timeout(timeout, handle)
+                .await
+                .map_err(|_| Status::deadline_exceeded(DEADLINE_EXCEEDED))?,
+            None => handle.await,
+        };
+
+        result
+            .map(Response::new)
+            .map_err(|error| Status::internal(error.to_string()))
+    }
+}
+
+/// The deploys of a streamed execute request, read by blocking on the runtime.
+///
+/// Iteration stops early if `interrupt` is set or the request stream fails, in which case the
+/// failure is kept in `error`.
+struct DeployStream<'a> {
+    requests: Streaming<ExecuteStreamRequest>,
+    runtime: &'a Handle,
+    interrupt: Interrupt,
+    error: Option<Status>,
+}
+
+impl Iterator for DeployStream<'_> {
+    type Item = DeployItem;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.error.is_some() || self.interrupt.is_interrupted() {
+            return None;
+        }
+        match self.runtime.block_on(self.requests.message()) {
+            Ok(Some(mut message)) if message.has_deploy() => Some(message.take_deploy()),
+            Ok(Some(_)) => {
+                self.error = Some(Status::invalid_argument(EXPECTED_DEPLOY));
+                None
+            }
+            Ok(None) => None,
+            Err(status) => {
+                self.error = Some(status);
+                None
+            }
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<E> execution_engine_service_server::ExecutionEngineService for EngineServer<E>
+where
+    E: ExecutionEngineService + Send + Sync + 'static,
+{
+    async fn commit(
+        &self,
+        request: Request<CommitRequest>,
+    ) -> Result<Response<CommitResponse>, Status> {
+        self.run(request, |engine, request, _| engine.commit(request))
+            .await
+    }
+
+    async fn query(
+        &self,
+        request: Request<QueryRequest>,
+    ) -> Result<Response<QueryResponse>, Status> {
+        self.run(request, |engine, request, _| engine.query(request))
+            .await
+    }
+
+    async fn contract_abi(
+        &self,
+        request: Request<ContractAbiRequest>,
+    ) -> Result<Response<ContractAbiResponse>, Status> {
+        self.run(request, |engine, request, _| engine.contract_abi(request))
+            .await
+    }
+
+    async fn execute(
+        &self,
+        request: Request<ExecuteRequest>,
+    ) -> Result<Response<ExecuteResponse>, Status> {
+        self.run(request, |engine, request, interrupt| {
+            engine.execute(request, interrupt)
+        })
+        .await
+    }
+
+    // Named after the method as declared in ipc.proto.
+    type execute_streamStream = mpsc::Receiver<Result<ExecuteStreamResponse, Status>>;
+
+    async fn execute_stream(
+        &self,
+        request: Request<Streaming<ExecuteStreamRequest>>,
+    ) -> Result<Response<Self::execute_streamStream>, Status> {
+        let timeout = get_timeout(request.metadata());
+        let mut requests = request.into_inner();
+        let header = match requests.message().await? {
+            Some(mut message) if message.has_header() => message.take_header(),
+            _ => return Err(Status::invalid_argument(EXPECTED_HEADER)),
+        };
+
+        let (mut sender, receiver) = mpsc::channel(RESPONSE_CHANNEL_CAPACITY);
+        let interrupt = Interrupt::new();
+
+        let handle = {
+            let engine = Arc::clone(&self.engine);
+            let interrupt = interrupt.clone();
+            let mut sender = sender.clone();
+            task::spawn_blocking(move || {
+                let runtime = Handle::current();
+                let mut deploys = DeployStream {
+                    requests,
+                    runtime: &runtime,
+                    interrupt: interrupt.clone(),
+                    error: None,
+                };
+                engine.execute_stream(header, &mut deploys, interrupt.clone(), |response| {
+                    if runtime.block_on(sender.send(Ok(response))).is_err() {
+                        // The client has gone away, so there is no point executing further.
+                        interrupt.interrupt();
+                    }
+                });
+                if let Some(status) = deploys.error {
+                    let _ = runtime.block_on(sender.send(Err(status)));
+                }
+            })
+        };
+
+        // Responses are streamed back while the deploys execute, so the deadline is enforced
+        // separately from the handler's return.
+        tokio::spawn(async move {
+            let result = match timeout {
+                Some(timeout) => match time::timeout(timeout, handle).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        interrupt.interrupt();
+                        let _ = sender
+                            .send(Err(Status::deadline_exceeded(DEADLINE_EXCEEDED)))
+                            .await;
+                        return;
+                    }
+                },
+                None => handle.await,
+            };
+            if let Err(error) = result {
+                let _ = sender.send(Err(Status::internal(error.to_string()))).await;
+            }
+        });
+
+        Ok(Response::new(receiver))
+    }
+
+    async fn run_genesis(
+        &self,
+        request: Request<RunGenesisRequest>,
+    ) -> Result<Response<GenesisResponse>, Status> {
+        self.run(request, |engine, request, _| engine.run_genesis(request))
+            .await
+    }
+
+    async fn upgrade(
+        &self,
+        request: Request<UpgradeRequest>,
+    ) -> Result<Response<UpgradeResponse>, Status> {
+        self.run(request, |engine, request, _| engine.upgrade(request))
+            .await
+    }
+
+    async fn bid_state(
+        &self,
+        _request: Request<BidStateRequest>,
+    ) -> Result<Response<BidStateResponse>, Status> {
+        Err(Status::unimplemented(UNIMPLEMENTED))
+    }
+
+    async fn distribute_rewards(
+        &self,
+        _request: Request<DistributeRewardsRequest>,
+    ) -> Result<Response<DistributeRewardsResponse>, Status> {
+        Err(Status::unimplemented(UNIMPLEMENTED))
+    }
+
+    async fn slash(
+        &self,
+        _request: Request<SlashRequest>,
+    ) -> Result<Response<SlashResponse>, Status> {
+        Err(Status::unimplemented(UNIMPLEMENTED))
+    }
+
+    async fn unbond_payout(
+        &self,
+        _request: Request<UnbondPayoutRequest>,
+    ) -> Result<Response<UnbondPayoutResponse>, Status> {
+        Err(Status::unimplemented(UNIMPLEMENTED))
+    }
+}
+
+/// A connection accepted on the server's Unix socket.
+pub struct UnixStream(net::UnixStream);
+
+impl From<net::UnixStream> for UnixStream {
+    fn from(stream: net::UnixStream) -> Self {
+        UnixStream(stream)
+    }
+}
+
+impl Connected for UnixStream {}
+
+impl AsyncRead for UnixStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for UnixStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::parse_grpc_timeout;
+
+    #[test]
+    fn should_parse_grpc_timeout() {
+        assert_eq!(parse_grpc_timeout("2H"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_grpc_timeout("3M"), Some(Duration::from_secs(180)));
+        assert_eq!(parse_grpc_timeout("5S"), Some(Duration::from_secs(5)));
+        assert_eq!(parse_grpc_timeout("100m"), Some(Duration::from_millis(100)));
+        assert_eq!(
+            parse_grpc_timeout("99999999u"),
+            Some(Duration::from_micros(99_999_999))
+        );
+        assert_eq!(parse_grpc_timeout("1n"), Some(Duration::from_nanos(1)));
+    }
+
+    #[test]
+    fn should_not_parse_invalid_grpc_timeout() {
+        assert_eq!(parse_grpc_timeout(""), None);
+        assert_eq!(parse_grpc_timeout("m"), None);
+        assert_eq!(parse_grpc_timeout("10"), None);
+        assert_eq!(parse_grpc_timeout("10x"), None);
+        assert_eq!(parse_grpc_timeout("-1S"), None);
+        assert_eq!(parse_grpc_timeout("+1S"), None);
+        assert_eq!(parse_grpc_timeout("123456789S"), None);
+    }
+}
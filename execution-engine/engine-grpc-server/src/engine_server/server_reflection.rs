@@ -0,0 +1,327 @@
+//! An implementation of the gRPC server reflection service, which lets clients such as `grpcurl`
+//! discover the services offered by the server and the messages they use.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use log::warn;
+use protobuf::{
+    descriptor::{DescriptorProto, EnumDescriptorProto, FileDescriptorProto},
+    Message,
+};
+use tokio::sync::mpsc;
+use tonic::{Request, Response, Status, Streaming};
+
+pub use super::server_reflection_server::ServerReflectionServer;
+use super::{
+    ipc,
+    reflection::{
+        self, ServerReflectionRequest, ServerReflectionRequest_oneof_message_request,
+        ServerReflectionResponse,
+    },
+    server_reflection_server::ServerReflection,
+    state, transforms,
+};
+
+const RESPONSE_CHANNEL_CAPACITY: usize = 16;
+const SERVICES: &[&str] = &[
+    "io.casperlabs.ipc.ExecutionEngineService",
+    "grpc.reflection.v1alpha.ServerReflection",
+];
+
+/// The file descriptors of the protos served, indexed by file name and by the symbols they define.
+struct Descriptors {
+    files: BTreeMap<String, &'static FileDescriptorProto>,
+    symbols: HashMap<String, String>,
+}
+
+impl Descriptors {
+    fn new(files: &[&'static FileDescriptorProto]) -> Self {
+        let mut descriptors = Descriptors {
+            files: BTreeMap::new(),
+            symbols: HashMap::new(),
+        };
+        for file in files {
+            descriptors.add_file(file);
+        }
+        descriptors
+    }
+
+    fn add_file(&mut self, file: &'static FileDescriptorProto) {
+        let file_name = file.get_name().to_string();
+        let package = file.get_package();
+        for message in file.get_message_type() {
+            self.add_message(&file_name, package, message);
+        }
+        for enum_type in file.get_enum_type() {
+            self.add_enum(&file_name, package, enum_type);
+        }
+        for service in file.get_service() {
+            let service_name = full_name(package, service.get_name());
+            for method in service.get_method() {
+                self.symbols.insert(
+                    full_name(&service_name, method.get_name()),
+                    file_name.clone(),
+                );
+            }
+            self.symbols.insert(service_name, file_name.clone());
+        }
+        self.files.insert(file_name, file);
+    }
+
+    fn add_message(&mut self, file_name: &str, scope: &str, message: &DescriptorProto) {
+        let message_name = full_name(scope, message.get_name());
+        for nested in message.get_nested_type() {
+            self.add_message(file_name, &message_name, nested);
+        }
+        for enum_type in message.get_enum_type() {
+            self.add_enum(file_name, &message_name, enum_type);
+        }
+        self.symbols.insert(message_name, file_name.to_string());
+    }
+
+    fn add_enum(&mut self, file_name: &str, scope: &str, enum_type: &EnumDescriptorProto) {
+        self.symbols.insert(
+            full_name(scope, enum_type.get_name()),
+            file_name.to_string(),
+        );
+    }
+
+    /// Returns the serialized descriptors of `file_name` and of all the files it transitively
+    /// imports, starting with `file_name` itself.
+    fn file_with_dependencies(&self, file_name: &str) -> Result<Vec<Vec<u8>>, Status> {
+        let mut pending = vec![file_name];
+        let mut visited: Vec<&str> = Vec::new();
+        while let Some(name) = pending.pop() {
+            if visited.contains(&name) {
+                continue;
+            }
+            let file = self
+                .files
+                .get(name)
+                .ok_or_else(|| Status::not_found(format!("file not found: {}", name)))?;
+            visited.push(name);
+            pending.extend(file.get_dependency().iter().rev().map(String::as_str));
+        }
+        visited
+            .into_iter()
+            .map(|name| {
+                self.files[name]
+                    .write_to_bytes()
+                    .map_err(|error| Status::internal(error.to_string()))
+            })
+            .collect()
+    }
+
+    fn file_containing_symbol(&self, symbol: &str) -> Result<Vec<Vec<u8>>, Status> {
+        let file_name = self
+            .symbols
+            .get(symbol)
+            .ok_or_else(|| Status::not_found(format!("symbol not found: {}", symbol)))?;
+        self.file_with_dependencies(file_name)
+    }
+
+    fn respond(&self, request: ServerReflectionRequest) -> ServerReflectionResponse {
+        let mut response = ServerReflectionResponse::new();
+        response.set_valid_host(request.get_host().to_string());
+
+        let result = match &request.message_request {
+            Some(ServerReflectionRequest_oneof_message_request::file_by_filename(file_name)) => {
+                self.file_with_dependencies(file_name).map(|files| {
+                    response
+                        .mut_file_descriptor_response()
+                        .set_file_descriptor_proto(files.into())
+                })
+            }
+            Some(ServerReflectionRequest_oneof_message_request::file_containing_symbol(symbol)) => {
+                self.file_containing_symbol(symbol).map(|files| {
+                    response
+                        .mut_file_descriptor_response()
+                        .set_file_descriptor_proto(files.into())
+                })
+            }
+            Some(ServerReflectionRequest_oneof_message_request::file_containing_extension(
+                extension,
+            )) => Err(Status::not_found(format!(
+                "extension not found: {}({})",
+                extension.get_containing_type(),
+                extension.get_extension_number()
+            ))),
+            Some(ServerReflectionRequest_oneof_message_request::all_extension_numbers_of_type(
+                type_name,
+            )) => {
+                if self.symbols.contains_key(type_name.as_str()) {
+                    // None of the served protos define any extensions.
+                    response
+                        .mut_all_extension_numbers_response()
+                        .set_base_type_name(type_name.clone());
+                    Ok(())
+                } else {
+                    Err(Status::not_found(format!("type not found: {}", type_name)))
+                }
+            }
+            Some(ServerReflectionRequest_oneof_message_request::list_services(_)) => {
+                let services = SERVICES
+                    .iter()
+                    .map(|name| {
+                        let mut service = reflection::ServiceResponse::new();
+                        service.set_name(name.to_string());
+                        service
+                    })
+                    .collect();
+                response.mut_list_services_response().set_service(services);
+                Ok(())
+            }
+            None => Err(Status::invalid_argument("no message request set")),
+        };
+
+        if let Err(status) = result {
+            warn!("server reflection request failed: {}", status.message());
+            let error_response = response.mut_error_response();
+            error_response.set_error_code(status.code() as i32);
+            error_response.set_error_message(status.message().to_string());
+        }
+        response.set_original_request(request);
+        response
+    }
+}
+
+fn full_name(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", scope, name)
+    }
+}
+
+/// Serves the gRPC server reflection service for the protos of the execution engine's services.
+pub struct ServerReflectionService {
+    descriptors: Arc<Descriptors>,
+}
+
+impl ServerReflectionService {
+    pub fn new() -> Self {
+        let descriptors = Descriptors::new(&[
+            state::file_descriptor_proto(),
+            transforms::file_descriptor_proto(),
+            ipc::file_descriptor_proto(),
+            reflection::file_descriptor_proto(),
+        ]);
+        ServerReflectionService {
+            descriptors: Arc::new(descriptors),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl ServerReflection for ServerReflectionService {
+    type ServerReflectionInfoStream = mpsc::Receiver<Result<ServerReflectionResponse, Status>>;
+
+    async fn server_reflection_info(
+        &self,
+        request: Request<Streaming<ServerReflectionRequest>>,
+    ) -> Result<Response<Self::ServerReflectionInfoStream>, Status> {
+        let mut requests = request.into_inner();
+        let (mut sender, receiver) = mpsc::channel(RESPONSE_CHANNEL_CAPACITY);
+        let descriptors = Arc::clone(&self.descriptors);
+
+        tokio::spawn(async move {
+            loop {
+                let response = match requests.message().await {
+                    Ok(Some(request)) => Ok(descriptors.respond(request)),
+                    Ok(None) => break,
+                    Err(status) => Err(status),
+                };
+                let is_err = response.is_err();
+                if sender.send(response).await.is_err() || is_err {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(receiver))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protobuf::descriptor::FileDescriptorProto;
+    use tonic::Code;
+
+    use super::*;
+
+    fn service() -> ServerReflectionService {
+        ServerReflectionService::new()
+    }
+
+    fn file_names(response: &ServerReflectionResponse) -> Vec<String> {
+        response
+            .get_file_descriptor_response()
+            .get_file_descriptor_proto()
+            .iter()
+            .map(|bytes| {
+                protobuf::parse_from_bytes::<FileDescriptorProto>(bytes)
+                    .unwrap()
+                    .get_name()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_list_services() {
+        let mut request = ServerReflectionRequest::new();
+        request.set_list_services(String::new());
+        let response = service().descriptors.respond(request);
+
+        let services: Vec<&str> = response
+            .get_list_services_response()
+            .get_service()
+            .iter()
+            .map(|service| service.get_name())
+            .collect();
+        assert_eq!(services, SERVICES);
+    }
+
+    #[test]
+    fn should_return_file_containing_symbol_with_dependencies() {
+        let mut request = ServerReflectionRequest::new();
+        request.set_file_containing_symbol("io.casperlabs.ipc.ExecutionEngineService".to_string());
+        let response = service().descriptors.respond(request.clone());
+
+        assert_eq!(
+            file_names(&response),
+            vec![
+                "io/casperlabs/ipc/ipc.proto",
+                "io/casperlabs/casper/consensus/state.proto",
+                "io/casperlabs/ipc/transforms.proto",
+            ]
+        );
+        assert_eq!(response.get_original_request(), &request);
+
+        let mut request = ServerReflectionRequest::new();
+        request.set_file_containing_symbol(
+            "io.casperlabs.casper.consensus.state.Key.Address".to_string(),
+        );
+        let response = service().descriptors.respond(request);
+        assert_eq!(
+            file_names(&response),
+            vec!["io/casperlabs/casper/consensus/state.proto"]
+        );
+    }
+
+    #[test]
+    fn should_return_not_found_for_unknown_symbol() {
+        let mut request = ServerReflectionRequest::new();
+        request.set_file_containing_symbol("io.casperlabs.ipc.Missing".to_string());
+        let response = service().descriptors.respond(request);
+
+        assert!(!response.has_file_descriptor_response());
+        assert_eq!(
+            response.get_error_response().get_error_code(),
+            Code::NotFound as i32
+        );
+    }
+}
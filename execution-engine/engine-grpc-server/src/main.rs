@@ -1,39 +1,50 @@
 use std::{
-    collections::BTreeMap,
-    fs,
-    path::PathBuf,
-    str::FromStr,
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
-    },
-    time::Duration,
+    collections::BTreeMap, convert::TryFrom, fs, path::PathBuf, process, str::FromStr, sync::Arc,
 };
 
-use clap::{App, Arg, ArgMatches};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 use dirs::home_dir;
 use engine_core::engine_state::{EngineConfig, EngineState};
 use lmdb::DatabaseFlags;
 use log::{error, info, Level, LevelFilter};
+use tokio::{runtime, signal};
 
 use engine_shared::{
     logging::{self, Settings, Style},
+    newtypes::{Blake2bHash, CorrelationId},
     os::get_page_size,
     socket,
+    stored_value::StoredValue,
 };
 use engine_storage::{
-    global_state::lmdb::LmdbGlobalState, transaction_source::lmdb::LmdbEnvironment,
-    trie_store::lmdb::LmdbTrieStore,
+    error,
+    global_state::{lmdb::LmdbGlobalState, sled::SledGlobalState},
+    transaction_source::{
+        lmdb::LmdbEnvironment, sled::SledEnvironment, Transaction, TransactionSource,
+    },
+    trie_store::{
+        cache::TrieCache,
+        lmdb::LmdbTrieStore,
+        sled::SledTrieStore,
+        verify::{self, VerificationReport},
+        TrieStore,
+    },
 };
+use types::{bytesrepr, Key};
 
 use casperlabs_engine_grpc_server::engine_server;
-use engine_storage::protocol_data_store::lmdb::LmdbProtocolDataStore;
+use engine_storage::protocol_data_store::{
+    lmdb::LmdbProtocolDataStore, sled::SledProtocolDataStore,
+};
 
 // exe / proc
 const PROC_NAME: &str = "casperlabs-engine-grpc-server";
 const APP_NAME: &str = "CasperLabs Execution Engine Server";
 const SERVER_LISTENING_TEMPLATE: &str = "{listener} is listening on socket: {socket}";
 const SERVER_START_EXPECT: &str = "failed to start Execution Engine Server";
+const RUNTIME_EXPECT: &str = "failed to start async runtime";
+// Threads driving the server's async IO, in addition to those executing requests.
+const ASYNC_CORE_THREADS: usize = 1;
 
 // data-dir / lmdb
 const ARG_DATA_DIR: &str = "data-dir";
@@ -48,6 +59,16 @@ const LMDB_ENVIRONMENT_EXPECT: &str = "Could not create LmdbEnvironment";
 const LMDB_TRIE_STORE_EXPECT: &str = "Could not create LmdbTrieStore";
 const LMDB_PROTOCOL_DATA_STORE_EXPECT: &str = "Could not create LmdbProtocolDataStore";
 const LMDB_GLOBAL_STATE_EXPECT: &str = "Could not create LmdbGlobalState";
+const SLED_ENVIRONMENT_EXPECT: &str = "Could not create SledEnvironment";
+const SLED_GLOBAL_STATE_EXPECT: &str = "Could not create SledGlobalState";
+
+// storage backend
+const ARG_STORAGE_BACKEND: &str = "storage-backend";
+const ARG_STORAGE_BACKEND_VALUE: &str = "BACKEND";
+const ARG_STORAGE_BACKEND_HELP: &str =
+    "Sets the global state storage backend.  The sled backend grows on demand and ignores --pages";
+const STORAGE_BACKEND_LMDB: &str = "lmdb";
+const STORAGE_BACKEND_SLED: &str = "sled";
 
 // pages / lmdb
 const ARG_PAGES: &str = "pages";
@@ -88,12 +109,22 @@ const ARG_LOG_STYLE_HELP: &str = "Sets logging style to structured or human-read
 const LOG_STYLE_STRUCTURED: &str = "structured";
 const LOG_STYLE_HUMAN_READABLE: &str = "human";
 
+// trie cache size
+const ARG_TRIE_CACHE_SIZE: &str = "trie-cache-size";
+// 64 MiB
+const ARG_TRIE_CACHE_SIZE_DEFAULT: &str = "67108864";
+const ARG_TRIE_CACHE_SIZE_VALUE: &str = "BYTES";
+const ARG_TRIE_CACHE_SIZE_HELP: &str =
+    "Sets the max size in bytes of trie nodes to cache between requests.  0 disables the cache";
+const ARG_TRIE_CACHE_SIZE_EXPECT: &str = "expected valid trie cache size";
+
 // thread count
 const ARG_THREAD_COUNT: &str = "threads";
 const ARG_THREAD_COUNT_SHORT: &str = "t";
 const ARG_THREAD_COUNT_DEFAULT: &str = "1";
 const ARG_THREAD_COUNT_VALUE: &str = "NUM";
-const ARG_THREAD_COUNT_HELP: &str = "Worker thread count";
+const ARG_THREAD_COUNT_HELP: &str =
+    "Worker thread count, i.e. the max number of requests executed concurrently";
 const ARG_THREAD_COUNT_EXPECT: &str = "expected valid thread count";
 
 // use system contracts
@@ -107,9 +138,18 @@ const ARG_ENABLE_BONDING: &str = "enable-bonding";
 const ARG_ENABLE_BONDING_SHORT: &str = "b";
 const ARG_ENABLE_BONDING_HELP: &str = "Enable bonding";
 
+// verify
+const CMD_VERIFY: &str = "verify";
+const CMD_VERIFY_ABOUT: &str =
+    "Verifies the integrity of the global state under the given state hashes, then exits";
+const ARG_STATE_HASH: &str = "state-hash";
+const ARG_STATE_HASH_VALUE: &str = "STATE_HASH";
+const ARG_STATE_HASH_HELP: &str = "Hex-encoded state hash to verify";
+const ARG_STATE_HASH_EXPECT: &str = "expected valid state hash";
+const VERIFY_EXPECT: &str = "Could not read global state";
+
 // runnable
 const SIGINT_HANDLE_EXPECT: &str = "Error setting Ctrl-C handler";
-const RUNNABLE_CHECK_INTERVAL_SECONDS: u64 = 3;
 
 fn main() {
     set_panic_hook();
@@ -118,6 +158,11 @@ fn main() {
 
     let _ = logging::initialize(get_log_settings(&arg_matches));
 
+    if let Some(verify_matches) = arg_matches.subcommand_matches(CMD_VERIFY) {
+        let all_ok = verify_global_state(&arg_matches, verify_matches);
+        process::exit(if all_ok { 0 } else { 1 });
+    }
+
     info!("starting Execution Engine Server");
 
     let socket = get_socket(&arg_matches);
@@ -133,19 +178,23 @@ fn main() {
 
     let thread_count = get_thread_count(&arg_matches);
 
-    let engine_config: EngineConfig = get_engine_config(&arg_matches);
-
-    let _server = get_grpc_server(&socket, data_dir, map_size, thread_count, engine_config);
-
-    log_listening_message(&socket);
+    let trie_cache = get_trie_cache(&arg_matches);
 
-    let interval = Duration::from_secs(RUNNABLE_CHECK_INTERVAL_SECONDS);
-
-    let runnable = get_sigint_handle();
+    let engine_config: EngineConfig = get_engine_config(&arg_matches);
 
-    while runnable.load(Ordering::SeqCst) {
-        std::thread::park_timeout(interval);
-    }
+    let storage_backend = arg_matches
+        .value_of(ARG_STORAGE_BACKEND)
+        .expect("should have default value if not explicitly set");
+
+    run_grpc_server(
+        &socket,
+        data_dir,
+        map_size,
+        thread_count,
+        engine_config,
+        storage_backend,
+        trie_cache,
+    );
 
     info!("stopping Execution Engine Server");
 }
@@ -169,6 +218,7 @@ fn set_panic_hook() {
 fn get_args() -> ArgMatches<'static> {
     App::new(APP_NAME)
         .version(env!("CARGO_PKG_VERSION"))
+        .setting(AppSettings::SubcommandsNegateReqs)
         .arg(
             Arg::with_name(ARG_LOG_LEVEL)
                 .required(false)
@@ -218,6 +268,26 @@ fn get_args() -> ArgMatches<'static> {
                 .help(ARG_PAGES_HELP)
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name(ARG_STORAGE_BACKEND)
+                .required(false)
+                .long(ARG_STORAGE_BACKEND)
+                .takes_value(true)
+                .possible_value(STORAGE_BACKEND_LMDB)
+                .possible_value(STORAGE_BACKEND_SLED)
+                .default_value(STORAGE_BACKEND_LMDB)
+                .value_name(ARG_STORAGE_BACKEND_VALUE)
+                .help(ARG_STORAGE_BACKEND_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_TRIE_CACHE_SIZE)
+                .required(false)
+                .long(ARG_TRIE_CACHE_SIZE)
+                .takes_value(true)
+                .default_value(ARG_TRIE_CACHE_SIZE_DEFAULT)
+                .value_name(ARG_TRIE_CACHE_SIZE_VALUE)
+                .help(ARG_TRIE_CACHE_SIZE_HELP),
+        )
         .arg(
             Arg::with_name(ARG_THREAD_COUNT)
                 .short(ARG_THREAD_COUNT_SHORT)
@@ -245,18 +315,24 @@ fn get_args() -> ArgMatches<'static> {
                 .help(ARG_SOCKET_HELP)
                 .index(1),
         )
+        .subcommand(
+            SubCommand::with_name(CMD_VERIFY)
+                .about(CMD_VERIFY_ABOUT)
+                .arg(
+                    Arg::with_name(ARG_STATE_HASH)
+                        .required(true)
+                        .multiple(true)
+                        .value_name(ARG_STATE_HASH_VALUE)
+                        .help(ARG_STATE_HASH_HELP)
+                        .index(1),
+                ),
+        )
         .get_matches()
 }
 
-/// Gets SIGINT handle to allow clean exit
-fn get_sigint_handle() -> Arc<AtomicBool> {
-    let handle = Arc::new(AtomicBool::new(true));
-    let h = handle.clone();
-    ctrlc::set_handler(move || {
-        h.store(false, Ordering::SeqCst);
-    })
-    .expect(SIGINT_HANDLE_EXPECT);
-    handle
+/// Completes on SIGINT to allow clean exit
+async fn sigint() {
+    signal::ctrl_c().await.expect(SIGINT_HANDLE_EXPECT)
 }
 
 /// Gets value of socket argument
@@ -291,6 +367,20 @@ fn get_map_size(arg_matches: &ArgMatches) -> usize {
     page_size * pages
 }
 
+/// Returns the cache of decoded trie nodes shared between requests, if enabled.
+fn get_trie_cache(arg_matches: &ArgMatches) -> Option<Arc<TrieCache>> {
+    let capacity: usize = arg_matches
+        .value_of(ARG_TRIE_CACHE_SIZE)
+        .map(str::parse)
+        .expect(ARG_TRIE_CACHE_SIZE_EXPECT)
+        .expect(ARG_TRIE_CACHE_SIZE_EXPECT);
+    if capacity == 0 {
+        None
+    } else {
+        Some(Arc::new(TrieCache::new(capacity)))
+    }
+}
+
 fn get_thread_count(arg_matches: &ArgMatches) -> usize {
     arg_matches
         .value_of(ARG_THREAD_COUNT)
@@ -309,26 +399,57 @@ fn get_engine_config(arg_matches: &ArgMatches) -> EngineConfig {
         .with_enable_bonding(enable_bonding)
 }
 
-/// Builds and returns a gRPC server.
-fn get_grpc_server(
+/// Runs a gRPC server on the socket until SIGINT is received.
+///
+/// Requests are executed on a pool of `thread_count` threads.
+fn run_grpc_server(
     socket: &socket::Socket,
     data_dir: PathBuf,
     map_size: usize,
     thread_count: usize,
     engine_config: EngineConfig,
-) -> grpc::Server {
-    let engine_state = get_engine_state(data_dir, map_size, engine_config);
-
-    engine_server::new(socket.as_str(), thread_count, engine_state)
+    storage_backend: &str,
+    trie_cache: Option<Arc<TrieCache>>,
+) {
+    let mut runtime = runtime::Builder::new()
+        .threaded_scheduler()
+        .core_threads(ASYNC_CORE_THREADS)
+        .max_threads(ASYNC_CORE_THREADS + thread_count)
+        .enable_all()
         .build()
-        .expect(SERVER_START_EXPECT)
+        .expect(RUNTIME_EXPECT);
+
+    let result = match storage_backend {
+        STORAGE_BACKEND_LMDB => {
+            let engine_state = get_lmdb_engine_state(data_dir, map_size, engine_config, trie_cache);
+            log_listening_message(socket);
+            runtime.block_on(engine_server::serve(
+                socket.as_str(),
+                engine_state,
+                sigint(),
+            ))
+        }
+        STORAGE_BACKEND_SLED => {
+            let engine_state = get_sled_engine_state(data_dir, engine_config, trie_cache);
+            log_listening_message(socket);
+            runtime.block_on(engine_server::serve(
+                socket.as_str(),
+                engine_state,
+                sigint(),
+            ))
+        }
+        _ => unreachable!("should validate storage-backend arg to match one of the options"),
+    };
+
+    result.expect(SERVER_START_EXPECT)
 }
 
-/// Builds and returns engine global state
-fn get_engine_state(
+/// Builds and returns engine global state backed by LMDB
+fn get_lmdb_engine_state(
     data_dir: PathBuf,
     map_size: usize,
     engine_config: EngineConfig,
+    trie_cache: Option<Arc<TrieCache>>,
 ) -> EngineState<LmdbGlobalState> {
     let environment = {
         let ret = LmdbEnvironment::new(&data_dir, map_size).expect(LMDB_ENVIRONMENT_EXPECT);
@@ -338,7 +459,10 @@ fn get_engine_state(
     let trie_store = {
         let ret = LmdbTrieStore::new(&environment, None, DatabaseFlags::empty())
             .expect(LMDB_TRIE_STORE_EXPECT);
-        Arc::new(ret)
+        match trie_cache {
+            Some(cache) => Arc::new(ret.with_cache(cache)),
+            None => Arc::new(ret),
+        }
     };
 
     let protocol_data_store = {
@@ -353,6 +477,105 @@ fn get_engine_state(
     EngineState::new(global_state, engine_config)
 }
 
+/// Builds and returns engine global state backed by sled
+fn get_sled_engine_state(
+    data_dir: PathBuf,
+    engine_config: EngineConfig,
+    trie_cache: Option<Arc<TrieCache>>,
+) -> EngineState<SledGlobalState> {
+    let environment = {
+        let ret = SledEnvironment::new(&data_dir).expect(SLED_ENVIRONMENT_EXPECT);
+        Arc::new(ret)
+    };
+
+    let trie_store = {
+        let ret = SledTrieStore::new(&environment, None);
+        match trie_cache {
+            Some(cache) => Arc::new(ret.with_cache(cache)),
+            None => Arc::new(ret),
+        }
+    };
+
+    let protocol_data_store = Arc::new(SledProtocolDataStore::new(&environment, None));
+
+    let global_state = SledGlobalState::empty(environment, trie_store, protocol_data_store)
+        .expect(SLED_GLOBAL_STATE_EXPECT);
+
+    EngineState::new(global_state, engine_config)
+}
+
+/// Verifies the tries under each state hash passed to the verify subcommand and prints a report for
+/// each.  Returns `true` if no issues were found.
+fn verify_global_state(arg_matches: &ArgMatches, verify_matches: &ArgMatches) -> bool {
+    let state_hashes: Vec<Blake2bHash> = verify_matches
+        .values_of(ARG_STATE_HASH)
+        .expect(ARG_STATE_HASH_EXPECT)
+        .map(parse_state_hash)
+        .collect();
+
+    let data_dir = get_data_dir(arg_matches);
+
+    let reports = match arg_matches
+        .value_of(ARG_STORAGE_BACKEND)
+        .expect("should have default value if not explicitly set")
+    {
+        STORAGE_BACKEND_LMDB => {
+            let environment =
+                LmdbEnvironment::open_read_only(&data_dir).expect(LMDB_ENVIRONMENT_EXPECT);
+            let trie_store = LmdbTrieStore::open(&environment, None).expect(LMDB_TRIE_STORE_EXPECT);
+            verify_tries::<_, _, error::Error>(&environment, &trie_store, &state_hashes)
+                .expect(VERIFY_EXPECT)
+        }
+        STORAGE_BACKEND_SLED => {
+            let environment = SledEnvironment::new(&data_dir).expect(SLED_ENVIRONMENT_EXPECT);
+            let trie_store = SledTrieStore::new(&environment, None);
+            verify_tries::<_, _, error::sled::Error>(&environment, &trie_store, &state_hashes)
+                .expect(VERIFY_EXPECT)
+        }
+        _ => unreachable!("should validate storage-backend arg to match one of the options"),
+    };
+
+    for report in &reports {
+        println!("{}", report);
+    }
+    reports.iter().all(VerificationReport::is_ok)
+}
+
+/// Verifies the tries under `state_hashes` within a single read transaction.
+fn verify_tries<'a, R, S, E>(
+    environment: &'a R,
+    trie_store: &S,
+    state_hashes: &[Blake2bHash],
+) -> Result<Vec<VerificationReport>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    E: From<R::Error> + From<bytesrepr::Error>,
+{
+    let txn = environment.create_read_txn()?;
+    let reports = state_hashes
+        .iter()
+        .map(|state_hash| {
+            verify::verify::<Key, StoredValue, _, _, E>(
+                CorrelationId::new(),
+                &txn,
+                trie_store,
+                state_hash,
+            )
+        })
+        .collect::<Result<Vec<_>, E>>()?;
+    txn.commit()?;
+    Ok(reports)
+}
+
+/// Parses a hex-encoded state hash
+fn parse_state_hash(input: &str) -> Blake2bHash {
+    base16::decode(input)
+        .ok()
+        .and_then(|bytes| Blake2bHash::try_from(bytes.as_slice()).ok())
+        .unwrap_or_else(|| panic!("{}: {:?}", ARG_STATE_HASH_EXPECT, input))
+}
+
 /// Builds and returns log settings
 fn get_log_settings(arg_matches: &ArgMatches) -> Settings {
     let max_level = match arg_matches
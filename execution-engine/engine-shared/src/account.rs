@@ -5,8 +5,8 @@ use std::collections::BTreeSet;
 
 use types::{
     account::{
-        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        AccountHash, ActionType, AddKeyFailure, ManageAccountFailure, RemoveKeyFailure,
+        SetThresholdFailure, UpdateKeyFailure, Weight,
     },
     bytesrepr::{self, Error, FromBytes, ToBytes},
     contracts::NamedKeys,
@@ -157,6 +157,32 @@ impl Account {
         self.associated_keys.get(&account_hash)
     }
 
+    /// Replaces the whole set of associated keys, provided the total weight of the new set would
+    /// still satisfy all of the action thresholds.
+    pub fn set_associated_keys(
+        &mut self,
+        associated_keys: AssociatedKeys,
+    ) -> Result<(), ManageAccountFailure> {
+        if associated_keys.is_empty() {
+            return Err(ManageAccountFailure::EmptyKeys);
+        }
+
+        let total_weight = associated_keys.total_keys_weight();
+        if total_weight < *self.action_thresholds().deployment()
+            || total_weight < *self.action_thresholds().key_management()
+        {
+            return Err(ManageAccountFailure::ThresholdViolation);
+        }
+
+        self.associated_keys = associated_keys;
+        Ok(())
+    }
+
+    /// Sets the main purse of the account.
+    pub fn set_main_purse(&mut self, main_purse: URef) {
+        self.main_purse = main_purse;
+    }
+
     pub fn set_action_threshold(
         &mut self,
         action_type: ActionType,
@@ -302,8 +328,8 @@ mod tests {
 
     use types::{
         account::{
-            AccountHash, ActionType, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure,
-            Weight,
+            AccountHash, ActionType, ManageAccountFailure, RemoveKeyFailure, SetThresholdFailure,
+            UpdateKeyFailure, Weight,
         },
         AccessRights, URef,
     };
@@ -651,4 +677,69 @@ mod tests {
             .update_associated_key(key_1, Weight::new(1))
             .expect("should work");
     }
+
+    #[test]
+    fn should_replace_associated_keys() {
+        let identity_key = AccountHash::new([1; 32]);
+        let key_1 = AccountHash::new([2u8; 32]);
+        let key_2 = AccountHash::new([3u8; 32]);
+
+        let mut account = Account::new(
+            identity_key,
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            AssociatedKeys::new(identity_key, Weight::new(3)),
+            ActionThresholds::new(Weight::new(2), Weight::new(3))
+                .expect("should create thresholds"),
+        );
+
+        let new_keys = {
+            let mut res = AssociatedKeys::new(key_1, Weight::new(2));
+            res.add_key(key_2, Weight::new(1))
+                .expect("should add key 2");
+            res
+        };
+
+        account
+            .set_associated_keys(new_keys.clone())
+            .expect("should replace keys");
+
+        assert_eq!(account.get_associated_key_weight(identity_key), None);
+        assert_eq!(
+            account.get_associated_keys().collect::<Vec<_>>(),
+            new_keys.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn should_not_replace_associated_keys_below_thresholds() {
+        let identity_key = AccountHash::new([1; 32]);
+        let key_1 = AccountHash::new([2u8; 32]);
+
+        let mut account = Account::new(
+            identity_key,
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            AssociatedKeys::new(identity_key, Weight::new(3)),
+            ActionThresholds::new(Weight::new(2), Weight::new(3))
+                .expect("should create thresholds"),
+        );
+
+        assert_eq!(
+            account
+                .set_associated_keys(AssociatedKeys::new(key_1, Weight::new(2)))
+                .unwrap_err(),
+            ManageAccountFailure::ThresholdViolation
+        );
+        assert_eq!(
+            account
+                .set_associated_keys(AssociatedKeys::default())
+                .unwrap_err(),
+            ManageAccountFailure::EmptyKeys
+        );
+        assert_eq!(
+            account.get_associated_key_weight(identity_key),
+            Some(&Weight::new(3))
+        );
+    }
 }
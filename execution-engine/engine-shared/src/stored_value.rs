@@ -14,7 +14,9 @@ enum Tag {
     Account = 1,
     ContractWasm = 2,
     Contract = 3,
-    ContractPackage = 4,
+    /// A contract package in the layout without version metadata and the `locked` flag.
+    LegacyContractPackage = 4,
+    ContractPackage = 5,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -194,6 +196,12 @@ impl FromBytes for StoredValue {
                     (StoredValue::ContractPackage(contract_package), remainder)
                 })
             }
+            tag if tag == Tag::LegacyContractPackage as u8 => ContractPackage::from_legacy_bytes(
+                remainder,
+            )
+            .map(|(contract_package, remainder)| {
+                (StoredValue::ContractPackage(contract_package), remainder)
+            }),
             tag if tag == Tag::Contract as u8 => Contract::from_bytes(remainder)
                 .map(|(contract, remainder)| (StoredValue::Contract(contract), remainder)),
             _ => Err(bytesrepr::Error::Formatting),
@@ -233,4 +241,18 @@ mod tests {
             bytesrepr::test_serialization_roundtrip(&v);
         }
     }
+
+    #[test]
+    fn should_deserialize_legacy_contract_package() {
+        let contract_package = ContractPackage::default();
+
+        let mut bytes = vec![Tag::LegacyContractPackage as u8];
+        bytes.append(&mut contract_package.access_key().to_bytes().unwrap());
+        bytes.append(&mut contract_package.versions().to_bytes().unwrap());
+        bytes.append(&mut contract_package.disabled_versions().to_bytes().unwrap());
+        bytes.append(&mut contract_package.groups().to_bytes().unwrap());
+
+        let stored_value: StoredValue = bytesrepr::deserialize(bytes).unwrap();
+        assert_eq!(stored_value, StoredValue::ContractPackage(contract_package));
+    }
 }
@@ -60,6 +60,8 @@ pub enum Transform {
     AddUInt512(U512),
     AddKeys(NamedKeys),
     Failure(Error),
+    /// Removes the value from global state.
+    Prune,
 }
 
 macro_rules! from_try_from_impl {
@@ -136,6 +138,10 @@ where
 }
 
 impl Transform {
+    /// Applies the transform to `stored_value`, returning the updated value.
+    ///
+    /// `Prune` removes the value rather than producing a new one, so it has to be handled by the
+    /// caller and yields a `TypeMismatch` error here.
     pub fn apply(self, stored_value: StoredValue) -> Result<StoredValue, Error> {
         match self {
             Transform::Identity => Ok(stored_value),
@@ -171,6 +177,11 @@ impl Transform {
                 }
             },
             Transform::Failure(error) => Err(error),
+            Transform::Prune => {
+                let expected = "a transform yielding a value".to_string();
+                let found = "Prune".to_string();
+                Err(TypeMismatch::new(expected, found).into())
+            }
         }
     }
 }
@@ -217,6 +228,11 @@ impl Add for Transform {
             (a @ Transform::Failure(_), _) => a,
             (_, b @ Transform::Failure(_)) => b,
             (_, b @ Transform::Write(_)) => b,
+            (_, b @ Transform::Prune) => b,
+            // Nothing but a write can follow a prune, as there is no value left to modify.
+            (Transform::Prune, b) => Transform::Failure(
+                TypeMismatch::new("Write or Prune".to_owned(), format!("{:?}", b)).into(),
+            ),
             (Transform::Write(v), b) => {
                 // second transform changes value being written
                 match b.apply(v) {
@@ -287,6 +303,7 @@ pub mod gens {
     pub fn transform_arb() -> impl Strategy<Value = Transform> {
         prop_oneof![
             Just(Transform::Identity),
+            Just(Transform::Prune),
             stored_value_arb().prop_map(Transform::Write),
             any::<i32>().prop_map(Transform::AddInt32),
             any::<u64>().prop_map(Transform::AddUInt64),
@@ -746,4 +763,29 @@ mod tests {
         assert_eq!(ZERO_U512, add(MAX_U512, ONE_U512));
         assert_eq!(MAX_U512 - 1, add(MAX_U512, MAX_U512));
     }
+
+    #[test]
+    fn prune_should_combine_with_other_transforms() {
+        let value = StoredValue::CLValue(CLValue::from_t(ONE_I32).unwrap());
+
+        assert_eq!(
+            Transform::Write(value.clone()) + Transform::Prune,
+            Transform::Prune
+        );
+        assert_eq!(
+            Transform::AddInt32(ONE_I32) + Transform::Prune,
+            Transform::Prune
+        );
+        assert_eq!(Transform::Prune + Transform::Identity, Transform::Prune);
+        assert_eq!(
+            Transform::Prune + Transform::Write(value.clone()),
+            Transform::Write(value.clone())
+        );
+
+        match Transform::Prune + Transform::AddInt32(ONE_I32) {
+            Transform::Failure(Error::TypeMismatch(_)) => (),
+            other => panic!("expected type mismatch, got {:?}", other),
+        }
+        assert!(Transform::Prune.apply(value).is_err());
+    }
 }
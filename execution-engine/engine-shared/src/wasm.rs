@@ -1,6 +1,4 @@
-use parity_wasm::elements::Module;
-
-use engine_wasm_prep::{PreprocessingError, Preprocessor};
+use engine_wasm_prep::{InstrumentedModule, PreprocessingError, Preprocessor};
 
 static DO_NOTHING: &str = r#"
     (module
@@ -21,7 +19,9 @@ pub fn do_nothing_bytes() -> Vec<u8> {
     wabt::wat2wasm(DO_NOTHING).expect("failed to parse wat")
 }
 
-pub fn do_nothing_module(preprocessor: &Preprocessor) -> Result<Module, PreprocessingError> {
+pub fn do_nothing_module(
+    preprocessor: &Preprocessor,
+) -> Result<InstrumentedModule, PreprocessingError> {
     let do_nothing_bytes = do_nothing_bytes();
     preprocessor.preprocess(&do_nothing_bytes)
 }
@@ -0,0 +1,327 @@
+//! Offline inspection of the global state stored by the execution engine.
+//!
+//! The LMDB environment is opened read-only, so this can be run against the data directory of a
+//! running `casperlabs-engine-grpc-server`.
+mod parse;
+mod stats;
+
+use std::{path::PathBuf, process, sync::Arc};
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use lmdb::{Cursor, Transaction as _};
+
+use engine_core::engine_state::{
+    query::{QueryRequest, QueryResult},
+    EngineConfig, EngineState,
+};
+use engine_shared::{
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+};
+use engine_storage::{
+    error,
+    global_state::lmdb::LmdbGlobalState,
+    protocol_data::ProtocolData,
+    protocol_data_store::lmdb::LmdbProtocolDataStore,
+    store::Store,
+    transaction_source::{lmdb::LmdbEnvironment, TransactionSource},
+    trie::Trie,
+    trie_store::{
+        lmdb::LmdbTrieStore,
+        operations::{self, ReadResult},
+    },
+};
+use types::{bytesrepr::FromBytes, Key, ProtocolVersion};
+
+use stats::TrieStats;
+
+const APP_NAME: &str = "Execution Engine State Inspector";
+
+// data directory
+const ARG_DATA_DIR: &str = "data-dir";
+const ARG_DATA_DIR_SHORT: &str = "d";
+const ARG_DATA_DIR_VALUE: &str = "DIR";
+const ARG_DATA_DIR_HELP: &str =
+    "Path to the data directory of the execution engine, which contains the global_state directory";
+const GLOBAL_STATE_DIR: &str = "global_state";
+
+// common positional arguments
+const ARG_STATE_HASH: &str = "state-hash";
+const ARG_STATE_HASH_HELP: &str = "Hex-encoded state root hash";
+const ARG_KEY: &str = "key";
+const ARG_KEY_HELP: &str =
+    "Key formatted as account-account_hash-<hex>, hash-<hex> or uref-<hex>[-<octal access rights>]";
+
+// query
+const CMD_QUERY: &str = "query";
+const CMD_QUERY_ABOUT: &str = "Prints the value under a key, optionally following a path of names";
+const ARG_PATH: &str = "path";
+const ARG_PATH_HELP: &str = "Names to follow through the named keys of accounts and contracts";
+
+// named-keys
+const CMD_NAMED_KEYS: &str = "named-keys";
+const CMD_NAMED_KEYS_ABOUT: &str = "Lists the named keys of an account or contract";
+
+// dump
+const CMD_DUMP: &str = "dump";
+const CMD_DUMP_ABOUT: &str = "Prints the keys and values under a state root";
+const ARG_PREFIX: &str = "prefix";
+const ARG_PREFIX_VALUE: &str = "HEX";
+const ARG_PREFIX_HELP: &str =
+    "Only print keys whose serialized form starts with these hex-encoded bytes, e.g. 00 for accounts";
+const ARG_KEYS_ONLY: &str = "keys-only";
+const ARG_KEYS_ONLY_HELP: &str = "Print keys without their values";
+
+// protocol-data
+const CMD_PROTOCOL_DATA: &str = "protocol-data";
+const CMD_PROTOCOL_DATA_ABOUT: &str = "Prints the protocol data stored for each protocol version";
+
+// stats
+const CMD_STATS: &str = "stats";
+const CMD_STATS_ABOUT: &str = "Prints counts of the trie nodes under a state root";
+
+// expects
+const DEFAULT_VALUE_EXPECT: &str = "should have default value";
+const REQUIRED_ARG_EXPECT: &str = "should have required argument";
+
+fn main() {
+    let arg_matches = get_args();
+
+    let data_dir = arg_matches
+        .value_of(ARG_DATA_DIR)
+        .map(PathBuf::from)
+        .expect(REQUIRED_ARG_EXPECT);
+
+    let result =
+        open_global_state(data_dir).and_then(|global_state| match arg_matches.subcommand() {
+            (CMD_QUERY, Some(matches)) => query(&global_state, matches),
+            (CMD_NAMED_KEYS, Some(matches)) => named_keys(&global_state, matches),
+            (CMD_DUMP, Some(matches)) => dump(&global_state, matches),
+            (CMD_PROTOCOL_DATA, Some(_)) => protocol_data(&global_state),
+            (CMD_STATS, Some(matches)) => stats(&global_state, matches),
+            _ => unreachable!("clap should require a subcommand"),
+        });
+
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn get_args() -> ArgMatches<'static> {
+    let state_hash = Arg::with_name(ARG_STATE_HASH)
+        .required(true)
+        .help(ARG_STATE_HASH_HELP)
+        .index(1);
+    let key = Arg::with_name(ARG_KEY)
+        .required(true)
+        .help(ARG_KEY_HELP)
+        .index(2);
+
+    App::new(APP_NAME)
+        .version(env!("CARGO_PKG_VERSION"))
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name(ARG_DATA_DIR)
+                .short(ARG_DATA_DIR_SHORT)
+                .long(ARG_DATA_DIR)
+                .value_name(ARG_DATA_DIR_VALUE)
+                .help(ARG_DATA_DIR_HELP)
+                .required(true)
+                .takes_value(true),
+        )
+        .subcommand(
+            SubCommand::with_name(CMD_QUERY)
+                .about(CMD_QUERY_ABOUT)
+                .arg(state_hash.clone())
+                .arg(key.clone())
+                .arg(
+                    Arg::with_name(ARG_PATH)
+                        .help(ARG_PATH_HELP)
+                        .multiple(true)
+                        .index(3),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name(CMD_NAMED_KEYS)
+                .about(CMD_NAMED_KEYS_ABOUT)
+                .arg(state_hash.clone())
+                .arg(key),
+        )
+        .subcommand(
+            SubCommand::with_name(CMD_DUMP)
+                .about(CMD_DUMP_ABOUT)
+                .arg(state_hash.clone())
+                .arg(
+                    Arg::with_name(ARG_PREFIX)
+                        .long(ARG_PREFIX)
+                        .value_name(ARG_PREFIX_VALUE)
+                        .help(ARG_PREFIX_HELP)
+                        .default_value("")
+                        .hide_default_value(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name(ARG_KEYS_ONLY)
+                        .long(ARG_KEYS_ONLY)
+                        .help(ARG_KEYS_ONLY_HELP),
+                ),
+        )
+        .subcommand(SubCommand::with_name(CMD_PROTOCOL_DATA).about(CMD_PROTOCOL_DATA_ABOUT))
+        .subcommand(
+            SubCommand::with_name(CMD_STATS)
+                .about(CMD_STATS_ABOUT)
+                .arg(state_hash),
+        )
+        .get_matches()
+}
+
+fn open_global_state(mut data_dir: PathBuf) -> Result<LmdbGlobalState, String> {
+    data_dir.push(GLOBAL_STATE_DIR);
+    let environment = LmdbEnvironment::open_read_only(&data_dir)
+        .map_err(|error| format!("failed to open {}: {}", data_dir.display(), error))?;
+    let trie_store = LmdbTrieStore::open(&environment, None)
+        .map_err(|error| format!("failed to open trie store: {}", error))?;
+    let protocol_data_store = LmdbProtocolDataStore::open(&environment, None)
+        .map_err(|error| format!("failed to open protocol data store: {}", error))?;
+    LmdbGlobalState::open(
+        Arc::new(environment),
+        Arc::new(trie_store),
+        Arc::new(protocol_data_store),
+    )
+    .map_err(|error| error.to_string())
+}
+
+fn get_state_hash(matches: &ArgMatches) -> Result<Blake2bHash, String> {
+    parse::parse_state_hash(matches.value_of(ARG_STATE_HASH).expect(REQUIRED_ARG_EXPECT))
+}
+
+fn get_key(matches: &ArgMatches) -> Result<Key, String> {
+    parse::parse_key(matches.value_of(ARG_KEY).expect(REQUIRED_ARG_EXPECT))
+}
+
+/// Runs `query_request` the same way as the `query` RPC of the engine server.
+fn run_query(
+    global_state: &LmdbGlobalState,
+    query_request: QueryRequest,
+) -> Result<StoredValue, String> {
+    let engine_state = EngineState::new(global_state.clone(), EngineConfig::new());
+    match engine_state.run_query(CorrelationId::new(), query_request) {
+        Ok(QueryResult::Success(value)) => Ok(value),
+        Ok(QueryResult::RootNotFound) => Err(String::from("state root not found")),
+        Ok(QueryResult::ValueNotFound(message)) => Err(message),
+        Ok(QueryResult::CircularReference(message)) => Err(message),
+        Err(error) => Err(format!("{:?}", error)),
+    }
+}
+
+fn query(global_state: &LmdbGlobalState, matches: &ArgMatches) -> Result<(), String> {
+    let state_hash = get_state_hash(matches)?;
+    let key = get_key(matches)?;
+    let path = matches
+        .values_of(ARG_PATH)
+        .map(|values| values.map(String::from).collect())
+        .unwrap_or_default();
+    let value = run_query(global_state, QueryRequest::new(state_hash, key, path))?;
+    println!("{:#?}", value);
+    Ok(())
+}
+
+fn named_keys(global_state: &LmdbGlobalState, matches: &ArgMatches) -> Result<(), String> {
+    let state_hash = get_state_hash(matches)?;
+    let key = get_key(matches)?;
+    let value = run_query(global_state, QueryRequest::new(state_hash, key, vec![]))?;
+    let named_keys = match &value {
+        StoredValue::Account(account) => account.named_keys(),
+        StoredValue::Contract(contract) => contract.named_keys(),
+        _ => return Err(format!("{} is not an account or contract", key.as_string())),
+    };
+    for (name, key) in named_keys {
+        println!("{}\t{}", name, key.as_string());
+    }
+    Ok(())
+}
+
+fn dump(global_state: &LmdbGlobalState, matches: &ArgMatches) -> Result<(), String> {
+    let state_hash = get_state_hash(matches)?;
+    let prefix = parse::parse_bytes(matches.value_of(ARG_PREFIX).expect(DEFAULT_VALUE_EXPECT))?;
+    let keys_only = matches.is_present(ARG_KEYS_ONLY);
+
+    let correlation_id = CorrelationId::new();
+    let txn = global_state
+        .environment
+        .create_read_txn()
+        .map_err(|error| error.to_string())?;
+    let store = &*global_state.trie_store;
+    let maybe_root: Option<Trie<Key, StoredValue>> = store
+        .get(&txn, &state_hash)
+        .map_err(|error| error.to_string())?;
+    if maybe_root.is_none() {
+        return Err(String::from("state root not found"));
+    }
+
+    let keys = operations::keys_with_prefix::<Key, StoredValue, _, _>(
+        correlation_id,
+        &txn,
+        store,
+        &state_hash,
+        &prefix,
+    );
+    for maybe_key in keys {
+        let key = maybe_key.map_err(|error| error.to_string())?;
+        if keys_only {
+            println!("{}", key.as_string());
+            continue;
+        }
+        let read_result: ReadResult<StoredValue> = operations::read::<_, _, _, _, error::Error>(
+            correlation_id,
+            &txn,
+            store,
+            &state_hash,
+            &key,
+        )
+        .map_err(|error| error.to_string())?;
+        match read_result {
+            ReadResult::Found(value) => println!("{}\t{:?}", key.as_string(), value),
+            ReadResult::NotFound | ReadResult::RootNotFound => {
+                return Err(format!("failed to read value under {}", key.as_string()))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn protocol_data(global_state: &LmdbGlobalState) -> Result<(), String> {
+    let env = global_state.environment.env();
+    let txn = env.begin_ro_txn().map_err(|error| error.to_string())?;
+    let db = Store::<ProtocolVersion, ProtocolData>::handle(&*global_state.protocol_data_store);
+    let mut cursor = txn.open_ro_cursor(db).map_err(|error| error.to_string())?;
+    for (key_bytes, value_bytes) in cursor.iter_start() {
+        let protocol_version: ProtocolVersion =
+            types::bytesrepr::deserialize(key_bytes.to_vec())
+                .map_err(|error| format!("failed to parse protocol version: {}", error))?;
+        let (protocol_data, _) = ProtocolData::from_bytes(value_bytes).map_err(|error| {
+            format!(
+                "failed to parse protocol data for {}: {}",
+                protocol_version, error
+            )
+        })?;
+        println!("{}: {:#?}", protocol_version, protocol_data);
+    }
+    Ok(())
+}
+
+fn stats(global_state: &LmdbGlobalState, matches: &ArgMatches) -> Result<(), String> {
+    let state_hash = get_state_hash(matches)?;
+    let txn = global_state
+        .environment
+        .create_read_txn()
+        .map_err(|error| error.to_string())?;
+    match TrieStats::collect(&txn, &global_state.trie_store, &state_hash)? {
+        Some(stats) => {
+            println!("{}", stats);
+            Ok(())
+        }
+        None => Err(String::from("state root not found")),
+    }
+}
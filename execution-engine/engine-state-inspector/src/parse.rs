@@ -0,0 +1,88 @@
+use std::convert::TryFrom;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::{account::AccountHash, AccessRights, Key, URef};
+
+const ACCOUNT_PREFIX: &str = "account-account_hash-";
+const HASH_PREFIX: &str = "hash-";
+const UREF_PREFIX: &str = "uref-";
+
+fn decode_array(input: &str) -> Result<[u8; 32], String> {
+    let bytes =
+        base16::decode(input).map_err(|error| format!("invalid hex {:?}: {}", input, error))?;
+    <[u8; 32]>::try_from(bytes.as_slice())
+        .map_err(|_| format!("expected 32 bytes but {:?} has {}", input, bytes.len()))
+}
+
+/// Parses a hex-encoded state root hash.
+pub fn parse_state_hash(input: &str) -> Result<Blake2bHash, String> {
+    decode_array(input).map(Blake2bHash::from)
+}
+
+/// Parses a hex-encoded byte string, as used for key prefixes.
+pub fn parse_bytes(input: &str) -> Result<Vec<u8>, String> {
+    base16::decode(input).map_err(|error| format!("invalid hex {:?}: {}", input, error))
+}
+
+/// Parses a [`Key`] in the format produced by [`Key::as_string`].
+///
+/// A [`Key::URef`] may omit its access rights suffix, in which case the key is normalized.
+pub fn parse_key(input: &str) -> Result<Key, String> {
+    if input.starts_with(ACCOUNT_PREFIX) {
+        let bytes = decode_array(&input[ACCOUNT_PREFIX.len()..])?;
+        return Ok(Key::Account(AccountHash::new(bytes)));
+    }
+    if input.starts_with(HASH_PREFIX) {
+        let bytes = decode_array(&input[HASH_PREFIX.len()..])?;
+        return Ok(Key::Hash(bytes));
+    }
+    if input.starts_with(UREF_PREFIX) {
+        let mut parts = input[UREF_PREFIX.len()..].splitn(2, '-');
+        let addr = decode_array(parts.next().unwrap_or_default())?;
+        let access_rights = match parts.next() {
+            Some(octal) => u8::from_str_radix(octal, 8)
+                .ok()
+                .and_then(AccessRights::from_bits)
+                .ok_or_else(|| format!("invalid access rights {:?}", octal))?,
+            None => AccessRights::NONE,
+        };
+        return Ok(Key::URef(URef::new(addr, access_rights)));
+    }
+    Err(format!(
+        "unrecognized key {:?}; expected a key starting with {:?}, {:?} or {:?}",
+        input, ACCOUNT_PREFIX, HASH_PREFIX, UREF_PREFIX
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_formatted_keys() {
+        let keys = vec![
+            Key::Account(AccountHash::new([1; 32])),
+            Key::Hash([2; 32]),
+            Key::URef(URef::new([3; 32], AccessRights::READ_ADD_WRITE)),
+            Key::URef(URef::new([4; 32], AccessRights::NONE)),
+        ];
+        for key in keys {
+            assert_eq!(parse_key(&key.as_string()), Ok(key));
+        }
+    }
+
+    #[test]
+    fn should_parse_uref_without_access_rights() {
+        let uref = URef::new([5; 32], AccessRights::READ);
+        let input = format!("uref-{}", base16::encode_lower(&uref.addr()));
+        assert_eq!(parse_key(&input), Ok(Key::URef(uref).normalize()));
+    }
+
+    #[test]
+    fn should_reject_malformed_keys() {
+        assert!(parse_key("hash-0102").is_err());
+        assert!(parse_key("uref-zz").is_err());
+        assert!(parse_key(&format!("uref-{}-999", "00".repeat(32))).is_err());
+        assert!(parse_key(&format!("local-{}", "00".repeat(32))).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt::{self, Display, Formatter};
+
+use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+use engine_storage::{
+    error, store::Store, transaction_source::Readable, trie::Trie, trie_store::lmdb::LmdbTrieStore,
+};
+use lmdb::Database;
+use types::{bytesrepr::ToBytes, Key};
+
+/// Counts of the nodes reachable from a single state root.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TrieStats {
+    pub leaves: usize,
+    pub nodes: usize,
+    pub extensions: usize,
+    /// The number of trie values on the longest path from the root to a leaf, including both.
+    pub max_depth: usize,
+    /// The total serialized size of all reachable trie values.
+    pub total_bytes: usize,
+}
+
+impl TrieStats {
+    /// Walks every trie value reachable from `root`, returning `None` if `root` is not in `store`.
+    ///
+    /// Subtries which are shared between several parents are counted once per parent, which
+    /// matches the shape of the trie as seen from `root`.
+    pub fn collect<T>(
+        txn: &T,
+        store: &LmdbTrieStore,
+        root: &Blake2bHash,
+    ) -> Result<Option<Self>, String>
+    where
+        T: Readable<Handle = Database>,
+        error::Error: From<T::Error>,
+    {
+        let mut stats = TrieStats::default();
+        let mut stack: Vec<(Blake2bHash, usize)> = vec![(*root, 1)];
+        while let Some((hash, depth)) = stack.pop() {
+            let trie: Trie<Key, StoredValue> = match store.get(txn, &hash) {
+                Ok(Some(trie)) => trie,
+                Ok(None) if hash == *root => return Ok(None),
+                Ok(None) => return Err(format!("missing trie value at {}", hash)),
+                Err(error) => return Err(format!("failed to read {}: {}", hash, error)),
+            };
+            stats.total_bytes += trie
+                .to_bytes()
+                .map_err(|error| format!("failed to serialize {}: {}", hash, error))?
+                .len();
+            stats.max_depth = stats.max_depth.max(depth);
+            match trie {
+                Trie::Leaf { .. } => stats.leaves += 1,
+                Trie::Node { pointer_block } => {
+                    stats.nodes += 1;
+                    for pointer in pointer_block[..].iter().flatten() {
+                        stack.push((*pointer.hash(), depth + 1));
+                    }
+                }
+                Trie::Extension { pointer, .. } => {
+                    stats.extensions += 1;
+                    stack.push((*pointer.hash(), depth + 1));
+                }
+            }
+        }
+        Ok(Some(stats))
+    }
+}
+
+impl Display for TrieStats {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "leaves:      {}", self.leaves)?;
+        writeln!(f, "nodes:       {}", self.nodes)?;
+        writeln!(f, "extensions:  {}", self.extensions)?;
+        writeln!(f, "max depth:   {}", self.max_depth)?;
+        write!(f, "total bytes: {}", self.total_bytes)
+    }
+}
@@ -1,22 +1,30 @@
 use std::sync;
 
+use ::sled as sled_external;
 use failure::Fail;
 use lmdb as lmdb_external;
 
+use engine_shared::newtypes::Blake2bHash;
 use types::bytesrepr;
 
-use super::in_memory;
+use super::{in_memory, sled};
 
 #[derive(Debug, Clone, Fail, PartialEq, Eq)]
 pub enum Error {
     #[fail(display = "{}", _0)]
     Lmdb(#[fail(cause)] lmdb_external::Error),
 
+    #[fail(display = "{}", _0)]
+    Sled(#[fail(cause)] sled_external::Error),
+
     #[fail(display = "{}", _0)]
     BytesRepr(#[fail(cause)] bytesrepr::Error),
 
     #[fail(display = "Another thread panicked while holding a lock")]
     Poison,
+
+    #[fail(display = "Root not found: {}", _0)]
+    RootNotFound(Blake2bHash),
 }
 
 impl wasmi::HostError for Error {}
@@ -27,6 +35,12 @@ impl From<lmdb_external::Error> for Error {
     }
 }
 
+impl From<sled_external::Error> for Error {
+    fn from(error: sled_external::Error) -> Self {
+        Error::Sled(error)
+    }
+}
+
 impl From<bytesrepr::Error> for Error {
     fn from(error: bytesrepr::Error) -> Self {
         Error::BytesRepr(error)
@@ -47,3 +61,13 @@ impl From<in_memory::Error> for Error {
         }
     }
 }
+
+impl From<sled::Error> for Error {
+    fn from(error: sled::Error) -> Self {
+        match error {
+            sled::Error::Sled(error) => Error::Sled(error),
+            sled::Error::BytesRepr(error) => Error::BytesRepr(error),
+            sled::Error::Poison => Error::Poison,
+        }
+    }
+}
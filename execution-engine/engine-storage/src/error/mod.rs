@@ -1,4 +1,5 @@
 pub mod in_memory;
 pub mod lmdb;
+pub mod sled;
 
 pub use self::lmdb::Error;
@@ -0,0 +1,38 @@
+use std::sync;
+
+use failure::Fail;
+use sled as sled_external;
+
+use types::bytesrepr;
+
+#[derive(Debug, Clone, Fail, PartialEq, Eq)]
+pub enum Error {
+    #[fail(display = "{}", _0)]
+    Sled(#[fail(cause)] sled_external::Error),
+
+    #[fail(display = "{}", _0)]
+    BytesRepr(#[fail(cause)] bytesrepr::Error),
+
+    #[fail(display = "Another thread panicked while holding a lock")]
+    Poison,
+}
+
+impl wasmi::HostError for Error {}
+
+impl From<sled_external::Error> for Error {
+    fn from(error: sled_external::Error) -> Self {
+        Error::Sled(error)
+    }
+}
+
+impl From<bytesrepr::Error> for Error {
+    fn from(error: bytesrepr::Error) -> Self {
+        Error::BytesRepr(error)
+    }
+}
+
+impl<T> From<sync::PoisonError<T>> for Error {
+    fn from(_error: sync::PoisonError<T>) -> Self {
+        Error::Poison
+    }
+}
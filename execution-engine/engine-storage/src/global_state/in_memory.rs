@@ -99,7 +99,9 @@ impl InMemoryGlobalState {
                         current_root = root_hash;
                     }
                     WriteResult::AlreadyExists => (),
-                    WriteResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
+                    WriteResult::RootNotFound => {
+                        return Err(error::Error::RootNotFound(current_root))
+                    }
                 }
             }
             txn.commit()?;
@@ -132,7 +134,7 @@ impl StateReader<Key, StoredValue> for InMemoryGlobalStateView {
         )? {
             ReadResult::Found(value) => Some(value),
             ReadResult::NotFound => None,
-            ReadResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
+            ReadResult::RootNotFound => return Err(error::Error::RootNotFound(self.root_hash)),
         };
         txn.commit()?;
         Ok(ret)
@@ -271,6 +273,23 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn read_from_view_with_unknown_root_fails_with_root_not_found() {
+        let correlation_id = CorrelationId::new();
+        let (state, _) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+        let view = InMemoryGlobalStateView {
+            environment: Arc::clone(&state.environment),
+            store: Arc::clone(&state.trie_store),
+            root_hash: fake_hash,
+        };
+        let key = create_test_pairs()[0].key;
+        assert_eq!(
+            view.read(correlation_id, &key),
+            Err(error::Error::RootNotFound(fake_hash))
+        );
+    }
+
     #[test]
     fn commit_updates_state() {
         let correlation_id = CorrelationId::new();
@@ -343,6 +362,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn commit_prunes_keys_from_state() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            tmp.insert(test_pairs[0].key, Transform::Prune);
+            // Pruning a key which does not exist is a no-op
+            tmp.insert(Key::Account(AccountHash::new([3u8; 32])), Transform::Prune);
+            tmp
+        };
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        assert_eq!(
+            None,
+            updated_checkout
+                .read(correlation_id, &test_pairs[0].key)
+                .unwrap()
+        );
+        assert_eq!(
+            Some(test_pairs[1].value.clone()),
+            updated_checkout
+                .read(correlation_id, &test_pairs[1].key)
+                .unwrap()
+        );
+
+        // The pruned state is the same as one in which the key was never written
+        let (_, expected_hash) = InMemoryGlobalState::from_pairs(
+            correlation_id,
+            &[(test_pairs[1].key, test_pairs[1].value.clone())],
+        )
+        .unwrap();
+        assert_eq!(expected_hash, updated_hash);
+    }
+
     #[test]
     fn initial_state_has_the_expected_hash() {
         let correlation_id = CorrelationId::new();
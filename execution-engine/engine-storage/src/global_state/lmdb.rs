@@ -22,6 +22,7 @@ use crate::{
     },
 };
 
+#[derive(Clone)]
 pub struct LmdbGlobalState {
     pub environment: Arc<LmdbEnvironment>,
     pub trie_store: Arc<LmdbTrieStore>,
@@ -58,6 +59,22 @@ impl LmdbGlobalState {
         ))
     }
 
+    /// Creates a state from an existing environment and trie_store without writing to them, so
+    /// that the environment may have been opened read-only.
+    pub fn open(
+        environment: Arc<LmdbEnvironment>,
+        trie_store: Arc<LmdbTrieStore>,
+        protocol_data_store: Arc<LmdbProtocolDataStore>,
+    ) -> Result<Self, error::Error> {
+        let (empty_root_hash, _) = create_hashed_empty_trie::<Key, StoredValue>()?;
+        Ok(LmdbGlobalState::new(
+            environment,
+            trie_store,
+            protocol_data_store,
+            empty_root_hash,
+        ))
+    }
+
     /// Creates a state from an existing environment, store, and root_hash.
     /// Intended to be used for testing.
     pub(crate) fn new(
@@ -93,7 +110,7 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         )? {
             ReadResult::Found(value) => Some(value),
             ReadResult::NotFound => None,
-            ReadResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
+            ReadResult::RootNotFound => return Err(error::Error::RootNotFound(self.root_hash)),
         };
         txn.commit()?;
         Ok(ret)
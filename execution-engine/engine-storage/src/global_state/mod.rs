@@ -1,5 +1,6 @@
 pub mod in_memory;
 pub mod lmdb;
+pub mod sled;
 
 use std::{collections::HashMap, fmt, hash::BuildHasher, time::Instant};
 
@@ -14,11 +15,12 @@ use engine_shared::{
 use types::{account::AccountHash, bytesrepr, Key, ProtocolVersion, U512};
 
 use crate::{
+    error,
     protocol_data::ProtocolData,
     transaction_source::{Transaction, TransactionSource},
     trie::Trie,
     trie_store::{
-        operations::{read, write, ReadResult, WriteResult},
+        operations::{delete, read, write_batch, DeleteResult, ReadResult, WriteResult},
         TrieStore,
     },
     GAUGE_METRIC_KEY,
@@ -125,7 +127,7 @@ where
     R: TransactionSource<'a, Handle = S::Handle>,
     S: TrieStore<Key, StoredValue>,
     S::Error: From<R::Error>,
-    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error> + From<error::Error>,
     H: BuildHasher,
 {
     let mut txn = environment.create_read_write_txn()?;
@@ -140,8 +142,17 @@ where
     let start = Instant::now();
     let mut reads: i32 = 0;
     let mut writes: i32 = 0;
+    let mut pairs: Vec<(Key, StoredValue)> = Vec::with_capacity(effects.len());
+    let mut keys_to_prune: Vec<Key> = Vec::new();
 
+    // Every key appears in `effects` at most once, so all of the current values can be read from
+    // the prestate before any of them is written.
     for (key, transform) in effects.into_iter() {
+        if let Transform::Prune = transform {
+            keys_to_prune.push(key);
+            continue;
+        }
+
         let read_result = read::<_, _, _, _, E>(correlation_id, &txn, store, &state_root, &key)?;
 
         log_duration(
@@ -158,30 +169,53 @@ where
             (ReadResult::NotFound, _) => {
                 return Ok(CommitResult::KeyNotFound(key));
             }
-            (ReadResult::Found(current_value), transform) => match transform.apply(current_value) {
-                Ok(updated_value) => updated_value,
-                Err(err) => return Ok(err.into()),
-            },
-            _x @ (ReadResult::RootNotFound, _) => panic!(stringify!(_x._1)),
+            (ReadResult::Found(current_value), transform) => {
+                match transform.apply(current_value.clone()) {
+                    // An unchanged value is left out of the batch, so it is neither rewritten nor
+                    // counted as a write
+                    Ok(updated_value) if updated_value == current_value => continue,
+                    Ok(updated_value) => updated_value,
+                    Err(err) => return Ok(err.into()),
+                }
+            }
+            (ReadResult::RootNotFound, _) => {
+                return Err(error::Error::RootNotFound(state_root).into());
+            }
         };
 
-        let write_result =
-            write::<_, _, _, _, E>(correlation_id, &mut txn, store, &state_root, &key, &value)?;
+        pairs.push((key, value));
+    }
 
-        log_duration(
-            correlation_id,
-            GLOBAL_STATE_COMMIT_WRITE_DURATION,
-            COMMIT,
-            start.elapsed(),
-        );
+    let write_result =
+        write_batch::<_, _, _, _, E>(correlation_id, &mut txn, store, &state_root, &pairs)?;
 
-        match write_result {
-            WriteResult::Written(root_hash) => {
+    log_duration(
+        correlation_id,
+        GLOBAL_STATE_COMMIT_WRITE_DURATION,
+        COMMIT,
+        start.elapsed(),
+    );
+
+    match write_result {
+        WriteResult::Written(root_hash) => {
+            state_root = root_hash;
+            writes += pairs.len() as i32;
+        }
+        WriteResult::AlreadyExists => (),
+        WriteResult::RootNotFound => return Err(error::Error::RootNotFound(state_root).into()),
+    }
+
+    // Pruning a key which is not in global state is not an error; there is just nothing to delete.
+    for key in keys_to_prune {
+        match delete::<_, _, _, _, E>(correlation_id, &mut txn, store, &state_root, &key)? {
+            DeleteResult::Deleted(root_hash) => {
                 state_root = root_hash;
                 writes += 1;
             }
-            WriteResult::AlreadyExists => (),
-            _x @ WriteResult::RootNotFound => panic!(stringify!(_x)),
+            DeleteResult::DoesNotExist => (),
+            DeleteResult::RootNotFound => {
+                return Err(error::Error::RootNotFound(state_root).into());
+            }
         }
     }
 
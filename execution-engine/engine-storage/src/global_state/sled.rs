@@ -0,0 +1,395 @@
+use std::{ops::Deref, sync::Arc};
+
+use engine_shared::{
+    additive_map::AdditiveMap,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+};
+use types::{Key, ProtocolVersion};
+
+use crate::{
+    error,
+    global_state::{commit, CommitResult, StateProvider, StateReader},
+    protocol_data::ProtocolData,
+    protocol_data_store::sled::SledProtocolDataStore,
+    store::Store,
+    transaction_source::{
+        sled::{SledEnvironment, SledReadTransaction},
+        Transaction, TransactionSource,
+    },
+    trie::{operations::create_hashed_empty_trie, Trie},
+    trie_store::{
+        operations::{read, ReadResult},
+        sled::SledTrieStore,
+    },
+};
+
+pub struct SledGlobalState {
+    pub environment: Arc<SledEnvironment>,
+    pub trie_store: Arc<SledTrieStore>,
+    pub protocol_data_store: Arc<SledProtocolDataStore>,
+    pub empty_root_hash: Blake2bHash,
+}
+
+/// Represents a "view" of global state at a particular root hash.
+pub struct SledGlobalStateView {
+    pub environment: Arc<SledEnvironment>,
+    pub store: Arc<SledTrieStore>,
+    pub root_hash: Blake2bHash,
+}
+
+impl SledGlobalState {
+    /// Creates an empty state from an existing environment and trie_store.
+    pub fn empty(
+        environment: Arc<SledEnvironment>,
+        trie_store: Arc<SledTrieStore>,
+        protocol_data_store: Arc<SledProtocolDataStore>,
+    ) -> Result<Self, error::Error> {
+        let root_hash: Blake2bHash = {
+            let (root_hash, root) = create_hashed_empty_trie::<Key, StoredValue>()?;
+            let mut txn = environment.create_read_write_txn()?;
+            trie_store.put(&mut txn, &root_hash, &root)?;
+            txn.commit()?;
+            root_hash
+        };
+        Ok(SledGlobalState::new(
+            environment,
+            trie_store,
+            protocol_data_store,
+            root_hash,
+        ))
+    }
+
+    /// Creates a state from an existing environment, store, and root_hash.
+    /// Intended to be used for testing.
+    pub(crate) fn new(
+        environment: Arc<SledEnvironment>,
+        trie_store: Arc<SledTrieStore>,
+        protocol_data_store: Arc<SledProtocolDataStore>,
+        empty_root_hash: Blake2bHash,
+    ) -> Self {
+        SledGlobalState {
+            environment,
+            trie_store,
+            protocol_data_store,
+            empty_root_hash,
+        }
+    }
+}
+
+impl StateReader<Key, StoredValue> for SledGlobalStateView {
+    type Error = error::Error;
+
+    fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read::<Key, StoredValue, SledReadTransaction, SledTrieStore, Self::Error>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(value) => Some(value),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => return Err(error::Error::RootNotFound(self.root_hash)),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+}
+
+impl StateProvider for SledGlobalState {
+    type Error = error::Error;
+
+    type Reader = SledGlobalStateView;
+
+    fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let maybe_root: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, &state_hash)?;
+        let maybe_state = maybe_root.map(|_| SledGlobalStateView {
+            environment: Arc::clone(&self.environment),
+            store: Arc::clone(&self.trie_store),
+            root_hash: state_hash,
+        });
+        txn.commit()?;
+        Ok(maybe_state)
+    }
+
+    fn commit(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+    ) -> Result<CommitResult, Self::Error> {
+        let commit_result = commit::<SledEnvironment, SledTrieStore, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            prestate_hash,
+            effects,
+        )?;
+        Ok(commit_result)
+    }
+
+    fn put_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<(), Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        self.protocol_data_store
+            .put(&mut txn, &protocol_version, protocol_data)?;
+        txn.commit().map_err(Into::into)
+    }
+
+    fn get_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<ProtocolData>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.protocol_data_store.get(&txn, &protocol_version)?;
+        txn.commit()?;
+        Ok(result)
+    }
+
+    fn empty_root(&self) -> Blake2bHash {
+        self.empty_root_hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, thread, time::Duration};
+
+    use tempfile::tempdir;
+
+    use types::{account::AccountHash, CLValue};
+
+    use crate::trie_store::operations::{write, WriteResult};
+
+    use super::*;
+
+    /// Opens the environment at `path`, waiting for the background threads of a previously dropped
+    /// environment at the same path to release its file lock.
+    fn reopen_environment(path: &PathBuf) -> SledEnvironment {
+        let mut attempts = 0;
+        loop {
+            match SledEnvironment::new(path) {
+                Ok(environment) => return environment,
+                Err(error) if attempts >= 100 => panic!("failed to reopen environment: {}", error),
+                Err(_) => {
+                    attempts += 1;
+                    thread::sleep(Duration::from_millis(10));
+                }
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct TestPair {
+        key: Key,
+        value: StoredValue,
+    }
+
+    fn create_test_pairs() -> [TestPair; 2] {
+        [
+            TestPair {
+                key: Key::Account(AccountHash::new([1_u8; 32])),
+                value: StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()),
+            },
+            TestPair {
+                key: Key::Account(AccountHash::new([2_u8; 32])),
+                value: StoredValue::CLValue(CLValue::from_t(2_i32).unwrap()),
+            },
+        ]
+    }
+
+    fn create_test_pairs_updated() -> [TestPair; 3] {
+        [
+            TestPair {
+                key: Key::Account(AccountHash::new([1u8; 32])),
+                value: StoredValue::CLValue(CLValue::from_t("one".to_string()).unwrap()),
+            },
+            TestPair {
+                key: Key::Account(AccountHash::new([2u8; 32])),
+                value: StoredValue::CLValue(CLValue::from_t("two".to_string()).unwrap()),
+            },
+            TestPair {
+                key: Key::Account(AccountHash::new([3u8; 32])),
+                value: StoredValue::CLValue(CLValue::from_t(3_i32).unwrap()),
+            },
+        ]
+    }
+
+    fn create_test_state() -> (SledGlobalState, Blake2bHash) {
+        let correlation_id = CorrelationId::new();
+        let _temp_dir = tempdir().unwrap();
+        let environment = Arc::new(SledEnvironment::new(&_temp_dir.path().to_path_buf()).unwrap());
+        let trie_store = Arc::new(SledTrieStore::new(&environment, None));
+        let protocol_data_store = Arc::new(SledProtocolDataStore::new(&environment, None));
+        let ret = SledGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+        let mut current_root = ret.empty_root_hash;
+        {
+            let mut txn = ret.environment.create_read_write_txn().unwrap();
+
+            for TestPair { key, value } in &create_test_pairs() {
+                match write::<_, _, _, SledTrieStore, error::Error>(
+                    correlation_id,
+                    &mut txn,
+                    &ret.trie_store,
+                    &current_root,
+                    key,
+                    value,
+                )
+                .unwrap()
+                {
+                    WriteResult::Written(root_hash) => {
+                        current_root = root_hash;
+                    }
+                    WriteResult::AlreadyExists => (),
+                    WriteResult::RootNotFound => panic!("SledGlobalState has invalid root"),
+                }
+            }
+
+            txn.commit().unwrap();
+        }
+        (ret, current_root)
+    }
+
+    #[test]
+    fn reads_from_a_checkout_return_expected_values() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn checkout_fails_if_unknown_hash_is_given() {
+        let (state, _) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+        let result = state.checkout(fake_hash).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn commit_updates_state() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            for TestPair { key, value } in &test_pairs_updated {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn commit_updates_state_and_original_state_stays_intact() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            for TestPair { key, value } in &test_pairs_updated {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+
+        let original_checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(
+                Some(value),
+                original_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+        assert_eq!(
+            None,
+            original_checkout
+                .read(correlation_id, &test_pairs_updated[2].key)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn committed_state_survives_reopening_environment() {
+        let correlation_id = CorrelationId::new();
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().to_path_buf();
+
+        let root_hash = {
+            let environment = Arc::new(SledEnvironment::new(&path).unwrap());
+            let trie_store = Arc::new(SledTrieStore::new(&environment, None));
+            let protocol_data_store = Arc::new(SledProtocolDataStore::new(&environment, None));
+            let state =
+                SledGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+
+            let effects: AdditiveMap<Key, Transform> = {
+                let mut tmp = AdditiveMap::new();
+                for TestPair { key, value } in &create_test_pairs() {
+                    tmp.insert(*key, Transform::Write(value.to_owned()));
+                }
+                tmp
+            };
+
+            match state
+                .commit(correlation_id, state.empty_root_hash, effects)
+                .unwrap()
+            {
+                CommitResult::Success { state_root, .. } => state_root,
+                _ => panic!("commit failed"),
+            }
+        };
+
+        let environment = Arc::new(reopen_environment(&path));
+        let trie_store = Arc::new(SledTrieStore::new(&environment, None));
+        let protocol_data_store = Arc::new(SledProtocolDataStore::new(&environment, None));
+        let state = SledGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+}
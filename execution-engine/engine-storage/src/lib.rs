@@ -3,6 +3,7 @@ pub mod error;
 pub mod global_state;
 pub mod protocol_data;
 pub mod protocol_data_store;
+pub mod snapshot;
 pub mod store;
 pub mod transaction_source;
 pub mod trie;
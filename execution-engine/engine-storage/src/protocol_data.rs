@@ -1,23 +1,37 @@
-use engine_wasm_prep::wasm_costs::{WasmCosts, WASM_COSTS_SERIALIZED_LENGTH};
+use engine_wasm_prep::{
+    wasm_costs::{WasmCosts, LEGACY_WASM_COSTS_SERIALIZED_LENGTH, WASM_COSTS_SERIALIZED_LENGTH},
+    wasm_limits::{WasmLimits, WASM_LIMITS_SERIALIZED_LENGTH},
+};
 use std::collections::BTreeMap;
 use types::{
-    bytesrepr::{self, FromBytes, ToBytes},
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
     ContractHash, HashAddr, KEY_HASH_LENGTH,
 };
 
-const PROTOCOL_DATA_SERIALIZED_LENGTH: usize = WASM_COSTS_SERIALIZED_LENGTH + 3 * KEY_HASH_LENGTH;
+/// Version of the serialized layout, written ahead of the fields.
+///
+/// Protocol data persisted before the layout was versioned has no version byte, no Wasm limits and
+/// no memory fill cost, and is recognized by its length alone.
+const PROTOCOL_DATA_VERSION: u8 = 1;
+const PROTOCOL_DATA_SERIALIZED_LENGTH: usize = U8_SERIALIZED_LENGTH
+    + WASM_COSTS_SERIALIZED_LENGTH
+    + WASM_LIMITS_SERIALIZED_LENGTH
+    + 3 * KEY_HASH_LENGTH;
+const LEGACY_PROTOCOL_DATA_SERIALIZED_LENGTH: usize =
+    LEGACY_WASM_COSTS_SERIALIZED_LENGTH + 3 * KEY_HASH_LENGTH;
 const DEFAULT_ADDRESS: [u8; 32] = [0; 32];
 
 /// Represents a protocol's data. Intended to be associated with a given protocol version.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ProtocolData {
     wasm_costs: WasmCosts,
+    wasm_limits: WasmLimits,
     mint: ContractHash,
     proof_of_stake: ContractHash,
     standard_payment: ContractHash,
 }
 
-/// Provides a default instance with non existing urefs and empty costs table.
+/// Provides a default instance with non existing urefs, empty costs table and default Wasm limits.
 ///
 /// Used in contexts where PoS or Mint contract is not ready yet, and pos, and
 /// mint installers are ran. For use with caution.
@@ -25,6 +39,7 @@ impl Default for ProtocolData {
     fn default() -> ProtocolData {
         ProtocolData {
             wasm_costs: WasmCosts::default(),
+            wasm_limits: WasmLimits::default(),
             mint: DEFAULT_ADDRESS,
             proof_of_stake: DEFAULT_ADDRESS,
             standard_payment: DEFAULT_ADDRESS,
@@ -33,15 +48,17 @@ impl Default for ProtocolData {
 }
 
 impl ProtocolData {
-    /// Creates a new [`ProtocolData`] value from a given [`WasmCosts`] value.
+    /// Creates a new [`ProtocolData`] value from given [`WasmCosts`] and [`WasmLimits`] values.
     pub fn new(
         wasm_costs: WasmCosts,
+        wasm_limits: WasmLimits,
         mint: ContractHash,
         proof_of_stake: ContractHash,
         standard_payment: ContractHash,
     ) -> Self {
         ProtocolData {
             wasm_costs,
+            wasm_limits,
             mint,
             proof_of_stake,
             standard_payment,
@@ -64,11 +81,13 @@ impl ProtocolData {
     /// Used during `commit_genesis` before all system contracts' URefs are known.
     pub fn partial_without_standard_payment(
         wasm_costs: WasmCosts,
+        wasm_limits: WasmLimits,
         mint: ContractHash,
         proof_of_stake: ContractHash,
     ) -> Self {
         ProtocolData {
             wasm_costs,
+            wasm_limits,
             mint,
             proof_of_stake,
             ..Default::default()
@@ -80,6 +99,11 @@ impl ProtocolData {
         &self.wasm_costs
     }
 
+    /// Gets the [`WasmLimits`] value from a given [`ProtocolData`] value.
+    pub fn wasm_limits(&self) -> &WasmLimits {
+        &self.wasm_limits
+    }
+
     pub fn mint(&self) -> ContractHash {
         self.mint
     }
@@ -126,7 +150,9 @@ impl ProtocolData {
 impl ToBytes for ProtocolData {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut PROTOCOL_DATA_VERSION.to_bytes()?);
         ret.append(&mut self.wasm_costs.to_bytes()?);
+        ret.append(&mut self.wasm_limits.to_bytes()?);
         ret.append(&mut self.mint.to_bytes()?);
         ret.append(&mut self.proof_of_stake.to_bytes()?);
         ret.append(&mut self.standard_payment.to_bytes()?);
@@ -138,9 +164,39 @@ impl ToBytes for ProtocolData {
     }
 }
 
+impl ProtocolData {
+    /// Deserializes protocol data persisted before the layout was versioned, applying the default
+    /// Wasm limits.
+    fn from_legacy_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (wasm_costs, rem) = WasmCosts::from_legacy_bytes(bytes)?;
+        let (mint, rem) = HashAddr::from_bytes(rem)?;
+        let (proof_of_stake, rem) = HashAddr::from_bytes(rem)?;
+        let (standard_payment, rem) = HashAddr::from_bytes(rem)?;
+
+        Ok((
+            ProtocolData {
+                wasm_costs,
+                wasm_limits: WasmLimits::default(),
+                mint,
+                proof_of_stake,
+                standard_payment,
+            },
+            rem,
+        ))
+    }
+}
+
 impl FromBytes for ProtocolData {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (wasm_costs, rem) = WasmCosts::from_bytes(bytes)?;
+        if bytes.len() == LEGACY_PROTOCOL_DATA_SERIALIZED_LENGTH {
+            return ProtocolData::from_legacy_bytes(bytes);
+        }
+        let (version, rem) = u8::from_bytes(bytes)?;
+        if version != PROTOCOL_DATA_VERSION {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let (wasm_costs, rem) = WasmCosts::from_bytes(rem)?;
+        let (wasm_limits, rem) = WasmLimits::from_bytes(rem)?;
         let (mint, rem) = HashAddr::from_bytes(rem)?;
         let (proof_of_stake, rem) = HashAddr::from_bytes(rem)?;
         let (standard_payment, rem) = HashAddr::from_bytes(rem)?;
@@ -148,6 +204,7 @@ impl FromBytes for ProtocolData {
         Ok((
             ProtocolData {
                 wasm_costs,
+                wasm_limits,
                 mint,
                 proof_of_stake,
                 standard_payment,
@@ -161,7 +218,9 @@ impl FromBytes for ProtocolData {
 pub(crate) mod gens {
     use proptest::prop_compose;
 
-    use engine_wasm_prep::wasm_costs::gens as wasm_costs_gens;
+    use engine_wasm_prep::{
+        wasm_costs::gens as wasm_costs_gens, wasm_limits::gens as wasm_limits_gens,
+    };
     use types::gens;
 
     use super::ProtocolData;
@@ -169,12 +228,14 @@ pub(crate) mod gens {
     prop_compose! {
         pub fn protocol_data_arb()(
             wasm_costs in wasm_costs_gens::wasm_costs_arb(),
+            wasm_limits in wasm_limits_gens::wasm_limits_arb(),
             mint in gens::u8_slice_32(),
             proof_of_stake in gens::u8_slice_32(),
             standard_payment in gens::u8_slice_32(),
         ) -> ProtocolData {
             ProtocolData {
                 wasm_costs,
+                wasm_limits,
                 mint,
                 proof_of_stake,
                 standard_payment,
@@ -187,10 +248,16 @@ pub(crate) mod gens {
 mod tests {
     use proptest::proptest;
 
-    use engine_wasm_prep::wasm_costs::WasmCosts;
-    use types::{bytesrepr, ContractHash};
+    use engine_wasm_prep::{
+        wasm_costs::{WasmCosts, LEGACY_WASM_COSTS_SERIALIZED_LENGTH},
+        wasm_limits::WasmLimits,
+    };
+    use types::{
+        bytesrepr::{self, ToBytes},
+        ContractHash,
+    };
 
-    use super::{gens, ProtocolData};
+    use super::{gens, ProtocolData, PROTOCOL_DATA_VERSION};
 
     fn wasm_costs_mock() -> WasmCosts {
         WasmCosts {
@@ -204,6 +271,7 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 3,
             opcodes_div: 8,
+            memfill: 1,
         }
     }
 
@@ -219,6 +287,7 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
+            memfill: 0,
         }
     }
 
@@ -231,6 +300,7 @@ mod tests {
             let standard_payment_reference = [3u8; 32];
             ProtocolData::new(
                 costs,
+                WasmLimits::default(),
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
@@ -243,6 +313,7 @@ mod tests {
             let standard_payment_reference = [2u8; 32];
             ProtocolData::new(
                 costs,
+                WasmLimits::default(),
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
@@ -261,6 +332,7 @@ mod tests {
             let costs = wasm_costs_mock();
             ProtocolData::new(
                 costs,
+                WasmLimits::default(),
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
@@ -291,6 +363,7 @@ mod tests {
             let costs = wasm_costs_mock();
             ProtocolData::new(
                 costs,
+                WasmLimits::default(),
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
@@ -308,6 +381,44 @@ mod tests {
         assert_eq!(actual[1], standard_payment_reference);
     }
 
+    #[test]
+    fn should_deserialize_legacy_layout() {
+        let costs = wasm_costs_mock();
+        let mint_reference = [1u8; 32];
+        let proof_of_stake_reference = [2u8; 32];
+        let standard_payment_reference = [3u8; 32];
+        let legacy_bytes = {
+            let mut ret = costs.to_bytes().expect("should serialize");
+            ret.truncate(LEGACY_WASM_COSTS_SERIALIZED_LENGTH);
+            ret.extend_from_slice(&mint_reference);
+            ret.extend_from_slice(&proof_of_stake_reference);
+            ret.extend_from_slice(&standard_payment_reference);
+            ret
+        };
+        let protocol_data: ProtocolData =
+            bytesrepr::deserialize(legacy_bytes).expect("should deserialize");
+        let expected = ProtocolData::new(
+            costs,
+            WasmLimits::default(),
+            mint_reference,
+            proof_of_stake_reference,
+            standard_payment_reference,
+        );
+        assert_eq!(protocol_data, expected);
+    }
+
+    #[test]
+    fn should_reject_unknown_version() {
+        let mut bytes = ProtocolData::default()
+            .to_bytes()
+            .expect("should serialize");
+        bytes[0] = PROTOCOL_DATA_VERSION + 1;
+        assert_eq!(
+            bytesrepr::deserialize::<ProtocolData>(bytes),
+            Err(bytesrepr::Error::Formatting)
+        );
+    }
+
     proptest! {
         #[test]
         fn should_serialize_and_deserialize_with_arbitrary_values(
@@ -4,6 +4,7 @@ use types::ProtocolVersion;
 
 pub mod in_memory;
 pub mod lmdb;
+pub mod sled;
 #[cfg(test)]
 mod tests;
 
@@ -0,0 +1,37 @@
+use types::ProtocolVersion;
+
+use crate::{
+    error,
+    protocol_data::ProtocolData,
+    protocol_data_store::{self, ProtocolDataStore},
+    store::Store,
+    transaction_source::sled::{SledDatabase, SledEnvironment},
+};
+
+/// A sled-backed protocol data store.
+#[derive(Debug, Clone)]
+pub struct SledProtocolDataStore {
+    db: SledDatabase,
+}
+
+impl SledProtocolDataStore {
+    pub fn new(env: &SledEnvironment, maybe_name: Option<&str>) -> Self {
+        let name = maybe_name
+            .map(|name| format!("{}-{}", protocol_data_store::NAME, name))
+            .unwrap_or_else(|| String::from(protocol_data_store::NAME));
+        let db = env.database(&name);
+        SledProtocolDataStore { db }
+    }
+}
+
+impl Store<ProtocolVersion, ProtocolData> for SledProtocolDataStore {
+    type Error = error::Error;
+
+    type Handle = SledDatabase;
+
+    fn handle(&self) -> Self::Handle {
+        self.db.clone()
+    }
+}
+
+impl ProtocolDataStore for SledProtocolDataStore {}
@@ -0,0 +1,388 @@
+//! A portable file format for archiving the global state at a single state root.
+//!
+//! A snapshot is written and read as a stream, so a state of any size can be archived without
+//! holding it in memory.  It is laid out as:
+//!
+//! * a header: the bytes of [`MAGIC`], the [`FORMAT_VERSION`] as a little-endian `u32` and the
+//!   state root hash
+//! * a sequence of records, each starting with a one-byte tag, and each being either
+//!   * a trie value: its hash followed by its `bytesrepr` serialization, or
+//!   * a protocol data entry: the `bytesrepr` serializations of a [`ProtocolVersion`] and its
+//!     [`ProtocolData`]
+//! * an end tag, followed by the BLAKE2b-256 checksum of every preceding byte
+//!
+//! Serialized values are each prefixed by their length as a little-endian `u32`.  Every trie value
+//! reachable from the state root is written exactly once, parents before their children.
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::HashSet,
+    convert::TryFrom,
+    error,
+    fmt::{self, Debug, Display, Formatter},
+    io::{self, Read, Write},
+};
+
+use blake2::{
+    digest::{Input, VariableOutput},
+    VarBlake2b,
+};
+
+use engine_shared::{
+    newtypes::{Blake2bHash, BLAKE2B_DIGEST_LENGTH},
+    stored_value::StoredValue,
+};
+use types::{
+    bytesrepr::{self, ToBytes},
+    Key, ProtocolVersion,
+};
+
+use crate::{
+    protocol_data::ProtocolData,
+    protocol_data_store::ProtocolDataStore,
+    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+    trie::Trie,
+    trie_store::TrieStore,
+};
+
+/// The bytes with which every snapshot starts.
+pub const MAGIC: [u8; 8] = *b"CLGSSNAP";
+
+/// The version of the snapshot format written by [`export`].
+pub const FORMAT_VERSION: u32 = 1;
+
+const TRIE_TAG: u8 = 0;
+const PROTOCOL_DATA_TAG: u8 = 1;
+const END_TAG: u8 = 2;
+
+/// An error which can occur while exporting or importing a snapshot.
+///
+/// `E` is the error type of the store being exported from or imported into.
+#[derive(Debug)]
+pub enum Error<E> {
+    Io(io::Error),
+    Storage(E),
+    InvalidMagic,
+    UnsupportedVersion(u32),
+    InvalidRecordTag(u8),
+    TooLarge(usize),
+    ChecksumMismatch,
+    TrieHashMismatch(Blake2bHash),
+    RootNotFound(Blake2bHash),
+    MissingTrie(Blake2bHash),
+    MissingProtocolData(ProtocolVersion),
+}
+
+impl<E: Display> Display for Error<E> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "I/O error: {}", error),
+            Error::Storage(error) => write!(f, "Storage error: {}", error),
+            Error::InvalidMagic => write!(f, "Not a global state snapshot"),
+            Error::UnsupportedVersion(version) => {
+                write!(f, "Unsupported snapshot format version {}", version)
+            }
+            Error::InvalidRecordTag(tag) => write!(f, "Invalid snapshot record tag {}", tag),
+            Error::TooLarge(length) => write!(f, "Value of {} bytes is too large", length),
+            Error::ChecksumMismatch => write!(f, "Snapshot checksum does not match its contents"),
+            Error::TrieHashMismatch(hash) => {
+                write!(f, "Trie value does not match its hash {}", hash)
+            }
+            Error::RootNotFound(hash) => write!(f, "State root {} not found", hash),
+            Error::MissingTrie(hash) => write!(f, "Trie value {} not found", hash),
+            Error::MissingProtocolData(version) => {
+                write!(f, "Protocol data for {} not found", version)
+            }
+        }
+    }
+}
+
+impl<E: Debug + Display> error::Error for Error<E> {}
+
+impl<E> From<io::Error> for Error<E> {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+fn storage<E, F: Into<E>>(error: F) -> Error<E> {
+    Error::Storage(error.into())
+}
+
+/// Describes the contents of an exported or imported snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotSummary {
+    pub state_root: Blake2bHash,
+    pub trie_count: usize,
+    pub protocol_versions: Vec<ProtocolVersion>,
+}
+
+fn new_hasher() -> VarBlake2b {
+    // Safe to unwrap here because our digest length is constant and valid
+    VarBlake2b::new(BLAKE2B_DIGEST_LENGTH).unwrap()
+}
+
+fn finish_hasher(hasher: VarBlake2b) -> [u8; BLAKE2B_DIGEST_LENGTH] {
+    let mut ret = [0u8; BLAKE2B_DIGEST_LENGTH];
+    hasher.variable_result(|hash| ret.clone_from_slice(hash));
+    ret
+}
+
+/// Writes to `inner` while hashing everything written.
+struct ChecksumWriter<W> {
+    inner: W,
+    hasher: VarBlake2b,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    fn new(inner: W) -> Self {
+        ChecksumWriter {
+            inner,
+            hasher: new_hasher(),
+        }
+    }
+
+    fn write_all(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.hasher.input(bytes);
+        self.inner.write_all(bytes)
+    }
+
+    fn write_u32(&mut self, value: u32) -> io::Result<()> {
+        self.write_all(&value.to_le_bytes())
+    }
+
+    fn write_bytes<E>(&mut self, bytes: &[u8]) -> Result<(), Error<E>> {
+        let length = u32::try_from(bytes.len()).map_err(|_| Error::TooLarge(bytes.len()))?;
+        self.write_u32(length)?;
+        self.write_all(bytes)?;
+        Ok(())
+    }
+
+    /// Writes the checksum of everything written so far.
+    fn finish(mut self) -> io::Result<()> {
+        let checksum = finish_hasher(self.hasher);
+        self.inner.write_all(&checksum)?;
+        self.inner.flush()
+    }
+}
+
+/// Reads from `inner` while hashing everything read.
+struct ChecksumReader<R> {
+    inner: R,
+    hasher: VarBlake2b,
+}
+
+impl<R: Read> ChecksumReader<R> {
+    fn new(inner: R) -> Self {
+        ChecksumReader {
+            inner,
+            hasher: new_hasher(),
+        }
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.inner.read_exact(buf)?;
+        self.hasher.input(&buf);
+        Ok(())
+    }
+
+    fn read_u8(&mut self) -> io::Result<u8> {
+        let mut buf = [0u8; 1];
+        self.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn read_u32(&mut self) -> io::Result<u32> {
+        let mut buf = [0u8; 4];
+        self.read_exact(&mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn read_hash(&mut self) -> io::Result<Blake2bHash> {
+        let mut buf = [0u8; BLAKE2B_DIGEST_LENGTH];
+        self.read_exact(&mut buf)?;
+        Ok(Blake2bHash::from(buf))
+    }
+
+    fn read_bytes(&mut self) -> io::Result<Vec<u8>> {
+        let length = self.read_u32()?;
+        // The length is not trusted to size the buffer up front, as the snapshot may be corrupt.
+        let mut ret = Vec::new();
+        (&mut self.inner)
+            .take(u64::from(length))
+            .read_to_end(&mut ret)?;
+        if ret.len() != length as usize {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        self.hasher.input(&ret);
+        Ok(ret)
+    }
+
+    /// Reads the checksum and checks that it matches everything read so far.
+    fn finish<E>(mut self) -> Result<(), Error<E>> {
+        let mut checksum = [0u8; BLAKE2B_DIGEST_LENGTH];
+        self.inner.read_exact(&mut checksum)?;
+        if checksum != finish_hasher(self.hasher) {
+            return Err(Error::ChecksumMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// Writes a snapshot of the state at `state_root` to `writer`.
+///
+/// As protocol data is stored by version rather than by state root, the entries to include are
+/// given by `protocol_versions`, each of which must be present in `protocol_data_store`.
+pub fn export<'a, R, S, P, W, E>(
+    environment: &'a R,
+    trie_store: &S,
+    protocol_data_store: &P,
+    state_root: Blake2bHash,
+    protocol_versions: &[ProtocolVersion],
+    writer: W,
+) -> Result<SnapshotSummary, Error<E>>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    P: ProtocolDataStore<Handle = S::Handle>,
+    E: From<R::Error> + From<bytesrepr::Error>,
+    W: Write,
+{
+    let txn = environment.create_read_txn().map_err(storage)?;
+    let mut writer = ChecksumWriter::new(writer);
+    writer.write_all(&MAGIC)?;
+    writer.write_u32(FORMAT_VERSION)?;
+    writer.write_all(&state_root.value())?;
+
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    let mut pending = vec![state_root];
+    while let Some(hash) = pending.pop() {
+        if !visited.insert(hash) {
+            continue;
+        }
+        let hash_bytes = hash.to_bytes().map_err(storage)?;
+        let trie_bytes = match txn
+            .read(trie_store.handle(), &hash_bytes)
+            .map_err(storage)?
+        {
+            Some(trie_bytes) => trie_bytes,
+            None if hash == state_root => return Err(Error::RootNotFound(hash)),
+            None => return Err(Error::MissingTrie(hash)),
+        };
+        let trie: Trie<Key, StoredValue> =
+            bytesrepr::deserialize(trie_bytes.clone()).map_err(storage)?;
+        match trie {
+            Trie::Leaf { .. } => (),
+            Trie::Node { pointer_block } => {
+                // Pushed in reverse so that children are written in index order.
+                for pointer in pointer_block[..].iter().rev().flatten() {
+                    pending.push(*pointer.hash());
+                }
+            }
+            Trie::Extension { pointer, .. } => pending.push(*pointer.hash()),
+        }
+        writer.write_all(&[TRIE_TAG])?;
+        writer.write_all(&hash_bytes)?;
+        writer.write_bytes(&trie_bytes)?;
+    }
+
+    for protocol_version in protocol_versions {
+        let version_bytes = protocol_version.to_bytes().map_err(storage)?;
+        let protocol_data_bytes = txn
+            .read(protocol_data_store.handle(), &version_bytes)
+            .map_err(storage)?
+            .ok_or_else(|| Error::MissingProtocolData(*protocol_version))?;
+        writer.write_all(&[PROTOCOL_DATA_TAG])?;
+        writer.write_bytes(&version_bytes)?;
+        writer.write_bytes(&protocol_data_bytes)?;
+    }
+
+    writer.write_all(&[END_TAG])?;
+    writer.finish()?;
+    txn.commit().map_err(storage)?;
+
+    Ok(SnapshotSummary {
+        state_root,
+        trie_count: visited.len(),
+        protocol_versions: protocol_versions.to_vec(),
+    })
+}
+
+/// Reads a snapshot from `reader` into the given stores.
+///
+/// Every trie value is checked against its hash, and nothing is committed unless the whole
+/// snapshot is read successfully and its checksum matches.
+pub fn import<'a, R, S, P, Rd, E>(
+    environment: &'a R,
+    trie_store: &S,
+    protocol_data_store: &P,
+    reader: Rd,
+) -> Result<SnapshotSummary, Error<E>>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    P: ProtocolDataStore<Handle = S::Handle>,
+    E: From<R::Error> + From<bytesrepr::Error>,
+    Rd: Read,
+{
+    let mut reader = ChecksumReader::new(reader);
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if magic != MAGIC {
+        return Err(Error::InvalidMagic);
+    }
+    let version = reader.read_u32()?;
+    if version != FORMAT_VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+    let state_root = reader.read_hash()?;
+
+    let mut txn = environment.create_read_write_txn().map_err(storage)?;
+    let mut imported: HashSet<Blake2bHash> = HashSet::new();
+    let mut protocol_versions = Vec::new();
+    loop {
+        match reader.read_u8()? {
+            TRIE_TAG => {
+                let hash = reader.read_hash()?;
+                let trie_bytes = reader.read_bytes()?;
+                if Blake2bHash::new(&trie_bytes) != hash {
+                    return Err(Error::TrieHashMismatch(hash));
+                }
+                let hash_bytes = hash.to_bytes().map_err(storage)?;
+                txn.write(trie_store.handle(), &hash_bytes, &trie_bytes)
+                    .map_err(storage)?;
+                imported.insert(hash);
+            }
+            PROTOCOL_DATA_TAG => {
+                let version_bytes = reader.read_bytes()?;
+                let protocol_data_bytes = reader.read_bytes()?;
+                let protocol_version: ProtocolVersion =
+                    bytesrepr::deserialize(version_bytes.clone()).map_err(storage)?;
+                let _: ProtocolData =
+                    bytesrepr::deserialize(protocol_data_bytes.clone()).map_err(storage)?;
+                txn.write(
+                    protocol_data_store.handle(),
+                    &version_bytes,
+                    &protocol_data_bytes,
+                )
+                .map_err(storage)?;
+                protocol_versions.push(protocol_version);
+            }
+            END_TAG => break,
+            tag => return Err(Error::InvalidRecordTag(tag)),
+        }
+    }
+    reader.finish()?;
+
+    if !imported.contains(&state_root) {
+        return Err(Error::RootNotFound(state_root));
+    }
+    txn.commit().map_err(storage)?;
+
+    Ok(SnapshotSummary {
+        state_root,
+        trie_count: imported.len(),
+        protocol_versions,
+    })
+}
@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use lmdb::DatabaseFlags;
+use tempfile::tempdir;
+
+use engine_shared::{
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+};
+use types::{CLValue, Key, ProtocolVersion};
+
+use super::{export, import, Error, SnapshotSummary};
+use crate::{
+    error::{self, in_memory},
+    global_state::{
+        in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, StateProvider, StateReader,
+    },
+    protocol_data::ProtocolData,
+    protocol_data_store::lmdb::LmdbProtocolDataStore,
+    transaction_source::lmdb::LmdbEnvironment,
+    trie_store::lmdb::LmdbTrieStore,
+    TEST_MAP_SIZE,
+};
+
+fn create_test_pairs() -> Vec<(Key, StoredValue)> {
+    (0u8..32)
+        .map(|i| {
+            let value = CLValue::from_t(u64::from(i)).unwrap();
+            (Key::Hash([i; 32]), StoredValue::CLValue(value))
+        })
+        .collect()
+}
+
+fn create_in_memory_state() -> (InMemoryGlobalState, Blake2bHash) {
+    let (state, root_hash) =
+        InMemoryGlobalState::from_pairs(CorrelationId::new(), &create_test_pairs()).unwrap();
+    state
+        .put_protocol_data(ProtocolVersion::V1_0_0, &ProtocolData::default())
+        .unwrap();
+    (state, root_hash)
+}
+
+fn export_in_memory_state(state: &InMemoryGlobalState, root_hash: Blake2bHash) -> Vec<u8> {
+    let mut snapshot = Vec::new();
+    export::<_, _, _, _, in_memory::Error>(
+        &*state.environment,
+        &*state.trie_store,
+        &*state.protocol_data_store,
+        root_hash,
+        &[ProtocolVersion::V1_0_0],
+        &mut snapshot,
+    )
+    .unwrap();
+    snapshot
+}
+
+fn create_empty_lmdb_state(environment: LmdbEnvironment) -> LmdbGlobalState {
+    let environment = Arc::new(environment);
+    let trie_store =
+        Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+    let protocol_data_store =
+        Arc::new(LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+    LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap()
+}
+
+fn import_into_lmdb_state(
+    state: &LmdbGlobalState,
+    snapshot: &[u8],
+) -> Result<SnapshotSummary, Error<error::Error>> {
+    import::<_, _, _, _, error::Error>(
+        &*state.environment,
+        &*state.trie_store,
+        &*state.protocol_data_store,
+        snapshot,
+    )
+}
+
+fn assert_has_test_pairs(state: &LmdbGlobalState, root_hash: Blake2bHash) {
+    let checkout = state.checkout(root_hash).unwrap().unwrap();
+    for (key, value) in create_test_pairs() {
+        assert_eq!(
+            checkout.read(CorrelationId::new(), &key).unwrap(),
+            Some(value)
+        );
+    }
+}
+
+#[test]
+fn should_roundtrip_from_in_memory_to_lmdb() {
+    let (in_memory_state, root_hash) = create_in_memory_state();
+    let snapshot = export_in_memory_state(&in_memory_state, root_hash);
+
+    let temp_dir = tempdir().unwrap();
+    let environment = LmdbEnvironment::new(&temp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let lmdb_state = create_empty_lmdb_state(environment);
+    let summary = import_into_lmdb_state(&lmdb_state, &snapshot).unwrap();
+
+    assert_eq!(summary.state_root, root_hash);
+    assert_eq!(summary.protocol_versions, vec![ProtocolVersion::V1_0_0]);
+    assert_has_test_pairs(&lmdb_state, root_hash);
+    assert_eq!(
+        lmdb_state
+            .get_protocol_data(ProtocolVersion::V1_0_0)
+            .unwrap(),
+        Some(ProtocolData::default())
+    );
+
+    // Exporting the imported state yields an identical snapshot.
+    let mut exported = Vec::new();
+    let exported_summary = export::<_, _, _, _, error::Error>(
+        &*lmdb_state.environment,
+        &*lmdb_state.trie_store,
+        &*lmdb_state.protocol_data_store,
+        root_hash,
+        &[ProtocolVersion::V1_0_0],
+        &mut exported,
+    )
+    .unwrap();
+    assert_eq!(exported_summary, summary);
+    assert_eq!(exported, snapshot);
+}
+
+#[test]
+fn should_reject_corrupted_snapshot_without_committing() {
+    let (in_memory_state, root_hash) = create_in_memory_state();
+    let mut snapshot = export_in_memory_state(&in_memory_state, root_hash);
+    let middle = snapshot.len() / 2;
+    snapshot[middle] ^= 0xff;
+
+    let temp_dir = tempdir().unwrap();
+    let environment = LmdbEnvironment::new(&temp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let lmdb_state = create_empty_lmdb_state(environment);
+    assert!(import_into_lmdb_state(&lmdb_state, &snapshot).is_err());
+    assert!(lmdb_state.checkout(root_hash).unwrap().is_none());
+}
+
+#[test]
+fn should_reject_truncated_snapshot() {
+    let (in_memory_state, root_hash) = create_in_memory_state();
+    let snapshot = export_in_memory_state(&in_memory_state, root_hash);
+
+    let state = InMemoryGlobalState::empty().unwrap();
+    let result = import::<_, _, _, _, in_memory::Error>(
+        &*state.environment,
+        &*state.trie_store,
+        &*state.protocol_data_store,
+        &snapshot[..snapshot.len() - 1],
+    );
+    match result {
+        Err(Error::Io(_)) => (),
+        _ => panic!("unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn should_reject_invalid_magic() {
+    let state = InMemoryGlobalState::empty().unwrap();
+    let result = import::<_, _, _, _, in_memory::Error>(
+        &*state.environment,
+        &*state.trie_store,
+        &*state.protocol_data_store,
+        &b"NOTASNAPSHOT"[..],
+    );
+    match result {
+        Err(Error::InvalidMagic) => (),
+        _ => panic!("unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn should_fail_to_export_missing_root_or_protocol_data() {
+    let (state, root_hash) = create_in_memory_state();
+    let missing_root = Blake2bHash::new(&[0u8; 32]);
+    let result = export::<_, _, _, _, in_memory::Error>(
+        &*state.environment,
+        &*state.trie_store,
+        &*state.protocol_data_store,
+        missing_root,
+        &[],
+        Vec::new(),
+    );
+    match result {
+        Err(Error::RootNotFound(hash)) if hash == missing_root => (),
+        _ => panic!("unexpected result: {:?}", result),
+    }
+
+    let missing_version = ProtocolVersion::from_parts(2, 0, 0);
+    let result = export::<_, _, _, _, in_memory::Error>(
+        &*state.environment,
+        &*state.trie_store,
+        &*state.protocol_data_store,
+        root_hash,
+        &[missing_version],
+        Vec::new(),
+    );
+    match result {
+        Err(Error::MissingProtocolData(version)) if version == missing_version => (),
+        _ => panic!("unexpected result: {:?}", result),
+    }
+}
@@ -1,6 +1,8 @@
 use std::path::PathBuf;
 
-use lmdb::{self, Database, Environment, RoTransaction, RwTransaction, WriteFlags};
+use lmdb::{
+    self, Database, Environment, EnvironmentFlags, RoTransaction, RwTransaction, WriteFlags,
+};
 
 use crate::{
     error,
@@ -74,6 +76,16 @@ impl LmdbEnvironment {
         Ok(LmdbEnvironment { path, env })
     }
 
+    /// Opens an existing environment which may only be used for read transactions.
+    pub fn open_read_only(path: &PathBuf) -> Result<Self, error::Error> {
+        let env = Environment::new()
+            .set_max_dbs(MAX_DBS)
+            .set_flags(EnvironmentFlags::READ_ONLY)
+            .open(path)?;
+        let path = path.to_owned();
+        Ok(LmdbEnvironment { path, env })
+    }
+
     pub fn path(&self) -> &PathBuf {
         &self.path
     }
@@ -1,5 +1,6 @@
 pub mod in_memory;
 pub mod lmdb;
+pub mod sled;
 
 /// A transaction which can be committed or aborted.
 pub trait Transaction: Sized {
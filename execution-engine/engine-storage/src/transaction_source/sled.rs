@@ -0,0 +1,222 @@
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard,
+    },
+};
+
+use sled::{Batch, Db};
+
+use crate::{
+    error::sled::Error,
+    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+};
+
+/// The key under which the generation of the most recent commit is persisted.
+///
+/// Database names are never empty, so this key cannot collide with a versioned key.
+const GENERATION_KEY: &[u8] = b"\x00GENERATION";
+
+/// A marker for use in a mutex which represents the capability to perform a
+/// write transaction.
+struct WriteCapability;
+
+type WriteLock<'a> = MutexGuard<'a, WriteCapability>;
+
+/// A named database within a [`SledEnvironment`].
+///
+/// All databases share a single sled tree. Each value is stored under a versioned key made up of
+/// the database name, the length-prefixed key and the generation of the commit which wrote it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SledDatabase {
+    name: Vec<u8>,
+}
+
+impl SledDatabase {
+    fn versioned_key(&self, key: &[u8], generation: u64) -> Vec<u8> {
+        let key_length = u32::try_from(key.len()).expect("key length should fit in a u32");
+        let mut ret = Vec::with_capacity(self.name.len() + 1 + 4 + key.len() + 8);
+        ret.extend_from_slice(&self.name);
+        ret.push(0);
+        ret.extend_from_slice(&key_length.to_be_bytes());
+        ret.extend_from_slice(key);
+        ret.extend_from_slice(&generation.to_be_bytes());
+        ret
+    }
+}
+
+/// Reads the most recent value of `key` written at or before `generation`.
+fn read_at(
+    db: &Db,
+    handle: &SledDatabase,
+    key: &[u8],
+    generation: u64,
+) -> Result<Option<Vec<u8>>, Error> {
+    let lower = handle.versioned_key(key, 0);
+    let upper = handle.versioned_key(key, generation);
+    match db.range(lower..=upper).next_back() {
+        Some(entry) => {
+            let (_, value) = entry?;
+            Ok(Some(value.to_vec()))
+        }
+        None => Ok(None),
+    }
+}
+
+/// A read transaction for the sled-backed store.
+///
+/// Sees the state as of the most recent commit at the time it was created.
+pub struct SledReadTransaction<'a> {
+    env: &'a SledEnvironment,
+    generation: u64,
+}
+
+impl<'a> Transaction for SledReadTransaction<'a> {
+    type Error = Error;
+
+    type Handle = SledDatabase;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<'a> Readable for SledReadTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        read_at(&self.env.db, &handle, key, self.generation)
+    }
+}
+
+/// A read-write transaction for the sled-backed store.
+///
+/// Writes are buffered until the transaction is committed, at which point they are applied
+/// atomically in a single batch.  Dropping the transaction without committing discards them.
+pub struct SledReadWriteTransaction<'a> {
+    env: &'a SledEnvironment,
+    generation: u64,
+    pending: HashMap<(SledDatabase, Vec<u8>), Vec<u8>>,
+    _write_lock: WriteLock<'a>,
+}
+
+impl<'a> Transaction for SledReadWriteTransaction<'a> {
+    type Error = Error;
+
+    type Handle = SledDatabase;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        let db = &self.env.db;
+        let generation = self.generation + 1;
+        let mut batch = Batch::default();
+        let mut is_empty = true;
+        for ((handle, key), value) in self.pending {
+            // Values in the trie store are content-addressed, so rewriting an existing key with the
+            // same value is common and need not create a new version.
+            if read_at(db, &handle, &key, self.generation)?.as_ref() == Some(&value) {
+                continue;
+            }
+            batch.insert(handle.versioned_key(&key, generation), value);
+            is_empty = false;
+        }
+        if is_empty {
+            return Ok(());
+        }
+        batch.insert(GENERATION_KEY, &generation.to_be_bytes()[..]);
+        db.apply_batch(batch)?;
+        db.flush()?;
+        self.env.generation.store(generation, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+impl<'a> Readable for SledReadWriteTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        let pending_key = (handle, key.to_vec());
+        if let Some(value) = self.pending.get(&pending_key) {
+            return Ok(Some(value.to_owned()));
+        }
+        read_at(&self.env.db, &pending_key.0, key, self.generation)
+    }
+}
+
+impl<'a> Writable for SledReadWriteTransaction<'a> {
+    fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
+        self.pending.insert((handle, key.to_vec()), value.to_vec());
+        Ok(())
+    }
+}
+
+/// The environment for a sled-backed store.
+///
+/// Wraps [`sled::Db`].  Unlike [`LmdbEnvironment`](super::lmdb::LmdbEnvironment), the
+/// underlying files grow on demand, so no map size needs to be chosen up front.
+pub struct SledEnvironment {
+    path: PathBuf,
+    db: Db,
+    generation: AtomicU64,
+    write_mutex: Mutex<WriteCapability>,
+}
+
+impl SledEnvironment {
+    pub fn new(path: &PathBuf) -> Result<Self, Error> {
+        let db = sled::open(path)?;
+        let generation = match db.get(GENERATION_KEY)? {
+            Some(bytes) => {
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(&bytes);
+                u64::from_be_bytes(buf)
+            }
+            None => 0,
+        };
+        let path = path.to_owned();
+        Ok(SledEnvironment {
+            path,
+            db,
+            generation: AtomicU64::new(generation),
+            write_mutex: Mutex::new(WriteCapability),
+        })
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Returns a handle to the database with the given name.
+    pub fn database(&self, name: &str) -> SledDatabase {
+        debug_assert!(!name.is_empty() && !name.contains('\0'));
+        SledDatabase {
+            name: name.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl<'a> TransactionSource<'a> for SledEnvironment {
+    type Error = Error;
+
+    type Handle = SledDatabase;
+
+    type ReadTransaction = SledReadTransaction<'a>;
+
+    type ReadWriteTransaction = SledReadWriteTransaction<'a>;
+
+    fn create_read_txn(&'a self) -> Result<SledReadTransaction<'a>, Self::Error> {
+        let generation = self.generation.load(Ordering::SeqCst);
+        Ok(SledReadTransaction {
+            env: self,
+            generation,
+        })
+    }
+
+    fn create_read_write_txn(&'a self) -> Result<SledReadWriteTransaction<'a>, Self::Error> {
+        let _write_lock = self.write_mutex.lock()?;
+        let generation = self.generation.load(Ordering::SeqCst);
+        Ok(SledReadWriteTransaction {
+            env: self,
+            generation,
+            pending: HashMap::new(),
+            _write_lock,
+        })
+    }
+}
@@ -0,0 +1,243 @@
+//! A size-bounded cache of decoded [`Trie`] values, shared between readers of a
+//! [`TrieStore`](super::TrieStore).
+//!
+//! Trie values are content-addressed, so a value cached under a given hash never goes stale.  A
+//! value read inside a read-write transaction which is never committed may remain cached, but it
+//! can only be reached from a root which was never committed either.  The cache is bounded by the
+//! total serialized size of the values it holds, as a single leaf may hold a whole contract's Wasm.
+//! When a new value doesn't fit, the least recently used values are evicted.
+use std::{
+    any::Any,
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use parking_lot::Mutex;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr::ToBytes;
+
+use crate::trie::Trie;
+
+type CachedTrie = Arc<dyn Any + Send + Sync>;
+
+struct CacheEntry {
+    trie: CachedTrie,
+    size: usize,
+    tick: u64,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<Blake2bHash, CacheEntry>,
+    recency: BTreeMap<u64, Blake2bHash>,
+    size: usize,
+    tick: u64,
+}
+
+impl CacheState {
+    fn touch(&mut self, hash: Blake2bHash) -> u64 {
+        self.tick += 1;
+        self.recency.insert(self.tick, hash);
+        self.tick
+    }
+
+    fn remove(&mut self, hash: &Blake2bHash) {
+        if let Some(entry) = self.entries.remove(hash) {
+            self.recency.remove(&entry.tick);
+            self.size -= entry.size;
+        }
+    }
+}
+
+/// A cache of decoded [`Trie`] values keyed by their hashes, holding values with a total serialized
+/// size of at most `capacity` bytes.
+pub struct TrieCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+impl TrieCache {
+    /// Creates an empty cache holding at most `capacity` bytes of serialized values.
+    pub fn new(capacity: usize) -> Self {
+        TrieCache {
+            capacity,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    /// Returns the total serialized size in bytes of the cached values.
+    pub fn size(&self) -> usize {
+        self.state.lock().size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a copy of the cached value at `hash`, marking it as most recently used.
+    ///
+    /// Returns `None` if nothing is cached at `hash`, or if the cached value is not a
+    /// `Trie<K, V>`.
+    pub fn get<K, V>(&self, hash: &Blake2bHash) -> Option<Trie<K, V>>
+    where
+        K: Clone + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        let mut state = self.state.lock();
+        let (trie, old_tick) = match state.entries.get(hash) {
+            Some(entry) => (entry.trie.downcast_ref::<Trie<K, V>>()?.clone(), entry.tick),
+            None => return None,
+        };
+        state.recency.remove(&old_tick);
+        let new_tick = state.touch(*hash);
+        if let Some(entry) = state.entries.get_mut(hash) {
+            entry.tick = new_tick;
+        }
+        Some(trie)
+    }
+
+    /// Caches `trie` at `hash`, evicting the least recently used values until it fits.
+    ///
+    /// A value whose serialized size exceeds the capacity of the cache is not cached.
+    pub fn insert<K, V>(&self, hash: Blake2bHash, trie: Trie<K, V>)
+    where
+        K: ToBytes + Send + Sync + 'static,
+        V: ToBytes + Send + Sync + 'static,
+    {
+        let size = trie.serialized_length();
+        if size > self.capacity {
+            return;
+        }
+        let mut state = self.state.lock();
+        state.remove(&hash);
+        while state.size + size > self.capacity {
+            let oldest_hash = match state.recency.values().next() {
+                Some(hash) => *hash,
+                None => break,
+            };
+            state.remove(&oldest_hash);
+        }
+        let tick = state.touch(hash);
+        let entry = CacheEntry {
+            trie: Arc::new(trie),
+            size,
+            tick,
+        };
+        state.entries.insert(hash, entry);
+        state.size += size;
+    }
+}
+
+impl Debug for TrieCache {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("TrieCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(n: u8) -> (Blake2bHash, Trie<Vec<u8>, Vec<u8>>) {
+        let trie = Trie::Leaf {
+            key: vec![n],
+            value: vec![n],
+        };
+        (Blake2bHash::new(&[n]), trie)
+    }
+
+    fn leaf_size() -> usize {
+        leaf(0).1.serialized_length()
+    }
+
+    #[test]
+    fn should_return_inserted_value() {
+        let cache = TrieCache::new(2 * leaf_size());
+        let (hash, trie) = leaf(1);
+        assert_eq!(cache.get::<Vec<u8>, Vec<u8>>(&hash), None);
+        cache.insert(hash, trie.clone());
+        assert_eq!(cache.get(&hash), Some(trie));
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_value() {
+        let cache = TrieCache::new(2 * leaf_size());
+        let (hash_1, trie_1) = leaf(1);
+        let (hash_2, trie_2) = leaf(2);
+        let (hash_3, trie_3) = leaf(3);
+        cache.insert(hash_1, trie_1.clone());
+        cache.insert(hash_2, trie_2);
+        // Reading the first value makes the second the least recently used.
+        assert_eq!(cache.get(&hash_1), Some(trie_1.clone()));
+        cache.insert(hash_3, trie_3.clone());
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.size(), 2 * leaf_size());
+        assert_eq!(cache.get(&hash_1), Some(trie_1));
+        assert_eq!(cache.get::<Vec<u8>, Vec<u8>>(&hash_2), None);
+        assert_eq!(cache.get(&hash_3), Some(trie_3));
+    }
+
+    #[test]
+    fn should_not_return_value_of_another_type() {
+        let cache = TrieCache::new(leaf_size());
+        let (hash, trie) = leaf(1);
+        cache.insert(hash, trie);
+        assert_eq!(cache.get::<Vec<u8>, u8>(&hash), None);
+    }
+
+    #[test]
+    fn should_evict_until_larger_value_fits() {
+        let cache = TrieCache::new(3 * leaf_size());
+        let (hash_1, trie_1) = leaf(1);
+        let (hash_2, trie_2) = leaf(2);
+        let hash_3 = Blake2bHash::new(&[3]);
+        let trie_3: Trie<Vec<u8>, Vec<u8>> = Trie::Leaf {
+            key: vec![3],
+            value: vec![3; 3 * leaf_size()],
+        };
+        let (hash_4, trie_4) = leaf(4);
+        cache.insert(hash_1, trie_1);
+        cache.insert(hash_2, trie_2.clone());
+        cache.insert(hash_4, trie_4.clone());
+
+        // A value larger than the whole cache is never cached.
+        cache.insert(hash_3, trie_3);
+        assert_eq!(cache.get::<Vec<u8>, Vec<u8>>(&hash_3), None);
+        assert_eq!(cache.len(), 3);
+
+        // A value of twice the size of a leaf evicts the two least recently used leaves.
+        let hash_5 = Blake2bHash::new(&[5]);
+        let trie_5: Trie<Vec<u8>, Vec<u8>> = Trie::Leaf {
+            key: vec![5],
+            value: vec![5; leaf_size() + 1],
+        };
+        cache.insert(hash_5, trie_5.clone());
+        assert!(cache.size() <= 3 * leaf_size());
+        assert_eq!(cache.get::<Vec<u8>, Vec<u8>>(&hash_2), None);
+        assert_eq!(cache.get(&hash_4), Some(trie_4));
+        assert_eq!(cache.get(&hash_5), Some(trie_5));
+    }
+
+    #[test]
+    fn should_not_cache_with_zero_capacity() {
+        let cache = TrieCache::new(0);
+        let (hash, trie) = leaf(1);
+        cache.insert(hash, trie);
+        assert!(cache.is_empty());
+    }
+}
@@ -104,6 +104,8 @@
 //! tmp_dir.close().unwrap();
 //! ```
 
+use std::sync::Arc;
+
 use lmdb::{Database, DatabaseFlags};
 
 use engine_shared::newtypes::Blake2bHash;
@@ -113,7 +115,7 @@ use crate::{
     store::Store,
     transaction_source::lmdb::LmdbEnvironment,
     trie::Trie,
-    trie_store::{self, TrieStore},
+    trie_store::{self, cache::TrieCache, TrieStore},
 };
 
 /// An LMDB-backed trie store.
@@ -122,6 +124,7 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct LmdbTrieStore {
     db: Database,
+    cache: Option<Arc<TrieCache>>,
 }
 
 impl LmdbTrieStore {
@@ -132,13 +135,13 @@ impl LmdbTrieStore {
     ) -> Result<Self, error::Error> {
         let name = Self::name(maybe_name);
         let db = env.env().create_db(Some(&name), flags)?;
-        Ok(LmdbTrieStore { db })
+        Ok(LmdbTrieStore { db, cache: None })
     }
 
     pub fn open(env: &LmdbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
         let name = Self::name(maybe_name);
         let db = env.env().open_db(Some(&name))?;
-        Ok(LmdbTrieStore { db })
+        Ok(LmdbTrieStore { db, cache: None })
     }
 
     fn name(maybe_name: Option<&str>) -> String {
@@ -146,6 +149,12 @@ impl LmdbTrieStore {
             .map(|name| format!("{}-{}", trie_store::NAME, name))
             .unwrap_or_else(|| String::from(trie_store::NAME))
     }
+
+    /// Sets the cache of decoded [`Trie`] values consulted when reading from this store.
+    pub fn with_cache(mut self, cache: Arc<TrieCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
 }
 
 impl<K, V> Store<Blake2bHash, Trie<K, V>> for LmdbTrieStore {
@@ -158,4 +167,8 @@ impl<K, V> Store<Blake2bHash, Trie<K, V>> for LmdbTrieStore {
     }
 }
 
-impl<K, V> TrieStore<K, V> for LmdbTrieStore {}
+impl<K, V> TrieStore<K, V> for LmdbTrieStore {
+    fn cache(&self) -> Option<&TrieCache> {
+        self.cache.as_deref()
+    }
+}
@@ -1,18 +1,27 @@
 //! A store for persisting [`Trie`](crate::trie::Trie) values at their hashes.
 //!
-//! See the [in_memory](in_memory/index.html#usage) and
-//! [lmdb](lmdb/index.html#usage) modules for usage examples.
+//! See the [in_memory](in_memory/index.html#usage), [lmdb](lmdb/index.html#usage) and
+//! [sled](sled/index.html#usage) modules for usage examples.
+pub mod cache;
 pub mod in_memory;
 pub mod lmdb;
-pub(crate) mod operations;
+pub mod operations;
+pub mod sled;
 #[cfg(test)]
 mod tests;
+pub mod verify;
 
 use engine_shared::newtypes::Blake2bHash;
 
+use self::cache::TrieCache;
 use crate::{store::Store, trie::Trie};
 
 const NAME: &str = "TRIE_STORE";
 
 /// An entity which persists [`Trie`] values at their hashes.
-pub trait TrieStore<K, V>: Store<Blake2bHash, Trie<K, V>> {}
+pub trait TrieStore<K, V>: Store<Blake2bHash, Trie<K, V>> {
+    /// Returns the cache of decoded [`Trie`] values consulted when reading from this store, if any.
+    fn cache(&self) -> Option<&TrieCache> {
+        None
+    }
+}
@@ -1,7 +1,12 @@
 #[cfg(test)]
 mod tests;
 
-use std::{cmp, collections::VecDeque, mem, time::Instant};
+use std::{
+    cmp,
+    collections::{BTreeMap, VecDeque},
+    iter, mem,
+    time::Instant,
+};
 
 use engine_shared::{
     logging::{log_duration, log_metric},
@@ -11,7 +16,7 @@ use types::bytesrepr::{self, FromBytes, ToBytes};
 
 use crate::{
     transaction_source::{Readable, Writable},
-    trie::{self, Parents, Pointer, Trie, RADIX},
+    trie::{self, Parents, Pointer, PointerBlock, Trie, RADIX},
     trie_store::TrieStore,
     GAUGE_METRIC_KEY,
 };
@@ -22,10 +27,20 @@ const TRIE_STORE_SCAN_DURATION: &str = "trie_store_scan_duration";
 const TRIE_STORE_SCAN_GETS: &str = "trie_store_scan_gets";
 const TRIE_STORE_WRITE_DURATION: &str = "trie_store_write_duration";
 const TRIE_STORE_WRITE_PUTS: &str = "trie_store_write_puts";
+const TRIE_STORE_WRITE_BATCH_DURATION: &str = "trie_store_write_batch_duration";
+const TRIE_STORE_WRITE_BATCH_GETS: &str = "trie_store_write_batch_gets";
+const TRIE_STORE_WRITE_BATCH_PUTS: &str = "trie_store_write_batch_puts";
+const TRIE_STORE_DELETE_DURATION: &str = "trie_store_delete_duration";
+const TRIE_STORE_DELETE_GETS: &str = "trie_store_delete_gets";
+const TRIE_STORE_DELETE_PUTS: &str = "trie_store_delete_puts";
+const TRIE_STORE_CACHE_HITS: &str = "trie_store_cache_hits";
+const TRIE_STORE_CACHE_MISSES: &str = "trie_store_cache_misses";
 const READ: &str = "read";
 const GET: &str = "get";
 const SCAN: &str = "scan";
 const WRITE: &str = "write";
+const WRITE_BATCH: &str = "write_batch";
+const DELETE: &str = "delete";
 const PUT: &str = "put";
 
 #[derive(Debug, PartialEq, Eq)]
@@ -35,6 +50,62 @@ pub enum ReadResult<V> {
     RootNotFound,
 }
 
+/// Counts reads served from and missed by a [`TrieStore`]'s cache.
+#[derive(Default)]
+struct CacheCounter {
+    hits: i32,
+    misses: i32,
+}
+
+impl CacheCounter {
+    fn log(&self, correlation_id: CorrelationId, tag: &str) {
+        log_metric(
+            correlation_id,
+            TRIE_STORE_CACHE_HITS,
+            tag,
+            GAUGE_METRIC_KEY,
+            f64::from(self.hits),
+        );
+        log_metric(
+            correlation_id,
+            TRIE_STORE_CACHE_MISSES,
+            tag,
+            GAUGE_METRIC_KEY,
+            f64::from(self.misses),
+        );
+    }
+}
+
+/// Returns the trie at `hash`, consulting the store's cache first if it has one.
+fn get_trie<K, V, T, S>(
+    txn: &T,
+    store: &S,
+    hash: &Blake2bHash,
+    cache_counter: &mut CacheCounter,
+) -> Result<Option<Trie<K, V>>, S::Error>
+where
+    K: ToBytes + FromBytes + Clone + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Clone + Send + Sync + 'static,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+{
+    let cache = match store.cache() {
+        Some(cache) => cache,
+        None => return store.get(txn, hash),
+    };
+    if let Some(trie) = cache.get(hash) {
+        cache_counter.hits += 1;
+        return Ok(Some(trie));
+    }
+    cache_counter.misses += 1;
+    let maybe_trie = store.get(txn, hash)?;
+    if let Some(trie) = maybe_trie.as_ref() {
+        cache.insert(*hash, trie.to_owned());
+    }
+    Ok(maybe_trie)
+}
+
 /// Returns a value from the corresponding key at a given root in a given store
 pub fn read<K, V, T, S, E>(
     correlation_id: CorrelationId,
@@ -44,8 +115,32 @@ pub fn read<K, V, T, S, E>(
     key: &K,
 ) -> Result<ReadResult<V>, E>
 where
-    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
-    V: ToBytes + FromBytes,
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug + Clone + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Clone + Send + Sync + 'static,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut cache_counter = CacheCounter::default();
+    let result = read_with_counter(correlation_id, txn, store, root, key, &mut cache_counter);
+    if store.cache().is_some() {
+        cache_counter.log(correlation_id, READ);
+    }
+    result
+}
+
+fn read_with_counter<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+    cache_counter: &mut CacheCounter,
+) -> Result<ReadResult<V>, E>
+where
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug + Clone + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Clone + Send + Sync + 'static,
     T: Readable<Handle = S::Handle>,
     S: TrieStore<K, V>,
     S::Error: From<T::Error>,
@@ -54,7 +149,7 @@ where
     let path: Vec<u8> = key.to_bytes()?;
 
     let mut depth: usize = 0;
-    let mut current: Trie<K, V> = match store.get(txn, root)? {
+    let mut current: Trie<K, V> = match get_trie(txn, store, root, cache_counter)? {
         Some(root) => root,
         None => return Ok(ReadResult::RootNotFound),
     };
@@ -100,7 +195,7 @@ where
                     pointer_block[index]
                 };
                 match maybe_pointer {
-                    Some(pointer) => match store.get(txn, pointer.hash())? {
+                    Some(pointer) => match get_trie(txn, store, pointer.hash(), cache_counter)? {
                         Some(next) => {
                             get_counter += 1;
                             depth += 1;
@@ -150,7 +245,7 @@ where
                 let sub_path = &path[depth..depth + affix.len()];
                 if sub_path == affix.as_slice() {
                     get_counter += 1;
-                    match store.get(txn, pointer.hash())? {
+                    match get_trie(txn, store, pointer.hash(), cache_counter)? {
                         Some(next) => {
                             get_counter += 1;
                             depth += affix.len();
@@ -222,8 +317,39 @@ fn scan<K, V, T, S, E>(
     root: &Trie<K, V>,
 ) -> Result<TrieScan<K, V>, E>
 where
-    K: ToBytes + FromBytes + Clone,
-    V: ToBytes + FromBytes + Clone,
+    K: ToBytes + FromBytes + Clone + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Clone + Send + Sync + 'static,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut cache_counter = CacheCounter::default();
+    let result = scan_with_counter(
+        correlation_id,
+        txn,
+        store,
+        key_bytes,
+        root,
+        &mut cache_counter,
+    );
+    if store.cache().is_some() {
+        cache_counter.log(correlation_id, SCAN);
+    }
+    result
+}
+
+fn scan_with_counter<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    key_bytes: &[u8],
+    root: &Trie<K, V>,
+    cache_counter: &mut CacheCounter,
+) -> Result<TrieScan<K, V>, E>
+where
+    K: ToBytes + FromBytes + Clone + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Clone + Send + Sync + 'static,
     T: Readable<Handle = S::Handle>,
     S: TrieStore<K, V>,
     S::Error: From<T::Error>,
@@ -285,7 +411,7 @@ where
                         return Ok(TrieScan::new(Trie::Node { pointer_block }, acc));
                     }
                 };
-                match store.get(txn, pointer.hash())? {
+                match get_trie(txn, store, pointer.hash(), cache_counter)? {
                     Some(next) => {
                         get_counter += 1;
                         current = next;
@@ -333,7 +459,7 @@ where
                     );
                     return Ok(TrieScan::new(Trie::Extension { affix, pointer }, acc));
                 }
-                match store.get(txn, pointer.hash())? {
+                match get_trie(txn, store, pointer.hash(), cache_counter)? {
                     Some(next) => {
                         get_counter += 1;
                         let index = {
@@ -627,8 +753,8 @@ pub fn write<K, V, T, S, E>(
     value: &V,
 ) -> Result<WriteResult, E>
 where
-    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
-    V: ToBytes + FromBytes + Clone + Eq,
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Clone + Eq + Send + Sync + 'static,
     T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
     S: TrieStore<K, V>,
     S::Error: From<T::Error>,
@@ -731,6 +857,646 @@ where
     }
 }
 
+/// An in-memory view of a trie which is being modified by [`write_batch`].
+///
+/// Subtries which have not been touched by the batch stay in the store and are referred to by
+/// their pointer; touched subtries are loaded and modified in place, and are only hashed once
+/// every write in the batch has been applied.
+enum PendingTrie<K, V> {
+    Stored(Pointer),
+    Leaf {
+        key: K,
+        value: V,
+    },
+    Node {
+        children: BTreeMap<u8, PendingTrie<K, V>>,
+    },
+    Extension {
+        affix: Vec<u8>,
+        child: Box<PendingTrie<K, V>>,
+    },
+}
+
+impl<K, V> PendingTrie<K, V> {
+    fn from_trie(trie: Trie<K, V>) -> Self {
+        match trie {
+            Trie::Leaf { key, value } => PendingTrie::Leaf { key, value },
+            Trie::Node { pointer_block } => {
+                let children = pointer_block[..]
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, maybe_pointer)| {
+                        maybe_pointer.map(|pointer| (index as u8, PendingTrie::Stored(pointer)))
+                    })
+                    .collect();
+                PendingTrie::Node { children }
+            }
+            Trie::Extension { affix, pointer } => PendingTrie::Extension {
+                affix,
+                child: Box::new(PendingTrie::Stored(pointer)),
+            },
+        }
+    }
+
+    /// Returns `true` if this trie is known to be identical to the stored trie it was loaded
+    /// from, i.e. none of its children have been replaced.
+    fn is_unmodified(&self) -> bool {
+        fn is_stored<K, V>(trie: &PendingTrie<K, V>) -> bool {
+            if let PendingTrie::Stored(_) = trie {
+                true
+            } else {
+                false
+            }
+        }
+        match self {
+            PendingTrie::Stored(_) => true,
+            PendingTrie::Leaf { .. } => false,
+            PendingTrie::Node { children } => children.values().all(is_stored),
+            PendingTrie::Extension { child, .. } => is_stored(child),
+        }
+    }
+}
+
+/// Inserts a leaf for `key` and `value` into `pending`, which is located at `depth` along `path`.
+///
+/// The resulting shape is the same as the one [`write`] produces, so that writing a set of leaves
+/// as a batch or one at a time yields the same root hash.
+#[allow(clippy::too_many_arguments)]
+fn insert_pending<K, V, T, S, E>(
+    txn: &T,
+    store: &S,
+    pending: PendingTrie<K, V>,
+    depth: usize,
+    path: &[u8],
+    key: &K,
+    value: &V,
+    get_counter: &mut i32,
+) -> Result<PendingTrie<K, V>, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let new_leaf = || PendingTrie::Leaf {
+        key: key.to_owned(),
+        value: value.to_owned(),
+    };
+    match pending {
+        PendingTrie::Stored(pointer) => {
+            *get_counter += 1;
+            let trie = match store.get(txn, pointer.hash())? {
+                Some(trie) => trie,
+                None => panic!(
+                    "No trie value at key: {:?} (reading from path: {:?})",
+                    pointer.hash(),
+                    path
+                ),
+            };
+            match trie {
+                Trie::Leaf {
+                    key: ref leaf_key,
+                    value: ref leaf_value,
+                } if key == leaf_key && value == leaf_value => Ok(PendingTrie::Stored(pointer)),
+                trie => {
+                    let updated = insert_pending::<K, V, T, S, E>(
+                        txn,
+                        store,
+                        PendingTrie::from_trie(trie),
+                        depth,
+                        path,
+                        key,
+                        value,
+                        get_counter,
+                    )?;
+                    if updated.is_unmodified() {
+                        Ok(PendingTrie::Stored(pointer))
+                    } else {
+                        Ok(updated)
+                    }
+                }
+            }
+        }
+        // Updating an existing leaf.
+        PendingTrie::Leaf {
+            key: ref leaf_key, ..
+        } if key == leaf_key => Ok(new_leaf()),
+        // The new leaf shares some common prefix with an existing leaf, so both are placed in a
+        // new node, below an extension holding the rest of the shared path if there is one.
+        PendingTrie::Leaf {
+            key: existing_key,
+            value: existing_value,
+        } => {
+            let existing_path = existing_key.to_bytes()?;
+            let affix = common_prefix(&path[depth..], &existing_path[depth..]);
+            let node_depth = depth + affix.len();
+            let mut children = BTreeMap::new();
+            children.insert(
+                existing_path[node_depth],
+                PendingTrie::Leaf {
+                    key: existing_key,
+                    value: existing_value,
+                },
+            );
+            children.insert(path[node_depth], new_leaf());
+            let new_node = PendingTrie::Node { children };
+            if affix.is_empty() {
+                Ok(new_node)
+            } else {
+                Ok(PendingTrie::Extension {
+                    affix,
+                    child: Box::new(new_node),
+                })
+            }
+        }
+        PendingTrie::Node { mut children } => {
+            let index = {
+                assert!(depth < path.len(), "depth must be < {}", path.len());
+                path[depth]
+            };
+            let child = match children.remove(&index) {
+                Some(child) => insert_pending::<K, V, T, S, E>(
+                    txn,
+                    store,
+                    child,
+                    depth + 1,
+                    path,
+                    key,
+                    value,
+                    get_counter,
+                )?,
+                None => new_leaf(),
+            };
+            children.insert(index, child);
+            Ok(PendingTrie::Node { children })
+        }
+        PendingTrie::Extension { affix, child } => {
+            let shared_length = common_prefix(&affix, &path[depth..]).len();
+            if shared_length == affix.len() {
+                let child = insert_pending::<K, V, T, S, E>(
+                    txn,
+                    store,
+                    *child,
+                    depth + affix.len(),
+                    path,
+                    key,
+                    value,
+                    get_counter,
+                )?;
+                return Ok(PendingTrie::Extension {
+                    affix,
+                    child: Box::new(child),
+                });
+            }
+            // The new leaf diverges from the extension's affix, so the extension is split around
+            // a new node holding both the new leaf and the remainder of the extension.
+            let existing = if shared_length + 1 == affix.len() {
+                *child
+            } else {
+                PendingTrie::Extension {
+                    affix: affix[shared_length + 1..].to_vec(),
+                    child,
+                }
+            };
+            let mut children = BTreeMap::new();
+            children.insert(affix[shared_length], existing);
+            children.insert(path[depth + shared_length], new_leaf());
+            let new_node = PendingTrie::Node { children };
+            if shared_length == 0 {
+                Ok(new_node)
+            } else {
+                Ok(PendingTrie::Extension {
+                    affix: affix[..shared_length].to_vec(),
+                    child: Box::new(new_node),
+                })
+            }
+        }
+    }
+}
+
+/// Hashes every modified trie in `pending` exactly once, children first, appending them to
+/// `new_elements`.  Returns the pointer to `pending`.
+fn hash_pending<K, V>(
+    pending: PendingTrie<K, V>,
+    new_elements: &mut Vec<(Blake2bHash, Trie<K, V>)>,
+) -> Result<Pointer, bytesrepr::Error>
+where
+    K: ToBytes,
+    V: ToBytes,
+{
+    let (trie, is_leaf) = match pending {
+        PendingTrie::Stored(pointer) => return Ok(pointer),
+        PendingTrie::Leaf { key, value } => (Trie::leaf(key, value), true),
+        PendingTrie::Node { children } => {
+            let mut pointer_block = PointerBlock::new();
+            for (index, child) in children {
+                pointer_block[index.into()] = Some(hash_pending(child, new_elements)?);
+            }
+            let pointer_block = Box::new(pointer_block);
+            (Trie::Node { pointer_block }, false)
+        }
+        PendingTrie::Extension { affix, child } => {
+            let pointer = hash_pending(*child, new_elements)?;
+            (Trie::extension(affix, pointer), false)
+        }
+    };
+    let hash = {
+        let trie_bytes = trie.to_bytes()?;
+        Blake2bHash::new(&trie_bytes)
+    };
+    new_elements.push((hash, trie));
+    if is_leaf {
+        Ok(Pointer::LeafPointer(hash))
+    } else {
+        Ok(Pointer::NodePointer(hash))
+    }
+}
+
+/// Writes a batch of key-value pairs to the trie at `root`, returning the new root hash.
+///
+/// Unlike calling [`write`] once per pair, the pairs are applied to an in-memory copy of the
+/// touched part of the trie, so that each modified trie is hashed and put into the store only
+/// once, in its final version.  If a key occurs more than once in `pairs`, the last value wins.
+pub fn write_batch<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+    pairs: &[(K, V)],
+) -> Result<WriteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let start = Instant::now();
+    let mut get_counter: i32 = 0;
+    let mut put_counter: i32 = 0;
+
+    let current_root = match store.get(txn, root)? {
+        None => return Ok(WriteResult::RootNotFound),
+        Some(current_root) => current_root,
+    };
+
+    // Sorting keeps writes to neighbouring keys together; the sort is stable so the last value
+    // given for a key is still the last one applied.
+    let mut sorted_pairs = pairs
+        .iter()
+        .map(|(key, value)| Ok((key.to_bytes()?, key, value)))
+        .collect::<Result<Vec<(Vec<u8>, &K, &V)>, bytesrepr::Error>>()?;
+    sorted_pairs.sort_by(|(left, ..), (right, ..)| left.cmp(right));
+
+    let mut pending = PendingTrie::from_trie(current_root);
+    for (path, key, value) in sorted_pairs {
+        pending = insert_pending::<K, V, T, S, E>(
+            txn,
+            store,
+            pending,
+            0,
+            &path,
+            key,
+            value,
+            &mut get_counter,
+        )?;
+    }
+
+    log_metric(
+        correlation_id,
+        TRIE_STORE_WRITE_BATCH_GETS,
+        GET,
+        GAUGE_METRIC_KEY,
+        f64::from(get_counter),
+    );
+
+    if pending.is_unmodified() {
+        log_duration(
+            correlation_id,
+            TRIE_STORE_WRITE_BATCH_DURATION,
+            WRITE_BATCH,
+            start.elapsed(),
+        );
+        return Ok(WriteResult::AlreadyExists);
+    }
+
+    let mut new_elements: Vec<(Blake2bHash, Trie<K, V>)> = Vec::new();
+    let root_hash = *hash_pending(pending, &mut new_elements)?.hash();
+    for (hash, element) in new_elements.iter() {
+        put_counter += 1;
+        store.put(txn, hash, element)?;
+    }
+    log_metric(
+        correlation_id,
+        TRIE_STORE_WRITE_BATCH_PUTS,
+        PUT,
+        GAUGE_METRIC_KEY,
+        f64::from(put_counter),
+    );
+    log_duration(
+        correlation_id,
+        TRIE_STORE_WRITE_BATCH_DURATION,
+        WRITE_BATCH,
+        start.elapsed(),
+    );
+    Ok(WriteResult::Written(root_hash))
+}
+
+/// The outcome of removing a leaf from a [`PendingTrie`].
+enum PendingRemoval<K, V> {
+    /// The key was not found; holds the (unchanged) trie.
+    NotFound(PendingTrie<K, V>),
+    /// The key was removed; holds what replaces the trie, if anything is left of it.
+    Removed(Option<PendingTrie<K, V>>),
+}
+
+/// Turns the only remaining child of a non-root node, at `index`, into what replaces that node.
+///
+/// A leaf moves up to take the place of the node, while a node or extension is put below an
+/// extension whose affix is `index` followed by the affix of the child extension, if any.
+fn collapse_node<K, V, T, S, E>(
+    txn: &T,
+    store: &S,
+    index: u8,
+    only_child: PendingTrie<K, V>,
+    get_counter: &mut i32,
+) -> Result<PendingTrie<K, V>, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let only_child = match only_child {
+        PendingTrie::Stored(Pointer::NodePointer(hash)) => {
+            *get_counter += 1;
+            match store.get(txn, &hash)? {
+                Some(Trie::Extension { affix, pointer }) => PendingTrie::Extension {
+                    affix,
+                    child: Box::new(PendingTrie::Stored(pointer)),
+                },
+                Some(_) => PendingTrie::Stored(Pointer::NodePointer(hash)),
+                None => panic!("No trie value at key: {:?}", hash),
+            }
+        }
+        only_child => only_child,
+    };
+    match only_child {
+        leaf @ PendingTrie::Stored(Pointer::LeafPointer(_)) | leaf @ PendingTrie::Leaf { .. } => {
+            Ok(leaf)
+        }
+        PendingTrie::Extension { affix, child } => {
+            let affix = iter::once(index).chain(affix).collect();
+            Ok(PendingTrie::Extension { affix, child })
+        }
+        node => Ok(PendingTrie::Extension {
+            affix: vec![index],
+            child: Box::new(node),
+        }),
+    }
+}
+
+/// Removes the leaf for `key` from `pending`, which is located at `depth` along `path`.
+///
+/// Nodes left with a single child are collapsed, so that the resulting shape is the same as if
+/// the key had never been written.  The root node, at depth zero, is never collapsed.
+fn remove_pending<K, V, T, S, E>(
+    txn: &T,
+    store: &S,
+    pending: PendingTrie<K, V>,
+    depth: usize,
+    path: &[u8],
+    key: &K,
+    get_counter: &mut i32,
+) -> Result<PendingRemoval<K, V>, E>
+where
+    K: ToBytes + FromBytes + Eq,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    match pending {
+        PendingTrie::Stored(pointer) => {
+            *get_counter += 1;
+            let trie = match store.get(txn, pointer.hash())? {
+                Some(trie) => trie,
+                None => panic!(
+                    "No trie value at key: {:?} (reading from path: {:?})",
+                    pointer.hash(),
+                    path
+                ),
+            };
+            match remove_pending::<K, V, T, S, E>(
+                txn,
+                store,
+                PendingTrie::from_trie(trie),
+                depth,
+                path,
+                key,
+                get_counter,
+            )? {
+                PendingRemoval::NotFound(_) => {
+                    Ok(PendingRemoval::NotFound(PendingTrie::Stored(pointer)))
+                }
+                removed => Ok(removed),
+            }
+        }
+        PendingTrie::Leaf {
+            key: ref leaf_key, ..
+        } if key == leaf_key => Ok(PendingRemoval::Removed(None)),
+        leaf @ PendingTrie::Leaf { .. } => Ok(PendingRemoval::NotFound(leaf)),
+        PendingTrie::Node { mut children } => {
+            let index = {
+                assert!(depth < path.len(), "depth must be < {}", path.len());
+                path[depth]
+            };
+            let child = match children.remove(&index) {
+                Some(child) => child,
+                None => return Ok(PendingRemoval::NotFound(PendingTrie::Node { children })),
+            };
+            match remove_pending::<K, V, T, S, E>(
+                txn,
+                store,
+                child,
+                depth + 1,
+                path,
+                key,
+                get_counter,
+            )? {
+                PendingRemoval::NotFound(child) => {
+                    children.insert(index, child);
+                    Ok(PendingRemoval::NotFound(PendingTrie::Node { children }))
+                }
+                PendingRemoval::Removed(Some(child)) => {
+                    children.insert(index, child);
+                    Ok(PendingRemoval::Removed(Some(PendingTrie::Node {
+                        children,
+                    })))
+                }
+                PendingRemoval::Removed(None) if depth == 0 || children.len() > 1 => {
+                    Ok(PendingRemoval::Removed(Some(PendingTrie::Node {
+                        children,
+                    })))
+                }
+                PendingRemoval::Removed(None) => match children.into_iter().next() {
+                    Some((only_index, only_child)) => {
+                        let collapsed = collapse_node::<K, V, T, S, E>(
+                            txn,
+                            store,
+                            only_index,
+                            only_child,
+                            get_counter,
+                        )?;
+                        Ok(PendingRemoval::Removed(Some(collapsed)))
+                    }
+                    None => Ok(PendingRemoval::Removed(None)),
+                },
+            }
+        }
+        PendingTrie::Extension { affix, child } => {
+            if !path[depth..].starts_with(&affix) {
+                return Ok(PendingRemoval::NotFound(PendingTrie::Extension {
+                    affix,
+                    child,
+                }));
+            }
+            match remove_pending::<K, V, T, S, E>(
+                txn,
+                store,
+                *child,
+                depth + affix.len(),
+                path,
+                key,
+                get_counter,
+            )? {
+                PendingRemoval::NotFound(child) => {
+                    Ok(PendingRemoval::NotFound(PendingTrie::Extension {
+                        affix,
+                        child: Box::new(child),
+                    }))
+                }
+                // The node below the extension was collapsed into a leaf, which moves up.
+                PendingRemoval::Removed(Some(
+                    leaf @ PendingTrie::Stored(Pointer::LeafPointer(_)),
+                ))
+                | PendingRemoval::Removed(Some(leaf @ PendingTrie::Leaf { .. })) => {
+                    Ok(PendingRemoval::Removed(Some(leaf)))
+                }
+                // The node below the extension was collapsed into another extension, which is
+                // merged into this one.
+                PendingRemoval::Removed(Some(PendingTrie::Extension {
+                    affix: child_affix,
+                    child,
+                })) => Ok(PendingRemoval::Removed(Some(PendingTrie::Extension {
+                    affix: affix.into_iter().chain(child_affix).collect(),
+                    child,
+                }))),
+                PendingRemoval::Removed(Some(child)) => {
+                    Ok(PendingRemoval::Removed(Some(PendingTrie::Extension {
+                        affix,
+                        child: Box::new(child),
+                    })))
+                }
+                PendingRemoval::Removed(None) => Ok(PendingRemoval::Removed(None)),
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeleteResult {
+    Deleted(Blake2bHash),
+    DoesNotExist,
+    RootNotFound,
+}
+
+/// Deletes the leaf for `key` from the trie at `root`, returning the new root hash.
+pub fn delete<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<DeleteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let start = Instant::now();
+    let mut get_counter: i32 = 0;
+    let mut put_counter: i32 = 0;
+
+    let current_root = match store.get(txn, root)? {
+        None => return Ok(DeleteResult::RootNotFound),
+        Some(current_root) => current_root,
+    };
+
+    let path: Vec<u8> = key.to_bytes()?;
+    let removal = remove_pending::<K, V, T, S, E>(
+        txn,
+        store,
+        PendingTrie::from_trie(current_root),
+        0,
+        &path,
+        key,
+        &mut get_counter,
+    )?;
+
+    log_metric(
+        correlation_id,
+        TRIE_STORE_DELETE_GETS,
+        GET,
+        GAUGE_METRIC_KEY,
+        f64::from(get_counter),
+    );
+
+    let new_root = match removal {
+        PendingRemoval::Removed(Some(new_root)) => new_root,
+        PendingRemoval::Removed(None) => unreachable!("the root node is never removed"),
+        PendingRemoval::NotFound(_) => {
+            log_duration(
+                correlation_id,
+                TRIE_STORE_DELETE_DURATION,
+                DELETE,
+                start.elapsed(),
+            );
+            return Ok(DeleteResult::DoesNotExist);
+        }
+    };
+
+    let mut new_elements: Vec<(Blake2bHash, Trie<K, V>)> = Vec::new();
+    let root_hash = *hash_pending(new_root, &mut new_elements)?.hash();
+    for (hash, element) in new_elements.iter() {
+        put_counter += 1;
+        store.put(txn, hash, element)?;
+    }
+    log_metric(
+        correlation_id,
+        TRIE_STORE_DELETE_PUTS,
+        PUT,
+        GAUGE_METRIC_KEY,
+        f64::from(put_counter),
+    );
+    log_duration(
+        correlation_id,
+        TRIE_STORE_DELETE_DURATION,
+        DELETE,
+        start.elapsed(),
+    );
+    Ok(DeleteResult::Deleted(root_hash))
+}
+
 enum KeysIteratorState<K, V, S: TrieStore<K, V>> {
     /// Iterate normally
     Ok,
@@ -882,7 +1648,6 @@ where
 /// Returns the iterator over the keys at a given root hash.
 ///
 /// The root should be the apex of the trie.
-#[allow(dead_code)]
 pub fn keys<'a, 'b, K, V, T, S>(
     correlation_id: CorrelationId,
     txn: &'b T,
@@ -902,7 +1667,6 @@ where
 /// Returns the iterator over the keys in the subtrie matching `prefix`.
 ///
 /// The root should be the apex of the trie.
-#[allow(dead_code)]
 pub fn keys_with_prefix<'a, 'b, K, V, T, S>(
     _correlation_id: CorrelationId,
     txn: &'b T,
@@ -0,0 +1,167 @@
+use super::*;
+
+fn leaves_to_pairs(leaves: &[TestTrie]) -> Vec<(TestKey, TestValue)> {
+    leaves
+        .iter()
+        .map(|leaf| match leaf {
+            Trie::Leaf { key, value } => (*key, *value),
+            _ => panic!("leaves should contain only leaves"),
+        })
+        .collect()
+}
+
+fn deletes_from_n_leaf_tries_had_expected_results<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    leaves: &[TestTrie],
+) -> Result<bool, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let pairs = leaves_to_pairs(leaves);
+    let keys: Vec<TestKey> = pairs.iter().map(|(key, _)| *key).collect();
+    for num_leaves in 0..=pairs.len() {
+        // Delete each key on its own, then every key in turn.
+        for index in 0..num_leaves {
+            if !delete_matches_writes_without_deleted_keys::<_, _, _, _, E>(
+                correlation_id,
+                environment,
+                store,
+                root_hash,
+                &pairs[..num_leaves],
+                &keys[index..=index],
+            )? {
+                return Ok(false);
+            }
+        }
+        if !delete_matches_writes_without_deleted_keys::<_, _, _, _, E>(
+            correlation_id,
+            environment,
+            store,
+            root_hash,
+            &pairs[..num_leaves],
+            &keys[..num_leaves],
+        )? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+#[test]
+fn lmdb_deletes_from_n_leaf_tries_had_expected_results() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+
+    for leaves in &[
+        &TEST_LEAVES[..],
+        &TEST_LEAVES_NON_COLLIDING[..],
+        &TEST_LEAVES_ADJACENTS[..],
+    ] {
+        assert!(
+            deletes_from_n_leaf_tries_had_expected_results::<_, _, error::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                &root_hash,
+                leaves,
+            )
+            .unwrap()
+        );
+    }
+}
+
+#[test]
+fn in_memory_deletes_from_n_leaf_tries_had_expected_results() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    for leaves in &[
+        &TEST_LEAVES[..],
+        &TEST_LEAVES_NON_COLLIDING[..],
+        &TEST_LEAVES_ADJACENTS[..],
+    ] {
+        assert!(
+            deletes_from_n_leaf_tries_had_expected_results::<_, _, in_memory::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                &root_hash,
+                leaves,
+            )
+            .unwrap()
+        );
+    }
+}
+
+#[test]
+fn in_memory_delete_of_all_leaves_yields_empty_root() {
+    let correlation_id = CorrelationId::new();
+    let (empty_root_hash, _) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_LEAVES_LENGTH]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    let mut current_root_hash = root_hash;
+    for (key, _) in leaves_to_pairs(&TEST_LEAVES) {
+        match operations::delete::<TestKey, TestValue, _, _, in_memory::Error>(
+            correlation_id,
+            &mut txn,
+            &context.store,
+            &current_root_hash,
+            &key,
+        )
+        .unwrap()
+        {
+            DeleteResult::Deleted(hash) => current_root_hash = hash,
+            other => panic!("unexpected delete result {:?}", other),
+        }
+        let result = read::<TestKey, TestValue, _, _, in_memory::Error>(
+            correlation_id,
+            &txn,
+            &context.store,
+            &current_root_hash,
+            &key,
+        )
+        .unwrap();
+        assert_eq!(result, ReadResult::NotFound);
+    }
+    assert_eq!(current_root_hash, empty_root_hash);
+}
+
+#[test]
+fn in_memory_delete_of_missing_key_does_not_exist() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[2]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    for (key, _) in leaves_to_pairs(&TEST_LEAVES[2..]) {
+        let result = operations::delete::<TestKey, TestValue, _, _, in_memory::Error>(
+            correlation_id,
+            &mut txn,
+            &context.store,
+            &root_hash,
+            &key,
+        )
+        .unwrap();
+        assert_eq!(result, DeleteResult::DoesNotExist);
+    }
+
+    let result = operations::delete::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &mut txn,
+        &context.store,
+        &Blake2bHash::new(b"missing"),
+        &TestKey([0u8; TEST_KEY_LENGTH]),
+    )
+    .unwrap();
+    assert_eq!(result, DeleteResult::RootNotFound);
+}
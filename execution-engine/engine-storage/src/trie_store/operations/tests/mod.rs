@@ -1,8 +1,10 @@
+mod delete;
 mod keys;
 mod proptests;
 mod read;
 mod scan;
 mod write;
+mod write_batch;
 
 use std::{collections::HashMap, convert};
 
@@ -23,7 +25,7 @@ use crate::{
         self,
         in_memory::InMemoryTrieStore,
         lmdb::LmdbTrieStore,
-        operations::{self, read, write, ReadResult, WriteResult},
+        operations::{self, read, write, DeleteResult, ReadResult, WriteResult},
         TrieStore,
     },
     TEST_MAP_SIZE,
@@ -32,7 +34,7 @@ use crate::{
 const TEST_KEY_LENGTH: usize = 7;
 
 /// A short key type for tests.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct TestKey([u8; TEST_KEY_LENGTH]);
 
 impl ToBytes for TestKey {
@@ -572,8 +574,8 @@ fn check_leaves_exist<K, V, T, S, E>(
     leaves: &[Trie<K, V>],
 ) -> Result<Vec<bool>, E>
 where
-    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
-    V: ToBytes + FromBytes + Eq + Copy,
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug + Clone + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Eq + Copy + Send + Sync + 'static,
     T: Readable<Handle = S::Handle>,
     S: TrieStore<K, V>,
     S::Error: From<T::Error>,
@@ -636,8 +638,8 @@ fn check_leaves<'a, K, V, R, S, E>(
     absent: &[Trie<K, V>],
 ) -> Result<(), E>
 where
-    K: ToBytes + FromBytes + Eq + std::fmt::Debug + Clone + Ord,
-    V: ToBytes + FromBytes + Eq + std::fmt::Debug + Copy,
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug + Clone + Ord + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Eq + std::fmt::Debug + Copy + Send + Sync + 'static,
     R: TransactionSource<'a, Handle = S::Handle>,
     S: TrieStore<K, V>,
     S::Error: From<R::Error>,
@@ -677,8 +679,8 @@ fn write_leaves<'a, K, V, R, S, E>(
     leaves: &[Trie<K, V>],
 ) -> Result<Vec<WriteResult>, E>
 where
-    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
-    V: ToBytes + FromBytes + Clone + Eq,
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Clone + Eq + Send + Sync + 'static,
     R: TransactionSource<'a, Handle = S::Handle>,
     S: TrieStore<K, V>,
     S::Error: From<R::Error>,
@@ -719,8 +721,8 @@ fn check_pairs<'a, K, V, R, S, E>(
     pairs: &[(K, V)],
 ) -> Result<bool, E>
 where
-    K: ToBytes + FromBytes + Eq + std::fmt::Debug + Clone + Ord,
-    V: ToBytes + FromBytes + Eq + std::fmt::Debug + Copy,
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug + Clone + Ord + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Eq + std::fmt::Debug + Copy + Send + Sync + 'static,
     R: TransactionSource<'a, Handle = S::Handle>,
     S: TrieStore<K, V>,
     S::Error: From<R::Error>,
@@ -765,8 +767,8 @@ fn write_pairs<'a, K, V, R, S, E>(
     pairs: &[(K, V)],
 ) -> Result<Vec<Blake2bHash>, E>
 where
-    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
-    V: ToBytes + FromBytes + Clone + Eq,
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Clone + Eq + Send + Sync + 'static,
     R: TransactionSource<'a, Handle = S::Handle>,
     S: TrieStore<K, V>,
     S::Error: From<R::Error>,
@@ -793,6 +795,122 @@ where
     Ok(results)
 }
 
+/// Writes `pairs` to the trie at `root_hash` as a single batch, checks that every pair can be read
+/// back from the resulting root, and that the root is the same as the one obtained by writing the
+/// pairs one at a time.
+fn batch_write_matches_sequential_writes<'a, K, V, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    pairs: &[(K, V)],
+) -> Result<bool, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::hash::Hash + std::fmt::Debug + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug + Send + Sync + 'static,
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let batch_root_hash = {
+        let mut txn = environment.create_read_write_txn()?;
+        let result = operations::write_batch::<_, _, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            root_hash,
+            pairs,
+        )?;
+        txn.commit()?;
+        match result {
+            WriteResult::Written(hash) => hash,
+            WriteResult::AlreadyExists => root_hash.to_owned(),
+            WriteResult::RootNotFound => panic!("write_batch given an invalid root"),
+        }
+    };
+
+    let expected_values: HashMap<&K, &V> = pairs.iter().map(|(k, v)| (k, v)).collect();
+    {
+        let txn = environment.create_read_txn()?;
+        for (key, value) in expected_values {
+            let result = read::<_, _, _, _, E>(correlation_id, &txn, store, &batch_root_hash, key)?;
+            if ReadResult::Found(value.to_owned()) != result {
+                return Ok(false);
+            }
+        }
+    }
+
+    let sequential_root_hash =
+        write_pairs::<_, _, _, _, E>(correlation_id, environment, store, root_hash, pairs)?
+            .last()
+            .cloned()
+            .unwrap_or_else(|| root_hash.to_owned());
+
+    Ok(batch_root_hash == sequential_root_hash)
+}
+
+/// Writes `pairs` to the trie at `root_hash`, deletes `keys_to_delete` from the result, and checks
+/// that the final root is the same as the one obtained by only writing the pairs which were not
+/// deleted.
+fn delete_matches_writes_without_deleted_keys<'a, K, V, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    pairs: &[(K, V)],
+    keys_to_delete: &[K],
+) -> Result<bool, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Clone + Eq + Send + Sync + 'static,
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let written_root_hash =
+        write_pairs::<_, _, _, _, E>(correlation_id, environment, store, root_hash, pairs)?
+            .last()
+            .cloned()
+            .unwrap_or_else(|| root_hash.to_owned());
+
+    let mut deleted_root_hash = written_root_hash;
+    let mut txn = environment.create_read_write_txn()?;
+    for key in keys_to_delete {
+        match operations::delete::<K, V, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            &deleted_root_hash,
+            key,
+        )? {
+            DeleteResult::Deleted(hash) => deleted_root_hash = hash,
+            DeleteResult::DoesNotExist => (),
+            DeleteResult::RootNotFound => panic!("delete given an invalid root"),
+        }
+    }
+    txn.commit()?;
+
+    let remaining_pairs: Vec<(K, V)> = pairs
+        .iter()
+        .filter(|(key, _)| !keys_to_delete.contains(key))
+        .cloned()
+        .collect();
+    let expected_root_hash = write_pairs::<_, _, _, _, E>(
+        correlation_id,
+        environment,
+        store,
+        root_hash,
+        &remaining_pairs,
+    )?
+    .last()
+    .cloned()
+    .unwrap_or_else(|| root_hash.to_owned());
+
+    Ok(deleted_root_hash == expected_root_hash)
+}
+
 fn writes_to_n_leaf_empty_trie_had_expected_results<'a, K, V, R, S, E>(
     correlation_id: CorrelationId,
     environment: &'a R,
@@ -801,8 +919,8 @@ fn writes_to_n_leaf_empty_trie_had_expected_results<'a, K, V, R, S, E>(
     test_leaves: &[Trie<K, V>],
 ) -> Result<Vec<Blake2bHash>, E>
 where
-    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug + Ord,
-    V: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug + Copy,
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug + Ord + Send + Sync + 'static,
+    V: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug + Copy + Send + Sync + 'static,
     R: TransactionSource<'a, Handle = S::Handle>,
     S: TrieStore<K, V>,
     S::Error: From<R::Error>,
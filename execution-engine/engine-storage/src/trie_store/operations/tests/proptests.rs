@@ -76,6 +76,57 @@ fn in_memory_roundtrip_succeeds(pairs: &[(TestKey, TestValue)]) -> bool {
     .unwrap()
 }
 
+fn lmdb_batch_write_matches_sequential_writes(pairs: &[(TestKey, TestValue)]) -> bool {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+
+    batch_write_matches_sequential_writes::<_, _, _, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+        pairs,
+    )
+    .unwrap()
+}
+
+fn in_memory_batch_write_matches_sequential_writes(pairs: &[(TestKey, TestValue)]) -> bool {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    batch_write_matches_sequential_writes::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+        pairs,
+    )
+    .unwrap()
+}
+
+fn in_memory_delete_matches_writes(pairs: &[(TestKey, TestValue)], num_to_delete: usize) -> bool {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let keys_to_delete: Vec<TestKey> = pairs
+        .iter()
+        .take(num_to_delete)
+        .map(|(key, _)| *key)
+        .collect();
+
+    delete_matches_writes_without_deleted_keys::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+        pairs,
+        &keys_to_delete,
+    )
+    .unwrap()
+}
+
 fn test_key_arb() -> impl Strategy<Value = TestKey> {
     array::uniform7(any::<u8>()).prop_map(TestKey)
 }
@@ -94,4 +145,22 @@ proptest! {
     fn prop_lmdb_roundtrip_succeeds(inputs in vec((test_key_arb(), test_value_arb()), get_range())) {
         assert!(lmdb_roundtrip_succeeds(&inputs));
     }
+
+    #[test]
+    fn prop_in_memory_batch_write_matches_sequential_writes(inputs in vec((test_key_arb(), test_value_arb()), get_range())) {
+        assert!(in_memory_batch_write_matches_sequential_writes(&inputs));
+    }
+
+    #[test]
+    fn prop_lmdb_batch_write_matches_sequential_writes(inputs in vec((test_key_arb(), test_value_arb()), get_range())) {
+        assert!(lmdb_batch_write_matches_sequential_writes(&inputs));
+    }
+
+    #[test]
+    fn prop_in_memory_delete_matches_writes(
+        inputs in vec((test_key_arb(), test_value_arb()), get_range()),
+        num_to_delete in 0usize..DEFAULT_MAX_LENGTH,
+    ) {
+        assert!(in_memory_delete_matches_writes(&inputs, num_to_delete));
+    }
 }
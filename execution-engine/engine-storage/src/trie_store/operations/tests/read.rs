@@ -8,8 +8,13 @@
 //! We use two strategies for testing.  See the [`partial_tries`] and
 //! [`full_tries`] modules for more info.
 
+use std::sync::Arc;
+
 use super::*;
-use crate::error::{self, in_memory};
+use crate::{
+    error::{self, in_memory},
+    trie_store::cache::TrieCache,
+};
 
 mod partial_tries {
     //! Here we construct 6 separate "partial" tries, increasing in size
@@ -40,6 +45,37 @@ mod partial_tries {
         }
     }
 
+    #[test]
+    fn lmdb_cached_reads_from_n_leaf_partial_trie_had_expected_results() {
+        for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+            let correlation_id = CorrelationId::new();
+            let (root_hash, tries) = generator().unwrap();
+            let context = LmdbTestContext::new(&tries).unwrap();
+            let cache_size = tries
+                .iter()
+                .map(|hashed_trie| hashed_trie.trie.serialized_length())
+                .sum();
+            let cache = Arc::new(TrieCache::new(cache_size));
+            let store = context.store.clone().with_cache(Arc::clone(&cache));
+            let test_leaves = TEST_LEAVES;
+            let (used, unused) = test_leaves.split_at(num_leaves);
+
+            // The second pass is served from the cache populated by the first.
+            for _ in 0..2 {
+                check_leaves::<_, _, _, _, error::Error>(
+                    correlation_id,
+                    &context.environment,
+                    &store,
+                    &root_hash,
+                    used,
+                    unused,
+                )
+                .unwrap();
+            }
+            assert!(!cache.is_empty());
+        }
+    }
+
     #[test]
     fn in_memory_reads_from_n_leaf_partial_trie_had_expected_results() {
         for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
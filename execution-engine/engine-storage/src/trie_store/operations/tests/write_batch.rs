@@ -0,0 +1,152 @@
+use super::*;
+
+fn leaves_to_pairs(leaves: &[TestTrie]) -> Vec<(TestKey, TestValue)> {
+    leaves
+        .iter()
+        .map(|leaf| match leaf {
+            Trie::Leaf { key, value } => (*key, *value),
+            _ => panic!("leaves should contain only leaves"),
+        })
+        .collect()
+}
+
+fn batch_writes_to_n_leaf_tries_match_sequential_writes<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    leaves: &[TestTrie],
+) -> Result<bool, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let pairs = leaves_to_pairs(leaves);
+    for num_pairs in 0..=pairs.len() {
+        if !batch_write_matches_sequential_writes::<_, _, _, _, E>(
+            correlation_id,
+            environment,
+            store,
+            root_hash,
+            &pairs[..num_pairs],
+        )? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+#[test]
+fn lmdb_batch_writes_match_sequential_writes() {
+    let correlation_id = CorrelationId::new();
+    for generator in &TEST_TRIE_GENERATORS {
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+
+        for leaves in &[
+            &TEST_LEAVES[..],
+            &TEST_LEAVES_UPDATED[..],
+            &TEST_LEAVES_NON_COLLIDING[..],
+            &TEST_LEAVES_ADJACENTS[..],
+        ] {
+            assert!(
+                batch_writes_to_n_leaf_tries_match_sequential_writes::<_, _, error::Error>(
+                    correlation_id,
+                    &context.environment,
+                    &context.store,
+                    &root_hash,
+                    leaves,
+                )
+                .unwrap()
+            );
+        }
+    }
+}
+
+#[test]
+fn in_memory_batch_writes_match_sequential_writes() {
+    let correlation_id = CorrelationId::new();
+    for generator in &TEST_TRIE_GENERATORS {
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+
+        for leaves in &[
+            &TEST_LEAVES[..],
+            &TEST_LEAVES_UPDATED[..],
+            &TEST_LEAVES_NON_COLLIDING[..],
+            &TEST_LEAVES_ADJACENTS[..],
+        ] {
+            assert!(
+                batch_writes_to_n_leaf_tries_match_sequential_writes::<_, _, in_memory::Error>(
+                    correlation_id,
+                    &context.environment,
+                    &context.store,
+                    &root_hash,
+                    leaves,
+                )
+                .unwrap()
+            );
+        }
+    }
+}
+
+#[test]
+fn in_memory_batch_write_uses_last_value_for_duplicate_keys() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let mut pairs = leaves_to_pairs(&TEST_LEAVES);
+    pairs.extend(leaves_to_pairs(&TEST_LEAVES_UPDATED));
+
+    assert!(
+        batch_write_matches_sequential_writes::<_, _, _, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            &pairs,
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn in_memory_noop_batch_write_already_exists() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_LEAVES_LENGTH]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    for pairs in &[Vec::new(), leaves_to_pairs(&TEST_LEAVES)] {
+        let result = operations::write_batch::<_, _, _, _, in_memory::Error>(
+            correlation_id,
+            &mut txn,
+            &context.store,
+            &root_hash,
+            pairs,
+        )
+        .unwrap();
+        assert_eq!(result, WriteResult::AlreadyExists);
+    }
+}
+
+#[test]
+fn in_memory_batch_write_to_missing_root_fails() {
+    let correlation_id = CorrelationId::new();
+    let (_root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    let result = operations::write_batch::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &mut txn,
+        &context.store,
+        &Blake2bHash::new(b"missing"),
+        &leaves_to_pairs(&TEST_LEAVES),
+    )
+    .unwrap();
+    assert_eq!(result, WriteResult::RootNotFound);
+}
@@ -0,0 +1,89 @@
+//! A sled-backed trie store.
+//!
+//! Unlike the [lmdb](../lmdb/index.html) store, the underlying files grow on demand, so there is no
+//! map size to configure.
+//!
+//! # Usage
+//!
+//! ```
+//! use casperlabs_engine_storage::store::Store;
+//! use casperlabs_engine_storage::transaction_source::{Transaction, TransactionSource};
+//! use casperlabs_engine_storage::transaction_source::sled::SledEnvironment;
+//! use casperlabs_engine_storage::trie::Trie;
+//! use casperlabs_engine_storage::trie_store::sled::SledTrieStore;
+//! use types::bytesrepr::ToBytes;
+//! use engine_shared::newtypes::Blake2bHash;
+//! use tempfile::tempdir;
+//!
+//! let leaf = Trie::Leaf { key: vec![0u8, 0, 0], value: b"val_1".to_vec() };
+//! let leaf_hash = Blake2bHash::new(&leaf.to_bytes().unwrap());
+//!
+//! let tmp_dir = tempdir().unwrap();
+//! let env = SledEnvironment::new(&tmp_dir.path().to_path_buf()).unwrap();
+//! let store = SledTrieStore::new(&env, None);
+//!
+//! {
+//!     let mut txn = env.create_read_write_txn().unwrap();
+//!     store.put(&mut txn, &leaf_hash, &leaf).unwrap();
+//!     txn.commit().unwrap();
+//! }
+//!
+//! {
+//!     let txn = env.create_read_txn().unwrap();
+//!     assert_eq!(Some(leaf), store.get(&txn, &leaf_hash).unwrap());
+//!     txn.commit().unwrap();
+//! }
+//!
+//! tmp_dir.close().unwrap();
+//! ```
+
+use std::sync::Arc;
+
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::{
+    error,
+    store::Store,
+    transaction_source::sled::{SledDatabase, SledEnvironment},
+    trie::Trie,
+    trie_store::{self, cache::TrieCache, TrieStore},
+};
+
+/// A sled-backed trie store.
+#[derive(Debug, Clone)]
+pub struct SledTrieStore {
+    db: SledDatabase,
+    cache: Option<Arc<TrieCache>>,
+}
+
+impl SledTrieStore {
+    pub fn new(env: &SledEnvironment, maybe_name: Option<&str>) -> Self {
+        let name = maybe_name
+            .map(|name| format!("{}-{}", trie_store::NAME, name))
+            .unwrap_or_else(|| String::from(trie_store::NAME));
+        let db = env.database(&name);
+        SledTrieStore { db, cache: None }
+    }
+
+    /// Sets the cache of decoded [`Trie`] values consulted when reading from this store.
+    pub fn with_cache(mut self, cache: Arc<TrieCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+}
+
+impl<K, V> Store<Blake2bHash, Trie<K, V>> for SledTrieStore {
+    type Error = error::Error;
+
+    type Handle = SledDatabase;
+
+    fn handle(&self) -> Self::Handle {
+        self.db.clone()
+    }
+}
+
+impl<K, V> TrieStore<K, V> for SledTrieStore {
+    fn cache(&self) -> Option<&TrieCache> {
+        self.cache.as_deref()
+    }
+}
@@ -61,6 +61,23 @@ fn lmdb_roundtrip_succeeds(inputs: Vec<Trie<Key, StoredValue>>) -> bool {
     ret
 }
 
+fn sled_roundtrip_succeeds(inputs: Vec<Trie<Key, StoredValue>>) -> bool {
+    use crate::{transaction_source::sled::SledEnvironment, trie_store::sled::SledTrieStore};
+
+    let tmp_dir = tempdir().unwrap();
+    let env = SledEnvironment::new(&tmp_dir.path().to_path_buf()).unwrap();
+    let store = SledTrieStore::new(&env, None);
+
+    let inputs: BTreeMap<Blake2bHash, Trie<Key, StoredValue>> = inputs
+        .into_iter()
+        .map(|trie| (Blake2bHash::new(&trie.to_bytes().unwrap()), trie))
+        .collect();
+
+    let ret = store_tests::roundtrip_succeeds(&env, &store, inputs).unwrap();
+    tmp_dir.close().unwrap();
+    ret
+}
+
 proptest! {
     #[test]
     fn prop_in_memory_roundtrip_succeeds(v in vec(trie_arb(), get_range())) {
@@ -71,4 +88,9 @@ proptest! {
     fn prop_lmdb_roundtrip_succeeds(v in vec(trie_arb(), get_range())) {
         assert!(lmdb_roundtrip_succeeds(v))
     }
+
+    #[test]
+    fn prop_sled_roundtrip_succeeds(v in vec(trie_arb(), get_range())) {
+        assert!(sled_roundtrip_succeeds(v))
+    }
 }
@@ -0,0 +1,420 @@
+//! Integrity checking of the [`Trie`] values reachable from a state root.
+//!
+//! Unlike reads, which trust the store and panic or return wrong data when it is corrupt, the
+//! checks here read every reachable value as raw bytes and report each inconsistency found.
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+};
+
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+use types::bytesrepr::{self, FromBytes, ToBytes};
+
+use crate::{
+    transaction_source::Readable,
+    trie::{Pointer, Trie},
+    trie_store::TrieStore,
+};
+
+/// An inconsistency found while verifying a trie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// A value which is pointed to, or the root itself, is not in the store.
+    Missing {
+        parent: Option<Blake2bHash>,
+        hash: Blake2bHash,
+    },
+    /// The hash of a value's bytes differs from the hash it is stored at.
+    HashMismatch {
+        expected: Blake2bHash,
+        actual: Blake2bHash,
+    },
+    /// A value's bytes could not be deserialized.
+    Undecodable {
+        hash: Blake2bHash,
+        error: bytesrepr::Error,
+    },
+    /// A leaf pointer points to a node or extension, or a node pointer points to a leaf, or an
+    /// extension points to another extension.
+    PointerMismatch {
+        parent: Blake2bHash,
+        hash: Blake2bHash,
+    },
+    /// A leaf's serialized key does not start with the path taken to reach it.
+    LeafKeyMismatch { hash: Blake2bHash, path: Vec<u8> },
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Issue::Missing {
+                parent: Some(parent),
+                hash,
+            } => write!(f, "{} is missing, but is pointed to by {}", hash, parent),
+            Issue::Missing { parent: None, hash } => write!(f, "root {} is missing", hash),
+            Issue::HashMismatch { expected, actual } => write!(
+                f,
+                "value stored at {} has hash {} instead",
+                expected, actual
+            ),
+            Issue::Undecodable { hash, error } => write!(
+                f,
+                "value stored at {} could not be decoded: {}",
+                hash, error
+            ),
+            Issue::PointerMismatch { parent, hash } => write!(
+                f,
+                "{} points to {} with a pointer of the wrong kind",
+                parent, hash
+            ),
+            Issue::LeafKeyMismatch { hash, path } => write!(
+                f,
+                "key of leaf {} does not start with its path {}",
+                hash,
+                path.iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect::<String>()
+            ),
+        }
+    }
+}
+
+/// The result of verifying the trie under a single root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationReport {
+    pub root: Blake2bHash,
+    pub leaves: usize,
+    pub nodes: usize,
+    pub extensions: usize,
+    pub issues: Vec<Issue>,
+}
+
+impl VerificationReport {
+    fn new(root: Blake2bHash) -> Self {
+        VerificationReport {
+            root,
+            leaves: 0,
+            nodes: 0,
+            extensions: 0,
+            issues: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if no issues were found.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl Display for VerificationReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "root {}: {} leaves, {} nodes, {} extensions, {} issues",
+            self.root,
+            self.leaves,
+            self.nodes,
+            self.extensions,
+            self.issues.len()
+        )?;
+        for issue in &self.issues {
+            write!(f, "\n  {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+/// A value still to be verified, along with how it was reached.
+struct PendingValue {
+    parent: Option<Blake2bHash>,
+    pointer: Option<Pointer>,
+    from_extension: bool,
+    hash: Blake2bHash,
+    path: Vec<u8>,
+}
+
+/// Checks every [`Trie`] value reachable from `root`.
+///
+/// Each value's bytes are rehashed and compared with the hash it is stored at, every pointer is
+/// checked to refer to a value of the right kind which is present in `store`, and every leaf's key
+/// is checked to start with the path from `root` to that leaf.  The walk continues past any issue
+/// found so that all of them are reported, and only fails if the store itself cannot be read.
+pub fn verify<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+) -> Result<VerificationReport, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    E: From<T::Error> + From<bytesrepr::Error>,
+{
+    let mut report = VerificationReport::new(*root);
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    let mut pending = vec![PendingValue {
+        parent: None,
+        pointer: None,
+        from_extension: false,
+        hash: *root,
+        path: Vec::new(),
+    }];
+
+    while let Some(PendingValue {
+        parent,
+        pointer,
+        from_extension,
+        hash,
+        path,
+    }) = pending.pop()
+    {
+        let bytes = match txn.read(store.handle(), &hash.to_bytes()?)? {
+            Some(bytes) => bytes,
+            None => {
+                report.issues.push(Issue::Missing { parent, hash });
+                continue;
+            }
+        };
+
+        let actual = Blake2bHash::new(&bytes);
+        if actual != hash {
+            report.issues.push(Issue::HashMismatch {
+                expected: hash,
+                actual,
+            });
+        }
+
+        let trie: Trie<K, V> = match bytesrepr::deserialize(bytes) {
+            Ok(trie) => trie,
+            Err(error) => {
+                report.issues.push(Issue::Undecodable { hash, error });
+                continue;
+            }
+        };
+
+        let is_expected_kind = match (&pointer, &trie) {
+            (None, _) => true,
+            (Some(Pointer::LeafPointer(_)), Trie::Leaf { .. }) => true,
+            (Some(Pointer::NodePointer(_)), Trie::Node { .. }) => true,
+            (Some(Pointer::NodePointer(_)), Trie::Extension { .. }) => !from_extension,
+            _ => false,
+        };
+        if let (false, Some(parent)) = (is_expected_kind, parent) {
+            report.issues.push(Issue::PointerMismatch { parent, hash });
+        }
+
+        // A value reachable along several paths would be reported on each of them, so each value
+        // is only walked once.
+        if !visited.insert(hash) {
+            continue;
+        }
+
+        match trie {
+            Trie::Leaf { key, .. } => {
+                report.leaves += 1;
+                if !key.to_bytes()?.starts_with(&path) {
+                    report.issues.push(Issue::LeafKeyMismatch { hash, path });
+                }
+            }
+            Trie::Node { pointer_block } => {
+                report.nodes += 1;
+                for (index, maybe_pointer) in pointer_block[..].iter().enumerate().rev() {
+                    if let Some(child_pointer) = maybe_pointer {
+                        let mut child_path = path.clone();
+                        child_path.push(index as u8);
+                        pending.push(PendingValue {
+                            parent: Some(hash),
+                            pointer: Some(*child_pointer),
+                            from_extension: false,
+                            hash: *child_pointer.hash(),
+                            path: child_path,
+                        });
+                    }
+                }
+            }
+            Trie::Extension { affix, pointer } => {
+                report.extensions += 1;
+                let mut child_path = path;
+                child_path.extend_from_slice(&affix);
+                pending.push(PendingValue {
+                    parent: Some(hash),
+                    pointer: Some(pointer),
+                    from_extension: true,
+                    hash: *pointer.hash(),
+                    path: child_path,
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::stored_value::StoredValue;
+    use types::{CLValue, Key};
+
+    use super::*;
+    use crate::{
+        error::in_memory,
+        global_state::in_memory::InMemoryGlobalState,
+        store::Store,
+        transaction_source::{Transaction, TransactionSource, Writable},
+        trie::Pointer,
+    };
+
+    type TestTrie = Trie<Key, StoredValue>;
+
+    fn create_leaf(byte: u8) -> TestTrie {
+        let value = CLValue::from_t(u64::from(byte)).unwrap();
+        Trie::leaf(Key::Hash([byte; 32]), StoredValue::CLValue(value))
+    }
+
+    fn hash_of(trie: &TestTrie) -> Blake2bHash {
+        Blake2bHash::new(&trie.to_bytes().unwrap())
+    }
+
+    /// Writes `bytes` to `state` at `hash`, bypassing the usual hashing of [`Store::put`].
+    fn write_raw(state: &InMemoryGlobalState, hash: &Blake2bHash, bytes: &[u8]) {
+        let mut txn = state.environment.create_read_write_txn().unwrap();
+        let handle = Store::<Blake2bHash, TestTrie>::handle(&*state.trie_store);
+        txn.write(handle, &hash.to_bytes().unwrap(), bytes).unwrap();
+        txn.commit().unwrap();
+    }
+
+    fn write_trie(state: &InMemoryGlobalState, trie: &TestTrie) -> Blake2bHash {
+        let hash = hash_of(trie);
+        write_raw(state, &hash, &trie.to_bytes().unwrap());
+        hash
+    }
+
+    fn verify_root(state: &InMemoryGlobalState, root: &Blake2bHash) -> VerificationReport {
+        let txn = state.environment.create_read_txn().unwrap();
+        let report = verify::<Key, StoredValue, _, _, in_memory::Error>(
+            CorrelationId::new(),
+            &txn,
+            &*state.trie_store,
+            root,
+        )
+        .unwrap();
+        txn.commit().unwrap();
+        report
+    }
+
+    #[test]
+    fn should_verify_valid_trie() {
+        let pairs: Vec<(Key, StoredValue)> = (0u8..32)
+            .map(|i| {
+                let value = CLValue::from_t(u64::from(i)).unwrap();
+                (Key::Hash([i; 32]), StoredValue::CLValue(value))
+            })
+            .collect();
+        let (state, root) = InMemoryGlobalState::from_pairs(CorrelationId::new(), &pairs).unwrap();
+
+        let report = verify_root(&state, &root);
+        assert!(report.is_ok(), "{}", report);
+        assert_eq!(report.leaves, pairs.len());
+        assert!(report.nodes > 0);
+
+        let report = verify_root(&state, &state.empty_root_hash);
+        assert!(report.is_ok(), "{}", report);
+        assert_eq!((report.leaves, report.nodes), (0, 1));
+    }
+
+    #[test]
+    fn should_report_missing_root_and_child() {
+        let state = InMemoryGlobalState::empty().unwrap();
+        let missing = hash_of(&create_leaf(1));
+
+        let report = verify_root(&state, &missing);
+        assert_eq!(
+            report.issues,
+            vec![Issue::Missing {
+                parent: None,
+                hash: missing
+            }]
+        );
+
+        let node = Trie::node(&[(1, Pointer::LeafPointer(missing))]);
+        let root = write_trie(&state, &node);
+        let report = verify_root(&state, &root);
+        assert_eq!(
+            report.issues,
+            vec![Issue::Missing {
+                parent: Some(root),
+                hash: missing
+            }]
+        );
+    }
+
+    #[test]
+    fn should_report_hash_mismatch() {
+        let state = InMemoryGlobalState::empty().unwrap();
+        let leaf = create_leaf(1);
+        let wrong_hash = hash_of(&create_leaf(2));
+        write_raw(&state, &wrong_hash, &leaf.to_bytes().unwrap());
+
+        let node = Trie::node(&[(1, Pointer::LeafPointer(wrong_hash))]);
+        let root = write_trie(&state, &node);
+        let report = verify_root(&state, &root);
+        assert_eq!(
+            report.issues,
+            vec![Issue::HashMismatch {
+                expected: wrong_hash,
+                actual: hash_of(&leaf),
+            }]
+        );
+    }
+
+    #[test]
+    fn should_report_undecodable_value() {
+        let state = InMemoryGlobalState::empty().unwrap();
+        let garbage = [0xffu8; 8];
+        let hash = Blake2bHash::new(&garbage);
+        write_raw(&state, &hash, &garbage);
+
+        let report = verify_root(&state, &hash);
+        match report.issues.as_slice() {
+            [Issue::Undecodable { hash: actual, .. }] if *actual == hash => (),
+            issues => panic!("unexpected issues: {:?}", issues),
+        }
+    }
+
+    #[test]
+    fn should_report_pointer_of_wrong_kind() {
+        let state = InMemoryGlobalState::empty().unwrap();
+        let leaf_hash = write_trie(&state, &create_leaf(1));
+
+        let node = Trie::node(&[(1, Pointer::NodePointer(leaf_hash))]);
+        let root = write_trie(&state, &node);
+        let report = verify_root(&state, &root);
+        assert_eq!(
+            report.issues,
+            vec![Issue::PointerMismatch {
+                parent: root,
+                hash: leaf_hash
+            }]
+        );
+    }
+
+    #[test]
+    fn should_report_leaf_under_wrong_path() {
+        let state = InMemoryGlobalState::empty().unwrap();
+        let leaf_hash = write_trie(&state, &create_leaf(1));
+
+        // The first byte of a serialized `Key::Hash` is its tag, 1, so it can't live at index 2.
+        let node = Trie::node(&[(2, Pointer::LeafPointer(leaf_hash))]);
+        let root = write_trie(&state, &node);
+        let report = verify_root(&state, &root);
+        assert_eq!(
+            report.issues,
+            vec![Issue::LeafKeyMismatch {
+                hash: leaf_hash,
+                path: vec![2]
+            }]
+        );
+    }
+}
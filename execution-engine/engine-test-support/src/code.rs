@@ -1,15 +1,38 @@
 use std::path::{Path, PathBuf};
 
+#[cfg(feature = "native")]
+use engine_core::runtime::NativeEntryPoint;
+use types::contracts::ContractVersion;
+
 use crate::Hash;
 
 /// Represents the types of session or payment code.
+///
+/// Stored contracts are called at the given entry point.
 pub enum Code {
     /// The filesystem path of compiled Wasm code.
     Path(PathBuf),
     /// A named key providing the location of a stored contract.
     NamedKey(String, String),
-    /// A hash providing the location of a stored contract.
+    /// A hash providing the location of a stored contract, i.e. its
+    /// [`ContractHash`](types::ContractHash).
     Hash(Hash, String),
+    /// A named key providing the location of a stored contract package, and the version of the
+    /// contract in it to call, or `None` for the latest version.
+    VersionedNamedKey(String, Option<ContractVersion>, String),
+    /// A hash providing the location of a stored contract package, i.e. its
+    /// [`ContractPackageHash`](types::ContractPackageHash), and the version of the contract in it
+    /// to call, or `None` for the latest version.
+    VersionedHash(Hash, Option<ContractVersion>, String),
+    /// A natively compiled entry point, run in-process in place of Wasm session code.
+    ///
+    /// Native session code is run after the deploy's payment code and is charged a fee, as Wasm
+    /// session code is, but only for the gas used by the host functions it calls.  It can't be
+    /// used as payment code.
+    ///
+    /// This variant is only available if the `native` feature is enabled.
+    #[cfg(feature = "native")]
+    Native(NativeEntryPoint),
 }
 
 // Note: can't just `impl<T: AsRef<Path>> From<T> for Code` because the compiler complains about
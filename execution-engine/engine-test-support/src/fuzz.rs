@@ -0,0 +1,437 @@
+//! Property-based fuzzing of the entry points of a stored contract.
+//!
+//! A [`Fuzzer`] runs random sequences of calls to the entry points of a contract stored in a
+//! [`TestContext`], passing random arguments of the declared types, and checks user-supplied
+//! invariants on global state after each call.  If an invariant is violated, the failing sequence
+//! is shrunk to a minimal reproduction which is returned as a [`FuzzFailure`].
+//!
+//! This module is only available if the `fuzz` feature is enabled.
+
+use std::{cell::RefCell, fmt, result};
+
+use proptest::{
+    collection::vec,
+    prelude::any,
+    prop_oneof,
+    sample::select,
+    strategy::{BoxedStrategy, Just, Strategy},
+    test_runner::{Config, TestCaseError, TestError, TestRunner},
+};
+
+use types::{
+    bytesrepr::ToBytes,
+    gens::{cl_value_arb, key_arb, u128_arb, u256_arb, u512_arb, uref_arb},
+    CLType, CLValue, ContractHash, EntryPoint, Key, RuntimeArgs,
+};
+
+use crate::{
+    AccountHash, Code, Error, Result, Session, SessionBuilder, Snapshot, TestContext,
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const DEFAULT_CASES: u32 = 64;
+const DEFAULT_MAX_CALLS: usize = 8;
+const MAX_COLLECTION_LEN: usize = 8;
+// Out of every ten arguments, one is given a value of a random type rather than the declared one.
+const TYPED_ARG_WEIGHT: u32 = 9;
+const MISTYPED_ARG_WEIGHT: u32 = 1;
+
+type Invariant = Box<dyn Fn(&TestContext) -> result::Result<(), String>>;
+
+/// A call to an entry point of the contract being fuzzed.
+#[derive(Clone, Debug)]
+pub struct EntryPointCall {
+    entry_point: String,
+    args: RuntimeArgs,
+}
+
+impl EntryPointCall {
+    /// Returns the name of the entry point called.
+    pub fn entry_point(&self) -> &str {
+        &self.entry_point
+    }
+
+    /// Returns the arguments passed to the entry point.
+    pub fn args(&self) -> &RuntimeArgs {
+        &self.args
+    }
+}
+
+/// A minimal sequence of calls which violates one of the invariants of a [`Fuzzer`].
+#[derive(Clone, Debug)]
+pub struct FuzzFailure {
+    calls: Vec<EntryPointCall>,
+    reason: String,
+}
+
+impl FuzzFailure {
+    /// Returns the sequence of calls which, run from the initial state of the [`TestContext`],
+    /// violates an invariant after its last call.
+    pub fn calls(&self) -> &[EntryPointCall] {
+        &self.calls
+    }
+
+    /// Returns the reason the invariant was violated.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.reason)?;
+        writeln!(
+            f,
+            "minimal failing sequence of {} call(s):",
+            self.calls.len()
+        )?;
+        for call in &self.calls {
+            writeln!(f, "  {}({:?})", call.entry_point, call.args)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fuzzes the entry points of a contract stored in a [`TestContext`].
+///
+/// Every sequence of calls is run from the state of the context when the `Fuzzer` was constructed.
+/// Calls are not expected to succeed; a call which fails is simply not committed.
+pub struct Fuzzer {
+    context: TestContext,
+    initial_state: Snapshot,
+    contract_hash: ContractHash,
+    entry_points: Vec<EntryPoint>,
+    account: AccountHash,
+    invariants: Vec<(String, Invariant)>,
+    cases: u32,
+    max_calls: usize,
+}
+
+impl Fuzzer {
+    /// Constructs a new `Fuzzer` of all the entry points of the contract stored under
+    /// `contract_hash` in `context`.
+    ///
+    /// Returns an [`Error`] if there is no such contract.
+    pub fn new(context: TestContext, contract_hash: ContractHash) -> Result<Self> {
+        let contract = context
+            .query_key(Key::Hash(contract_hash), &[] as &[&str])?
+            .into_contract()?;
+        let entry_points = contract.entry_points().clone().take_entry_points();
+        if entry_points.is_empty() {
+            return Err(Error::from(String::from("contract has no entry points")));
+        }
+        let initial_state = context.snapshot();
+        Ok(Fuzzer {
+            context,
+            initial_state,
+            contract_hash,
+            entry_points,
+            account: DEFAULT_ACCOUNT_ADDR,
+            invariants: Vec::new(),
+            cases: DEFAULT_CASES,
+            max_calls: DEFAULT_MAX_CALLS,
+        })
+    }
+
+    /// Returns `self` with `invariant` added to those checked after each call.
+    ///
+    /// The invariant should return an `Err` describing the violation if the global state of the
+    /// context is invalid.
+    pub fn with_invariant<F>(mut self, name: &str, invariant: F) -> Self
+    where
+        F: Fn(&TestContext) -> result::Result<(), String> + 'static,
+    {
+        self.invariants
+            .push((name.to_string(), Box::new(invariant)));
+        self
+    }
+
+    /// Returns `self` with the calls made from the given account, which otherwise defaults to
+    /// [`DEFAULT_ACCOUNT_ADDR`].
+    pub fn with_account(mut self, account: AccountHash) -> Self {
+        self.account = account;
+        self
+    }
+
+    /// Returns `self` with the number of sequences of calls to run set, which otherwise defaults
+    /// to 64.
+    pub fn with_cases(mut self, cases: u32) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Returns `self` with the maximum number of calls in a sequence set, which otherwise defaults
+    /// to 8.
+    pub fn with_max_calls(mut self, max_calls: usize) -> Self {
+        self.max_calls = max_calls;
+        self
+    }
+
+    /// Runs random sequences of calls, checking the invariants after each call.
+    ///
+    /// Returns a [`FuzzFailure`] holding the shrunk sequence of calls if an invariant is violated.
+    pub fn run(self) -> result::Result<(), FuzzFailure> {
+        let strategy = vec(
+            entry_point_call_arb(self.entry_points.clone()),
+            1..=self.max_calls.max(1),
+        );
+        let mut runner = TestRunner::new(Config::with_cases(self.cases));
+        let Fuzzer {
+            context,
+            initial_state,
+            contract_hash,
+            account,
+            invariants,
+            ..
+        } = self;
+        let context = RefCell::new(context);
+
+        let result = runner.run(&strategy, |calls| {
+            let mut context = context.borrow_mut();
+            context.rollback(&initial_state);
+            for call in calls {
+                context.run(session(contract_hash, account, call));
+                for (name, invariant) in &invariants {
+                    invariant(&context).map_err(|error| {
+                        TestCaseError::fail(format!("invariant '{}' violated: {}", name, error))
+                    })?;
+                }
+            }
+            Ok(())
+        });
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(TestError::Fail(reason, calls)) => Err(FuzzFailure {
+                calls,
+                reason: reason.to_string(),
+            }),
+            Err(TestError::Abort(reason)) => Err(FuzzFailure {
+                calls: Vec::new(),
+                reason: reason.to_string(),
+            }),
+        }
+    }
+}
+
+fn session(contract_hash: ContractHash, account: AccountHash, call: EntryPointCall) -> Session {
+    SessionBuilder::new(Code::Hash(contract_hash, call.entry_point), call.args)
+        .with_address(account)
+        .with_authorization_keys(&[account])
+        .without_expect_success()
+        .build()
+}
+
+/// Returns a strategy for calls to one of `entry_points`, where every argument is passed a
+/// [`CLValue`] of its declared type, except for a small minority passed a value of a random type.
+pub fn entry_point_call_arb(
+    entry_points: Vec<EntryPoint>,
+) -> impl Strategy<Value = EntryPointCall> {
+    select(entry_points).prop_flat_map(|entry_point| {
+        let arg_names = entry_point
+            .args()
+            .iter()
+            .map(|parameter| parameter.name().to_string())
+            .collect::<Vec<_>>();
+        let values = entry_point
+            .args()
+            .iter()
+            .map(|parameter| {
+                prop_oneof![
+                    TYPED_ARG_WEIGHT => cl_value_of_type_arb(parameter.cl_type().clone()),
+                    MISTYPED_ARG_WEIGHT => cl_value_arb(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let entry_point_name = entry_point.name().to_string();
+        values.prop_map(move |values: Vec<CLValue>| {
+            let mut args = RuntimeArgs::new();
+            for (name, value) in arg_names.iter().zip(values) {
+                args.insert_cl_value(name.clone(), value);
+            }
+            EntryPointCall {
+                entry_point: entry_point_name.clone(),
+                args,
+            }
+        })
+    })
+}
+
+/// Returns a strategy for [`CLValue`]s of the given type.
+///
+/// A parameter declared as `CLType::Any` is passed a value of a random type.  Where `CLType::Any`
+/// is nested in another type, the unit value is used in its place.
+fn cl_value_of_type_arb(cl_type: CLType) -> BoxedStrategy<CLValue> {
+    if cl_type == CLType::Any {
+        return cl_value_arb().boxed();
+    }
+    serialized_value_arb(&cl_type)
+        .prop_map(move |bytes| CLValue::from_components(cl_type.clone(), bytes))
+        .boxed()
+}
+
+/// Returns a strategy for the serialized form of values of the given type.
+fn serialized_value_arb(cl_type: &CLType) -> BoxedStrategy<Vec<u8>> {
+    match cl_type {
+        CLType::Bool => serialized_arb(any::<bool>()),
+        CLType::I32 => serialized_arb(any::<i32>()),
+        CLType::I64 => serialized_arb(any::<i64>()),
+        CLType::U8 => serialized_arb(any::<u8>()),
+        CLType::U32 => serialized_arb(any::<u32>()),
+        CLType::U64 => serialized_arb(any::<u64>()),
+        CLType::U128 => serialized_arb(u128_arb()),
+        CLType::U256 => serialized_arb(u256_arb()),
+        CLType::U512 => serialized_arb(u512_arb()),
+        CLType::Unit | CLType::Any => Just(Vec::new()).boxed(),
+        CLType::String => serialized_arb(".{0,32}"),
+        CLType::Key => serialized_arb(key_arb()),
+        CLType::URef => serialized_arb(uref_arb()),
+        // `None` is serialized as the tag 0, and `Some` as the tag 1 followed by its value
+        CLType::Option(inner) => prop_oneof![
+            Just(vec![0]),
+            serialized_value_arb(inner).prop_map(|value| concat(vec![vec![1], value])),
+        ]
+        .boxed(),
+        CLType::List(inner) => vec(serialized_value_arb(inner), 0..MAX_COLLECTION_LEN)
+            .prop_map(length_prefixed)
+            .boxed(),
+        CLType::FixedList(inner, len) => vec(serialized_value_arb(inner), *len as usize)
+            .prop_map(concat)
+            .boxed(),
+        // `Err` is serialized as the tag 0, and `Ok` as the tag 1, followed by their value
+        CLType::Result { ok, err } => prop_oneof![
+            serialized_value_arb(err).prop_map(|value| concat(vec![vec![0], value])),
+            serialized_value_arb(ok).prop_map(|value| concat(vec![vec![1], value])),
+        ]
+        .boxed(),
+        CLType::Map { key, value } => vec(
+            (serialized_value_arb(key), serialized_value_arb(value)),
+            0..MAX_COLLECTION_LEN,
+        )
+        .prop_map(|entries| {
+            length_prefixed(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| concat(vec![key, value]))
+                    .collect(),
+            )
+        })
+        .boxed(),
+        CLType::Tuple1([t1]) => serialized_value_arb(t1),
+        CLType::Tuple2([t1, t2]) => (serialized_value_arb(t1), serialized_value_arb(t2))
+            .prop_map(|(t1, t2)| concat(vec![t1, t2]))
+            .boxed(),
+        CLType::Tuple3([t1, t2, t3]) => (
+            serialized_value_arb(t1),
+            serialized_value_arb(t2),
+            serialized_value_arb(t3),
+        )
+            .prop_map(|(t1, t2, t3)| concat(vec![t1, t2, t3]))
+            .boxed(),
+    }
+}
+
+fn serialized_arb<T: ToBytes + fmt::Debug>(
+    strategy: impl Strategy<Value = T> + 'static,
+) -> BoxedStrategy<Vec<u8>> {
+    strategy
+        .prop_map(|value| value.to_bytes().expect("should serialize"))
+        .boxed()
+}
+
+fn concat(parts: Vec<Vec<u8>>) -> Vec<u8> {
+    parts.into_iter().flatten().collect()
+}
+
+/// Concatenates serialized `items` after their count, as collections are serialized.
+fn length_prefixed(items: Vec<Vec<u8>>) -> Vec<u8> {
+    let len = items.len() as u32;
+    concat(vec![
+        len.to_bytes().expect("should serialize"),
+        concat(items),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{
+        strategy::{Strategy, ValueTree},
+        test_runner::TestRunner,
+    };
+
+    use std::collections::BTreeMap;
+
+    use types::{
+        bytesrepr::FromBytes, CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess,
+        EntryPointType, Key, Parameter, URef, U128, U512,
+    };
+
+    use super::entry_point_call_arb;
+
+    fn entry_point(name: &str, args: Vec<Parameter>) -> EntryPoint {
+        EntryPoint::new(
+            name,
+            args,
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        )
+    }
+
+    #[test]
+    fn should_generate_calls_with_declared_args() {
+        let entry_points = vec![
+            entry_point("inc", vec![]),
+            entry_point("set", vec![Parameter::new("value", CLType::U64)]),
+        ];
+        let strategy = entry_point_call_arb(entry_points);
+        let mut runner = TestRunner::default();
+        for _ in 0..32 {
+            let call = strategy.new_tree(&mut runner).unwrap().current();
+            match call.entry_point() {
+                "inc" => assert!(call.args().is_empty()),
+                "set" => {
+                    assert_eq!(call.args().len(), 1);
+                    assert!(call.args().get("value").is_some());
+                }
+                other => panic!("unexpected entry point {}", other),
+            }
+        }
+    }
+
+    /// Returns the `CLType` of `T` and a check that a `CLValue` is a well formed `T`.
+    fn declared<T: CLTyped + FromBytes>() -> (CLType, fn(CLValue) -> bool) {
+        (T::cl_type(), |cl_value| cl_value.into_t::<T>().is_ok())
+    }
+
+    #[test]
+    fn should_generate_mostly_well_formed_args_of_declared_type() {
+        let declared_types = vec![
+            declared::<String>(),
+            declared::<U512>(),
+            declared::<Option<Key>>(),
+            declared::<Vec<URef>>(),
+            declared::<[u8; 32]>(),
+            declared::<Result<(), u32>>(),
+            declared::<BTreeMap<String, i64>>(),
+            declared::<(bool, i32, U128)>(),
+        ];
+        let mut runner = TestRunner::default();
+        for (declared_type, is_well_formed) in declared_types {
+            let strategy = entry_point_call_arb(vec![entry_point(
+                "call",
+                vec![Parameter::new("arg", declared_type.clone())],
+            )]);
+            let mut typed = 0;
+            for _ in 0..64 {
+                let call = strategy.new_tree(&mut runner).unwrap().current();
+                let arg = call.args().get("arg").cloned().expect("should have arg");
+                if *arg.cl_type() == declared_type {
+                    assert!(is_well_formed(arg), "malformed {:?}", declared_type);
+                    typed += 1;
+                }
+            }
+            assert!(typed >= 32, "{} of 64 args are {:?}", typed, declared_type);
+        }
+    }
+}
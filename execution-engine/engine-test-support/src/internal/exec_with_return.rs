@@ -5,11 +5,11 @@ use engine_core::{
         executable_deploy_item::ExecutableDeployItem, execution_effect::ExecutionEffect,
         EngineConfig, EngineState,
     },
-    execution::{self, AddressGenerator},
+    execution::{self, AddressGenerator, Interrupt, OutputRecorder},
     runtime::{self, Runtime},
     runtime_context::RuntimeContext,
 };
-use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_grpc_server::engine_server::ExecutionEngineService;
 use engine_shared::{gas::Gas, newtypes::CorrelationId};
 use engine_storage::{global_state::StateProvider, protocol_data::ProtocolData};
 use engine_wasm_prep::Preprocessor;
@@ -18,7 +18,7 @@ use types::{
     ProtocolVersion, RuntimeArgs, URef, U512,
 };
 
-use crate::internal::{utils, WasmTestBuilder, DEFAULT_WASM_COSTS};
+use crate::internal::{utils, WasmTestBuilder, DEFAULT_WASM_COSTS, DEFAULT_WASM_LIMITS};
 
 /// This function allows executing the contract stored in the given `wasm_file`, while capturing the
 /// output. It is essentially the same functionality as `Executor::exec`, but the return value of
@@ -85,7 +85,13 @@ where
         let mint = builder.get_mint_contract_hash();
         let pos = builder.get_mint_contract_hash();
         let standard_payment = builder.get_standard_payment_contract_hash();
-        ProtocolData::new(*DEFAULT_WASM_COSTS, mint, pos, standard_payment)
+        ProtocolData::new(
+            *DEFAULT_WASM_COSTS,
+            *DEFAULT_WASM_LIMITS,
+            mint,
+            pos,
+            standard_payment,
+        )
     };
 
     let context = RuntimeContext::new(
@@ -116,8 +122,9 @@ where
     };
 
     let wasm_costs = *DEFAULT_WASM_COSTS;
+    let wasm_limits = *DEFAULT_WASM_LIMITS;
 
-    let preprocessor = Preprocessor::new(wasm_costs);
+    let preprocessor = Preprocessor::new(wasm_costs, wasm_limits);
     let parity_module = builder
         .get_engine_state()
         .get_module(
@@ -130,16 +137,19 @@ where
         )
         .expect("should get wasm module");
 
+    let (module_bytes, instrumented_module) = parity_module.take_module().into_parts();
     let (instance, memory) =
-        runtime::instance_and_memory(parity_module.clone().take_module(), protocol_version)
+        runtime::instance_and_memory(instrumented_module, protocol_version, &wasm_limits)
             .expect("should be able to make wasm instance from module");
 
     let mut runtime = Runtime::new(
         config,
         Default::default(),
         memory,
-        parity_module.take_module(),
+        module_bytes,
         context,
+        Interrupt::new(),
+        OutputRecorder::new(),
     );
 
     match instance.invoke_export(entry_point_name, &[], &mut runtime) {
@@ -14,7 +14,7 @@ use engine_core::engine_state::{
     run_genesis_request::RunGenesisRequest,
 };
 use engine_shared::{motes::Motes, newtypes::Blake2bHash, test_utils};
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{wasm_costs::WasmCosts, wasm_limits::WasmLimits};
 use types::{account::AccountHash, ProtocolVersion, U512};
 
 use super::{DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE};
@@ -54,6 +54,7 @@ lazy_static! {
     pub static ref DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
     pub static ref DEFAULT_PAYMENT: U512 = 100_000_000.into();
     pub static ref DEFAULT_WASM_COSTS: WasmCosts = test_utils::wasm_costs_mock();
+    pub static ref DEFAULT_WASM_LIMITS: WasmLimits = WasmLimits::default();
     pub static ref DEFAULT_EXEC_CONFIG: ExecConfig = {
         let mint_installer_bytes;
         let pos_installer_bytes;
@@ -69,6 +70,7 @@ lazy_static! {
             standard_payment_installer_bytes,
             DEFAULT_ACCOUNTS.clone(),
             *DEFAULT_WASM_COSTS,
+            *DEFAULT_WASM_LIMITS,
         )
     };
     pub static ref DEFAULT_GENESIS_CONFIG: GenesisConfig = {
@@ -1,11 +1,11 @@
 use engine_grpc_server::engine_server::{
     ipc::{
         ChainSpec_ActivationPoint, ChainSpec_CostTable_WasmCosts, ChainSpec_UpgradePoint,
-        DeployCode, UpgradeRequest,
+        ChainSpec_WasmLimits, DeployCode, UpgradeRequest,
     },
     state,
 };
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{wasm_costs::WasmCosts, wasm_limits::WasmLimits};
 use types::ProtocolVersion;
 
 pub struct UpgradeRequestBuilder {
@@ -14,6 +14,7 @@ pub struct UpgradeRequestBuilder {
     new_protocol_version: state::ProtocolVersion,
     upgrade_installer: DeployCode,
     new_costs: Option<ChainSpec_CostTable_WasmCosts>,
+    new_limits: Option<ChainSpec_WasmLimits>,
     activation_point: ChainSpec_ActivationPoint,
 }
 
@@ -54,10 +55,16 @@ impl UpgradeRequestBuilder {
         new_costs.set_max_stack_height(wasm_costs.max_stack_height);
         new_costs.set_mem(wasm_costs.mem);
         new_costs.set_memcpy(wasm_costs.memcpy);
+        new_costs.set_memfill(wasm_costs.memfill);
         self.new_costs = Some(new_costs);
         self
     }
 
+    pub fn with_new_limits(mut self, wasm_limits: WasmLimits) -> Self {
+        self.new_limits = Some(wasm_limits.into());
+        self
+    }
+
     pub fn with_activation_point(mut self, rank: u64) -> Self {
         self.activation_point = {
             let mut ret = ChainSpec_ActivationPoint::new();
@@ -79,6 +86,9 @@ impl UpgradeRequestBuilder {
                 upgrade_point.set_new_costs(cost_table);
             }
         }
+        if let Some(new_limits) = self.new_limits {
+            upgrade_point.set_new_limits(new_limits);
+        }
         upgrade_point.set_protocol_version(self.new_protocol_version);
         upgrade_point.set_upgrade_installer(self.upgrade_installer);
 
@@ -97,6 +107,7 @@ impl Default for UpgradeRequestBuilder {
             new_protocol_version: Default::default(),
             upgrade_installer: Default::default(),
             new_costs: None,
+            new_limits: None,
             activation_point: Default::default(),
         }
     }
@@ -20,8 +20,8 @@ use types::Key;
 
 use crate::internal::{
     DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_GENESIS_TIMESTAMP,
-    DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
-    STANDARD_PAYMENT_INSTALL_CONTRACT,
+    DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS, DEFAULT_WASM_LIMITS, MINT_INSTALL_CONTRACT,
+    POS_INSTALL_CONTRACT, STANDARD_PAYMENT_INSTALL_CONTRACT,
 };
 
 lazy_static! {
@@ -109,12 +109,14 @@ pub fn create_exec_config(accounts: Vec<GenesisAccount>) -> ExecConfig {
     let proof_of_stake_installer_bytes = read_wasm_file_bytes(POS_INSTALL_CONTRACT);
     let standard_payment_installer_bytes = read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT);
     let wasm_costs = *DEFAULT_WASM_COSTS;
+    let wasm_limits = *DEFAULT_WASM_LIMITS;
     ExecConfig::new(
         mint_installer_bytes,
         proof_of_stake_installer_bytes,
         standard_payment_installer_bytes,
         accounts,
         wasm_costs,
+        wasm_limits,
     )
 }
 
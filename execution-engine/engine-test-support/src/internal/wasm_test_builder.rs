@@ -2,31 +2,37 @@ use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
     ffi::OsStr,
-    fs,
-    path::PathBuf,
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
     rc::Rc,
     sync::Arc,
 };
 
-use grpc::RequestOptions;
 use lmdb::DatabaseFlags;
 use log::LevelFilter;
 
+#[cfg(feature = "native")]
+use engine_core::runtime::NativeEntryPoint;
 use engine_core::{
     engine_state::{
-        execute_request::ExecuteRequest, execution_result::ExecutionResult,
-        run_genesis_request::RunGenesisRequest, EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
+        self,
+        contract_abi::{ContractAbi, ContractAbiRequest, ContractAbiResult},
+        execute_request::ExecuteRequest,
+        execution_result::ExecutionResult,
+        run_genesis_request::RunGenesisRequest,
+        EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
     },
-    execution,
+    execution::{self, Output, OutputRecorder},
 };
 use engine_grpc_server::engine_server::{
     ipc::{
         CommitRequest, CommitResponse, GenesisResponse, QueryRequest, UpgradeRequest,
         UpgradeResponse,
     },
-    ipc_grpc::ExecutionEngineService,
     mappings::{MappingError, TransformMap},
     transforms::TransformEntry,
+    ExecutionEngineService,
 };
 use engine_shared::{
     account::Account,
@@ -39,15 +45,19 @@ use engine_shared::{
     transform::Transform,
 };
 use engine_storage::{
+    error::{self, in_memory},
     global_state::{in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, StateProvider},
     protocol_data_store::lmdb::LmdbProtocolDataStore,
+    snapshot::{self, SnapshotSummary},
     transaction_source::lmdb::LmdbEnvironment,
     trie_store::lmdb::LmdbTrieStore,
 };
 use types::{
     account::AccountHash,
     bytesrepr::{self},
-    CLValue, Contract, ContractHash, ContractWasm, Key, URef, U512,
+    contracts::ContractVersionInfo,
+    CLValue, Contract, ContractHash, ContractPackage, ContractPackageHash, ContractWasm, Key,
+    ProtocolVersion, URef, U512,
 };
 
 use crate::internal::utils;
@@ -70,6 +80,8 @@ pub struct WasmTestBuilder<S> {
     engine_state: Rc<EngineState<S>>,
     /// [`ExecutionResult`] is wrapped in [`Rc`] to work around a missing [`Clone`] implementation
     exec_responses: Vec<Vec<Rc<ExecutionResult>>>,
+    /// Output of each exec call, i.e. `exec_outputs[0]` is for first exec call etc.
+    exec_outputs: Vec<Output>,
     upgrade_responses: Vec<UpgradeResponse>,
     genesis_hash: Option<Vec<u8>>,
     post_state_hash: Option<Vec<u8>>,
@@ -109,6 +121,7 @@ impl Default for InMemoryWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            exec_outputs: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
@@ -130,6 +143,7 @@ impl<S> Clone for WasmTestBuilder<S> {
         WasmTestBuilder {
             engine_state: Rc::clone(&self.engine_state),
             exec_responses: self.exec_responses.clone(),
+            exec_outputs: self.exec_outputs.clone(),
             upgrade_responses: self.upgrade_responses.clone(),
             genesis_hash: self.genesis_hash.clone(),
             post_state_hash: self.post_state_hash.clone(),
@@ -170,6 +184,47 @@ impl InMemoryWasmTestBuilder {
             ..Default::default()
         }
     }
+
+    /// Creates a builder whose global state is restored from the snapshot at `snapshot_path`
+    /// rather than by running genesis.
+    ///
+    /// The snapshot must include the protocol data for `protocol_version`.
+    pub fn from_snapshot<P: AsRef<Path>>(
+        snapshot_path: P,
+        engine_config: EngineConfig,
+        protocol_version: ProtocolVersion,
+    ) -> Self {
+        let global_state = InMemoryGlobalState::empty().expect("should create global state");
+        let summary = snapshot::import::<_, _, _, _, in_memory::Error>(
+            &*global_state.environment,
+            &*global_state.trie_store,
+            &*global_state.protocol_data_store,
+            open_snapshot(snapshot_path.as_ref()),
+        )
+        .expect("should import snapshot");
+        let mut builder = Self::new(global_state, engine_config, summary.state_root.to_vec());
+        builder.load_genesis_properties(protocol_version);
+        builder
+    }
+
+    /// Writes a snapshot of the current post-state, along with the protocol data for each of
+    /// `protocol_versions`, to `snapshot_path`.
+    pub fn export_snapshot<P: AsRef<Path>>(
+        &self,
+        snapshot_path: P,
+        protocol_versions: &[ProtocolVersion],
+    ) -> SnapshotSummary {
+        let global_state = self.engine_state.state();
+        snapshot::export::<_, _, _, _, in_memory::Error>(
+            &*global_state.environment,
+            &*global_state.trie_store,
+            &*global_state.protocol_data_store,
+            self.post_state_root(),
+            protocol_versions,
+            create_snapshot(snapshot_path.as_ref()),
+        )
+        .expect("should export snapshot")
+    }
 }
 
 impl LmdbWasmTestBuilder {
@@ -198,6 +253,7 @@ impl LmdbWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            exec_outputs: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
@@ -259,6 +315,7 @@ impl LmdbWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            exec_outputs: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: None,
             post_state_hash: Some(post_state_hash),
@@ -272,6 +329,52 @@ impl LmdbWasmTestBuilder {
         }
     }
 
+    /// Creates a builder whose global state in `data_dir` is restored from the snapshot at
+    /// `snapshot_path` rather than by running genesis.
+    ///
+    /// The snapshot must include the protocol data for `protocol_version`.
+    pub fn from_snapshot<T: AsRef<OsStr> + ?Sized, P: AsRef<Path>>(
+        data_dir: &T,
+        snapshot_path: P,
+        engine_config: EngineConfig,
+        protocol_version: ProtocolVersion,
+    ) -> Self {
+        let mut builder = Self::new_with_config(data_dir, engine_config);
+        let summary = {
+            let global_state = builder.engine_state.state();
+            snapshot::import::<_, _, _, _, error::Error>(
+                &*global_state.environment,
+                &*global_state.trie_store,
+                &*global_state.protocol_data_store,
+                open_snapshot(snapshot_path.as_ref()),
+            )
+            .expect("should import snapshot")
+        };
+        builder.genesis_hash = Some(summary.state_root.to_vec());
+        builder.post_state_hash = Some(summary.state_root.to_vec());
+        builder.load_genesis_properties(protocol_version);
+        builder
+    }
+
+    /// Writes a snapshot of the current post-state, along with the protocol data for each of
+    /// `protocol_versions`, to `snapshot_path`.
+    pub fn export_snapshot<P: AsRef<Path>>(
+        &self,
+        snapshot_path: P,
+        protocol_versions: &[ProtocolVersion],
+    ) -> SnapshotSummary {
+        let global_state = self.engine_state.state();
+        snapshot::export::<_, _, _, _, error::Error>(
+            &*global_state.environment,
+            &*global_state.trie_store,
+            &*global_state.protocol_data_store,
+            self.post_state_root(),
+            protocol_versions,
+            create_snapshot(snapshot_path.as_ref()),
+        )
+        .expect("should export snapshot")
+    }
+
     fn create_and_get_global_state_dir<T: AsRef<OsStr> + ?Sized>(data_dir: &T) -> PathBuf {
         let global_state_path = {
             let mut path = PathBuf::from(data_dir);
@@ -295,6 +398,7 @@ where
         WasmTestBuilder {
             engine_state: result.0.engine_state,
             exec_responses: Vec::new(),
+            exec_outputs: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: result.0.genesis_hash,
             post_state_hash: result.0.post_state_hash,
@@ -315,11 +419,7 @@ where
             .try_into()
             .expect("could not parse");
 
-        let genesis_response = self
-            .engine_state
-            .run_genesis(RequestOptions::new(), run_genesis_request_proto)
-            .wait_drop_metadata()
-            .expect("Unable to get genesis response");
+        let genesis_response = self.engine_state.run_genesis(run_genesis_request_proto);
         if genesis_response.has_failed_deploy() {
             panic!(
                 "genesis failure: {:?}",
@@ -354,6 +454,33 @@ where
         self
     }
 
+    /// Sets the system contract hashes and the genesis account from the current post-state, as
+    /// though genesis had been run with `protocol_version` and had resulted in that state.
+    fn load_genesis_properties(&mut self, protocol_version: ProtocolVersion) {
+        let protocol_data = self
+            .engine_state
+            .get_protocol_data(protocol_version)
+            .expect("should read protocol data")
+            .expect("should have protocol data stored");
+        let genesis_account = match self.query(None, Key::Account(SYSTEM_ACCOUNT_ADDR), &[]) {
+            Ok(StoredValue::Account(account)) => account,
+            other => panic!("should have system account but received {:?}", other),
+        };
+
+        self.mint_contract_hash = Some(protocol_data.mint());
+        self.pos_contract_hash = Some(protocol_data.proof_of_stake());
+        self.standard_payment_hash = Some(protocol_data.standard_payment());
+        self.genesis_account = Some(genesis_account);
+    }
+
+    fn post_state_root(&self) -> Blake2bHash {
+        let post_state_hash = self
+            .post_state_hash
+            .as_ref()
+            .expect("builder must have a post-state hash");
+        Blake2bHash::try_from(post_state_hash.as_slice()).expect("should be a valid hash")
+    }
+
     pub fn query(
         &self,
         maybe_post_state: Option<Vec<u8>>,
@@ -368,11 +495,7 @@ where
 
         let query_request = create_query_request(post_state, base_key, path_vec);
 
-        let mut query_response = self
-            .engine_state
-            .query(RequestOptions::new(), query_request)
-            .wait_drop_metadata()
-            .expect("should get query response");
+        let mut query_response = self.engine_state.query(query_request);
 
         if query_response.has_failure() {
             return Err(query_response.take_failure());
@@ -380,7 +503,38 @@ where
         bytesrepr::deserialize(query_response.take_success()).map_err(|err| format!("{}", err))
     }
 
-    pub fn exec(&mut self, mut exec_request: ExecuteRequest) -> &mut Self {
+    pub fn exec(&mut self, exec_request: ExecuteRequest) -> &mut Self {
+        self.exec_with(exec_request, |engine_state, exec_request, output| {
+            engine_state.run_execute_with_output(CorrelationId::new(), exec_request, output)
+        })
+    }
+
+    /// Runs an execute request as [`exec()`](WasmTestBuilder::exec) does, but with
+    /// `native_entry_point` run as the session code of each deploy.
+    #[cfg(feature = "native")]
+    pub fn exec_native(
+        &mut self,
+        exec_request: ExecuteRequest,
+        native_entry_point: NativeEntryPoint,
+    ) -> &mut Self {
+        self.exec_with(exec_request, |engine_state, exec_request, output| {
+            engine_state.run_native_execute(
+                CorrelationId::new(),
+                exec_request,
+                native_entry_point,
+                output,
+            )
+        })
+    }
+
+    fn exec_with<F>(&mut self, mut exec_request: ExecuteRequest, run: F) -> &mut Self
+    where
+        F: FnOnce(
+            &EngineState<S>,
+            ExecuteRequest,
+            OutputRecorder,
+        ) -> Result<Vec<ExecutionResult>, engine_state::Error>,
+    {
         let exec_request = {
             let hash = self
                 .post_state_hash
@@ -390,10 +544,10 @@ where
                 hash.as_slice().try_into().expect("expected a valid hash");
             exec_request
         };
-        let exec_response = self
-            .engine_state
-            .run_execute(CorrelationId::new(), exec_request);
+        let output = OutputRecorder::new();
+        let exec_response = run(&self.engine_state, exec_request, output.clone());
         assert!(exec_response.is_ok());
+        self.exec_outputs.push(output.take());
         // Parse deploy results
         let execution_results = exec_response.as_ref().unwrap();
         // Cache transformations
@@ -429,10 +583,7 @@ where
     ) -> CommitResponse {
         let commit_request = create_commit_request(&prestate_hash, &effects);
 
-        self.engine_state
-            .commit(RequestOptions::new(), commit_request)
-            .wait_drop_metadata()
-            .expect("Should have commit response")
+        self.engine_state.commit(commit_request)
     }
 
     /// Runs a commit request, expects a successful response, and
@@ -473,11 +624,7 @@ where
             upgrade_request.set_parent_state_hash(hash.to_vec());
             upgrade_request
         };
-        let upgrade_response = self
-            .engine_state
-            .upgrade(RequestOptions::new(), upgrade_request.clone())
-            .wait_drop_metadata()
-            .expect("should upgrade");
+        let upgrade_response = self.engine_state.upgrade(upgrade_request.clone());
 
         let upgrade_success = upgrade_response.get_success();
         self.post_state_hash = Some(upgrade_success.get_post_state_hash().to_vec());
@@ -567,6 +714,13 @@ where
             .expect("Should have post-state hash.")
     }
 
+    /// Sets the post-state hash which subsequent calls run against, e.g. to return to an earlier
+    /// state.
+    pub fn set_post_state_hash(&mut self, post_state_hash: Vec<u8>) -> &mut Self {
+        self.post_state_hash = Some(post_state_hash);
+        self
+    }
+
     pub fn get_engine_state(&self) -> &EngineState<S> {
         &self.engine_state
     }
@@ -579,6 +733,11 @@ where
         self.exec_responses.get(index)
     }
 
+    /// Returns the output of the deploys run by the exec call at `index`.
+    pub fn get_exec_output(&self, index: usize) -> Option<&Output> {
+        self.exec_outputs.get(index)
+    }
+
     pub fn get_exec_responses_count(&self) -> usize {
         self.exec_responses.len()
     }
@@ -639,6 +798,58 @@ where
         }
     }
 
+    pub fn get_contract_package(
+        &self,
+        contract_package_hash: ContractPackageHash,
+    ) -> Option<ContractPackage> {
+        let contract_package_value: StoredValue = self
+            .query(None, contract_package_hash.into(), &[])
+            .expect("should have contract package value");
+
+        if let StoredValue::ContractPackage(contract_package) = contract_package_value {
+            Some(contract_package)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_contract_versions(
+        &self,
+        contract_package_hash: ContractPackageHash,
+    ) -> Vec<ContractVersionInfo> {
+        self.get_contract_package(contract_package_hash)
+            .expect("should have contract package")
+            .list_versions()
+    }
+
+    /// Returns the ABI of the contract stored under `key`, which may be either a contract hash or
+    /// a contract package hash, as seen at `protocol_version`.
+    pub fn get_contract_abi(
+        &self,
+        key: Key,
+        protocol_version: ProtocolVersion,
+    ) -> Result<ContractAbi, String> {
+        let post_state = self
+            .post_state_hash
+            .clone()
+            .expect("builder must have a post-state hash");
+        let state_hash = post_state
+            .as_slice()
+            .try_into()
+            .expect("should convert post-state hash");
+        let contract_abi_request = ContractAbiRequest::new(state_hash, key, protocol_version);
+
+        match self
+            .engine_state
+            .get_contract_abi(CorrelationId::new(), contract_abi_request)
+        {
+            Ok(ContractAbiResult::Success(contract_abi)) => Ok(contract_abi),
+            Ok(ContractAbiResult::ValueNotFound(message)) => Err(message),
+            Ok(ContractAbiResult::RootNotFound) => Err("Root not found".to_string()),
+            Err(error) => Err(format!("{:?}", error)),
+        }
+    }
+
     pub fn get_contract_wasm(&self, contract_hash: ContractHash) -> Option<ContractWasm> {
         let contract_value: StoredValue = self
             .query(None, contract_hash.into(), &[])
@@ -707,6 +918,18 @@ fn create_commit_request(
 }
 
 #[allow(clippy::implicit_hasher)]
+fn open_snapshot(path: &Path) -> BufReader<File> {
+    let file = File::open(path)
+        .unwrap_or_else(|error| panic!("should open snapshot {}: {}", path.display(), error));
+    BufReader::new(file)
+}
+
+fn create_snapshot(path: &Path) -> BufWriter<File> {
+    let file = File::create(path)
+        .unwrap_or_else(|error| panic!("should create snapshot {}: {}", path.display(), error));
+    BufWriter::new(file)
+}
+
 fn get_genesis_transforms(genesis_response: &GenesisResponse) -> AdditiveMap<Key, Transform> {
     let commit_transforms: TransformMap = genesis_response
         .get_success()
@@ -66,18 +66,23 @@
 mod account;
 mod code;
 mod error;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
 // This module is not intended to be used by third party crates.
 #[doc(hidden)]
 pub mod internal;
+mod run_result;
 mod session;
 mod test_context;
 mod value;
 
 pub use account::Account;
 pub use code::Code;
+pub use engine_shared::{additive_map::AdditiveMap, transform::Transform};
 pub use error::{Error, Result};
+pub use run_result::RunResult;
 pub use session::{Session, SessionBuilder, SessionTransferInfo};
-pub use test_context::{TestContext, TestContextBuilder};
+pub use test_context::{Snapshot, TestContext, TestContextBuilder};
 pub use types::account::AccountHash;
 pub use value::Value;
 
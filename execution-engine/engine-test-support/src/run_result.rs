@@ -0,0 +1,105 @@
+use engine_core::{
+    engine_state::{execution_result::ExecutionResult, Error as EngineStateError},
+    execution::{Error as ExecutionError, Output},
+};
+use engine_shared::{additive_map::AdditiveMap, transform::Transform};
+use types::{ApiError, CLValue, Key, U512};
+
+/// The result of running a [`Session`](crate::Session) in a [`TestContext`](crate::TestContext).
+#[derive(Clone, Debug)]
+pub struct RunResult {
+    cost: U512,
+    transforms: AdditiveMap<Key, Transform>,
+    returned_value: Option<CLValue>,
+    printed: Vec<String>,
+    error: Option<String>,
+    api_error: Option<ApiError>,
+}
+
+impl RunResult {
+    pub(crate) fn new(execution_result: &ExecutionResult, output: Output) -> Self {
+        let api_error = match execution_result.as_error() {
+            Some(EngineStateError::Exec(ExecutionError::Revert(api_error))) => Some(*api_error),
+            _ => None,
+        };
+        RunResult {
+            cost: execution_result.cost().value(),
+            transforms: execution_result.effect().transforms.clone(),
+            returned_value: output.returned,
+            printed: output.printed,
+            error: execution_result.as_error().map(ToString::to_string),
+            api_error,
+        }
+    }
+
+    /// Returns `true` if the session ran without error.
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns the gas cost of running the session, including payment.
+    pub fn cost(&self) -> U512 {
+        self.cost
+    }
+
+    /// Returns the transforms the session applies to global state.
+    pub fn transforms(&self) -> &AdditiveMap<Key, Transform> {
+        &self.transforms
+    }
+
+    /// Returns the value the session passed to `runtime::ret`, if any.
+    ///
+    /// Note that calling `runtime::ret` ends the session with an error, and so the session must be
+    /// built with [`without_expect_success()`](crate::SessionBuilder::without_expect_success).
+    pub fn returned_value(&self) -> Option<&CLValue> {
+        self.returned_value.as_ref()
+    }
+
+    /// Returns the lines printed with `runtime::print`, in order.
+    ///
+    /// These are only captured if the `test-support` feature is enabled.
+    pub fn printed(&self) -> &[String] {
+        &self.printed
+    }
+
+    /// Returns the error the session ended with, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Returns the [`ApiError`] the session reverted with, if any.
+    pub fn api_error(&self) -> Option<ApiError> {
+        self.api_error
+    }
+
+    /// Returns the name of the [`ApiError`] the session reverted with, if any, e.g.
+    /// "InvalidArgument" or "User(3)".
+    pub fn api_error_name(&self) -> Option<String> {
+        self.api_error.map(api_error_name)
+    }
+}
+
+/// Extracts the name of `api_error` from its `Debug` representation, e.g. "InvalidArgument" from
+/// "ApiError::InvalidArgument [3]".
+fn api_error_name(api_error: ApiError) -> String {
+    let debug = format!("{:?}", api_error);
+    let name = debug.trim_start_matches("ApiError::");
+    match name.rfind(" [") {
+        Some(index) => name[..index].to_string(),
+        None => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::ApiError;
+
+    use super::api_error_name;
+
+    #[test]
+    fn should_name_api_errors() {
+        assert_eq!(api_error_name(ApiError::InvalidArgument), "InvalidArgument");
+        assert_eq!(api_error_name(ApiError::User(3)), "User(3)");
+        assert_eq!(api_error_name(ApiError::Mint(1)), "Mint(1)");
+    }
+}
@@ -1,6 +1,8 @@
 use rand::Rng;
 
 use engine_core::engine_state::execute_request::ExecuteRequest;
+#[cfg(feature = "native")]
+use engine_core::runtime::NativeEntryPoint;
 use types::{runtime_args, ProtocolVersion, RuntimeArgs, URef, U512};
 
 use crate::{
@@ -43,6 +45,9 @@ pub struct Session {
     pub(crate) expect_success: bool,
     pub(crate) check_transfer_success: Option<SessionTransferInfo>,
     pub(crate) commit: bool,
+    pub(crate) block_time: Option<u64>,
+    #[cfg(feature = "native")]
+    pub(crate) native_entry_point: Option<NativeEntryPoint>,
 }
 
 /// Builder for a [`Session`].
@@ -52,6 +57,9 @@ pub struct SessionBuilder {
     expect_failure: bool,
     check_transfer_success: Option<SessionTransferInfo>,
     without_commit: bool,
+    block_time: Option<u64>,
+    #[cfg(feature = "native")]
+    native_entry_point: Option<NativeEntryPoint>,
 }
 
 impl SessionBuilder {
@@ -61,6 +69,8 @@ impl SessionBuilder {
     pub fn new(session_code: Code, session_args: RuntimeArgs) -> Self {
         let di_builder = DeployItemBuilder::new()
             .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT });
+        #[cfg(feature = "native")]
+        let mut native_entry_point = None;
         let di_builder = match session_code {
             Code::Path(path) => di_builder.with_session_code(path, session_args),
             Code::NamedKey(name, entry_point) => {
@@ -69,6 +79,15 @@ impl SessionBuilder {
             Code::Hash(hash, entry_point) => {
                 di_builder.with_stored_session_hash(hash, &entry_point, session_args)
             }
+            Code::VersionedNamedKey(name, version, entry_point) => di_builder
+                .with_stored_versioned_contract_by_name(&name, version, &entry_point, session_args),
+            Code::VersionedHash(hash, version, entry_point) => di_builder
+                .with_stored_versioned_contract_by_hash(hash, version, &entry_point, session_args),
+            #[cfg(feature = "native")]
+            Code::Native(entry_point) => {
+                native_entry_point = Some(entry_point);
+                di_builder.with_session_bytes(vec![], session_args)
+            }
         };
         let expect_failure = false;
         let check_transfer_success = None;
@@ -79,6 +98,9 @@ impl SessionBuilder {
             expect_failure,
             check_transfer_success,
             without_commit,
+            block_time: None,
+            #[cfg(feature = "native")]
+            native_entry_point,
         }
     }
 
@@ -89,6 +111,8 @@ impl SessionBuilder {
     }
 
     /// Returns `self` with the provided payment code and args set.
+    ///
+    /// Panics if `code` is native code.
     pub fn with_payment_code(mut self, code: Code, args: RuntimeArgs) -> Self {
         self.di_builder = match code {
             Code::Path(path) => self.di_builder.with_payment_code(path, args),
@@ -100,13 +124,24 @@ impl SessionBuilder {
                 self.di_builder
                     .with_stored_payment_hash(hash, &entry_point, args)
             }
+            Code::VersionedNamedKey(name, version, entry_point) => self
+                .di_builder
+                .with_stored_versioned_payment_contract_by_name(&name, version, &entry_point, args),
+            Code::VersionedHash(hash, version, entry_point) => self
+                .di_builder
+                .with_stored_versioned_payment_contract_by_hash(hash, version, &entry_point, args),
+            #[cfg(feature = "native")]
+            Code::Native(_) => panic!("native code can't be used as payment code"),
         };
         self
     }
 
     /// Returns `self` with the provided block time set.
+    ///
+    /// If not set, the session is run at the [`TestContext`](crate::TestContext)'s current block
+    /// time.
     pub fn with_block_time(mut self, block_time: u64) -> Self {
-        self.er_builder = self.er_builder.with_block_time(block_time);
+        self.block_time = Some(block_time);
         self
     }
 
@@ -161,6 +196,9 @@ impl SessionBuilder {
             expect_success: !self.expect_failure,
             check_transfer_success: self.check_transfer_success,
             commit: !self.without_commit,
+            block_time: self.block_time,
+            #[cfg(feature = "native")]
+            native_entry_point: self.native_entry_point,
         }
     }
 }
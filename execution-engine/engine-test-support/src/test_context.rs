@@ -1,3 +1,5 @@
+use std::mem;
+
 use num_traits::identities::Zero;
 
 use engine_core::engine_state::{
@@ -11,12 +13,21 @@ use types::{AccessRights, Key, URef, U512};
 
 use crate::{
     internal::{InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG, DEFAULT_GENESIS_CONFIG_HASH},
-    Account, AccountHash, Error, Result, Session, URefAddr, Value,
+    Account, AccountHash, Error, Result, RunResult, Session, URefAddr, Value,
 };
 
 /// Context in which to run a test of a Wasm smart contract.
 pub struct TestContext {
     inner: InMemoryWasmTestBuilder,
+    block_time: u64,
+}
+
+/// A snapshot of the global state and block time of a [`TestContext`], which it can later be rolled
+/// back to.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Snapshot {
+    post_state_hash: Vec<u8>,
+    block_time: u64,
 }
 
 impl TestContext {
@@ -44,8 +55,13 @@ impl TestContext {
     /// If `session` was built without
     /// [`without_commit()`](crate::SessionBuilder::without_commit) (the default), then `run()` will
     /// commit the resulting transforms.
-    pub fn run(&mut self, session: Session) -> &mut Self {
-        match session.check_transfer_success {
+    ///
+    /// If `session` was built without
+    /// [`with_block_time()`](crate::SessionBuilder::with_block_time) (the default), then it is run
+    /// at the context's current [`block_time()`](TestContext::block_time).
+    pub fn run(&mut self, mut session: Session) -> &mut Self {
+        session.inner.block_time = session.block_time.unwrap_or(self.block_time);
+        match session.check_transfer_success.take() {
             Some(session_transfer_info) => {
                 let source_initial_balance = self
                     .maybe_purse_balance(Some(session_transfer_info.source_purse))
@@ -53,7 +69,7 @@ impl TestContext {
                 let maybe_target_initial_balance =
                     self.maybe_purse_balance(session_transfer_info.maybe_target_purse);
 
-                let builder = self.inner.exec(session.inner);
+                let builder = Self::exec(&mut self.inner, &mut session);
                 if session.expect_success {
                     builder.expect_success();
                 }
@@ -93,7 +109,7 @@ impl TestContext {
                 }
             }
             None => {
-                let builder = self.inner.exec(session.inner);
+                let builder = Self::exec(&mut self.inner, &mut session);
                 if session.expect_success {
                     builder.expect_success();
                 }
@@ -105,17 +121,89 @@ impl TestContext {
         self
     }
 
+    /// Executes `session` with `builder`, natively if it has a native entry point.
+    fn exec<'a>(
+        builder: &'a mut InMemoryWasmTestBuilder,
+        session: &mut Session,
+    ) -> &'a mut InMemoryWasmTestBuilder {
+        let exec_request = mem::take(&mut session.inner);
+        #[cfg(feature = "native")]
+        {
+            if let Some(native_entry_point) = session.native_entry_point {
+                return builder.exec_native(exec_request, native_entry_point);
+            }
+        }
+        builder.exec(exec_request)
+    }
+
+    /// Runs the supplied [`Session`] as [`run()`](TestContext::run) does, returning a [`RunResult`]
+    /// describing its execution.
+    pub fn run_with_result(&mut self, session: Session) -> RunResult {
+        self.run(session);
+        let index = self.inner.get_exec_responses_count() - 1;
+        let execution_result = self
+            .inner
+            .get_exec_response(index)
+            .and_then(|exec_response| exec_response.first())
+            .expect("should have an execution result");
+        let output = self
+            .inner
+            .get_exec_output(index)
+            .cloned()
+            .expect("should have an output");
+        RunResult::new(execution_result, output)
+    }
+
     /// Queries for a [`Value`] stored under the given `key` and `path`.
     ///
     /// Returns an [`Error`] if not found.
     pub fn query<T: AsRef<str>>(&self, key: AccountHash, path: &[T]) -> Result<Value> {
+        self.query_key(Key::Account(key), path)
+    }
+
+    /// Queries for a [`Value`] stored under the given `key` and `path`, where `key` can be any
+    /// [`Key`], e.g. the hash of a stored contract or a [`URef`].
+    ///
+    /// Returns an [`Error`] if not found.
+    pub fn query_key<T: AsRef<str>>(&self, key: Key, path: &[T]) -> Result<Value> {
         let path = path.iter().map(AsRef::as_ref).collect::<Vec<_>>();
         self.inner
-            .query(None, Key::Account(key), &path)
+            .query(None, key, &path)
             .map(Value::new)
             .map_err(Error::from)
     }
 
+    /// Returns the block time at which sessions are run, unless they set their own.
+    pub fn block_time(&self) -> u64 {
+        self.block_time
+    }
+
+    /// Advances the block time at which subsequent sessions are run by `duration`.
+    pub fn advance_block_time(&mut self, duration: u64) -> &mut Self {
+        self.block_time += duration;
+        self
+    }
+
+    /// Takes a [`Snapshot`] of the current global state and block time.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            post_state_hash: self.inner.get_post_state_hash(),
+            block_time: self.block_time,
+        }
+    }
+
+    /// Rolls the global state and block time back to those of `snapshot`, discarding the effects
+    /// of all sessions committed since it was taken.
+    ///
+    /// The same snapshot can be rolled back to any number of times, e.g. to explore different
+    /// branches of a scenario.
+    pub fn rollback(&mut self, snapshot: &Snapshot) -> &mut Self {
+        self.inner
+            .set_post_state_hash(snapshot.post_state_hash.clone());
+        self.block_time = snapshot.block_time;
+        self
+    }
+
     /// Gets the balance of the purse under the given [`URefAddr`].
     ///
     /// Note that this requires performing an earlier query to retrieve `purse_addr`.
@@ -145,6 +233,7 @@ impl TestContext {
 /// Builder for a [`TestContext`].
 pub struct TestContextBuilder {
     genesis_config: GenesisConfig,
+    block_time: u64,
 }
 
 impl TestContextBuilder {
@@ -155,6 +244,7 @@ impl TestContextBuilder {
     pub fn new() -> Self {
         TestContextBuilder {
             genesis_config: DEFAULT_GENESIS_CONFIG.clone(),
+            block_time: 0,
         }
     }
 
@@ -170,6 +260,12 @@ impl TestContextBuilder {
         self
     }
 
+    /// Returns `self` with the provided initial block time set, which otherwise defaults to 0.
+    pub fn with_block_time(mut self, block_time: u64) -> Self {
+        self.block_time = block_time;
+        self
+    }
+
     /// Builds the [`TestContext`].
     pub fn build(self) -> TestContext {
         let mut inner = InMemoryWasmTestBuilder::default();
@@ -179,7 +275,10 @@ impl TestContextBuilder {
             self.genesis_config.take_ee_config(),
         );
         inner.run_genesis(&run_genesis_request);
-        TestContext { inner }
+        TestContext {
+            inner,
+            block_time: self.block_time,
+        }
     }
 }
 
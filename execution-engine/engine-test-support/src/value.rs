@@ -1,6 +1,8 @@
 use std::convert::{TryFrom, TryInto};
 
 use engine_shared::stored_value::StoredValue;
+#[cfg(feature = "fuzz")]
+use types::Contract;
 use types::{
     bytesrepr::{FromBytes, ToBytes},
     CLTyped, CLValue,
@@ -38,4 +40,9 @@ impl Value {
     pub fn into_account(self) -> Result<Account> {
         self.inner.try_into()
     }
+
+    #[cfg(feature = "fuzz")]
+    pub(crate) fn into_contract(self) -> Result<Contract> {
+        Ok(Contract::try_from(self.inner)?)
+    }
 }
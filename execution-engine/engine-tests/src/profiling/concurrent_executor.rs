@@ -6,19 +6,18 @@
 
 use std::{
     iter::Sum,
-    sync::Arc,
     thread::{self, JoinHandle},
     time::{Duration, Instant},
 };
 
 use clap::{crate_version, App, Arg};
 use crossbeam_channel::{Iter, Receiver, Sender};
-use grpc::{ClientStubExt, RequestOptions};
 use log::info;
+use tokio::runtime::{Handle, Runtime};
+use tonic::transport::Channel;
 
 use engine_grpc_server::engine_server::{
-    ipc::ExecuteRequest,
-    ipc_grpc::{ExecutionEngineService, ExecutionEngineServiceClient},
+    self, execution_engine_service_client::ExecutionEngineServiceClient, ipc::ExecuteRequest,
 };
 use engine_test_support::internal::{DeployItemBuilder, ExecuteRequestBuilder, DEFAULT_PAYMENT};
 use types::{runtime_args, RuntimeArgs, U512};
@@ -189,7 +188,8 @@ impl Worker {
         id: usize,
         message_receiver: Receiver<Message>,
         result_sender: Sender<Duration>,
-        client: Arc<ExecutionEngineServiceClient>,
+        runtime: Handle,
+        mut client: ExecutionEngineServiceClient<Channel>,
     ) -> Self {
         let thread_name = format!("{}{}", THREAD_PREFIX, id);
         let handle = thread::Builder::new()
@@ -202,7 +202,14 @@ impl Worker {
                     Message::Run {
                         request_num,
                         request,
-                    } => Self::do_work(request_num, request, &thread_name, &result_sender, &client),
+                    } => Self::do_work(
+                        request_num,
+                        request,
+                        &thread_name,
+                        &result_sender,
+                        &runtime,
+                        &mut client,
+                    ),
                     Message::Close => {
                         break;
                     }
@@ -220,17 +227,18 @@ impl Worker {
         request: ExecuteRequest,
         thread_name: &str,
         result_sender: &Sender<Duration>,
-        client: &ExecutionEngineServiceClient,
+        runtime: &Handle,
+        client: &mut ExecutionEngineServiceClient<Channel>,
     ) {
         info!(
             "Client sending 'execute' request {} on {}",
             request_num, thread_name
         );
         let start = Instant::now();
-        let response = client
-            .execute(RequestOptions::new(), request)
-            .wait_drop_metadata()
-            .expect("Expected ExecuteResponse");
+        let response = runtime
+            .block_on(client.execute(request))
+            .expect("Expected ExecuteResponse")
+            .into_inner();
         let duration = Instant::now() - start;
 
         let deploy_result = response
@@ -297,8 +305,13 @@ pub struct ClientPool {
 }
 
 impl ClientPool {
-    /// Creates a new thread pool with `size` worker threads associated with it.
-    pub fn new(size: usize, client: Arc<ExecutionEngineServiceClient>) -> Self {
+    /// Creates a new thread pool with `size` worker threads associated with it, each sending
+    /// requests via `client` on `runtime`.
+    pub fn new(
+        size: usize,
+        runtime: Handle,
+        client: ExecutionEngineServiceClient<Channel>,
+    ) -> Self {
         assert!(size > 0);
         let mut workers = Vec::with_capacity(size);
         let (message_sender, message_receiver) = crossbeam_channel::unbounded();
@@ -306,8 +319,13 @@ impl ClientPool {
         for id in 0..size {
             let message_receiver = message_receiver.clone();
             let result_sender = result_sender.clone();
-            let client = Arc::clone(&client);
-            workers.push(Worker::new(id, message_receiver, result_sender, client));
+            workers.push(Worker::new(
+                id,
+                message_receiver,
+                result_sender,
+                runtime.clone(),
+                client.clone(),
+            ));
         }
         ClientPool {
             workers,
@@ -371,12 +389,11 @@ fn main() {
     env_logger::init();
 
     let args = Args::new();
-    let client_config = Default::default();
-    let client = Arc::new(
-        ExecutionEngineServiceClient::new_plain_unix(args.socket.as_str(), client_config)
-            .expect("Expected to create Test Client"),
-    );
-    let pool = ClientPool::new(args.thread_count, client);
+    let mut runtime = Runtime::new().expect("Expected to create runtime");
+    let client = runtime
+        .block_on(engine_server::connect(args.socket.as_str()))
+        .expect("Expected to create Test Client");
+    let pool = ClientPool::new(args.thread_count, runtime.handle().clone(), client);
 
     let message_sender = pool.message_sender();
     let result_receiver = pool.result_receiver();
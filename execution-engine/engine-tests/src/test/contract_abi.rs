@@ -0,0 +1,180 @@
+use engine_core::engine_state::execute_request::ExecuteRequest;
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    contracts::Parameter, runtime_args, CLType, ContractHash, ContractPackageHash,
+    EntryPointAccess, EntryPointType, Key, ProtocolVersion, RuntimeArgs,
+};
+
+const CONTRACT_ABI: &str = "contract_abi.wasm";
+const CONTRACT_HASH_KEY: &str = "contract_abi_hash";
+const PACKAGE_HASH_KEY: &str = "contract_abi_package_hash";
+const COUNT_KEY: &str = "count";
+const COUNTER_INC: &str = "counter_inc";
+const COUNTER_GET: &str = "counter_get";
+const COUNTER_RESET: &str = "counter_reset";
+const ARG_STEP: &str = "step";
+const ARG_AMOUNT: &str = "amount";
+
+fn call_entry_point(entry_point: &str, args: RuntimeArgs, deploy_hash: [u8; 32]) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_stored_session_named_key(CONTRACT_HASH_KEY, entry_point, args)
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash)
+        .build();
+
+    ExecuteRequestBuilder::new().push_deploy(deploy).build()
+}
+
+fn setup() -> (InMemoryWasmTestBuilder, ContractHash, ContractPackageHash) {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_ABI, RuntimeArgs::default())
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let contract_hash: ContractHash = account
+        .named_keys()
+        .get(CONTRACT_HASH_KEY)
+        .cloned()
+        .and_then(Key::into_hash)
+        .expect("should have contract hash");
+    let package_hash: ContractPackageHash = account
+        .named_keys()
+        .get(PACKAGE_HASH_KEY)
+        .cloned()
+        .and_then(Key::into_hash)
+        .expect("should have contract package hash");
+
+    (builder, contract_hash, package_hash)
+}
+
+#[ignore]
+#[test]
+fn should_query_contract_abi_generated_by_macro() {
+    let (builder, contract_hash, package_hash) = setup();
+
+    let contract_abi = builder
+        .get_contract_abi(Key::Hash(contract_hash), *DEFAULT_PROTOCOL_VERSION)
+        .expect("should get contract abi");
+    assert_eq!(contract_abi.contract_hash(), contract_hash);
+    assert_eq!(contract_abi.contract_package_hash(), package_hash);
+    assert!(contract_abi.named_keys().contains_key(COUNT_KEY));
+
+    let entry_points = contract_abi.entry_points();
+    assert_eq!(entry_points.len(), 3);
+
+    let counter_inc = entry_points
+        .iter()
+        .find(|entry_point| entry_point.name() == COUNTER_INC)
+        .expect("should have counter_inc");
+    assert_eq!(
+        counter_inc.args(),
+        &[Parameter::new(
+            ARG_STEP,
+            CLType::Option(Box::new(CLType::I32))
+        )][..]
+    );
+    assert_eq!(counter_inc.ret(), &CLType::Unit);
+    assert_eq!(counter_inc.access(), &EntryPointAccess::Public);
+    assert_eq!(counter_inc.entry_point_type(), EntryPointType::Contract);
+
+    let counter_get = entry_points
+        .iter()
+        .find(|entry_point| entry_point.name() == COUNTER_GET)
+        .expect("should have counter_get");
+    assert!(counter_get.args().is_empty());
+    assert_eq!(counter_get.ret(), &CLType::I32);
+
+    let counter_reset = entry_points
+        .iter()
+        .find(|entry_point| entry_point.name() == COUNTER_RESET)
+        .expect("should have counter_reset");
+    assert_eq!(
+        counter_reset.access(),
+        &EntryPointAccess::groups(&["admin"])
+    );
+
+    let json = contract_abi.to_json();
+    assert!(json["named_keys"][COUNT_KEY].is_string());
+    assert_eq!(json["entry_points"].as_array().map(Vec::len), Some(3));
+
+    // Querying by package hash returns the ABI of the current version
+    let package_abi = builder
+        .get_contract_abi(Key::Hash(package_hash), *DEFAULT_PROTOCOL_VERSION)
+        .expect("should get contract abi by package hash");
+    assert_eq!(package_abi, contract_abi);
+}
+
+#[ignore]
+#[test]
+fn should_read_named_args_in_generated_entry_points() {
+    let (mut builder, contract_hash, _package_hash) = setup();
+
+    let exec_request_1 = call_entry_point(COUNTER_INC, RuntimeArgs::default(), [2; 32]);
+    builder.exec(exec_request_1).expect_success().commit();
+
+    let exec_request_2 = call_entry_point(
+        COUNTER_INC,
+        runtime_args! { ARG_STEP => Some(5i32) },
+        [3; 32],
+    );
+    builder.exec(exec_request_2).expect_success().commit();
+
+    let count = builder
+        .query(None, Key::Hash(contract_hash), &[COUNT_KEY])
+        .expect("should query count");
+    let count: i32 = match count {
+        StoredValue::CLValue(cl_value) => cl_value.into_t().expect("should be i32"),
+        other => panic!("unexpected stored value {:?}", other),
+    };
+    assert_eq!(count, 6);
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_query_abi_of_non_contract() {
+    let (builder, _contract_hash, _package_hash) = setup();
+
+    let error = builder
+        .get_contract_abi(
+            Key::Account(DEFAULT_ACCOUNT_ADDR),
+            *DEFAULT_PROTOCOL_VERSION,
+        )
+        .expect_err("should not get abi of an account");
+    assert!(error.contains("is not a contract"), "{}", error);
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_query_abi_at_incompatible_protocol_version() {
+    let (builder, contract_hash, _package_hash) = setup();
+
+    let error = builder
+        .get_contract_abi(
+            Key::Hash(contract_hash),
+            ProtocolVersion::from_parts(2, 0, 0),
+        )
+        .expect_err("should not get abi at an incompatible protocol version");
+    assert!(
+        error.contains("IncompatibleProtocolMajorVersion"),
+        "{}",
+        error
+    );
+}
@@ -0,0 +1,79 @@
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{AccountHash, Weight},
+    runtime_args, RuntimeArgs,
+};
+
+const CONTRACT_MANAGE_ACCOUNT: &str = "manage_account.wasm";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ARG_ACCOUNT: &str = "account";
+const NEW_MAIN_PURSE: &str = "new_main_purse";
+
+#[ignore]
+#[test]
+fn should_rotate_keys_and_migrate_main_purse() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MANAGE_ACCOUNT,
+        runtime_args! { ARG_ACCOUNT => ACCOUNT_1_ADDR },
+    )
+    .build();
+
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit();
+
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+
+    let associated_keys: Vec<_> = default_account.get_associated_keys().collect();
+    assert_eq!(associated_keys, vec![(&ACCOUNT_1_ADDR, &Weight::new(1))]);
+
+    let new_main_purse = default_account
+        .named_keys()
+        .get(NEW_MAIN_PURSE)
+        .and_then(|key| key.as_uref())
+        .cloned()
+        .expect("should have new main purse");
+    assert_eq!(default_account.main_purse(), new_main_purse);
+
+    // Old identity key is no longer able to deploy
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DO_NOTHING,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    builder.exec(exec_request_2).commit();
+    assert!(builder.is_error());
+
+    // New key deploys and pays from the new main purse
+    let exec_request_3 = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+            .with_session_code(CONTRACT_DO_NOTHING, RuntimeArgs::default())
+            .with_deploy_hash([3u8; 32])
+            .with_authorization_keys(&[ACCOUNT_1_ADDR])
+            .build();
+        ExecuteRequestBuilder::from_deploy_item(deploy).build()
+    };
+
+    let balance_before = builder.get_purse_balance(new_main_purse);
+    builder.exec(exec_request_3).expect_success().commit();
+    let balance_after = builder.get_purse_balance(new_main_purse);
+    assert!(balance_after < balance_before);
+}
@@ -7,6 +7,7 @@ mod get_phase;
 mod list_named_keys;
 mod main_purse;
 mod mint_purse;
+mod remove_key;
 mod revert;
 mod subcall;
 mod transfer;
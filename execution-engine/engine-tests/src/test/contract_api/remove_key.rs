@@ -0,0 +1,69 @@
+use engine_shared::{stored_value::StoredValue, transform::Transform};
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, CLValue, Key, RuntimeArgs};
+
+const CONTRACT_REMOVE_KEY: &str = "remove_key.wasm";
+const LOCAL_KEY: [u8; 32] = [66u8; 32];
+const VALUE_KEY: &str = "value";
+const ARG_COMMAND: &str = "command";
+const CMD_WRITE: &str = "write";
+const CMD_REMOVE: &str = "remove";
+const INITIAL_VALUE: u64 = 10;
+
+#[ignore]
+#[test]
+fn should_remove_uref_and_local_key() {
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_REMOVE_KEY,
+        runtime_args! { ARG_COMMAND => CMD_WRITE },
+    )
+    .build();
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_REMOVE_KEY,
+        runtime_args! { ARG_COMMAND => CMD_REMOVE },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit();
+
+    let uref = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(VALUE_KEY)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should have value uref");
+    let local_key = Key::Hash(LOCAL_KEY);
+
+    let expected_value = StoredValue::CLValue(CLValue::from_t(INITIAL_VALUE).unwrap());
+    for key in &[Key::from(uref), local_key] {
+        let value = builder
+            .query(None, *key, &[])
+            .expect("should have written value");
+        assert_eq!(value, expected_value);
+    }
+
+    builder.exec(exec_request_2).expect_success().commit();
+
+    let transforms = builder.get_transforms();
+    let transforms = transforms.last().expect("should have transforms");
+    for key in &[Key::from(uref).normalize(), local_key] {
+        assert_eq!(transforms.get(key), Some(&Transform::Prune));
+        assert!(
+            builder.query(None, *key, &[]).is_err(),
+            "{} should have been removed",
+            key
+        );
+    }
+}
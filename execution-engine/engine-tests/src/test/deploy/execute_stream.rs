@@ -0,0 +1,129 @@
+use engine_core::execution::Interrupt;
+use engine_grpc_server::engine_server::{
+    ipc::{self, DeployResult, ExecuteStreamRequest_Header},
+    ExecutionEngineService,
+};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, ProtocolVersion, RuntimeArgs, U512};
+
+const ARG_AMOUNT: &str = "amount";
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+
+fn do_nothing_deploy(deploy_hash: [u8; 32]) -> ipc::DeployItem {
+    DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => U512::from(10_000_000) })
+        .with_deploy_hash(deploy_hash)
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .build()
+        .into()
+}
+
+fn header(parent_state_hash: Vec<u8>) -> ExecuteStreamRequest_Header {
+    let mut header = ExecuteStreamRequest_Header::new();
+    header.set_parent_state_hash(parent_state_hash);
+    header.set_protocol_version(ProtocolVersion::V1_0_0.into());
+    header
+}
+
+/// Whether two deploy results are alike, ignoring the order of their effects.
+fn same_outcome(left: &DeployResult, right: &DeployResult) -> bool {
+    let left_result = left.get_execution_result();
+    let right_result = right.get_execution_result();
+    left.has_precondition_failure() == right.has_precondition_failure()
+        && left.get_precondition_failure() == right.get_precondition_failure()
+        && left_result.get_cost() == right_result.get_cost()
+        && left_result.get_error() == right_result.get_error()
+        && left_result.get_effects().get_transform_map().len()
+            == right_result.get_effects().get_transform_map().len()
+}
+
+#[ignore]
+#[test]
+fn should_stream_same_results_as_execute() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let post_state_hash = builder.get_post_state_hash();
+
+    // The empty deploy fails its precondition, but the others are still executed.
+    let deploys = vec![
+        do_nothing_deploy([1; 32]),
+        ipc::DeployItem::new(),
+        do_nothing_deploy([2; 32]),
+    ];
+
+    let mut exec_request: ipc::ExecuteRequest = ExecuteRequestBuilder::new()
+        .with_pre_state_hash(&post_state_hash)
+        .build()
+        .into();
+    exec_request.set_deploys(deploys.clone().into());
+    let exec_response = builder
+        .get_engine_state()
+        .execute(exec_request, Interrupt::new());
+    let expected = exec_response.get_success().get_deploy_results();
+
+    let mut responses = Vec::new();
+    builder.get_engine_state().execute_stream(
+        header(post_state_hash),
+        deploys,
+        Interrupt::new(),
+        |response| responses.push(response),
+    );
+
+    assert_eq!(responses.len(), expected.len());
+    for (response, expected) in responses.iter().zip(expected) {
+        assert!(response.has_deploy_result());
+        assert!(same_outcome(response.get_deploy_result(), expected));
+    }
+    assert!(responses[1].get_deploy_result().has_precondition_failure());
+}
+
+#[ignore]
+#[test]
+fn should_stream_missing_parent() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let unknown_state_hash = vec![1; 32];
+    let mut responses = Vec::new();
+    builder.get_engine_state().execute_stream(
+        header(unknown_state_hash.clone()),
+        vec![do_nothing_deploy([1; 32])],
+        Interrupt::new(),
+        |response| responses.push(response),
+    );
+
+    assert_eq!(responses.len(), 1);
+    assert_eq!(
+        responses[0].get_missing_parent().get_hash(),
+        &unknown_state_hash[..]
+    );
+}
+
+#[ignore]
+#[test]
+fn should_stop_streaming_when_interrupted() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let interrupt = Interrupt::new();
+    interrupt.interrupt();
+
+    let mut responses = Vec::new();
+    builder.get_engine_state().execute_stream(
+        header(builder.get_post_state_hash()),
+        vec![do_nothing_deploy([1; 32]), do_nothing_deploy([2; 32])],
+        interrupt,
+        |response| responses.push(response),
+    );
+
+    assert_eq!(responses.len(), 1);
+    assert!(responses[0].has_failure());
+}
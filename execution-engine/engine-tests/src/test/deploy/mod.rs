@@ -1,3 +1,4 @@
+mod execute_stream;
 mod non_standard_payment;
 mod preconditions;
 mod stored_contracts;
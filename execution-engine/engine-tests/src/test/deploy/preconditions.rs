@@ -1,6 +1,10 @@
 use assert_matches::assert_matches;
 
-use engine_core::engine_state::Error;
+use engine_core::{
+    engine_state::Error,
+    execution::{self, Interrupt},
+};
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
 use engine_test_support::{
     internal::{
         utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
@@ -8,7 +12,7 @@ use engine_test_support::{
     },
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+use types::{account::AccountHash, runtime_args, ProtocolVersion, RuntimeArgs, U512};
 
 const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
 const ARG_AMOUNT: &str = "amount";
@@ -119,3 +123,78 @@ fn should_raise_precondition_authorization_failure_invalid_authorized_keys() {
     let precondition_failure = utils::get_precondition_failure(response);
     assert_matches!(precondition_failure, Error::Authorization);
 }
+
+#[ignore]
+#[test]
+fn should_return_error_for_unknown_protocol_version() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let unknown_protocol_version = ProtocolVersion::from_parts(9, 9, 9);
+    let exec_request = ExecuteRequestBuilder::new()
+        .with_pre_state_hash(&builder.get_post_state_hash())
+        .with_protocol_version(unknown_protocol_version)
+        .build();
+
+    let result = builder
+        .get_engine_state()
+        .run_execute(CorrelationId::new(), exec_request);
+    assert_matches!(
+        result,
+        Err(Error::InvalidProtocolVersion(version)) if version == unknown_protocol_version
+    );
+}
+
+#[ignore]
+#[test]
+fn should_return_error_for_unknown_parent_state_hash() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let unknown_state_hash = Blake2bHash::new(&[1u8; 32]);
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code("do_nothing.wasm", RuntimeArgs::default())
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => U512::from(10_000_000) })
+        .with_deploy_hash([1; 32])
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(deploy)
+        .with_pre_state_hash(&unknown_state_hash.to_vec())
+        .build();
+
+    let result = builder
+        .get_engine_state()
+        .run_execute(CorrelationId::new(), exec_request);
+    assert_matches!(result, Err(Error::RootNotFound(hash)) if hash == unknown_state_hash);
+}
+
+#[ignore]
+#[test]
+fn should_return_error_when_interrupted() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code("do_nothing.wasm", RuntimeArgs::default())
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => U512::from(10_000_000) })
+        .with_deploy_hash([1; 32])
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(deploy)
+        .with_pre_state_hash(&builder.get_post_state_hash())
+        .build();
+
+    let interrupt = Interrupt::new();
+    interrupt.interrupt();
+
+    let result = builder.get_engine_state().run_execute_with_interrupt(
+        CorrelationId::new(),
+        exec_request,
+        interrupt,
+    );
+    assert_matches!(result, Err(Error::Exec(execution::Error::Interrupted)));
+}
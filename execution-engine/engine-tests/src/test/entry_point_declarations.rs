@@ -0,0 +1,126 @@
+use assert_matches::assert_matches;
+use engine_core::{
+    engine_state::{execute_request::ExecuteRequest, upgrade::ActivationPoint, Error},
+    execution,
+};
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, ProtocolVersion, RuntimeArgs};
+
+const CONTRACT_ENTRY_POINT_DECLARATIONS: &str = "entry_point_declarations.wasm";
+const DECLARES_U512_ARG: &str = "declares_u512_arg";
+const DECLARES_I32_RET: &str = "declares_i32_ret";
+const ARG_ENTRY_POINT: &str = "entry_point";
+const ARG_AMOUNT: &str = "amount";
+const ACTIVATION_POINT: ActivationPoint = 1;
+
+/// The first protocol version whose contracts have their entry point declarations checked.
+fn checking_protocol_version() -> ProtocolVersion {
+    ProtocolVersion::from_parts(1, 1, 0)
+}
+
+fn call_entry_point(entry_point: &str, protocol_version: ProtocolVersion) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_ENTRY_POINT_DECLARATIONS,
+        runtime_args! { ARG_ENTRY_POINT => entry_point.to_string() },
+    )
+    .with_protocol_version(protocol_version)
+    .build()
+}
+
+fn upgrade(builder: &mut InMemoryWasmTestBuilder) {
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(checking_protocol_version())
+        .with_activation_point(ACTIVATION_POINT)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+}
+
+fn last_error(builder: &InMemoryWasmTestBuilder) -> &Error {
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    assert_eq!(response.len(), 1);
+    let exec_response = response.last().expect("should have response");
+    exec_response.as_error().expect("should have error")
+}
+
+#[ignore]
+#[test]
+fn should_call_contract_stored_with_old_declarations() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(call_entry_point(
+            DECLARES_U512_ARG,
+            *DEFAULT_PROTOCOL_VERSION,
+        ))
+        .expect_success()
+        .commit();
+
+    upgrade(&mut builder);
+
+    // The contract was stored before the upgrade, so an `Option<U512>` is still accepted for its
+    // `U512` parameter, and `()` for its `I32` return type
+    builder
+        .exec(call_entry_point(
+            DECLARES_U512_ARG,
+            checking_protocol_version(),
+        ))
+        .expect_success()
+        .commit()
+        .exec(call_entry_point(
+            DECLARES_I32_RET,
+            checking_protocol_version(),
+        ))
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_check_declarations_of_contract_stored_after_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    upgrade(&mut builder);
+
+    builder
+        .exec(call_entry_point(
+            DECLARES_U512_ARG,
+            checking_protocol_version(),
+        ))
+        .commit();
+    let error = last_error(&builder);
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::InvalidArgument { name, .. }) if name == ARG_AMOUNT
+    );
+
+    builder
+        .exec(call_entry_point(
+            DECLARES_I32_RET,
+            checking_protocol_version(),
+        ))
+        .commit();
+    let error = last_error(&builder);
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::InvalidReturnValue { entry_point, .. })
+            if entry_point == DECLARES_I32_RET
+    );
+}
@@ -0,0 +1,118 @@
+use proptest::{
+    strategy::{Strategy, ValueTree},
+    test_runner::TestRunner,
+};
+
+use types::{
+    ApiError, CLType, ContractHash, EntryPoint, EntryPointAccess, EntryPointType, Key, Parameter,
+    RuntimeArgs,
+};
+
+use engine_test_support::{
+    fuzz::{self, Fuzzer},
+    Code, SessionBuilder, TestContext, TestContextBuilder, Value, DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_COUNTER_DEFINE: &str = "counter_define.wasm";
+const COUNTER_CONTRACT_HASH_KEY_NAME: &str = "counter_contract_hash";
+const COUNTER_VALUE_UREF: &str = "counter";
+const ENTRY_POINT_COUNTER: &str = "counter";
+const ARG_COUNTER_METHOD: &str = "method";
+const CASES: u32 = 8;
+const CALLS: usize = 16;
+
+fn setup() -> (TestContext, ContractHash) {
+    let mut context = TestContextBuilder::new().build();
+    let session = SessionBuilder::new(Code::from(CONTRACT_COUNTER_DEFINE), RuntimeArgs::new())
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .build();
+    context.run(session);
+    let contract_hash = context
+        .query(DEFAULT_ACCOUNT_ADDR, &[COUNTER_CONTRACT_HASH_KEY_NAME])
+        .and_then(Value::into_t::<Key>)
+        .expect("should have counter contract hash")
+        .into_hash()
+        .expect("should be a hash");
+    (context, contract_hash)
+}
+
+fn counter_value(context: &TestContext, contract_hash: ContractHash) -> i32 {
+    context
+        .query_key(Key::Hash(contract_hash), &[COUNTER_VALUE_UREF])
+        .and_then(Value::into_t)
+        .expect("should have counter value")
+}
+
+#[ignore]
+#[test]
+fn should_hold_invariant_of_fuzzed_counter() {
+    let (context, contract_hash) = setup();
+    Fuzzer::new(context, contract_hash)
+        .expect("should find counter contract")
+        .with_cases(CASES)
+        .with_invariant("counter is never negative", move |context| {
+            let value = counter_value(context, contract_hash);
+            if value < 0 {
+                return Err(format!("counter is {}", value));
+            }
+            Ok(())
+        })
+        .run()
+        .unwrap_or_else(|failure| panic!("{}", failure));
+}
+
+#[ignore]
+#[test]
+fn should_shrink_sequence_violating_invariant() {
+    let (context, contract_hash) = setup();
+    let failure = Fuzzer::new(context, contract_hash)
+        .expect("should find counter contract")
+        .with_cases(CASES)
+        .with_invariant("always violated", |_| Err("violated".to_string()))
+        .run()
+        .expect_err("should violate invariant");
+
+    assert_eq!(failure.calls().len(), 1);
+    assert!(failure.reason().contains("always violated"));
+}
+
+#[ignore]
+#[test]
+fn should_execute_entry_point_taking_arguments() {
+    let (mut context, contract_hash) = setup();
+    // The body of the counter entry point reverts with `InvalidArgument` for an unknown method.
+    let entry_point = EntryPoint::new(
+        ENTRY_POINT_COUNTER,
+        vec![Parameter::new(ARG_COUNTER_METHOD, CLType::String)],
+        CLType::I32,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let strategy = fuzz::entry_point_call_arb(vec![entry_point]);
+    let mut runner = TestRunner::default();
+    let mut executed = 0;
+    for _ in 0..CALLS {
+        let call = strategy.new_tree(&mut runner).unwrap().current();
+        let session = SessionBuilder::new(
+            Code::Hash(contract_hash, call.entry_point().to_string()),
+            call.args().clone(),
+        )
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .without_expect_success()
+        .build();
+        let result = context.run_with_result(session);
+        // An argument rejected by the engine before the call doesn't revert with an `ApiError`.
+        if result.is_success() || result.api_error() == Some(ApiError::InvalidArgument) {
+            executed += 1;
+        }
+    }
+    assert!(
+        executed >= CALLS / 2,
+        "only {} of {} calls executed",
+        executed,
+        CALLS
+    );
+}
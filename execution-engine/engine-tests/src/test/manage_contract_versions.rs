@@ -0,0 +1,136 @@
+use engine_core::engine_state::execute_request::ExecuteRequest;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    contracts::{ContractVersion, ContractVersionStatus},
+    runtime_args, ContractHash, ContractPackageHash, Key, RuntimeArgs,
+};
+
+const CONTRACT_MANAGE_CONTRACT_VERSIONS: &str = "manage_contract_versions.wasm";
+const PACKAGE_HASH_KEY: &str = "package_hash_key";
+const CONTRACT_HASH_V1_KEY: &str = "contract_hash_v1";
+const DISABLE_VERSION: &str = "disable_version";
+const ENABLE_VERSION: &str = "enable_version";
+const SET_VERSION_METADATA: &str = "set_version_metadata";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+const ARG_METADATA: &str = "metadata";
+const ARG_AMOUNT: &str = "amount";
+const CONTRACT_VERSION_1: ContractVersion = 1;
+const CONTRACT_VERSION_2: ContractVersion = 2;
+const METADATA: &str = "1.0.0";
+
+fn call_entry_point(
+    version: ContractVersion,
+    entry_point: &str,
+    args: RuntimeArgs,
+    deploy_hash: [u8; 32],
+) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_stored_versioned_contract_by_name(PACKAGE_HASH_KEY, Some(version), entry_point, args)
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash)
+        .build();
+
+    ExecuteRequestBuilder::new().push_deploy(deploy).build()
+}
+
+#[ignore]
+#[test]
+fn should_disable_and_enable_contract_version_with_metadata() {
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MANAGE_CONTRACT_VERSIONS,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let package_hash: ContractPackageHash = account
+        .named_keys()
+        .get(PACKAGE_HASH_KEY)
+        .cloned()
+        .and_then(Key::into_hash)
+        .expect("should have contract package");
+    let contract_hash_v1: ContractHash = account
+        .named_keys()
+        .get(CONTRACT_HASH_V1_KEY)
+        .cloned()
+        .and_then(Key::into_hash)
+        .expect("should have contract hash");
+
+    let versions = builder.get_contract_versions(package_hash);
+    assert_eq!(versions.len(), 2);
+    assert!(versions
+        .iter()
+        .all(|version| version.status == ContractVersionStatus::Enabled));
+
+    let exec_request_2 = call_entry_point(
+        CONTRACT_VERSION_2,
+        DISABLE_VERSION,
+        runtime_args! { ARG_CONTRACT_HASH => contract_hash_v1 },
+        [2; 32],
+    );
+    builder.exec(exec_request_2).expect_success().commit();
+
+    let versions = builder.get_contract_versions(package_hash);
+    assert_eq!(versions[0].contract_hash, contract_hash_v1);
+    assert_eq!(versions[0].status, ContractVersionStatus::Disabled);
+    assert_eq!(versions[1].status, ContractVersionStatus::Enabled);
+
+    // Disabled version can't be called
+    let exec_request_3 = call_entry_point(
+        CONTRACT_VERSION_1,
+        SET_VERSION_METADATA,
+        runtime_args! { ARG_CONTRACT_HASH => contract_hash_v1, ARG_METADATA => METADATA },
+        [3; 32],
+    );
+    builder.exec(exec_request_3).commit();
+    assert!(builder.is_error());
+
+    let exec_request_4 = call_entry_point(
+        CONTRACT_VERSION_2,
+        SET_VERSION_METADATA,
+        runtime_args! { ARG_CONTRACT_HASH => contract_hash_v1, ARG_METADATA => METADATA },
+        [4; 32],
+    );
+    builder.exec(exec_request_4).expect_success().commit();
+
+    let exec_request_5 = call_entry_point(
+        CONTRACT_VERSION_2,
+        ENABLE_VERSION,
+        runtime_args! { ARG_CONTRACT_HASH => contract_hash_v1 },
+        [5; 32],
+    );
+    builder.exec(exec_request_5).expect_success().commit();
+
+    let versions = builder.get_contract_versions(package_hash);
+    assert_eq!(versions[0].contract_hash, contract_hash_v1);
+    assert_eq!(versions[0].status, ContractVersionStatus::Enabled);
+    assert_eq!(versions[0].metadata.as_deref(), Some(METADATA));
+    assert_eq!(versions[1].metadata, None);
+
+    // Enabled version can be called again
+    let exec_request_6 = call_entry_point(
+        CONTRACT_VERSION_1,
+        DISABLE_VERSION,
+        runtime_args! { ARG_CONTRACT_HASH => contract_hash_v1 },
+        [6; 32],
+    );
+    builder.exec(exec_request_6).expect_success().commit();
+}
@@ -1,12 +1,21 @@
 mod check_transfer_success;
+mod contract_abi;
 mod contract_api;
 mod contract_context;
 mod counter;
 mod deploy;
+mod entry_point_declarations;
 mod explorer;
+mod fuzz;
 mod groups;
+mod manage_contract_versions;
 mod manage_groups;
+#[cfg(feature = "native")]
+mod native;
 mod regression;
+mod rotate_access_key;
+mod snapshot;
 mod system_contracts;
+mod test_context;
 mod upgrade;
 mod wasmless_transfer;
@@ -0,0 +1,92 @@
+use contract::contract_api::{runtime, storage};
+use engine_core::engine_state::CONV_RATE;
+use engine_shared::{gas::Gas, motes::Motes};
+use types::{runtime_args, ApiError, Key, RuntimeArgs};
+
+use engine_test_support::{
+    Code, RunResult, SessionBuilder, TestContext, TestContextBuilder, Value, DEFAULT_ACCOUNT_ADDR,
+};
+
+const ARG_VALUE: &str = "value";
+const VALUE_KEY_NAME: &str = "native_value";
+const REVERT_CODE: u16 = 42;
+
+fn store_value() {
+    let value: u64 = runtime::get_named_arg(ARG_VALUE);
+    let uref = storage::new_uref(value);
+    runtime::put_key(VALUE_KEY_NAME, uref.into());
+}
+
+fn revert() {
+    runtime::revert(ApiError::User(REVERT_CODE))
+}
+
+fn run(context: &mut TestContext, entry_point: fn(), args: RuntimeArgs) -> RunResult {
+    let session = SessionBuilder::new(Code::Native(entry_point), args)
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .without_expect_success()
+        .build();
+    context.run_with_result(session)
+}
+
+#[ignore]
+#[test]
+fn should_run_native_session_code() {
+    let mut context = TestContextBuilder::new().build();
+
+    let result = run(
+        &mut context,
+        store_value,
+        runtime_args! { ARG_VALUE => 7u64 },
+    );
+    assert!(result.is_success(), "{:?}", result.error());
+
+    let uref = context
+        .query(DEFAULT_ACCOUNT_ADDR, &[VALUE_KEY_NAME])
+        .and_then(Value::into_t::<Key>)
+        .expect("should have stored value");
+    let value: u64 = context
+        .query_key(uref, &[] as &[&str])
+        .and_then(Value::into_t)
+        .expect("should read stored value");
+    assert_eq!(value, 7);
+}
+
+#[ignore]
+#[test]
+fn should_revert_native_session_code() {
+    let mut context = TestContextBuilder::new().build();
+
+    let result = run(&mut context, revert, RuntimeArgs::new());
+    assert_eq!(result.api_error(), Some(ApiError::User(REVERT_CODE)));
+    assert!(context
+        .query(DEFAULT_ACCOUNT_ADDR, &[VALUE_KEY_NAME])
+        .is_err());
+}
+
+#[ignore]
+#[test]
+fn should_charge_for_native_session_code() {
+    let mut context = TestContextBuilder::new().build();
+    let main_purse = context
+        .main_purse_address(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have main purse");
+    let initial_balance = context.get_balance(main_purse.addr());
+
+    let result = run(
+        &mut context,
+        store_value,
+        runtime_args! { ARG_VALUE => 7u64 },
+    );
+    assert!(result.is_success(), "{:?}", result.error());
+
+    // the cost includes the standard payment code as well as the host functions called natively
+    let cost = result.cost();
+    assert!(!cost.is_zero());
+    let fee = Motes::from_gas(Gas::new(cost), CONV_RATE).expect("should convert gas to motes");
+    assert_eq!(
+        context.get_balance(main_purse.addr()),
+        initial_balance - fee.value()
+    );
+}
@@ -0,0 +1,142 @@
+use assert_matches::assert_matches;
+use contracts::CONTRACT_INITIAL_VERSION;
+use engine_core::{
+    engine_state::{execute_request::ExecuteRequest, Error},
+    execution,
+};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{contracts, runtime_args, ContractPackage, Key, RuntimeArgs, URef};
+
+const CONTRACT_ROTATE_ACCESS_KEY: &str = "rotate_access_key.wasm";
+const PACKAGE_HASH_KEY: &str = "package_hash_key";
+const PACKAGE_ACCESS_KEY: &str = "package_access_key";
+const ROTATE_ACCESS_KEY: &str = "rotate_access_key";
+const LOCK_PACKAGE: &str = "lock_package";
+const ADD_VERSION: &str = "add_version";
+const ARG_AMOUNT: &str = "amount";
+
+fn call_entry_point(entry_point: &str, deploy_hash: [u8; 32]) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_stored_versioned_contract_by_name(
+            PACKAGE_HASH_KEY,
+            Some(CONTRACT_INITIAL_VERSION),
+            entry_point,
+            RuntimeArgs::default(),
+        )
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash)
+        .build();
+
+    ExecuteRequestBuilder::new().push_deploy(deploy).build()
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_ROTATE_ACCESS_KEY,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn get_package_and_access_key(builder: &InMemoryWasmTestBuilder) -> (ContractPackage, URef) {
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let package_hash = account
+        .named_keys()
+        .get(PACKAGE_HASH_KEY)
+        .expect("should have contract package");
+    let access_uref = account
+        .named_keys()
+        .get(PACKAGE_ACCESS_KEY)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should have access key");
+    let contract_package = builder
+        .query(None, *package_hash, &[])
+        .expect("should have result")
+        .as_contract_package()
+        .cloned()
+        .expect("should be package");
+    (contract_package, access_uref)
+}
+
+#[ignore]
+#[test]
+fn should_rotate_access_key() {
+    let mut builder = setup();
+
+    let (contract_package, old_access_uref) = get_package_and_access_key(&builder);
+    assert_eq!(contract_package.access_key(), old_access_uref);
+
+    builder
+        .exec(call_entry_point(ROTATE_ACCESS_KEY, [2; 32]))
+        .expect_success()
+        .commit();
+
+    let (contract_package, new_access_uref) = get_package_and_access_key(&builder);
+    assert_ne!(new_access_uref, old_access_uref);
+    assert_eq!(contract_package.access_key(), new_access_uref);
+    assert!(!contract_package.is_locked());
+
+    // The new access key still allows adding versions
+    builder
+        .exec(call_entry_point(ADD_VERSION, [3; 32]))
+        .expect_success()
+        .commit();
+
+    let (contract_package, _) = get_package_and_access_key(&builder);
+    assert_eq!(contract_package.versions().len(), 2);
+}
+
+#[ignore]
+#[test]
+fn should_not_add_version_to_locked_package() {
+    let mut builder = setup();
+
+    let (_, old_access_uref) = get_package_and_access_key(&builder);
+
+    builder
+        .exec(call_entry_point(LOCK_PACKAGE, [2; 32]))
+        .expect_success()
+        .commit();
+
+    let (contract_package, new_access_uref) = get_package_and_access_key(&builder);
+    assert_ne!(new_access_uref, old_access_uref);
+    assert_eq!(contract_package.access_key(), new_access_uref);
+    assert!(contract_package.is_locked());
+
+    builder
+        .exec(call_entry_point(ADD_VERSION, [3; 32]))
+        .commit();
+
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    assert_eq!(response.len(), 1);
+    let exec_response = response.last().expect("should have response");
+    let error = exec_response.as_error().expect("should have error");
+    let error = assert_matches!(error, Error::Exec(execution::Error::Revert(e)) => e);
+    assert_eq!(error, &contracts::Error::LockedContractPackage.into());
+
+    let (contract_package, _) = get_package_and_access_key(&builder);
+    assert_eq!(contract_package.versions().len(), 1);
+}
@@ -0,0 +1,87 @@
+use tempfile::TempDir;
+
+use engine_core::engine_state::execute_request::ExecuteRequest;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, LmdbWasmTestBuilder,
+        DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{Key, RuntimeArgs};
+
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const SNAPSHOT_FILE_NAME: &str = "global_state.snapshot";
+
+fn do_nothing_exec_request() -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, DO_NOTHING_WASM, RuntimeArgs::new())
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_start_in_memory_and_lmdb_builders_from_snapshot() {
+    let temp_dir = TempDir::new().expect("should create temp dir");
+    let snapshot_path = temp_dir.path().join(SNAPSHOT_FILE_NAME);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(do_nothing_exec_request())
+        .expect_success()
+        .commit();
+    let summary = builder.export_snapshot(&snapshot_path, &[*DEFAULT_PROTOCOL_VERSION]);
+    assert_eq!(summary.state_root.to_vec(), builder.get_post_state_hash());
+
+    let expected_account = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[])
+        .expect("should have account");
+
+    let mut in_memory_builder = InMemoryWasmTestBuilder::from_snapshot(
+        &snapshot_path,
+        Default::default(),
+        *DEFAULT_PROTOCOL_VERSION,
+    );
+    let lmdb_data_dir = TempDir::new().expect("should create temp dir");
+    let mut lmdb_builder = LmdbWasmTestBuilder::from_snapshot(
+        lmdb_data_dir.path(),
+        &snapshot_path,
+        Default::default(),
+        *DEFAULT_PROTOCOL_VERSION,
+    );
+
+    assert_eq!(
+        in_memory_builder.get_post_state_hash(),
+        builder.get_post_state_hash()
+    );
+    assert_eq!(
+        in_memory_builder.get_genesis_account(),
+        builder.get_genesis_account()
+    );
+    assert_eq!(
+        in_memory_builder.get_mint_contract_hash(),
+        builder.get_mint_contract_hash()
+    );
+    assert_eq!(
+        in_memory_builder.query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[]),
+        Ok(expected_account.clone())
+    );
+    assert_eq!(
+        lmdb_builder.get_post_state_hash(),
+        builder.get_post_state_hash()
+    );
+    assert_eq!(
+        lmdb_builder.query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[]),
+        Ok(expected_account)
+    );
+
+    // Both restored builders can continue executing deploys.
+    in_memory_builder
+        .exec(do_nothing_exec_request())
+        .expect_success()
+        .commit();
+    lmdb_builder
+        .exec(do_nothing_exec_request())
+        .expect_success()
+        .commit();
+}
@@ -8,7 +8,7 @@ use engine_test_support::internal::{
 };
 #[cfg(feature = "use-system-contracts")]
 use engine_test_support::{internal::ExecuteRequestBuilder, DEFAULT_ACCOUNT_ADDR};
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{wasm_costs::WasmCosts, wasm_limits::WasmLimits};
 use types::ProtocolVersion;
 #[cfg(feature = "use-system-contracts")]
 use types::{runtime_args, CLValue, Key, RuntimeArgs, U512};
@@ -35,6 +35,7 @@ fn get_upgraded_wasm_costs() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        memfill: 1,
     }
 }
 
@@ -399,6 +400,58 @@ fn should_allow_only_wasm_costs_minor_version() {
     );
 }
 
+#[ignore]
+#[test]
+fn should_allow_only_wasm_limits_patch_version() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let new_limits = WasmLimits {
+        max_memory_pages: 32,
+        max_module_size: 1024 * 1024,
+        ..WasmLimits::default()
+    };
+
+    let mut upgrade_request = {
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(new_protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_new_limits(new_limits)
+            .build()
+    };
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let protocol_data = builder
+        .get_engine_state()
+        .get_protocol_data(new_protocol_version)
+        .expect("should have result")
+        .expect("should have upgraded protocol data");
+
+    assert_eq!(
+        new_limits,
+        *protocol_data.wasm_limits(),
+        "upgraded limits should equal new limits"
+    );
+    assert_eq!(
+        *DEFAULT_WASM_COSTS,
+        *protocol_data.wasm_costs(),
+        "costs should be unchanged"
+    );
+}
+
 #[cfg(feature = "use-system-contracts")]
 #[ignore]
 #[test]
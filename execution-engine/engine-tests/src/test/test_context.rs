@@ -0,0 +1,202 @@
+use types::{
+    contracts::CONTRACT_INITIAL_VERSION, runtime_args, ApiError, CLValue, ContractHash, Key,
+    RuntimeArgs,
+};
+
+use engine_test_support::{
+    Code, Error, RunResult, SessionBuilder, TestContext, TestContextBuilder, Value,
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_COUNTER_DEFINE: &str = "counter_define.wasm";
+const CONTRACT_GET_BLOCKTIME: &str = "get_blocktime.wasm";
+const CONTRACT_PRINT_AND_RETURN: &str = "print_and_return.wasm";
+const HASH_KEY_NAME: &str = "counter_package_hash";
+const COUNTER_CONTRACT_HASH_KEY_NAME: &str = "counter_contract_hash";
+const COUNTER_VALUE_UREF: &str = "counter";
+const ENTRYPOINT_COUNTER: &str = "counter";
+const ARG_COUNTER_METHOD: &str = "method";
+const ARG_KNOWN_BLOCK_TIME: &str = "known_block_time";
+const ARG_LINES: &str = "lines";
+const ARG_ERROR: &str = "error";
+const METHOD_INC: &str = "inc";
+const BLOCK_TIME: u64 = 42;
+
+fn run(context: &mut TestContext, code: Code, args: RuntimeArgs) {
+    let session = SessionBuilder::new(code, args)
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .build();
+    context.run(session);
+}
+
+fn run_print_and_return(context: &mut TestContext, error: Option<u32>) -> RunResult {
+    let lines = vec!["first".to_string(), "second".to_string()];
+    let session = SessionBuilder::new(
+        Code::from(CONTRACT_PRINT_AND_RETURN),
+        runtime_args! { ARG_LINES => lines, ARG_ERROR => error },
+    )
+    .with_address(DEFAULT_ACCOUNT_ADDR)
+    .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+    .without_expect_success()
+    .build();
+    context.run_with_result(session)
+}
+
+fn setup() -> (TestContext, ContractHash) {
+    let mut context = TestContextBuilder::new().build();
+    run(
+        &mut context,
+        Code::from(CONTRACT_COUNTER_DEFINE),
+        RuntimeArgs::new(),
+    );
+    let contract_hash = context
+        .query(DEFAULT_ACCOUNT_ADDR, &[COUNTER_CONTRACT_HASH_KEY_NAME])
+        .and_then(Value::into_t::<Key>)
+        .expect("should have counter contract hash")
+        .into_hash()
+        .expect("should be a hash");
+    (context, contract_hash)
+}
+
+fn counter_value(context: &TestContext, contract_hash: ContractHash) -> i32 {
+    context
+        .query_key(Key::Hash(contract_hash), &[COUNTER_VALUE_UREF])
+        .and_then(Value::into_t)
+        .expect("should have counter value")
+}
+
+fn increment(context: &mut TestContext, code: Code) {
+    run(
+        context,
+        code,
+        runtime_args! { ARG_COUNTER_METHOD => METHOD_INC },
+    );
+}
+
+#[ignore]
+#[test]
+fn should_call_stored_contract_by_hash_and_by_package_version() {
+    let (mut context, contract_hash) = setup();
+    assert_eq!(counter_value(&context, contract_hash), 0);
+
+    increment(
+        &mut context,
+        Code::Hash(contract_hash, ENTRYPOINT_COUNTER.to_string()),
+    );
+    assert_eq!(counter_value(&context, contract_hash), 1);
+
+    increment(
+        &mut context,
+        Code::VersionedNamedKey(
+            HASH_KEY_NAME.to_string(),
+            None,
+            ENTRYPOINT_COUNTER.to_string(),
+        ),
+    );
+    assert_eq!(counter_value(&context, contract_hash), 2);
+
+    let package_hash = context
+        .query(DEFAULT_ACCOUNT_ADDR, &[HASH_KEY_NAME])
+        .and_then(Value::into_t::<Key>)
+        .expect("should have counter package hash")
+        .into_hash()
+        .expect("should be a hash");
+    increment(
+        &mut context,
+        Code::VersionedHash(
+            package_hash,
+            Some(CONTRACT_INITIAL_VERSION),
+            ENTRYPOINT_COUNTER.to_string(),
+        ),
+    );
+    assert_eq!(counter_value(&context, contract_hash), 3);
+}
+
+#[ignore]
+#[test]
+fn should_roll_back_to_snapshot() {
+    let (mut context, contract_hash) = setup();
+    let snapshot = context.snapshot();
+
+    for expected_value in 1..=2 {
+        increment(
+            &mut context,
+            Code::Hash(contract_hash, ENTRYPOINT_COUNTER.to_string()),
+        );
+        assert_eq!(counter_value(&context, contract_hash), expected_value);
+    }
+
+    context.rollback(&snapshot);
+    assert_eq!(counter_value(&context, contract_hash), 0);
+    assert_eq!(context.snapshot(), snapshot);
+
+    // The scenario can branch again from the same snapshot.
+    increment(
+        &mut context,
+        Code::Hash(contract_hash, ENTRYPOINT_COUNTER.to_string()),
+    );
+    assert_eq!(counter_value(&context, contract_hash), 1);
+}
+
+#[ignore]
+#[test]
+fn should_run_sessions_at_advanced_block_time() {
+    let mut context = TestContextBuilder::new()
+        .with_block_time(BLOCK_TIME)
+        .build();
+    let snapshot = context.snapshot();
+
+    run(
+        &mut context,
+        Code::from(CONTRACT_GET_BLOCKTIME),
+        runtime_args! { ARG_KNOWN_BLOCK_TIME => BLOCK_TIME },
+    );
+
+    context.advance_block_time(BLOCK_TIME);
+    assert_eq!(context.block_time(), 2 * BLOCK_TIME);
+    run(
+        &mut context,
+        Code::from(CONTRACT_GET_BLOCKTIME),
+        runtime_args! { ARG_KNOWN_BLOCK_TIME => 2 * BLOCK_TIME },
+    );
+
+    context.rollback(&snapshot);
+    assert_eq!(context.block_time(), BLOCK_TIME);
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_query_missing_key() {
+    let (context, contract_hash) = setup();
+    let result: Result<Value, Error> = context.query_key(Key::Hash(contract_hash), &["missing"]);
+    assert!(result.is_err());
+}
+
+#[ignore]
+#[test]
+fn should_return_printed_lines_and_returned_value() {
+    let mut context = TestContextBuilder::new().build();
+    let result = run_print_and_return(&mut context, None);
+
+    assert_eq!(result.printed(), ["first", "second"]);
+    assert_eq!(
+        result.returned_value(),
+        Some(&CLValue::from_t(2u32).expect("should create CLValue"))
+    );
+    assert!(result.api_error().is_none());
+    assert!(result.cost() > 0.into());
+}
+
+#[ignore]
+#[test]
+fn should_return_api_error_of_reverted_session() {
+    let mut context = TestContextBuilder::new().build();
+    let result = run_print_and_return(&mut context, Some(7));
+
+    assert!(!result.is_success());
+    assert_eq!(result.printed(), ["first", "second"]);
+    assert!(result.returned_value().is_none());
+    assert_eq!(result.api_error(), Some(ApiError::User(7)));
+    assert_eq!(result.api_error_name().as_deref(), Some("User(7)"));
+}
@@ -0,0 +1,119 @@
+//! The functions provided by the host, which contracts import from the [`HOST_MODULE`] module.
+
+use parity_wasm::elements::{FunctionType, ValueType};
+
+/// The name of the module from which contracts import the host functions.
+pub const HOST_MODULE: &str = "env";
+
+// The name of each host function, with its number of params and whether it returns a result.  All
+// params and results are `i32`s.
+const HOST_FUNCTIONS: &[(&str, usize, bool)] = &[
+    ("read_value", 3, true),
+    ("read_value_local", 3, true),
+    ("load_named_keys", 2, true),
+    ("write", 4, false),
+    ("write_local", 4, false),
+    ("remove_local", 2, false),
+    ("remove_uref", 2, false),
+    ("add", 4, false),
+    ("new_uref", 3, false),
+    ("ret", 2, false),
+    ("get_key", 5, true),
+    ("has_key", 2, true),
+    ("put_key", 4, false),
+    ("gas", 1, false),
+    ("is_valid_uref", 2, true),
+    ("revert", 1, false),
+    ("add_associated_key", 3, true),
+    ("remove_associated_key", 2, true),
+    ("update_associated_key", 3, true),
+    ("set_action_threshold", 2, true),
+    ("manage_account", 4, true),
+    ("remove_key", 2, false),
+    ("get_caller", 1, true),
+    ("get_blocktime", 1, false),
+    ("create_purse", 2, true),
+    ("transfer_to_account", 4, true),
+    ("transfer_from_purse_to_account", 6, true),
+    ("transfer_from_purse_to_purse", 6, true),
+    ("get_balance", 3, true),
+    ("get_phase", 1, false),
+    ("get_system_contract", 3, true),
+    ("get_main_purse", 1, false),
+    ("read_host_buffer", 3, true),
+    ("create_contract_package_at_hash", 2, false),
+    ("create_contract_user_group", 8, true),
+    ("add_contract_version", 10, true),
+    ("disable_contract_version", 4, true),
+    ("rotate_access_key", 4, true),
+    ("enable_contract_version", 4, true),
+    ("set_contract_version_metadata", 6, true),
+    ("call_contract", 7, true),
+    ("call_versioned_contract", 9, true),
+    ("get_named_arg_size", 3, true),
+    ("get_named_arg", 4, true),
+    ("remove_contract_user_group", 4, true),
+    ("provision_contract_user_group_uref", 5, true),
+    ("remove_contract_user_group_urefs", 6, true),
+    ("memory_copy", 3, false),
+    ("memory_fill", 3, false),
+];
+
+// Host functions which are only provided when the `test-support` feature is enabled.
+#[cfg(feature = "test-support")]
+const TEST_SUPPORT_HOST_FUNCTIONS: &[(&str, usize, bool)] = &[("print", 2, false)];
+#[cfg(not(feature = "test-support"))]
+const TEST_SUPPORT_HOST_FUNCTIONS: &[(&str, usize, bool)] = &[];
+
+fn function_type(params: usize, has_result: bool) -> FunctionType {
+    let return_type = if has_result {
+        Some(ValueType::I32)
+    } else {
+        None
+    };
+    FunctionType::new(vec![ValueType::I32; params], return_type)
+}
+
+/// Returns the names and signatures of all the host functions.
+pub fn host_functions() -> impl Iterator<Item = (&'static str, FunctionType)> {
+    HOST_FUNCTIONS
+        .iter()
+        .chain(TEST_SUPPORT_HOST_FUNCTIONS.iter())
+        .map(|(name, params, has_result)| (*name, function_type(*params, *has_result)))
+}
+
+/// Returns the signature of the host function called `name`, or `None` if there is no such host
+/// function.
+pub fn host_function_type(name: &str) -> Option<FunctionType> {
+    host_functions()
+        .find(|(host_function_name, _)| *host_function_name == name)
+        .map(|(_, function_type)| function_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn should_have_unique_names() {
+        let names: BTreeSet<_> = host_functions().map(|(name, _)| name).collect();
+        assert_eq!(names.len(), host_functions().count());
+    }
+
+    #[test]
+    fn should_find_host_function_type() {
+        let expected = FunctionType::new(vec![ValueType::I32; 2], Some(ValueType::I32));
+        assert_eq!(host_function_type("has_key"), Some(expected));
+        assert_eq!(host_function_type("not_a_host_function"), None);
+    }
+
+    #[test]
+    fn should_only_provide_print_with_test_support() {
+        assert_eq!(
+            host_function_type("print").is_some(),
+            cfg!(feature = "test-support")
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Instantiation of an instrumented module against stand-ins for the host functions, so that a
+//! module can be checked for acceptance without an execution engine.
+
+use parity_wasm::elements::{self, FunctionType, Module};
+use wasmi::{
+    memory_units::Pages, Error as InterpreterError, FuncInstance, FuncRef, ImportsBuilder,
+    MemoryDescriptor, MemoryInstance, MemoryRef, ModuleImportResolver, ModuleInstance, Signature,
+    ValueType,
+};
+
+use crate::{host_functions, PreprocessingError};
+
+/// Resolves imports as the execution engine does, but to host functions which can't be invoked.
+struct HostResolver {
+    max_memory_pages: u32,
+}
+
+fn value_type(value_type: elements::ValueType) -> ValueType {
+    match value_type {
+        elements::ValueType::I32 => ValueType::I32,
+        elements::ValueType::I64 => ValueType::I64,
+        elements::ValueType::F32 => ValueType::F32,
+        elements::ValueType::F64 => ValueType::F64,
+    }
+}
+
+fn signature(function_type: &FunctionType) -> Signature {
+    let params: Vec<ValueType> = function_type
+        .params()
+        .iter()
+        .map(|param| value_type(*param))
+        .collect();
+    Signature::new(params, function_type.return_type().map(value_type))
+}
+
+impl ModuleImportResolver for HostResolver {
+    fn resolve_func(
+        &self,
+        field_name: &str,
+        _signature: &Signature,
+    ) -> Result<FuncRef, InterpreterError> {
+        host_functions::host_functions()
+            .enumerate()
+            .find(|(_, (name, _))| *name == field_name)
+            .map(|(index, (_, function_type))| {
+                FuncInstance::alloc_host(signature(&function_type), index)
+            })
+            .ok_or_else(|| {
+                InterpreterError::Function(format!(
+                    "host module doesn't export function with name {}",
+                    field_name
+                ))
+            })
+    }
+
+    fn resolve_memory(
+        &self,
+        field_name: &str,
+        descriptor: &MemoryDescriptor,
+    ) -> Result<MemoryRef, InterpreterError> {
+        if field_name != "memory" {
+            return Err(InterpreterError::Instantiation(
+                "Memory imported under unknown name".to_owned(),
+            ));
+        }
+        let exceeds_max = descriptor
+            .maximum()
+            .map_or(true, |maximum| maximum > self.max_memory_pages);
+        if descriptor.initial() > self.max_memory_pages || exceeds_max {
+            return Err(InterpreterError::Instantiation(
+                "Module requested too much memory".to_owned(),
+            ));
+        }
+        MemoryInstance::alloc(
+            Pages(descriptor.initial() as usize),
+            descriptor.maximum().map(|maximum| Pages(maximum as usize)),
+        )
+    }
+}
+
+/// Checks that the instrumented `module` is valid and can be instantiated against the host
+/// functions and a memory of no more than `max_memory_pages` pages, without a start function.
+pub(crate) fn check_instantiation(
+    module: Module,
+    max_memory_pages: u32,
+) -> Result<(), PreprocessingError> {
+    let instantiation_error = |error: InterpreterError| match error {
+        InterpreterError::Validation(message) => PreprocessingError::Invalid(message),
+        error => PreprocessingError::Instantiation(error.to_string()),
+    };
+    let module = wasmi::Module::from_parity_wasm_module(module).map_err(instantiation_error)?;
+    let resolver = HostResolver { max_memory_pages };
+    let imports = ImportsBuilder::new().with_resolver(host_functions::HOST_MODULE, &resolver);
+    let not_started_module = ModuleInstance::new(&module, &imports).map_err(instantiation_error)?;
+    if not_started_module.has_start() {
+        return Err(PreprocessingError::StartFunction);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{
+        builder,
+        elements::{Instruction, Instructions, Section},
+    };
+
+    use crate::{wasm_costs::WasmCosts, wasm_limits::WasmLimits, PreprocessingError, Preprocessor};
+
+    fn module_bytes(with_start: bool) -> Vec<u8> {
+        let mut module = builder::module()
+            .memory()
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![Instruction::End]))
+            .build()
+            .build()
+            .build();
+        if with_start {
+            let code_section_index = module
+                .sections()
+                .iter()
+                .position(|section| match section {
+                    Section::Code(_) => true,
+                    _ => false,
+                })
+                .expect("should have code section");
+            module
+                .sections_mut()
+                .insert(code_section_index, Section::Start(0));
+        }
+        parity_wasm::serialize(module).expect("should serialize")
+    }
+
+    fn preprocessor() -> Preprocessor {
+        Preprocessor::new(WasmCosts::default(), WasmLimits::default())
+    }
+
+    #[test]
+    fn should_accept_instantiable_module() {
+        preprocessor()
+            .check(&module_bytes(false))
+            .expect("should pass checks");
+    }
+
+    #[test]
+    fn should_reject_start_function() {
+        match preprocessor().check(&module_bytes(true)) {
+            Err(PreprocessingError::StartFunction) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
@@ -1,20 +1,86 @@
+pub mod host_functions;
+mod instantiation;
+pub mod post_mvp;
+mod validation;
 pub mod wasm_costs;
+pub mod wasm_limits;
 
 use std::fmt::{self, Display, Formatter};
 
-use parity_wasm::elements::{self, Module};
+use parity_wasm::elements::{self, External, FunctionType, Instruction, Module};
 use pwasm_utils::{self, stack_height};
 
-use crate::wasm_costs::WasmCosts;
-
-//NOTE: size of Wasm memory page is 64 KiB
-pub const MEM_PAGES: u32 = 64;
+use crate::{wasm_costs::WasmCosts, wasm_limits::WasmLimits};
 
 #[derive(Debug, Clone)]
 pub enum PreprocessingError {
     Deserialize(String),
     OperationForbiddenByGasRules,
     StackLimiter,
+    /// The module doesn't declare its own memory.
+    MissingMemory,
+    /// The module's memory is declared with more pages than allowed.
+    MemoryTooLarge {
+        initial: u32,
+        maximum: Option<u32>,
+        limit: u32,
+    },
+    /// The module imports something other than a host function.
+    UnsupportedImport {
+        module: String,
+        field: String,
+    },
+    /// The module imports a host function which doesn't exist.
+    UnknownHostFunction(String),
+    /// The module imports a host function with the wrong signature.
+    HostFunctionSignatureMismatch {
+        name: String,
+        expected: FunctionType,
+        actual: FunctionType,
+    },
+    /// A function of the module uses a floating point instruction.
+    FloatingPointInstruction {
+        function_index: u32,
+        instruction: Instruction,
+    },
+    /// The serialized module is larger than allowed.
+    ModuleTooLarge {
+        size: usize,
+        limit: u32,
+    },
+    /// The module's table is declared with more entries than allowed.
+    TableTooLarge {
+        initial: u32,
+        maximum: Option<u32>,
+        limit: u32,
+    },
+    /// A function of the module has more locals than allowed.
+    TooManyLocals {
+        function_index: u32,
+        count: u64,
+        limit: u32,
+    },
+    /// The module declares more globals than allowed.
+    TooManyGlobals {
+        count: usize,
+        limit: u32,
+    },
+    /// The module has more functions than allowed.
+    TooManyFunctions {
+        count: usize,
+        limit: u32,
+    },
+    /// A function of the module uses a post-MVP instruction which can't be lowered to MVP code.
+    UnsupportedInstruction {
+        function_index: u32,
+        instruction: String,
+    },
+    /// The instrumented module isn't valid Wasm.
+    Invalid(String),
+    /// The instrumented module can't be instantiated against the host functions.
+    Instantiation(String),
+    /// The module has a start function, which isn't supported.
+    StartFunction,
 }
 
 impl From<elements::Error> for PreprocessingError {
@@ -29,36 +95,158 @@ impl Display for PreprocessingError {
             PreprocessingError::Deserialize(error) => write!(f, "Deserialization error: {}", error),
             PreprocessingError::OperationForbiddenByGasRules => write!(f, "Encountered operation forbidden by gas rules. Consult instruction -> metering config map"),
             PreprocessingError::StackLimiter => write!(f, "Stack limiter error"),
+            PreprocessingError::MissingMemory => write!(f, "Module doesn't declare its own memory"),
+            PreprocessingError::MemoryTooLarge { initial, maximum, limit } => write!(f, "Memory of {} initial and {:?} maximum pages exceeds limit of {} pages", initial, maximum, limit),
+            PreprocessingError::UnsupportedImport { module, field } => write!(f, "Unsupported import {}::{}: only host functions can be imported", module, field),
+            PreprocessingError::UnknownHostFunction(name) => write!(f, "Imported host function {} doesn't exist", name),
+            PreprocessingError::HostFunctionSignatureMismatch { name, expected, actual } => write!(f, "Imported host function {} has signature {:?}, expected {:?}", name, actual, expected),
+            PreprocessingError::FloatingPointInstruction { function_index, instruction } => write!(f, "Function {} uses floating point instruction {}", function_index, instruction),
+            PreprocessingError::ModuleTooLarge { size, limit } => write!(f, "Module of {} bytes exceeds limit of {} bytes", size, limit),
+            PreprocessingError::TableTooLarge { initial, maximum, limit } => write!(f, "Table of {} initial and {:?} maximum entries exceeds limit of {} entries", initial, maximum, limit),
+            PreprocessingError::TooManyLocals { function_index, count, limit } => write!(f, "Function {} has {} locals, exceeding limit of {}", function_index, count, limit),
+            PreprocessingError::TooManyGlobals { count, limit } => write!(f, "Module has {} globals, exceeding limit of {}", count, limit),
+            PreprocessingError::TooManyFunctions { count, limit } => write!(f, "Module has {} functions, exceeding limit of {}", count, limit),
+            PreprocessingError::UnsupportedInstruction { function_index, instruction } => write!(f, "Function {} uses unsupported instruction {}", function_index, instruction),
+            PreprocessingError::Invalid(error) => write!(f, "Invalid module: {}", error),
+            PreprocessingError::Instantiation(error) => write!(f, "Instantiation error: {}", error),
+            PreprocessingError::StartFunction => write!(f, "Module has a start function, which isn't supported"),
         }
     }
 }
 
+/// A module instrumented for execution, along with the bytes of the original module it was
+/// instrumented from.
+///
+/// The original bytes, exactly as supplied, are what gets stored in global state, so that they can
+/// be instrumented again under the costs of later protocol versions.
+#[derive(Clone, Debug)]
+pub struct InstrumentedModule {
+    original: Vec<u8>,
+    instrumented: Module,
+}
+
+impl InstrumentedModule {
+    /// Returns the bytes of the module as supplied, before instrumentation.
+    pub fn original(&self) -> &[u8] {
+        &self.original
+    }
+
+    /// Returns the module with gas metering, stack-height limiting and an imported memory.
+    pub fn instrumented(&self) -> &Module {
+        &self.instrumented
+    }
+
+    /// Returns the original bytes and the instrumented module.
+    pub fn into_parts(self) -> (Vec<u8>, Module) {
+        (self.original, self.instrumented)
+    }
+}
+
 pub struct Preprocessor {
     wasm_costs: WasmCosts,
-    // Number of memory pages.
-    mem_pages: u32,
+    wasm_limits: WasmLimits,
 }
 
 impl Preprocessor {
-    pub fn new(wasm_costs: WasmCosts) -> Self {
+    pub fn new(wasm_costs: WasmCosts, wasm_limits: WasmLimits) -> Self {
         Self {
             wasm_costs,
-            mem_pages: MEM_PAGES,
+            wasm_limits,
         }
     }
 
-    pub fn preprocess(&self, module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
+    /// Deserializes, validates and instruments `module_bytes`.
+    pub fn preprocess(
+        &self,
+        module_bytes: &[u8],
+    ) -> Result<InstrumentedModule, PreprocessingError> {
+        if module_bytes.len() > self.wasm_limits.max_module_size as usize {
+            return Err(PreprocessingError::ModuleTooLarge {
+                size: module_bytes.len(),
+                limit: self.wasm_limits.max_module_size,
+            });
+        }
         let module = deserialize(module_bytes)?;
-        let module = pwasm_utils::externalize_mem(module, None, self.mem_pages);
-        let module = pwasm_utils::inject_gas_counter(module, &self.wasm_costs.to_set())
+        self.instrument(module_bytes, module)
+    }
+
+    /// Preprocesses `module_bytes` stored in global state as contract Wasm.
+    ///
+    /// Contract Wasm stored before the original module was kept is already instrumented, under the
+    /// costs in force when it was stored.  Such a module is recognised by its imported memory, as
+    /// validation rejects an imported memory in any module supplied by a deploy, and it's run as
+    /// it is rather than being instrumented again.
+    pub fn preprocess_stored(
+        &self,
+        module_bytes: &[u8],
+    ) -> Result<InstrumentedModule, PreprocessingError> {
+        let module = deserialize(module_bytes)?;
+        if !imports_memory(&module) {
+            return self.preprocess(module_bytes);
+        }
+        Ok(InstrumentedModule {
+            original: module_bytes.to_vec(),
+            instrumented: module,
+        })
+    }
+
+    /// Validates and instruments `module`, deserialized from `module_bytes`.
+    fn instrument(
+        &self,
+        module_bytes: &[u8],
+        module: Module,
+    ) -> Result<InstrumentedModule, PreprocessingError> {
+        validation::validate_module(&module, &self.wasm_limits)?;
+        let instrumented =
+            pwasm_utils::externalize_mem(module, None, self.wasm_limits.max_memory_pages);
+        let instrumented = pwasm_utils::inject_gas_counter(instrumented, &self.wasm_costs.to_set())
             .map_err(|_| PreprocessingError::OperationForbiddenByGasRules)?;
-        let module = stack_height::inject_limiter(module, self.wasm_costs.max_stack_height)
-            .map_err(|_| PreprocessingError::StackLimiter)?;
-        Ok(module)
+        let instrumented =
+            stack_height::inject_limiter(instrumented, self.wasm_costs.max_stack_height)
+                .map_err(|_| PreprocessingError::StackLimiter)?;
+        Ok(InstrumentedModule {
+            original: module_bytes.to_vec(),
+            instrumented,
+        })
+    }
+
+    /// Checks that `module_bytes` would be accepted when deployed, by preprocessing it and then
+    /// instantiating the instrumented module against the host functions, as the execution engine
+    /// does before running it.
+    pub fn check(&self, module_bytes: &[u8]) -> Result<(), PreprocessingError> {
+        let (_original, instrumented) = self.preprocess(module_bytes)?.into_parts();
+        instantiation::check_instantiation(instrumented, self.wasm_limits.max_memory_pages)
     }
 }
 
-// Returns a parity Module from bytes without making modifications or limits
+// Returns `true` if `module` imports its memory, which only an instrumented module does.
+fn imports_memory(module: &Module) -> bool {
+    module
+        .import_section()
+        .map(|import_section| {
+            import_section
+                .entries()
+                .iter()
+                .any(|import_entry| match import_entry.external() {
+                    External::Memory(_) => true,
+                    _ => false,
+                })
+        })
+        .unwrap_or_default()
+}
+
+// Returns a parity Module from bytes without making modifications or limits, other than lowering
+// any sign-extension and bulk-memory instructions to MVP code
 pub fn deserialize(module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
-    parity_wasm::deserialize_buffer::<Module>(module_bytes).map_err(Into::into)
+    let error = match parity_wasm::deserialize_buffer::<Module>(module_bytes) {
+        Ok(module) => return Ok(module),
+        Err(error) => error,
+    };
+    // Only fall back to the original error if the module isn't valid post-MVP Wasm either.
+    let lowered_bytes = match post_mvp::lower(module_bytes) {
+        Ok(lowered_bytes) => lowered_bytes,
+        Err(PreprocessingError::Deserialize(_)) => return Err(error.into()),
+        Err(other) => return Err(other),
+    };
+    parity_wasm::deserialize_buffer::<Module>(&lowered_bytes).map_err(Into::into)
 }
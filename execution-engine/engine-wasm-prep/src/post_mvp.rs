@@ -0,0 +1,429 @@
+//! Lowering of the post-MVP sign-extension and bulk-memory instructions emitted by newer toolchains
+//! into MVP code, as neither `pwasm_utils` nor the interpreter support them.
+//!
+//! Sign-extension instructions become the shifts (or wrap and extend) computing the same value, so
+//! they're metered and stack-limited like any other integer instruction.  `memory.copy` and
+//! `memory.fill` become calls to the [`MEMORY_COPY`] and [`MEMORY_FILL`] host functions, which
+//! charge gas per byte.  The remaining bulk-memory instructions only make sense with passive
+//! segments or table manipulation, neither of which the host supports, so they're rejected.
+
+use parity_wasm_post_mvp::elements::{
+    BulkInstruction, External, FunctionType, ImportCountType, ImportEntry, ImportSection,
+    Instruction, Internal, Module, Section, SignExtInstruction, Type, TypeSection, ValueType,
+};
+
+use crate::{host_functions, PreprocessingError};
+
+/// The host function called in place of `memory.copy`, with the same operands.
+pub const MEMORY_COPY: &str = "memory_copy";
+/// The host function called in place of `memory.fill`, with the same operands.
+pub const MEMORY_FILL: &str = "memory_fill";
+
+fn deserialize_error(error: parity_wasm_post_mvp::elements::Error) -> PreprocessingError {
+    PreprocessingError::Deserialize(error.to_string())
+}
+
+/// Returns whether `module` uses `memory.copy` and `memory.fill`, or an error if it uses any other
+/// bulk-memory instruction.
+fn bulk_memory_uses(module: &Module) -> Result<(bool, bool), PreprocessingError> {
+    let function_bodies = module
+        .code_section()
+        .map(|code_section| code_section.bodies())
+        .unwrap_or_default();
+    let imported_functions = module.import_count(ImportCountType::Function);
+
+    let (mut uses_copy, mut uses_fill) = (false, false);
+    for (index, function_body) in function_bodies.iter().enumerate() {
+        for instruction in function_body.code().elements() {
+            match instruction {
+                Instruction::Bulk(BulkInstruction::MemoryCopy) => uses_copy = true,
+                Instruction::Bulk(BulkInstruction::MemoryFill) => uses_fill = true,
+                Instruction::Bulk(_) => {
+                    return Err(PreprocessingError::UnsupportedInstruction {
+                        function_index: (imported_functions + index) as u32,
+                        instruction: instruction.to_string(),
+                    })
+                }
+                _ => (),
+            }
+        }
+    }
+    Ok((uses_copy, uses_fill))
+}
+
+/// Returns the index of the host function `name` if `module` already imports it.
+fn host_function_import(module: &Module, name: &str) -> Option<u32> {
+    module
+        .import_section()?
+        .entries()
+        .iter()
+        .filter(|import_entry| match import_entry.external() {
+            External::Function(_) => true,
+            _ => false,
+        })
+        .position(|import_entry| {
+            import_entry.module() == host_functions::HOST_MODULE && import_entry.field() == name
+        })
+        .map(|index| index as u32)
+}
+
+/// Appends an import of the host function `name`, which takes three `i32`s, and returns its index.
+///
+/// The function indices of the module's own functions are left for the caller to shift.
+fn add_host_function_import(module: &mut Module, name: &str) -> Result<u32, PreprocessingError> {
+    let function_type = FunctionType::new(vec![ValueType::I32; 3], vec![]);
+    if module.type_section().is_none() {
+        module
+            .insert_section(Section::Type(TypeSection::default()))
+            .map_err(deserialize_error)?;
+    }
+    let types = module
+        .type_section_mut()
+        .map(TypeSection::types_mut)
+        .expect("type section should exist");
+    let type_index = match types
+        .iter()
+        .position(|Type::Function(existing_type)| *existing_type == function_type)
+    {
+        Some(type_index) => type_index,
+        None => {
+            types.push(Type::Function(function_type));
+            types.len() - 1
+        }
+    };
+
+    let function_index = module.import_count(ImportCountType::Function) as u32;
+    if module.import_section().is_none() {
+        module
+            .insert_section(Section::Import(ImportSection::default()))
+            .map_err(deserialize_error)?;
+    }
+    module
+        .import_section_mut()
+        .expect("import section should exist")
+        .entries_mut()
+        .push(ImportEntry::new(
+            host_functions::HOST_MODULE.to_string(),
+            name.to_string(),
+            External::Function(type_index as u32),
+        ));
+    Ok(function_index)
+}
+
+/// Returns the MVP instructions computing the same value as `instruction`.
+fn lower_sign_ext(instruction: SignExtInstruction) -> Vec<Instruction> {
+    use Instruction::*;
+    match instruction {
+        SignExtInstruction::I32Extend8S => vec![I32Const(24), I32Shl, I32Const(24), I32ShrS],
+        SignExtInstruction::I32Extend16S => vec![I32Const(16), I32Shl, I32Const(16), I32ShrS],
+        SignExtInstruction::I64Extend8S => vec![I64Const(56), I64Shl, I64Const(56), I64ShrS],
+        SignExtInstruction::I64Extend16S => vec![I64Const(48), I64Shl, I64Const(48), I64ShrS],
+        SignExtInstruction::I64Extend32S => vec![I32WrapI64, I64ExtendSI32],
+    }
+}
+
+/// Deserializes `module_bytes`, which may use sign-extension and bulk-memory instructions, and
+/// returns the serialized equivalent MVP module.
+pub(crate) fn lower(module_bytes: &[u8]) -> Result<Vec<u8>, PreprocessingError> {
+    let mut module: Module =
+        parity_wasm_post_mvp::deserialize_buffer(module_bytes).map_err(deserialize_error)?;
+    let (uses_copy, uses_fill) = bulk_memory_uses(&module)?;
+
+    // Imports precede the module's own functions, so any added imports shift the latter's indices.
+    let imported_functions = module.import_count(ImportCountType::Function) as u32;
+    let host_function = |module: &mut Module, used: bool, name: &str| {
+        if !used {
+            return Ok(None);
+        }
+        match host_function_import(module, name) {
+            Some(function_index) => Ok(Some(function_index)),
+            None => add_host_function_import(module, name).map(Some),
+        }
+    };
+    let memory_copy = host_function(&mut module, uses_copy, MEMORY_COPY)?;
+    let memory_fill = host_function(&mut module, uses_fill, MEMORY_FILL)?;
+    let shift = module.import_count(ImportCountType::Function) as u32 - imported_functions;
+    let shifted = |function_index: u32| {
+        if function_index >= imported_functions {
+            function_index + shift
+        } else {
+            function_index
+        }
+    };
+
+    if let Some(code_section) = module.code_section_mut() {
+        for function_body in code_section.bodies_mut() {
+            let instructions = function_body.code_mut().elements_mut();
+            let mut lowered = Vec::with_capacity(instructions.len());
+            for instruction in instructions.drain(..) {
+                match (instruction, memory_copy, memory_fill) {
+                    (Instruction::Call(function_index), _, _) => {
+                        lowered.push(Instruction::Call(shifted(function_index)))
+                    }
+                    (Instruction::SignExt(sign_ext), _, _) => {
+                        lowered.extend(lower_sign_ext(sign_ext))
+                    }
+                    (Instruction::Bulk(BulkInstruction::MemoryCopy), Some(function_index), _)
+                    | (Instruction::Bulk(BulkInstruction::MemoryFill), _, Some(function_index)) => {
+                        lowered.push(Instruction::Call(function_index))
+                    }
+                    (instruction, _, _) => lowered.push(instruction),
+                }
+            }
+            *instructions = lowered;
+        }
+    }
+    if let Some(export_section) = module.export_section_mut() {
+        for export_entry in export_section.entries_mut() {
+            if let Internal::Function(function_index) = export_entry.internal_mut() {
+                *function_index = shifted(*function_index);
+            }
+        }
+    }
+    if let Some(elements_section) = module.elements_section_mut() {
+        for element_segment in elements_section.entries_mut() {
+            for function_index in element_segment.members_mut() {
+                *function_index = shifted(*function_index);
+            }
+        }
+    }
+    if let Some(start_function) = module.start_section() {
+        module.set_start_section(shifted(start_function));
+    }
+
+    // Passive segments are only usable by the rejected instructions, so are dropped along with the
+    // data count which covers them.  The names section would be left naming the wrong functions.
+    if let Some(data_section) = module.data_section_mut() {
+        data_section
+            .entries_mut()
+            .retain(|data_segment| !data_segment.passive());
+    }
+    if let Some(elements_section) = module.elements_section_mut() {
+        elements_section
+            .entries_mut()
+            .retain(|element_segment| !element_segment.passive());
+    }
+    module.sections_mut().retain(|section| match section {
+        Section::DataCount(_) => false,
+        _ => true,
+    });
+    module.clear_custom_section("name");
+
+    parity_wasm_post_mvp::serialize(module).map_err(deserialize_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::{
+        External as MvpExternal, Instruction as MvpInstruction, Internal as MvpInternal,
+        Module as MvpModule,
+    };
+    use parity_wasm_post_mvp::elements::{
+        CodeSection, DataSection, DataSegment, ExportEntry, ExportSection, Func, FuncBody,
+        FunctionSection, Instructions, MemorySection, MemoryType,
+    };
+
+    use super::*;
+    use crate::{wasm_costs::WasmCosts, wasm_limits::WasmLimits, Preprocessor};
+
+    const HAS_KEY: &str = "has_key";
+    const CALL: &str = "call";
+
+    // Builds a module importing `has_key` and with a passive data segment, whose exported function
+    // runs `instructions` then calls a second, empty function.
+    fn build(instructions: Vec<Instruction>) -> Vec<u8> {
+        let mut code = instructions;
+        code.extend(vec![Instruction::Call(2), Instruction::End]);
+        let mut passive_segment = DataSegment::new(0, None, vec![1, 2, 3]);
+        passive_segment.set_passive(true);
+        let module = Module::new(vec![
+            Section::Type(TypeSection::with_types(vec![
+                Type::Function(FunctionType::default()),
+                Type::Function(FunctionType::new(
+                    vec![ValueType::I32; 2],
+                    vec![ValueType::I32],
+                )),
+            ])),
+            Section::Import(ImportSection::with_entries(vec![ImportEntry::new(
+                host_functions::HOST_MODULE.to_string(),
+                HAS_KEY.to_string(),
+                External::Function(1),
+            )])),
+            Section::Function(FunctionSection::with_entries(vec![
+                Func::new(0),
+                Func::new(0),
+            ])),
+            Section::Memory(MemorySection::with_entries(vec![MemoryType::new(1, None)])),
+            Section::Export(ExportSection::with_entries(vec![ExportEntry::new(
+                CALL.to_string(),
+                Internal::Function(1),
+            )])),
+            Section::Code(CodeSection::with_bodies(vec![
+                FuncBody::new(vec![], Instructions::new(code)),
+                FuncBody::new(vec![], Instructions::new(vec![Instruction::End])),
+            ])),
+            Section::Data(DataSection::with_entries(vec![passive_segment])),
+        ]);
+        parity_wasm_post_mvp::serialize(module).expect("should serialize")
+    }
+
+    fn lowered(instructions: Vec<Instruction>) -> MvpModule {
+        let module_bytes = build(instructions);
+        assert!(parity_wasm::deserialize_buffer::<MvpModule>(&module_bytes).is_err());
+        crate::deserialize(&module_bytes).expect("should lower")
+    }
+
+    fn imported_function_names(module: &MvpModule) -> Vec<&str> {
+        module
+            .import_section()
+            .expect("should have imports")
+            .entries()
+            .iter()
+            .filter(|import_entry| match import_entry.external() {
+                MvpExternal::Function(_) => true,
+                _ => false,
+            })
+            .map(|import_entry| import_entry.field())
+            .collect()
+    }
+
+    fn exported_function_index(module: &MvpModule) -> u32 {
+        match module
+            .export_section()
+            .expect("should have exports")
+            .entries()[0]
+            .internal()
+        {
+            MvpInternal::Function(function_index) => *function_index,
+            internal => panic!("unexpected export: {:?}", internal),
+        }
+    }
+
+    fn lowered_code(module: &MvpModule) -> &[MvpInstruction] {
+        module.code_section().expect("should have code").bodies()[0]
+            .code()
+            .elements()
+    }
+
+    #[test]
+    fn should_lower_sign_extension_to_shifts() {
+        let module = lowered(vec![
+            Instruction::I32Const(0xff),
+            Instruction::SignExt(SignExtInstruction::I32Extend8S),
+            Instruction::Drop,
+            Instruction::I64Const(0xffff_ffff),
+            Instruction::SignExt(SignExtInstruction::I64Extend32S),
+            Instruction::Drop,
+        ]);
+
+        assert_eq!(imported_function_names(&module), vec![HAS_KEY]);
+        assert_eq!(exported_function_index(&module), 1);
+        assert_eq!(
+            lowered_code(&module),
+            &[
+                MvpInstruction::I32Const(0xff),
+                MvpInstruction::I32Const(24),
+                MvpInstruction::I32Shl,
+                MvpInstruction::I32Const(24),
+                MvpInstruction::I32ShrS,
+                MvpInstruction::Drop,
+                MvpInstruction::I64Const(0xffff_ffff),
+                MvpInstruction::I32WrapI64,
+                MvpInstruction::I64ExtendSI32,
+                MvpInstruction::Drop,
+                MvpInstruction::Call(2),
+                MvpInstruction::End,
+            ]
+        );
+        assert!(module
+            .data_section()
+            .map_or(true, |data_section| data_section.entries().is_empty()));
+    }
+
+    #[test]
+    fn should_lower_memory_copy_and_fill_to_host_calls() {
+        let module = lowered(vec![
+            Instruction::I32Const(8),
+            Instruction::I32Const(0),
+            Instruction::I32Const(4),
+            Instruction::Bulk(BulkInstruction::MemoryCopy),
+            Instruction::I32Const(0),
+            Instruction::I32Const(0xaa),
+            Instruction::I32Const(4),
+            Instruction::Bulk(BulkInstruction::MemoryFill),
+        ]);
+
+        // The host functions are imported after `has_key`, shifting the module's own functions.
+        assert_eq!(
+            imported_function_names(&module),
+            vec![HAS_KEY, MEMORY_COPY, MEMORY_FILL]
+        );
+        assert_eq!(exported_function_index(&module), 3);
+        assert_eq!(
+            lowered_code(&module),
+            &[
+                MvpInstruction::I32Const(8),
+                MvpInstruction::I32Const(0),
+                MvpInstruction::I32Const(4),
+                MvpInstruction::Call(1),
+                MvpInstruction::I32Const(0),
+                MvpInstruction::I32Const(0xaa),
+                MvpInstruction::I32Const(4),
+                MvpInstruction::Call(2),
+                MvpInstruction::Call(4),
+                MvpInstruction::End,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_reject_other_bulk_memory_instructions() {
+        let module_bytes = build(vec![
+            Instruction::I32Const(0),
+            Instruction::I32Const(0),
+            Instruction::I32Const(3),
+            Instruction::Bulk(BulkInstruction::MemoryInit(0)),
+        ]);
+        match crate::deserialize(&module_bytes) {
+            Err(PreprocessingError::UnsupportedInstruction { function_index, .. }) => {
+                assert_eq!(function_index, 1)
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_instrument_lowered_module() {
+        let module_bytes = build(vec![
+            Instruction::I32Const(0),
+            Instruction::SignExt(SignExtInstruction::I32Extend16S),
+            Instruction::I32Const(0),
+            Instruction::I32Const(4),
+            Instruction::Bulk(BulkInstruction::MemoryFill),
+        ]);
+        let wasm_costs = WasmCosts {
+            regular: 1,
+            max_stack_height: 64 * 1024,
+            opcodes_mul: 1,
+            opcodes_div: 1,
+            ..Default::default()
+        };
+        let preprocessor = Preprocessor::new(wasm_costs, WasmLimits::default());
+        let instrumented_module = preprocessor
+            .preprocess(&module_bytes)
+            .expect("should preprocess");
+
+        let instrumented = instrumented_module.instrumented();
+        let gas_import = imported_function_names(instrumented)
+            .iter()
+            .position(|name| *name == "gas")
+            .expect("should import gas") as u32;
+        let code = instrumented
+            .code_section()
+            .expect("should have code")
+            .bodies()[0]
+            .code()
+            .elements();
+        assert!(code.contains(&MvpInstruction::Call(gas_import)));
+    }
+}
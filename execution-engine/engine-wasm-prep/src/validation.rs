@@ -0,0 +1,537 @@
+//! Up-front validation of a module's imports, memory, size and instructions, so that modules which
+//! would fail to be instantiated or metered, or which exceed the chain's limits, are rejected with
+//! a precise error.
+
+use parity_wasm::elements::{External, ImportCountType, Module, Type};
+use pwasm_utils::rules::InstructionType;
+
+use crate::{host_functions, wasm_limits::WasmLimits, PreprocessingError};
+
+/// Checks that `module` declares its own memory of no more than `mem_pages` pages.
+fn validate_memory(module: &Module, mem_pages: u32) -> Result<(), PreprocessingError> {
+    let memory_type = module
+        .memory_section()
+        .and_then(|memory_section| memory_section.entries().first())
+        .ok_or(PreprocessingError::MissingMemory)?;
+    let limits = memory_type.limits();
+    if limits.initial() > mem_pages || limits.maximum().map_or(false, |max| max > mem_pages) {
+        return Err(PreprocessingError::MemoryTooLarge {
+            initial: limits.initial(),
+            maximum: limits.maximum(),
+            limit: mem_pages,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that any table of `module` has no more than `max_table_size` entries.
+fn validate_table(module: &Module, max_table_size: u32) -> Result<(), PreprocessingError> {
+    let table_types = module
+        .table_section()
+        .map(|table_section| table_section.entries())
+        .unwrap_or_default();
+    for table_type in table_types {
+        let limits = table_type.limits();
+        if limits.initial() > max_table_size
+            || limits.maximum().map_or(false, |max| max > max_table_size)
+        {
+            return Err(PreprocessingError::TableTooLarge {
+                initial: limits.initial(),
+                maximum: limits.maximum(),
+                limit: max_table_size,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks the numbers of globals and functions of `module`, and of locals of each of its functions,
+/// against `wasm_limits`.
+fn validate_counts(module: &Module, wasm_limits: &WasmLimits) -> Result<(), PreprocessingError> {
+    let global_count = module
+        .global_section()
+        .map_or(0, |global_section| global_section.entries().len());
+    if global_count > wasm_limits.max_globals as usize {
+        return Err(PreprocessingError::TooManyGlobals {
+            count: global_count,
+            limit: wasm_limits.max_globals,
+        });
+    }
+
+    let functions = module
+        .function_section()
+        .map(|function_section| function_section.entries())
+        .unwrap_or_default();
+    let imported_functions = module.import_count(ImportCountType::Function);
+    let function_count = imported_functions + functions.len();
+    if function_count > wasm_limits.max_functions as usize {
+        return Err(PreprocessingError::TooManyFunctions {
+            count: function_count,
+            limit: wasm_limits.max_functions,
+        });
+    }
+
+    let function_bodies = module
+        .code_section()
+        .map(|code_section| code_section.bodies())
+        .unwrap_or_default();
+    let types = module
+        .type_section()
+        .map(|type_section| type_section.types())
+        .unwrap_or_default();
+    for (index, (function, function_body)) in functions.iter().zip(function_bodies).enumerate() {
+        let param_count = match types.get(function.type_ref() as usize) {
+            Some(Type::Function(function_type)) => function_type.params().len() as u64,
+            None => 0,
+        };
+        let local_count = function_body
+            .locals()
+            .iter()
+            .map(|local| u64::from(local.count()))
+            .sum::<u64>()
+            + param_count;
+        if local_count > u64::from(wasm_limits.max_locals) {
+            return Err(PreprocessingError::TooManyLocals {
+                function_index: (imported_functions + index) as u32,
+                count: local_count,
+                limit: wasm_limits.max_locals,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that every import of `module` is a host function with the expected signature.
+fn validate_imports(module: &Module) -> Result<(), PreprocessingError> {
+    let import_entries = module
+        .import_section()
+        .map(|import_section| import_section.entries())
+        .unwrap_or_default();
+
+    for import_entry in import_entries {
+        let type_index = match import_entry.external() {
+            External::Function(type_index)
+                if import_entry.module() == host_functions::HOST_MODULE =>
+            {
+                *type_index
+            }
+            _ => {
+                return Err(PreprocessingError::UnsupportedImport {
+                    module: import_entry.module().to_string(),
+                    field: import_entry.field().to_string(),
+                })
+            }
+        };
+
+        let name = import_entry.field();
+        let expected = host_functions::host_function_type(name)
+            .ok_or_else(|| PreprocessingError::UnknownHostFunction(name.to_string()))?;
+        let actual = module
+            .type_section()
+            .and_then(|type_section| type_section.types().get(type_index as usize))
+            .map(|Type::Function(function_type)| function_type.clone())
+            .ok_or_else(|| {
+                PreprocessingError::Deserialize(format!(
+                    "import of {} refers to missing type {}",
+                    name, type_index
+                ))
+            })?;
+        if actual != expected {
+            return Err(PreprocessingError::HostFunctionSignatureMismatch {
+                name: name.to_string(),
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that no function of `module` uses a floating point instruction.
+fn validate_instructions(module: &Module) -> Result<(), PreprocessingError> {
+    let function_bodies = module
+        .code_section()
+        .map(|code_section| code_section.bodies())
+        .unwrap_or_default();
+    let imported_functions = module.import_count(ImportCountType::Function);
+
+    for (index, function_body) in function_bodies.iter().enumerate() {
+        for instruction in function_body.code().elements() {
+            match InstructionType::op(instruction) {
+                InstructionType::Float
+                | InstructionType::FloatComparsion
+                | InstructionType::FloatConst
+                | InstructionType::FloatConversion => {
+                    return Err(PreprocessingError::FloatingPointInstruction {
+                        function_index: (imported_functions + index) as u32,
+                        instruction: instruction.clone(),
+                    })
+                }
+                _ => (),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `module` would be accepted by the host before it's instrumented.
+pub(crate) fn validate_module(
+    module: &Module,
+    wasm_limits: &WasmLimits,
+) -> Result<(), PreprocessingError> {
+    validate_memory(module, wasm_limits.max_memory_pages)?;
+    validate_table(module, wasm_limits.max_table_size)?;
+    validate_imports(module)?;
+    validate_counts(module, wasm_limits)?;
+    validate_instructions(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::{
+        CodeSection, Func, FuncBody, FunctionSection, FunctionType, GlobalEntry, GlobalSection,
+        GlobalType, ImportEntry, ImportSection, InitExpr, Instruction, Instructions, Local,
+        MemorySection, MemoryType, Section, TableSection, TableType, TypeSection, ValueType,
+    };
+
+    use super::*;
+    use crate::wasm_limits::DEFAULT_MAX_MEMORY_PAGES;
+
+    const HAS_KEY: &str = "has_key";
+
+    fn has_key_type() -> FunctionType {
+        FunctionType::new(vec![ValueType::I32; 2], Some(ValueType::I32))
+    }
+
+    struct ModuleParts {
+        function_types: Vec<FunctionType>,
+        imports: Vec<ImportEntry>,
+        memory: Option<MemoryType>,
+        table: Option<TableType>,
+        globals: Vec<GlobalEntry>,
+        locals: Vec<Local>,
+        instructions: Vec<Instruction>,
+    }
+
+    impl Default for ModuleParts {
+        fn default() -> Self {
+            ModuleParts {
+                function_types: vec![FunctionType::default(), has_key_type()],
+                imports: vec![ImportEntry::new(
+                    host_functions::HOST_MODULE.to_string(),
+                    HAS_KEY.to_string(),
+                    External::Function(1),
+                )],
+                memory: Some(MemoryType::new(1, None)),
+                table: None,
+                globals: vec![],
+                locals: vec![],
+                instructions: vec![Instruction::End],
+            }
+        }
+    }
+
+    impl ModuleParts {
+        fn build(self) -> Module {
+            let types = self
+                .function_types
+                .into_iter()
+                .map(Type::Function)
+                .collect();
+            let memory_types = self.memory.into_iter().collect();
+            let table_types = self.table.into_iter().collect();
+            Module::new(vec![
+                Section::Type(TypeSection::with_types(types)),
+                Section::Import(ImportSection::with_entries(self.imports)),
+                Section::Function(FunctionSection::with_entries(vec![Func::new(0)])),
+                Section::Table(TableSection::with_entries(table_types)),
+                Section::Memory(MemorySection::with_entries(memory_types)),
+                Section::Global(GlobalSection::with_entries(self.globals)),
+                Section::Code(CodeSection::with_bodies(vec![FuncBody::new(
+                    self.locals,
+                    Instructions::new(self.instructions),
+                )])),
+            ])
+        }
+    }
+
+    fn validate(module_parts: ModuleParts) -> Result<(), PreprocessingError> {
+        validate_module(&module_parts.build(), &WasmLimits::default())
+    }
+
+    fn validate_with_limits(
+        module_parts: ModuleParts,
+        wasm_limits: WasmLimits,
+    ) -> Result<(), PreprocessingError> {
+        validate_module(&module_parts.build(), &wasm_limits)
+    }
+
+    #[test]
+    fn should_accept_valid_module() {
+        validate(ModuleParts::default()).expect("should be valid");
+    }
+
+    #[test]
+    fn should_reject_missing_memory() {
+        let module_parts = ModuleParts {
+            memory: None,
+            ..Default::default()
+        };
+        match validate(module_parts) {
+            Err(PreprocessingError::MissingMemory) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_reject_too_much_memory() {
+        for memory in &[
+            MemoryType::new(DEFAULT_MAX_MEMORY_PAGES + 1, None),
+            MemoryType::new(1, Some(DEFAULT_MAX_MEMORY_PAGES + 1)),
+        ] {
+            let module_parts = ModuleParts {
+                memory: Some(memory.clone()),
+                ..Default::default()
+            };
+            match validate(module_parts) {
+                Err(PreprocessingError::MemoryTooLarge { limit, .. }) => {
+                    assert_eq!(limit, DEFAULT_MAX_MEMORY_PAGES)
+                }
+                result => panic!("unexpected result: {:?}", result),
+            }
+        }
+
+        let module_parts = ModuleParts {
+            memory: Some(MemoryType::new(
+                DEFAULT_MAX_MEMORY_PAGES,
+                Some(DEFAULT_MAX_MEMORY_PAGES),
+            )),
+            ..Default::default()
+        };
+        validate(module_parts).expect("should be valid");
+    }
+
+    #[test]
+    fn should_reject_unsupported_imports() {
+        let imports = vec![
+            ImportEntry::new(
+                "other".to_string(),
+                HAS_KEY.to_string(),
+                External::Function(1),
+            ),
+            ImportEntry::new(
+                host_functions::HOST_MODULE.to_string(),
+                "memory".to_string(),
+                External::Memory(MemoryType::new(1, None)),
+            ),
+            ImportEntry::new(
+                host_functions::HOST_MODULE.to_string(),
+                "global".to_string(),
+                External::Global(GlobalType::new(ValueType::I32, false)),
+            ),
+        ];
+        for import in imports {
+            let expected_field = import.field().to_string();
+            let module_parts = ModuleParts {
+                imports: vec![import],
+                ..Default::default()
+            };
+            match validate(module_parts) {
+                Err(PreprocessingError::UnsupportedImport { field, .. }) => {
+                    assert_eq!(field, expected_field)
+                }
+                result => panic!("unexpected result: {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn should_reject_unknown_host_function() {
+        let module_parts = ModuleParts {
+            imports: vec![ImportEntry::new(
+                host_functions::HOST_MODULE.to_string(),
+                "not_a_host_function".to_string(),
+                External::Function(1),
+            )],
+            ..Default::default()
+        };
+        match validate(module_parts) {
+            Err(PreprocessingError::UnknownHostFunction(name)) => {
+                assert_eq!(name, "not_a_host_function")
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_reject_host_function_with_wrong_signature() {
+        let wrong_type = FunctionType::new(vec![ValueType::I32; 2], None);
+        let module_parts = ModuleParts {
+            function_types: vec![FunctionType::default(), wrong_type.clone()],
+            ..Default::default()
+        };
+        match validate(module_parts) {
+            Err(PreprocessingError::HostFunctionSignatureMismatch {
+                name,
+                expected,
+                actual,
+            }) => {
+                assert_eq!(name, HAS_KEY);
+                assert_eq!(expected, has_key_type());
+                assert_eq!(actual, wrong_type);
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_only_accept_print_with_test_support() {
+        let module_parts = ModuleParts {
+            function_types: vec![
+                FunctionType::default(),
+                FunctionType::new(vec![ValueType::I32; 2], None),
+            ],
+            imports: vec![ImportEntry::new(
+                host_functions::HOST_MODULE.to_string(),
+                "print".to_string(),
+                External::Function(1),
+            )],
+            ..Default::default()
+        };
+        let result = validate(module_parts);
+        if cfg!(feature = "test-support") {
+            result.expect("should be valid");
+        } else {
+            match result {
+                Err(PreprocessingError::UnknownHostFunction(name)) => assert_eq!(name, "print"),
+                result => panic!("unexpected result: {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn should_reject_floating_point_instruction() {
+        let module_parts = ModuleParts {
+            instructions: vec![
+                Instruction::F64Const(0),
+                Instruction::Drop,
+                Instruction::End,
+            ],
+            ..Default::default()
+        };
+        match validate(module_parts) {
+            Err(PreprocessingError::FloatingPointInstruction {
+                function_index,
+                instruction,
+            }) => {
+                // The one imported function precedes the module's own function.
+                assert_eq!(function_index, 1);
+                assert_eq!(instruction, Instruction::F64Const(0));
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_reject_too_large_table() {
+        let wasm_limits = WasmLimits {
+            max_table_size: 8,
+            ..Default::default()
+        };
+        for table in &[TableType::new(9, None), TableType::new(1, Some(9))] {
+            let module_parts = ModuleParts {
+                table: Some(table.clone()),
+                ..Default::default()
+            };
+            match validate_with_limits(module_parts, wasm_limits) {
+                Err(PreprocessingError::TableTooLarge { limit, .. }) => assert_eq!(limit, 8),
+                result => panic!("unexpected result: {:?}", result),
+            }
+        }
+
+        let module_parts = ModuleParts {
+            table: Some(TableType::new(8, Some(8))),
+            ..Default::default()
+        };
+        validate_with_limits(module_parts, wasm_limits).expect("should be valid");
+    }
+
+    #[test]
+    fn should_reject_too_many_globals() {
+        let global = GlobalEntry::new(
+            GlobalType::new(ValueType::I32, true),
+            InitExpr::new(vec![Instruction::I32Const(0), Instruction::End]),
+        );
+        let wasm_limits = WasmLimits {
+            max_globals: 1,
+            ..Default::default()
+        };
+        let module_parts = ModuleParts {
+            globals: vec![global.clone(), global],
+            ..Default::default()
+        };
+        match validate_with_limits(module_parts, wasm_limits) {
+            Err(PreprocessingError::TooManyGlobals { count, limit }) => {
+                assert_eq!(count, 2);
+                assert_eq!(limit, 1);
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_count_imported_functions() {
+        // The one imported function and the module's own function.
+        let wasm_limits = WasmLimits {
+            max_functions: 1,
+            ..Default::default()
+        };
+        match validate_with_limits(ModuleParts::default(), wasm_limits) {
+            Err(PreprocessingError::TooManyFunctions { count, limit }) => {
+                assert_eq!(count, 2);
+                assert_eq!(limit, 1);
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        let wasm_limits = WasmLimits {
+            max_functions: 2,
+            ..Default::default()
+        };
+        validate_with_limits(ModuleParts::default(), wasm_limits).expect("should be valid");
+    }
+
+    #[test]
+    fn should_count_params_as_locals() {
+        let wasm_limits = WasmLimits {
+            max_locals: 4,
+            ..Default::default()
+        };
+        let module_parts = ModuleParts {
+            function_types: vec![
+                FunctionType::new(vec![ValueType::I32], None),
+                has_key_type(),
+            ],
+            locals: vec![Local::new(2, ValueType::I32), Local::new(2, ValueType::I64)],
+            ..Default::default()
+        };
+        match validate_with_limits(module_parts, wasm_limits) {
+            Err(PreprocessingError::TooManyLocals {
+                function_index,
+                count,
+                limit,
+            }) => {
+                assert_eq!(function_index, 1);
+                assert_eq!(count, 5);
+                assert_eq!(limit, 4);
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        let module_parts = ModuleParts {
+            locals: vec![Local::new(4, ValueType::I32)],
+            ..Default::default()
+        };
+        validate_with_limits(module_parts, wasm_limits).expect("should be valid");
+    }
+}
@@ -4,8 +4,11 @@ use pwasm_utils::rules::{InstructionType, Metering, Set};
 
 use types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
 
-const NUM_FIELDS: usize = 10;
+const NUM_FIELDS: usize = 11;
 pub const WASM_COSTS_SERIALIZED_LENGTH: usize = NUM_FIELDS * U32_SERIALIZED_LENGTH;
+const LEGACY_NUM_FIELDS: usize = 10;
+/// Serialized length of the Wasm costs persisted before `memfill` was added.
+pub const LEGACY_WASM_COSTS_SERIALIZED_LENGTH: usize = LEGACY_NUM_FIELDS * U32_SERIALIZED_LENGTH;
 
 // Taken (partially) from parity-ethereum
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
@@ -33,6 +36,8 @@ pub struct WasmCosts {
     /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` /
     /// `opcodes_div`
     pub opcodes_div: u32,
+    /// Memory fill cost, per byte
+    pub memfill: u32,
 }
 
 impl WasmCosts {
@@ -64,6 +69,7 @@ impl ToBytes for WasmCosts {
         ret.append(&mut self.max_stack_height.to_bytes()?);
         ret.append(&mut self.opcodes_mul.to_bytes()?);
         ret.append(&mut self.opcodes_div.to_bytes()?);
+        ret.append(&mut self.memfill.to_bytes()?);
         Ok(ret)
     }
 
@@ -72,8 +78,10 @@ impl ToBytes for WasmCosts {
     }
 }
 
-impl FromBytes for WasmCosts {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+impl WasmCosts {
+    /// Deserializes Wasm costs persisted before `memfill` was added, charging memory fills as
+    /// memory copies.
+    pub fn from_legacy_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (regular, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
         let (div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
@@ -95,11 +103,26 @@ impl FromBytes for WasmCosts {
             max_stack_height,
             opcodes_mul,
             opcodes_div,
+            memfill: memcpy,
         };
         Ok((wasm_costs, rem))
     }
 }
 
+impl FromBytes for WasmCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (wasm_costs, rem) = WasmCosts::from_legacy_bytes(bytes)?;
+        let (memfill, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((
+            WasmCosts {
+                memfill,
+                ..wasm_costs
+            },
+            rem,
+        ))
+    }
+}
+
 pub mod gens {
     use proptest::{num, prop_compose};
 
@@ -117,6 +140,7 @@ pub mod gens {
             max_stack_height in num::u32::ANY,
             opcodes_mul in num::u32::ANY,
             opcodes_div in num::u32::ANY,
+            memfill in 1..=u32::MAX,
         ) -> WasmCosts {
             WasmCosts {
                 regular,
@@ -129,6 +153,7 @@ pub mod gens {
                 max_stack_height,
                 opcodes_mul,
                 opcodes_div,
+                memfill,
             }
         }
     }
@@ -138,10 +163,12 @@ pub mod gens {
 mod tests {
     use proptest::proptest;
 
-    use types::bytesrepr;
+    use types::bytesrepr::{self, ToBytes};
 
     use super::gens;
-    use crate::wasm_costs::WasmCosts;
+    use crate::wasm_costs::{
+        WasmCosts, LEGACY_WASM_COSTS_SERIALIZED_LENGTH, WASM_COSTS_SERIALIZED_LENGTH,
+    };
 
     fn wasm_costs_mock() -> WasmCosts {
         WasmCosts {
@@ -155,6 +182,7 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 3,
             opcodes_div: 8,
+            memfill: 1,
         }
     }
 
@@ -170,6 +198,7 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
+            memfill: 0,
         }
     }
 
@@ -181,6 +210,40 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&free);
     }
 
+    #[test]
+    fn should_append_memfill_to_legacy_layout() {
+        let mock = wasm_costs_mock();
+        let bytes = mock.to_bytes().expect("should serialize");
+        assert_eq!(bytes.len(), WASM_COSTS_SERIALIZED_LENGTH);
+        assert_eq!(
+            &bytes[LEGACY_WASM_COSTS_SERIALIZED_LENGTH..],
+            mock.memfill
+                .to_bytes()
+                .expect("should serialize")
+                .as_slice()
+        );
+    }
+
+    #[test]
+    fn should_deserialize_legacy_layout() {
+        let mock = WasmCosts {
+            memfill: 7,
+            ..wasm_costs_mock()
+        };
+        let bytes = mock.to_bytes().expect("should serialize");
+        let (legacy, rem) =
+            WasmCosts::from_legacy_bytes(&bytes[..LEGACY_WASM_COSTS_SERIALIZED_LENGTH])
+                .expect("should deserialize");
+        assert!(rem.is_empty());
+        assert_eq!(
+            legacy,
+            WasmCosts {
+                memfill: mock.memcpy,
+                ..mock
+            }
+        );
+    }
+
     proptest! {
         #[test]
         fn should_serialize_and_deserialize_with_arbitrary_values(
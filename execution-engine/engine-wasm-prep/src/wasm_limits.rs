@@ -0,0 +1,134 @@
+use types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
+
+const NUM_FIELDS: usize = 6;
+pub const WASM_LIMITS_SERIALIZED_LENGTH: usize = NUM_FIELDS * U32_SERIALIZED_LENGTH;
+
+/// Default maximum number of memory pages (64 KiB each) a module may declare.
+pub const DEFAULT_MAX_MEMORY_PAGES: u32 = 64;
+/// Default maximum number of entries in a module's table.
+pub const DEFAULT_MAX_TABLE_SIZE: u32 = 4096;
+/// Default maximum number of locals, including parameters, of a single function.
+pub const DEFAULT_MAX_LOCALS: u32 = 4096;
+/// Default maximum number of globals a module may declare.
+pub const DEFAULT_MAX_GLOBALS: u32 = 256;
+/// Default maximum number of functions, including imported ones, of a module.
+pub const DEFAULT_MAX_FUNCTIONS: u32 = 16_384;
+/// Default maximum size in bytes of a serialized module.
+pub const DEFAULT_MAX_MODULE_SIZE: u32 = 2 * 1024 * 1024;
+
+/// Limits on the shape of the Wasm modules accepted for execution.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WasmLimits {
+    /// Max number of memory pages (64kb each) a module can declare
+    pub max_memory_pages: u32,
+    /// Max number of entries in a module's table
+    pub max_table_size: u32,
+    /// Max number of locals of a single function, including its parameters
+    pub max_locals: u32,
+    /// Max number of globals a module can declare
+    pub max_globals: u32,
+    /// Max number of functions of a module, including imported host functions
+    pub max_functions: u32,
+    /// Max size of a serialized module, in bytes
+    pub max_module_size: u32,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        WasmLimits {
+            max_memory_pages: DEFAULT_MAX_MEMORY_PAGES,
+            max_table_size: DEFAULT_MAX_TABLE_SIZE,
+            max_locals: DEFAULT_MAX_LOCALS,
+            max_globals: DEFAULT_MAX_GLOBALS,
+            max_functions: DEFAULT_MAX_FUNCTIONS,
+            max_module_size: DEFAULT_MAX_MODULE_SIZE,
+        }
+    }
+}
+
+impl ToBytes for WasmLimits {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut self.max_memory_pages.to_bytes()?);
+        ret.append(&mut self.max_table_size.to_bytes()?);
+        ret.append(&mut self.max_locals.to_bytes()?);
+        ret.append(&mut self.max_globals.to_bytes()?);
+        ret.append(&mut self.max_functions.to_bytes()?);
+        ret.append(&mut self.max_module_size.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        WASM_LIMITS_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for WasmLimits {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (max_memory_pages, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (max_table_size, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_locals, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_globals, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_functions, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_module_size, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let wasm_limits = WasmLimits {
+            max_memory_pages,
+            max_table_size,
+            max_locals,
+            max_globals,
+            max_functions,
+            max_module_size,
+        };
+        Ok((wasm_limits, rem))
+    }
+}
+
+pub mod gens {
+    use proptest::{num, prop_compose};
+
+    use crate::wasm_limits::WasmLimits;
+
+    prop_compose! {
+        pub fn wasm_limits_arb()(
+            max_memory_pages in num::u32::ANY,
+            max_table_size in num::u32::ANY,
+            max_locals in num::u32::ANY,
+            max_globals in num::u32::ANY,
+            max_functions in num::u32::ANY,
+            max_module_size in num::u32::ANY,
+        ) -> WasmLimits {
+            WasmLimits {
+                max_memory_pages,
+                max_table_size,
+                max_locals,
+                max_globals,
+                max_functions,
+                max_module_size,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::bytesrepr;
+
+    use super::gens;
+    use crate::wasm_limits::WasmLimits;
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        bytesrepr::test_serialization_roundtrip(&WasmLimits::default());
+    }
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize_with_arbitrary_values(
+            wasm_limits in gens::wasm_limits_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&wasm_limits);
+        }
+    }
+}
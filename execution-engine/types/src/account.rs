@@ -335,6 +335,56 @@ impl TryFrom<i32> for UpdateKeyFailure {
     }
 }
 
+/// Errors that can occur while replacing an account's associated keys map and optionally its main
+/// purse in a single operation.
+#[derive(PartialEq, Eq, Fail, Debug, Copy, Clone)]
+#[repr(i32)]
+pub enum ManageAccountFailure {
+    /// The new associated keys map is empty.
+    #[fail(display = "Unable to replace associated keys with an empty set")]
+    EmptyKeys = 1,
+    /// The new associated keys map contains more than [`MAX_ASSOCIATED_KEYS`] entries.
+    #[fail(
+        display = "Unable to replace associated keys because maximum amount of keys is exceeded"
+    )]
+    MaxKeysLimit = 2,
+    /// Caller doesn't have sufficient permissions to manage the given account.
+    #[fail(display = "Unable to manage account due to insufficient permissions")]
+    PermissionDenied = 3,
+    /// The total weight of the new associated keys map would fall below one of the action
+    /// thresholds for the given account.
+    #[fail(display = "Unable to replace associated keys which would violate action thresholds")]
+    ThresholdViolation = 4,
+    /// The new main purse is not a purse, or is not owned with full access rights by the caller.
+    #[fail(display = "Unable to set main purse to an invalid purse")]
+    InvalidPurse = 5,
+}
+
+// This conversion is not intended to be used by third party crates.
+#[doc(hidden)]
+impl TryFrom<i32> for ManageAccountFailure {
+    type Error = TryFromIntError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            d if d == ManageAccountFailure::EmptyKeys as i32 => Ok(ManageAccountFailure::EmptyKeys),
+            d if d == ManageAccountFailure::MaxKeysLimit as i32 => {
+                Ok(ManageAccountFailure::MaxKeysLimit)
+            }
+            d if d == ManageAccountFailure::PermissionDenied as i32 => {
+                Ok(ManageAccountFailure::PermissionDenied)
+            }
+            d if d == ManageAccountFailure::ThresholdViolation as i32 => {
+                Ok(ManageAccountFailure::ThresholdViolation)
+            }
+            d if d == ManageAccountFailure::InvalidPurse as i32 => {
+                Ok(ManageAccountFailure::InvalidPurse)
+            }
+            _ => Err(TryFromIntError(())),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{convert::TryFrom, vec::Vec};
@@ -403,4 +453,15 @@ mod tests {
                    `UpdateKeyFailure`, or `max_valid_value_for_variant` in this test?"
         );
     }
+
+    #[test]
+    fn try_from_i32_for_manage_account_failure() {
+        let max_valid_value_for_variant = ManageAccountFailure::InvalidPurse as i32;
+        assert_eq!(
+            Err(TryFromIntError(())),
+            ManageAccountFailure::try_from(max_valid_value_for_variant + 1),
+            "Did you forget to update `ManageAccountFailure::try_from` for a new variant of \
+                   `ManageAccountFailure`, or `max_valid_value_for_variant` in this test?"
+        );
+    }
 }
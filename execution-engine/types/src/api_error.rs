@@ -7,8 +7,8 @@ use core::{
 
 use crate::{
     account::{
-        AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, TryFromIntError,
-        TryFromSliceForAccountHashError, UpdateKeyFailure,
+        AddKeyFailure, ManageAccountFailure, RemoveKeyFailure, SetThresholdFailure,
+        TryFromIntError, TryFromSliceForAccountHashError, UpdateKeyFailure,
     },
     bytesrepr, contracts,
     system_contract_errors::{mint, pos},
@@ -489,6 +489,18 @@ impl From<SetThresholdFailure> for ApiError {
     }
 }
 
+impl From<ManageAccountFailure> for ApiError {
+    fn from(error: ManageAccountFailure) -> Self {
+        match error {
+            ManageAccountFailure::EmptyKeys => ApiError::MissingKey,
+            ManageAccountFailure::MaxKeysLimit => ApiError::MaxKeysLimit,
+            ManageAccountFailure::PermissionDenied => ApiError::PermissionDenied,
+            ManageAccountFailure::ThresholdViolation => ApiError::ThresholdViolation,
+            ManageAccountFailure::InvalidPurse => ApiError::InvalidPurse,
+        }
+    }
+}
+
 impl From<CLValueError> for ApiError {
     fn from(error: CLValueError) -> Self {
         match error {
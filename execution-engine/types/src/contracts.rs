@@ -18,6 +18,8 @@ use core::fmt;
 pub const MAX_GROUPS: u8 = 10;
 /// Maximum number of URefs which can be assigned across all user groups.
 pub const MAX_TOTAL_UREFS: usize = 100;
+/// Maximum length in bytes of the metadata attached to a single contract version.
+pub const MAX_VERSION_METADATA_LENGTH: usize = 256;
 
 /// Set of errors which may happen when working with contract headers.
 #[derive(Debug, PartialEq)]
@@ -47,6 +49,12 @@ pub enum Error {
     GroupInUse = 8,
     /// URef already exists in given group.
     URefAlreadyExists = 9,
+    /// Attempted to add or disable a version of a contract package which has been permanently
+    /// locked.
+    LockedContractPackage = 10,
+    /// Attempted to attach metadata to a contract version which exceeds the allowed maximum
+    /// length.
+    VersionMetadataTooLong = 11,
 }
 
 /// A (labelled) "user group". Each method of a versioned contract may be
@@ -168,6 +176,31 @@ pub type DisabledVersions = BTreeSet<ContractVersionKey>;
 /// Collection of named groups.
 pub type Groups = BTreeMap<Group, BTreeSet<URef>>;
 
+/// Metadata attached to contract versions, such as a semver label or a changelog hash.
+pub type VersionMetadata = BTreeMap<ContractVersionKey, String>;
+
+/// Status of a single contract version within a [`ContractPackage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractVersionStatus {
+    /// The version can be called.
+    Enabled,
+    /// The version has been disabled and can't be called.
+    Disabled,
+}
+
+/// Summary of a single contract version within a [`ContractPackage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractVersionInfo {
+    /// Key of the contract version.
+    pub contract_version_key: ContractVersionKey,
+    /// Hash of the contract stored under this version.
+    pub contract_hash: ContractHash,
+    /// Whether the version is enabled or disabled.
+    pub status: ContractVersionStatus,
+    /// Metadata attached to the version, if any.
+    pub metadata: Option<String>,
+}
+
 /// Contract definition, metadata, and security container.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct ContractPackage {
@@ -182,6 +215,10 @@ pub struct ContractPackage {
     /// version of the contract. A method is callable by any context which
     /// "knows" any of the URefs assoicated with the mthod's user group.
     groups: Groups,
+    /// Metadata attached to versions
+    version_metadata: VersionMetadata,
+    /// Once set, versions can no longer be added to or disabled in this package.
+    locked: bool,
 }
 
 impl ContractPackage {
@@ -197,6 +234,8 @@ impl ContractPackage {
             versions,
             disabled_versions,
             groups,
+            version_metadata: VersionMetadata::default(),
+            locked: false,
         }
     }
 
@@ -205,6 +244,22 @@ impl ContractPackage {
         self.access_key
     }
 
+    /// Replaces the access key for this contract. The previous access key no longer grants
+    /// control over the package.
+    pub fn set_access_key(&mut self, access_key: URef) {
+        self.access_key = access_key;
+    }
+
+    /// Checks if this contract package has been permanently locked.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Permanently locks this contract package, so that no versions can be added or disabled.
+    pub fn lock(&mut self) {
+        self.locked = true;
+    }
+
     /// Get the mutable group definitions for this contract.
     pub fn groups_mut(&mut self) -> &mut Groups {
         &mut self.groups
@@ -252,12 +307,11 @@ impl ContractPackage {
 
     /// Disable the contract version corresponding to the given hash (if it exists).
     pub fn disable_contract_version(&mut self, contract_hash: ContractHash) -> Result<(), Error> {
-        let contract_version_key = self
-            .versions
-            .iter()
-            .filter_map(|(k, v)| if *v == contract_hash { Some(*k) } else { None })
-            .next()
-            .ok_or(Error::ContractNotFound)?;
+        if self.locked {
+            return Err(Error::LockedContractPackage);
+        }
+
+        let contract_version_key = self.find_contract_version_key(contract_hash)?;
 
         if !self.disabled_versions.contains(&contract_version_key) {
             self.disabled_versions.insert(contract_version_key);
@@ -266,6 +320,91 @@ impl ContractPackage {
         Ok(())
     }
 
+    /// Enable the previously disabled contract version corresponding to the given hash (if it
+    /// exists).
+    pub fn enable_contract_version(&mut self, contract_hash: ContractHash) -> Result<(), Error> {
+        if self.locked {
+            return Err(Error::LockedContractPackage);
+        }
+
+        let contract_version_key = self.find_contract_version_key(contract_hash)?;
+
+        self.disabled_versions.remove(&contract_version_key);
+
+        Ok(())
+    }
+
+    /// Attach metadata to the contract version corresponding to the given hash (if it exists),
+    /// replacing any metadata previously attached to it.
+    pub fn set_version_metadata(
+        &mut self,
+        contract_hash: ContractHash,
+        metadata: String,
+    ) -> Result<(), Error> {
+        if self.locked {
+            return Err(Error::LockedContractPackage);
+        }
+
+        if metadata.len() > MAX_VERSION_METADATA_LENGTH {
+            return Err(Error::VersionMetadataTooLong);
+        }
+
+        let contract_version_key = self.find_contract_version_key(contract_hash)?;
+
+        self.version_metadata.insert(contract_version_key, metadata);
+
+        Ok(())
+    }
+
+    /// Returns the metadata attached to the given contract version (if any).
+    pub fn version_metadata(&self, contract_version_key: ContractVersionKey) -> Option<&str> {
+        self.version_metadata
+            .get(&contract_version_key)
+            .map(String::as_str)
+    }
+
+    /// Returns metadata attached to all of this contract's versions.
+    pub fn all_version_metadata(&self) -> &VersionMetadata {
+        &self.version_metadata
+    }
+
+    /// Returns mutable reference to metadata attached to all of this contract's versions.
+    pub fn all_version_metadata_mut(&mut self) -> &mut VersionMetadata {
+        &mut self.version_metadata
+    }
+
+    /// Lists all of this contract's versions (enabled and disabled) in ascending order, along
+    /// with their status and metadata.
+    pub fn list_versions(&self) -> Vec<ContractVersionInfo> {
+        self.versions
+            .iter()
+            .map(|(&contract_version_key, &contract_hash)| {
+                let status = if self.disabled_versions.contains(&contract_version_key) {
+                    ContractVersionStatus::Disabled
+                } else {
+                    ContractVersionStatus::Enabled
+                };
+                ContractVersionInfo {
+                    contract_version_key,
+                    contract_hash,
+                    status,
+                    metadata: self.version_metadata.get(&contract_version_key).cloned(),
+                }
+            })
+            .collect()
+    }
+
+    fn find_contract_version_key(
+        &self,
+        contract_hash: ContractHash,
+    ) -> Result<ContractVersionKey, Error> {
+        self.versions
+            .iter()
+            .filter_map(|(k, v)| if *v == contract_hash { Some(*k) } else { None })
+            .next()
+            .ok_or(Error::ContractNotFound)
+    }
+
     /// Returns reference to all of this contract's versions.
     pub fn versions(&self) -> &ContractVersions {
         &self.versions
@@ -341,6 +480,20 @@ impl ContractPackage {
             None => None,
         }
     }
+
+    /// Deserializes a `ContractPackage` written in the layout which predates version metadata and
+    /// package locking. Such a package has no version metadata and is not locked.
+    ///
+    /// The legacy layout is a prefix of the current one, which appends both fields after `groups`.
+    pub fn from_legacy_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (access_key, bytes) = URef::from_bytes(bytes)?;
+        let (versions, bytes) = ContractVersions::from_bytes(bytes)?;
+        let (disabled_versions, bytes) = DisabledVersions::from_bytes(bytes)?;
+        let (groups, bytes) = Groups::from_bytes(bytes)?;
+        let result = ContractPackage::new(access_key, versions, disabled_versions, groups);
+
+        Ok((result, bytes))
+    }
 }
 
 impl ToBytes for ContractPackage {
@@ -351,6 +504,8 @@ impl ToBytes for ContractPackage {
         result.append(&mut self.versions.to_bytes()?);
         result.append(&mut self.disabled_versions.to_bytes()?);
         result.append(&mut self.groups.to_bytes()?);
+        result.append(&mut self.version_metadata.to_bytes()?);
+        result.append(&mut self.locked.to_bytes()?);
 
         Ok(result)
     }
@@ -360,6 +515,8 @@ impl ToBytes for ContractPackage {
             + self.versions.serialized_length()
             + self.disabled_versions.serialized_length()
             + self.groups.serialized_length()
+            + self.version_metadata.serialized_length()
+            + self.locked.serialized_length()
     }
 }
 
@@ -369,11 +526,15 @@ impl FromBytes for ContractPackage {
         let (versions, bytes) = ContractVersions::from_bytes(bytes)?;
         let (disabled_versions, bytes) = DisabledVersions::from_bytes(bytes)?;
         let (groups, bytes) = Groups::from_bytes(bytes)?;
+        let (version_metadata, bytes) = VersionMetadata::from_bytes(bytes)?;
+        let (locked, bytes) = bool::from_bytes(bytes)?;
         let result = ContractPackage {
             access_key,
             versions,
             disabled_versions,
             groups,
+            version_metadata,
+            locked,
         };
 
         Ok((result, bytes))
@@ -875,6 +1036,11 @@ impl Parameter {
         }
     }
 
+    /// Get the name of this argument.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Get the type of this argument.
     pub fn cl_type(&self) -> &CLType {
         &self.cl_type
@@ -1049,4 +1215,154 @@ mod tests {
             "version should not be enabled"
         );
     }
+
+    #[test]
+    fn should_enable_contract_version() {
+        const CONTRACT_HASH: ContractHash = [123; 32];
+        let mut contract_package = make_contract_package();
+
+        assert_eq!(
+            contract_package.enable_contract_version(CONTRACT_HASH),
+            Err(Error::ContractNotFound),
+            "should return contract not found error"
+        );
+
+        let next_version = contract_package.insert_contract_version(1, CONTRACT_HASH);
+        contract_package
+            .disable_contract_version(CONTRACT_HASH)
+            .expect("should disable version");
+        assert!(!contract_package.is_version_enabled(next_version));
+
+        assert_eq!(
+            contract_package.enable_contract_version(CONTRACT_HASH),
+            Ok(()),
+            "should be able to enable version"
+        );
+        assert!(
+            contract_package.is_version_enabled(next_version),
+            "version should be enabled again"
+        );
+        assert_eq!(
+            contract_package.lookup_contract_hash(next_version),
+            Some(&CONTRACT_HASH)
+        );
+    }
+
+    #[test]
+    fn should_set_version_metadata() {
+        const CONTRACT_HASH: ContractHash = [123; 32];
+        let mut contract_package = make_contract_package();
+
+        assert_eq!(
+            contract_package.set_version_metadata(CONTRACT_HASH, "1.0.0".to_string()),
+            Err(Error::ContractNotFound)
+        );
+
+        let next_version = contract_package.insert_contract_version(1, CONTRACT_HASH);
+        assert_eq!(contract_package.version_metadata(next_version), None);
+
+        contract_package
+            .set_version_metadata(CONTRACT_HASH, "1.0.0".to_string())
+            .expect("should set metadata");
+        assert_eq!(
+            contract_package.version_metadata(next_version),
+            Some("1.0.0")
+        );
+
+        let too_long = "a".repeat(MAX_VERSION_METADATA_LENGTH + 1);
+        assert_eq!(
+            contract_package.set_version_metadata(CONTRACT_HASH, too_long),
+            Err(Error::VersionMetadataTooLong)
+        );
+        assert_eq!(
+            contract_package.version_metadata(next_version),
+            Some("1.0.0")
+        );
+    }
+
+    #[test]
+    fn should_list_versions_with_status() {
+        const CONTRACT_HASH_1: ContractHash = [123; 32];
+        const CONTRACT_HASH_2: ContractHash = [124; 32];
+        let mut contract_package = make_contract_package();
+        let version_1 = contract_package.insert_contract_version(2, CONTRACT_HASH_1);
+        let version_2 = contract_package.insert_contract_version(2, CONTRACT_HASH_2);
+        contract_package
+            .disable_contract_version(CONTRACT_HASH_1)
+            .expect("should disable version");
+        contract_package
+            .set_version_metadata(CONTRACT_HASH_2, "2.0.0".to_string())
+            .expect("should set metadata");
+
+        let versions = contract_package.list_versions();
+        assert_eq!(versions.len(), 3);
+        assert_eq!(
+            &versions[1..],
+            &[
+                ContractVersionInfo {
+                    contract_version_key: version_1,
+                    contract_hash: CONTRACT_HASH_1,
+                    status: ContractVersionStatus::Disabled,
+                    metadata: None,
+                },
+                ContractVersionInfo {
+                    contract_version_key: version_2,
+                    contract_hash: CONTRACT_HASH_2,
+                    status: ContractVersionStatus::Enabled,
+                    metadata: Some("2.0.0".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_not_disable_version_of_locked_package() {
+        const CONTRACT_HASH: ContractHash = [123; 32];
+        let mut contract_package = make_contract_package();
+        let next_version = contract_package.insert_contract_version(1, CONTRACT_HASH);
+
+        contract_package.lock();
+        assert!(contract_package.is_locked());
+
+        assert_eq!(
+            contract_package.disable_contract_version(CONTRACT_HASH),
+            Err(Error::LockedContractPackage),
+            "should not be able to disable version of a locked package"
+        );
+        assert!(contract_package.is_version_enabled(next_version));
+    }
+
+    #[test]
+    fn should_deserialize_legacy_layout() {
+        let contract_package = make_contract_package();
+
+        let mut bytes = contract_package.access_key.to_bytes().unwrap();
+        bytes.append(&mut contract_package.versions.to_bytes().unwrap());
+        bytes.append(&mut contract_package.disabled_versions.to_bytes().unwrap());
+        bytes.append(&mut contract_package.groups.to_bytes().unwrap());
+        // New fields are only ever appended to the legacy layout
+        assert!(contract_package.to_bytes().unwrap().starts_with(&bytes));
+
+        let (decoded_package, rem) =
+            ContractPackage::from_legacy_bytes(&bytes).expect("should deserialize");
+        assert_eq!(contract_package, decoded_package);
+        assert!(decoded_package.all_version_metadata().is_empty());
+        assert!(!decoded_package.is_locked());
+        assert_eq!(rem.len(), 0);
+    }
+
+    #[test]
+    fn roundtrip_serialization_of_locked_package() {
+        let mut contract_package = make_contract_package();
+        contract_package.set_access_key(URef::new([7; 32], AccessRights::READ_ADD_WRITE));
+        contract_package
+            .set_version_metadata([42; 32], "1.0.0".to_string())
+            .expect("should set metadata");
+        contract_package.lock();
+        let bytes = contract_package.to_bytes().expect("should serialize");
+        let (decoded_package, rem) =
+            ContractPackage::from_bytes(&bytes).expect("should deserialize");
+        assert_eq!(contract_package, decoded_package);
+        assert_eq!(rem.len(), 0);
+    }
 }
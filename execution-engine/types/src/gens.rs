@@ -14,7 +14,9 @@ use proptest::{
 
 use crate::{
     account::{AccountHash, Weight},
-    contracts::{ContractVersions, DisabledVersions, Groups, NamedKeys, Parameters},
+    contracts::{
+        ContractVersions, DisabledVersions, Groups, NamedKeys, Parameters, VersionMetadata,
+    },
     AccessRights, CLType, CLValue, Contract, ContractPackage, ContractVersionKey, ContractWasm,
     EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Group, Key, NamedArg, Parameter,
     Phase, ProtocolVersion, SemVer, URef, U128, U256, U512,
@@ -312,6 +314,10 @@ pub fn disabled_versions_arb() -> impl Strategy<Value = DisabledVersions> {
     btree_set(contract_version_key_arb(), 0..5)
 }
 
+pub fn version_metadata_arb() -> impl Strategy<Value = VersionMetadata> {
+    btree_map(contract_version_key_arb(), "[0-9a-z.]{0,16}", 0..5)
+}
+
 pub fn groups_arb() -> impl Strategy<Value = Groups> {
     btree_map(group_arb(), btree_set(uref_arb(), 1..10), 0..5)
 }
@@ -321,9 +327,19 @@ pub fn contract_package_arb() -> impl Strategy<Value = ContractPackage> {
         uref_arb(),
         contract_versions_arb(),
         disabled_versions_arb(),
+        version_metadata_arb(),
         groups_arb(),
+        any::<bool>(),
     )
-        .prop_map(|(access_key, versions, disabled_versions, groups)| {
-            ContractPackage::new(access_key, versions, disabled_versions, groups)
-        })
+        .prop_map(
+            |(access_key, versions, disabled_versions, version_metadata, groups, locked)| {
+                let mut contract_package =
+                    ContractPackage::new(access_key, versions, disabled_versions, groups);
+                *contract_package.all_version_metadata_mut() = version_metadata;
+                if locked {
+                    contract_package.lock();
+                }
+                contract_package
+            },
+        )
 }
@@ -137,6 +137,7 @@ object ChainSpec extends ParserImplicits {
       memInitialPages: Int Refined NonNegative,
       memGrowPerPage: Int Refined NonNegative,
       memCopyPerByte: Int Refined NonNegative,
+      memFillPerByte: Int Refined Positive,
       maxStackHeight: Int Refined NonNegative,
       opcodesMultiplier: Int Refined NonNegative,
       opcodesDivisor: Int Refined Positive
@@ -425,6 +426,7 @@ object ChainSpecReader {
           .withMaxStackHeight(wasmCosts.maxStackHeight.value)
           .withOpcodesMul(wasmCosts.opcodesMultiplier.value)
           .withOpcodesDiv(wasmCosts.opcodesDivisor.value)
+          .withMemfill(wasmCosts.memFillPerByte.value)
       )
 
   private def toDeployConfig(deployConfig: Deploy): ipc.ChainSpec.DeployConfig =
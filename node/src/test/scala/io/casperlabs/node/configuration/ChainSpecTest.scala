@@ -150,6 +150,7 @@ class ChainSpecTest extends WordSpecLike with Matchers with Inspectors {
           wasmCosts.maxStackHeight shouldBe 8
           wasmCosts.opcodesMul shouldBe 9
           wasmCosts.opcodesDiv shouldBe 10
+          wasmCosts.memfill shouldBe 11
         }
       }
 
@@ -174,6 +175,7 @@ class ChainSpecTest extends WordSpecLike with Matchers with Inspectors {
           wasmCosts.maxStackHeight shouldBe 28
           wasmCosts.opcodesMul shouldBe 29
           wasmCosts.opcodesDiv shouldBe 210
+          wasmCosts.memfill shouldBe 211
         }
       }
 
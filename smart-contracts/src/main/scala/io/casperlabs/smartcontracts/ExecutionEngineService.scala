@@ -108,6 +108,8 @@ class GrpcExecutionEngineService[F[_]: Defer: Concurrent: Log: TaskLift: Metrics
               s"Missing states: ${Base16.encode(missing.toByteArray)}"
             )
           )
+        case ExecuteResponse.Result.Failure(err) =>
+          Left(new SmartContractEngineError(err))
       }
     }
 